@@ -70,6 +70,13 @@ impl CodexOAuthClient {
         }
     }
 
+    /// Send requests with `client`, e.g. one with the user's proxy and CA
+    /// settings.
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Generate PKCE challenge and verifier
     fn generate_pkce() -> Result<(String, String)> {
         // Generate random verifier (43 characters as per RFC 7636)
@@ -250,7 +257,6 @@ impl CodexOAuthClient {
     }
 
     /// Refresh an access token
-    #[allow(dead_code)]
     pub async fn refresh_token(&self, refresh_token: &str) -> Result<CodeXTokenResponse> {
         let params = [
             ("grant_type", "refresh_token"),
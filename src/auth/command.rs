@@ -0,0 +1,169 @@
+//! Credentials fetched by running an external command.
+//!
+//! Lets accounts keep their API key in a password manager (`op read ...`,
+//! `pass show ...`, `vault kv get -field=key ...`) instead of rco's own
+//! storage. The command is run without a shell; the first line of its
+//! stdout is the secret. Results are cached in memory for the life of the
+//! process so a long-running `rco mcp` does not re-prompt on every request.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use once_cell::sync::Lazy;
+
+/// How long a command may run before it is killed.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// How long a fetched secret is reused.
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 300;
+
+/// Secrets fetched this process, by command line.
+static CACHE: Lazy<Mutex<HashMap<String, (String, Instant)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Run `command` and return the secret it prints, reusing a cached value
+/// younger than `cache_ttl`.
+pub fn fetch_secret(command: &str, timeout: Duration, cache_ttl: Duration) -> Result<String> {
+    if let Some((secret, fetched)) = CACHE.lock().unwrap().get(command) {
+        if fetched.elapsed() < cache_ttl {
+            return Ok(secret.clone());
+        }
+    }
+
+    let secret = run(command, timeout)?;
+    if !cache_ttl.is_zero() {
+        CACHE
+            .lock()
+            .unwrap()
+            .insert(command.to_string(), (secret.clone(), Instant::now()));
+    }
+    Ok(secret)
+}
+
+/// [`fetch_secret`] with an account's optional timeout and cache settings.
+pub fn fetch_with(
+    command: &str,
+    timeout_secs: Option<u64>,
+    cache_ttl_secs: Option<u64>,
+) -> Result<String> {
+    fetch_secret(
+        command,
+        Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+        Duration::from_secs(cache_ttl_secs.unwrap_or(DEFAULT_CACHE_TTL_SECS)),
+    )
+}
+
+fn run(command: &str, timeout: Duration) -> Result<String> {
+    let words = shell_words::split(command)
+        .with_context(|| format!("Invalid credential command: {}", command))?;
+    let Some((program, args)) = words.split_first() else {
+        bail!("Credential command is empty");
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run credential command '{}'", program))?;
+
+    // Drain the pipes on threads so a chatty command cannot block on a full pipe
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!(
+                "Credential command '{}' timed out after {}s",
+                program,
+                timeout.as_secs()
+            );
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let detail = stderr.lines().next().unwrap_or("").trim();
+        return Err(anyhow!(
+            "Credential command '{}' failed ({}){}",
+            program,
+            status,
+            if detail.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", detail)
+            }
+        ));
+    }
+
+    let secret = stdout.lines().next().unwrap_or("").trim().to_string();
+    if secret.is_empty() {
+        bail!("Credential command '{}' printed nothing", program);
+    }
+    Ok(secret)
+}
+
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn uses_the_first_line_of_stdout() {
+        let secret = fetch_secret("printf 'sk-abc\\nurl: x\\n'", 5 * SECOND, Duration::ZERO);
+        assert_eq!(secret.unwrap(), "sk-abc");
+    }
+
+    #[test]
+    fn caches_within_the_ttl() {
+        let command = "sh -c 'echo $$'";
+        let first = fetch_secret(command, 5 * SECOND, 60 * SECOND).unwrap();
+        assert_eq!(
+            fetch_secret(command, 5 * SECOND, 60 * SECOND).unwrap(),
+            first
+        );
+        assert_ne!(
+            fetch_secret(command, 5 * SECOND, Duration::ZERO).unwrap(),
+            first
+        );
+    }
+
+    #[test]
+    fn reports_failures_and_timeouts() {
+        let err = fetch_secret(
+            "sh -c 'echo denied >&2; exit 3'",
+            5 * SECOND,
+            Duration::ZERO,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("denied"), "{err}");
+
+        let err = fetch_secret("sleep 5", Duration::from_millis(100), Duration::ZERO).unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+}
@@ -0,0 +1,399 @@
+//! Credentials for provider requests that stay valid for the whole session.
+//!
+//! Providers used to read their token once at construction, so a long
+//! `rco mcp` session failed as soon as an OAuth access token expired. A
+//! [`CredentialProvider`] hands out the current token before every request,
+//! refreshes it when it is about to expire or after the server rejects it,
+//! and persists the rotated tokens. Refreshes are single-flight: concurrent
+//! requests (e.g. parallel commit message variants) wait for one refresh
+//! instead of each spending the refresh token.
+
+use std::future::Future;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use super::codex_oauth::CodexOAuthClient;
+use super::oauth::{OAuthClient, TokenResponse};
+use super::oidc::OidcClient;
+use super::token_storage::{self, TokenStorage};
+use crate::config::accounts::{AccountConfig, AuthMethod, OidcGrant};
+use crate::config::{http, Config};
+use crate::providers::error::ProviderError;
+
+/// Exchanges a refresh token for new tokens.
+#[async_trait]
+pub trait TokenRefresher: Send + Sync {
+    async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse>;
+
+    /// Get new tokens without a refresh token, for grants that need no
+    /// user (client credentials). `None` when the user must log in again.
+    async fn reacquire(&self) -> Result<Option<TokenResponse>> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl TokenRefresher for OAuthClient {
+    async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse> {
+        self.refresh_token(refresh_token).await
+    }
+}
+
+#[async_trait]
+impl TokenRefresher for CodexOAuthClient {
+    async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse> {
+        let response = self.refresh_token(refresh_token).await?;
+        Ok(TokenResponse {
+            access_token: response.access_token,
+            token_type: response.token_type,
+            expires_in: response.expires_in,
+            refresh_token: Some(response.refresh_token),
+            scope: None,
+        })
+    }
+}
+
+/// The OAuth client that issued a set of tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issuer {
+    Claude,
+    Codex,
+}
+
+impl Issuer {
+    /// The issuer for an OAuth provider or AI provider name.
+    pub fn for_provider(provider: &str) -> Self {
+        match provider.to_lowercase().as_str() {
+            "codex" | "chatgpt" | "openai" => Self::Codex,
+            _ => Self::Claude,
+        }
+    }
+
+    fn refresher(self, client: reqwest::Client) -> Box<dyn TokenRefresher> {
+        match self {
+            Self::Claude => Box::new(OAuthClient::new().with_http_client(client)),
+            Self::Codex => Box::new(CodexOAuthClient::new().with_http_client(client)),
+        }
+    }
+}
+
+/// Where refreshed tokens are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenStore {
+    /// The single-login token storage used without accounts.
+    Default,
+    /// Storage namespaced to an account id.
+    Account(String),
+    /// Keep refreshed tokens in memory only.
+    Memory,
+}
+
+enum Source {
+    Fixed(String),
+    OAuth {
+        refresher: Box<dyn TokenRefresher>,
+        store: TokenStore,
+        tokens: Mutex<TokenStorage>,
+    },
+}
+
+/// The credential a provider authenticates with.
+pub struct CredentialProvider {
+    source: Source,
+}
+
+impl CredentialProvider {
+    /// An API key or other token that never changes.
+    pub fn fixed(secret: impl Into<String>) -> Self {
+        Self {
+            source: Source::Fixed(secret.into()),
+        }
+    }
+
+    /// OAuth tokens that are refreshed with `refresher` and saved to `store`.
+    pub fn oauth(
+        refresher: Box<dyn TokenRefresher>,
+        store: TokenStore,
+        tokens: TokenStorage,
+    ) -> Self {
+        Self {
+            source: Source::OAuth {
+                refresher,
+                store,
+                tokens: Mutex::new(tokens),
+            },
+        }
+    }
+
+    /// The logged-in OAuth session from `rco auth login`, if any.
+    pub fn stored_login(issuer: Issuer, config: &Config) -> Result<Option<Self>> {
+        let Some(tokens) = token_storage::get_tokens()? else {
+            return Ok(None);
+        };
+        let refresher = issuer.refresher(http::client(config)?);
+        Ok(Some(Self::oauth(refresher, TokenStore::Default, tokens)))
+    }
+
+    /// Credentials for an account: its refreshable OAuth tokens, or
+    /// `secret` as resolved by [`super::account_credential`].
+    pub fn for_account(account: &AccountConfig, secret: &str, config: &Config) -> Result<Self> {
+        if let AuthMethod::OAuth {
+            provider,
+            account_id,
+            oidc,
+        } = &account.auth
+        {
+            if let Some(oidc) = oidc {
+                let client = Box::new(OidcClient::new(
+                    oidc.as_ref().clone(),
+                    http::account_client(account, config)?,
+                ));
+                if oidc.grant == OidcGrant::ClientCredentials {
+                    // Nothing to persist: a token is requested on first use
+                    return Ok(Self::oauth(
+                        client,
+                        TokenStore::Memory,
+                        TokenStorage::expired(),
+                    ));
+                }
+                if let Some(tokens) = token_storage::get_tokens_for_account(account_id)? {
+                    return Ok(Self::oauth(
+                        client,
+                        TokenStore::Account(account_id.clone()),
+                        tokens,
+                    ));
+                }
+            } else if let Some(tokens) = token_storage::get_tokens_for_account(account_id)? {
+                return Ok(Self::oauth(
+                    Issuer::for_provider(provider)
+                        .refresher(http::account_client(account, config)?),
+                    TokenStore::Account(account_id.clone()),
+                    tokens,
+                ));
+            }
+        }
+        Ok(Self::fixed(secret))
+    }
+
+    /// Whether this is an OAuth access token rather than an API key.
+    pub fn is_oauth(&self) -> bool {
+        matches!(self.source, Source::OAuth { .. })
+    }
+
+    /// The token to send now, refreshed first if it expires within minutes.
+    pub async fn token(&self) -> Result<String> {
+        match &self.source {
+            Source::Fixed(secret) => Ok(secret.clone()),
+            Source::OAuth {
+                refresher,
+                store,
+                tokens,
+            } => {
+                let mut tokens = tokens.lock().await;
+                if tokens.expires_soon() {
+                    refresh(refresher.as_ref(), store, &mut tokens).await?;
+                }
+                Ok(tokens.access_token.clone())
+            }
+        }
+    }
+
+    /// After the server rejected `rejected`, get a token worth retrying
+    /// with: one another request already refreshed, or a fresh one. `None`
+    /// when there is nothing to refresh.
+    pub async fn refresh_after_unauthorized(&self, rejected: &str) -> Result<Option<String>> {
+        let Source::OAuth {
+            refresher,
+            store,
+            tokens,
+        } = &self.source
+        else {
+            return Ok(None);
+        };
+
+        let mut tokens = tokens.lock().await;
+        if tokens.access_token != rejected {
+            return Ok(Some(tokens.access_token.clone()));
+        }
+        Ok(refresh(refresher.as_ref(), store, &mut tokens)
+            .await?
+            .then(|| tokens.access_token.clone()))
+    }
+
+    /// Run `request` with the current token. If the server rejects the token
+    /// ([`ProviderError::Auth`]) and it can be refreshed, run it once more.
+    pub async fn with_token<F, Fut, T>(&self, request: F) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let token = self.token().await?;
+        match request(token.clone()).await {
+            Err(e) if matches!(e.downcast_ref(), Some(ProviderError::Auth { .. })) => {
+                match self.refresh_after_unauthorized(&token).await? {
+                    Some(fresh) => request(fresh).await,
+                    None => Err(e),
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+/// Refresh `tokens` in place and persist them. Called with the lock held.
+/// Returns false when there was no way to get new tokens.
+async fn refresh(
+    refresher: &dyn TokenRefresher,
+    store: &TokenStore,
+    tokens: &mut TokenStorage,
+) -> Result<bool> {
+    let refresh_token = tokens.refresh_token.clone();
+    let response = match &refresh_token {
+        Some(refresh_token) => Some(refresher.refresh(refresh_token).await),
+        None => refresher.reacquire().await.transpose(),
+    };
+    let Some(response) = response else {
+        return Ok(false);
+    };
+    let response = response.map_err(|e| {
+        e.context("Could not refresh the OAuth token; log in again with: rco auth login")
+    })?;
+
+    // Some servers rotate the refresh token, others keep the old one valid
+    let rotated = response.refresh_token.or(refresh_token);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    *tokens = TokenStorage {
+        access_token: response.access_token,
+        refresh_token: rotated,
+        expires_at: response.expires_in.map(|secs| now + secs),
+        token_type: tokens.token_type.clone(),
+        scope: response.scope.or_else(|| tokens.scope.clone()),
+    };
+    tracing::debug!("Refreshed OAuth access token");
+
+    let saved = match store {
+        TokenStore::Default => token_storage::store_tokens(
+            &tokens.access_token,
+            tokens.refresh_token.as_deref(),
+            response.expires_in,
+        ),
+        TokenStore::Account(account_id) => token_storage::store_tokens_for_account(
+            account_id,
+            &tokens.access_token,
+            tokens.refresh_token.as_deref(),
+            response.expires_in,
+        ),
+        TokenStore::Memory => Ok(()),
+    };
+    if let Err(e) = saved {
+        crate::output::context::get().warning(format!(
+            "⚠️  Refreshed the OAuth token but could not save it: {e}"
+        ));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Hands out `access-N` tokens and counts refreshes.
+    struct CountingRefresher(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl TokenRefresher for CountingRefresher {
+        async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse> {
+            assert!(refresh_token.starts_with("refresh"));
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            Ok(TokenResponse {
+                access_token: format!("access-{n}"),
+                token_type: "Bearer".to_string(),
+                expires_in: Some(3600),
+                refresh_token: Some(format!("refresh-{n}")),
+                scope: None,
+            })
+        }
+    }
+
+    fn provider(expires_at: u64) -> (CredentialProvider, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let tokens = TokenStorage {
+            access_token: "access-0".to_string(),
+            refresh_token: Some("refresh-0".to_string()),
+            expires_at: Some(expires_at),
+            token_type: "Bearer".to_string(),
+            scope: None,
+        };
+        let provider = CredentialProvider::oauth(
+            Box::new(CountingRefresher(count.clone())),
+            TokenStore::Memory,
+            tokens,
+        );
+        (provider, count)
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_share_one_refresh() {
+        let (provider, count) = provider(0);
+
+        let tokens = futures::future::join_all((0..5).map(|_| provider.token())).await;
+        assert!(tokens.iter().all(|t| t.as_deref().unwrap() == "access-1"));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    fn auth_error(message: &str) -> anyhow::Error {
+        ProviderError::Auth {
+            message: message.to_string(),
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn retries_once_with_a_refreshed_token_after_401() {
+        let (provider, count) = provider(u64::MAX);
+        let attempts = AtomicUsize::new(0);
+
+        let used = provider
+            .with_token(|token| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if token == "access-0" {
+                        Err(auth_error("expired"))
+                    } else {
+                        Ok(token)
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(used, "access-1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // A second request that was rejected with the old token reuses the new one
+        let fresh = provider
+            .refresh_after_unauthorized("access-0")
+            .await
+            .unwrap();
+        assert_eq!(fresh.as_deref(), Some("access-1"));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn fixed_keys_are_not_retried() {
+        let provider = CredentialProvider::fixed("sk-test");
+        let err = provider
+            .with_token(|_| async { Err::<(), _>(auth_error("bad key")) })
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "bad key");
+    }
+}
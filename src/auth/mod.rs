@@ -1,5 +1,8 @@
 pub mod codex_oauth;
+pub mod command;
+pub mod credentials;
 pub mod oauth;
+pub mod oidc;
 pub mod token_storage;
 
 use crate::config::accounts::{AccountConfig, AuthMethod};
@@ -49,10 +52,7 @@ pub fn is_account_authenticated(account: &AccountConfig) -> bool {
                 .flatten()
                 .is_some()
         }
-        AuthMethod::OAuth {
-            provider: _,
-            account_id,
-        } => {
+        AuthMethod::OAuth { account_id, .. } => {
             // Check if we have valid OAuth tokens
             if let Ok(Some(tokens)) = token_storage::get_tokens_for_account(account_id) {
                 !tokens.is_expired()
@@ -71,9 +71,39 @@ pub fn is_account_authenticated(account: &AccountConfig) -> bool {
                 .flatten()
                 .is_some()
         }
+        AuthMethod::Command {
+            command,
+            timeout_secs,
+            cache_ttl_secs,
+        } => command::fetch_with(command, *timeout_secs, *cache_ttl_secs).is_ok(),
     }
 }
 
+/// Resolve the credential (API key or access token) for an account, if any.
+pub fn account_credential(account: &AccountConfig) -> Result<Option<String>> {
+    use crate::config::secure_storage;
+
+    Ok(match &account.auth {
+        AuthMethod::ApiKey { key_id } => token_storage::get_api_key_for_account(key_id)?
+            .or_else(|| secure_storage::get_secret(key_id).ok().flatten()),
+        AuthMethod::OAuth { account_id, .. } => {
+            token_storage::get_tokens_for_account(account_id)?.map(|t| t.access_token)
+        }
+        AuthMethod::EnvVar { name } => std::env::var(name).ok(),
+        AuthMethod::Bearer { token_id } => token_storage::get_bearer_token_for_account(token_id)?
+            .or_else(|| secure_storage::get_secret(token_id).ok().flatten()),
+        AuthMethod::Command {
+            command,
+            timeout_secs,
+            cache_ttl_secs,
+        } => Some(command::fetch_with(
+            command,
+            *timeout_secs,
+            *cache_ttl_secs,
+        )?),
+    })
+}
+
 /// Get authentication header for an account
 #[allow(dead_code)]
 pub fn get_account_auth_header(account: &AccountConfig) -> Result<String> {
@@ -95,10 +125,7 @@ pub fn get_account_auth_header(account: &AccountConfig) -> Result<String> {
                 )
             }
         }
-        AuthMethod::OAuth {
-            provider: _,
-            account_id,
-        } => {
+        AuthMethod::OAuth { account_id, .. } => {
             if let Ok(Some(tokens)) = token_storage::get_tokens_for_account(account_id) {
                 Ok(format!("Bearer {}", tokens.access_token))
             } else {
@@ -126,6 +153,11 @@ pub fn get_account_auth_header(account: &AccountConfig) -> Result<String> {
                 anyhow::bail!("Bearer token not found for account '{}'", account.alias)
             }
         }
+        AuthMethod::Command {
+            command,
+            timeout_secs,
+            cache_ttl_secs,
+        } => command::fetch_with(command, *timeout_secs, *cache_ttl_secs),
     }
 }
 
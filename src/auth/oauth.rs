@@ -13,27 +13,41 @@ pub const AUTHORIZE_URL: &str = "https://claude.ai/oauth/authorize";
 pub const TOKEN_URL: &str = "https://claude.ai/oauth/token";
 pub const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e"; // Public client ID for CLI apps
 pub const REDIRECT_URI: &str = "http://localhost:8989/callback";
+pub const DEVICE_CODE_URL: &str = "https://claude.ai/oauth/device/code";
+const DEFAULT_SCOPE: &str = "openid profile email";
+
+/// Grant type for polling the token endpoint in the device flow (RFC 8628).
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Added to the polling interval each time the server answers `slow_down`.
+const SLOW_DOWN_STEP: u64 = 5;
+
+/// Longest wait between polls after repeated network errors.
+const MAX_POLL_INTERVAL: u64 = 60;
 
 #[derive(Debug, Serialize)]
-#[allow(dead_code)]
 struct DeviceCodeRequest {
     client_id: String,
     scope: String,
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 pub struct DeviceCodeResponse {
     pub device_code: String,
     pub user_code: String,
     pub verification_uri: String,
     pub verification_uri_complete: Option<String>,
     pub expires_in: u64,
+    /// Seconds to wait between polls; RFC 8628 says to assume 5 if absent.
+    #[serde(default = "default_poll_interval")]
     pub interval: u64,
 }
 
+fn default_poll_interval() -> u64 {
+    5
+}
+
 #[derive(Debug, Serialize)]
-#[allow(dead_code)]
 struct TokenRequest {
     grant_type: String,
     device_code: String,
@@ -68,7 +82,12 @@ struct ErrorResponse {
 pub struct OAuthClient {
     client: Client,
     client_id: String,
+    scope: String,
     redirect_uri: String,
+    device_code_url: String,
+    token_url: String,
+    /// Length of one interval second while polling; shortened in tests.
+    poll_unit: Duration,
 }
 
 impl Default for OAuthClient {
@@ -82,12 +101,136 @@ impl OAuthClient {
         Self {
             client: Client::new(),
             client_id: CLIENT_ID.to_string(),
+            scope: DEFAULT_SCOPE.to_string(),
             redirect_uri: REDIRECT_URI.to_string(),
+            device_code_url: DEVICE_CODE_URL.to_string(),
+            token_url: TOKEN_URL.to_string(),
+            poll_unit: Duration::from_secs(1),
+        }
+    }
+
+    /// Send requests with `client`, e.g. one with the user's proxy and CA
+    /// settings.
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Use different device authorization and token endpoints.
+    pub fn with_endpoints(mut self, device_code_url: &str, token_url: &str) -> Self {
+        self.device_code_url = device_code_url.to_string();
+        self.token_url = token_url.to_string();
+        self
+    }
+
+    /// Authenticate as a different client, e.g. one registered with an
+    /// OIDC issuer.
+    pub fn with_client(mut self, client_id: &str, scope: &str) -> Self {
+        self.client_id = client_id.to_string();
+        self.scope = scope.to_string();
+        self
+    }
+
+    /// Start the device authorization flow (RFC 8628) for machines without
+    /// a browser: returns the code the user enters at the verification URI.
+    pub async fn request_device_code(&self) -> Result<DeviceCodeResponse> {
+        let request = DeviceCodeRequest {
+            client_id: self.client_id.clone(),
+            scope: self.scope.clone(),
+        };
+
+        let response = self
+            .client
+            .post(&self.device_code_url)
+            .form(&request)
+            .send()
+            .await
+            .context("Failed to request a device code")?;
+
+        if response.status().is_success() {
+            response
+                .json::<DeviceCodeResponse>()
+                .await
+                .context("Failed to parse device code response")
+        } else {
+            let error: ErrorResponse = response.json().await?;
+            anyhow::bail!(
+                "Device authorization failed: {} - {}",
+                error.error,
+                error.error_description.unwrap_or_default()
+            )
+        }
+    }
+
+    /// Poll the token endpoint until the user approves the device code.
+    ///
+    /// Waits `interval` between polls, adds 5 seconds on `slow_down`, keeps
+    /// going on `authorization_pending`, and backs off on network errors.
+    pub async fn poll_device_token(&self, device: &DeviceCodeResponse) -> Result<TokenResponse> {
+        let request = TokenRequest {
+            grant_type: DEVICE_CODE_GRANT.to_string(),
+            device_code: device.device_code.clone(),
+            client_id: self.client_id.clone(),
+        };
+        let deadline = std::time::Instant::now() + self.poll_unit * device.expires_in as u32;
+        let mut interval = device.interval.max(1);
+        let mut backoff = interval;
+
+        loop {
+            sleep(self.poll_unit * backoff as u32).await;
+            if std::time::Instant::now() >= deadline {
+                anyhow::bail!("The device code expired before it was approved; run login again");
+            }
+
+            let response = match self
+                .client
+                .post(&self.token_url)
+                .form(&request)
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::debug!("Device token poll failed: {}", e);
+                    backoff = (backoff * 2).min(MAX_POLL_INTERVAL);
+                    continue;
+                }
+            };
+
+            if response.status().is_success() {
+                return response
+                    .json::<TokenResponse>()
+                    .await
+                    .context("Failed to parse token response");
+            }
+            if response.status().is_server_error() {
+                backoff = (backoff * 2).min(MAX_POLL_INTERVAL);
+                continue;
+            }
+
+            let error: ErrorResponse = response
+                .json()
+                .await
+                .context("Failed to parse device token error")?;
+            match error.error.as_str() {
+                "authorization_pending" => {}
+                "slow_down" => interval += SLOW_DOWN_STEP,
+                "access_denied" => anyhow::bail!("Authorization was denied"),
+                "expired_token" => {
+                    anyhow::bail!("The device code expired before it was approved; run login again")
+                }
+                other => anyhow::bail!(
+                    "Token request failed: {} - {}",
+                    other,
+                    error.error_description.unwrap_or_default()
+                ),
+            }
+            backoff = interval;
         }
     }
 
     /// Generate PKCE challenge and verifier
-    fn generate_pkce() -> Result<(String, String)> {
+    pub(crate) fn generate_pkce() -> Result<(String, String)> {
         // Generate random verifier
         let mut bytes = [0u8; 32];
         generate_random_bytes(&mut bytes)?;
@@ -111,7 +254,7 @@ impl OAuthClient {
             ("client_id", &self.client_id),
             ("redirect_uri", &self.redirect_uri),
             ("response_type", &"code".to_string()),
-            ("scope", &"openid profile email".to_string()),
+            ("scope", &self.scope),
             ("state", &state),
             ("code_challenge", &challenge),
             ("code_challenge_method", &"S256".to_string()),
@@ -232,7 +375,7 @@ impl OAuthClient {
 
         let response = self
             .client
-            .post(TOKEN_URL)
+            .post(&self.token_url)
             .form(&params)
             .send()
             .await
@@ -263,7 +406,7 @@ impl OAuthClient {
 
         let response = self
             .client
-            .post(TOKEN_URL)
+            .post(&self.token_url)
             .json(&request)
             .send()
             .await
@@ -302,3 +445,98 @@ fn generate_random_bytes(dest: &mut [u8]) -> Result<()> {
     rng.fill_bytes(dest);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_for(server: &mockito::Server) -> OAuthClient {
+        let mut client = OAuthClient::new().with_endpoints(
+            &format!("{}/oauth/device/code", server.url()),
+            &format!("{}/oauth/token", server.url()),
+        );
+        client.poll_unit = Duration::from_millis(1);
+        client
+    }
+
+    fn token_error(error: &str) -> String {
+        format!(r#"{{"error":"{}"}}"#, error)
+    }
+
+    #[tokio::test]
+    async fn device_flow_polls_until_approved() {
+        let mut server = mockito::Server::new_async().await;
+        let device = server
+            .mock("POST", "/oauth/device/code")
+            .match_body(mockito::Matcher::UrlEncoded(
+                "client_id".into(),
+                CLIENT_ID.into(),
+            ))
+            .with_body(
+                r#"{"device_code":"dev-123","user_code":"ABCD-EFGH",
+                    "verification_uri":"https://claude.ai/device","expires_in":600}"#,
+            )
+            .create_async()
+            .await;
+        let pending = server
+            .mock("POST", "/oauth/token")
+            .with_status(400)
+            .with_body(token_error("authorization_pending"))
+            .expect(2)
+            .create_async()
+            .await;
+        let slow_down = server
+            .mock("POST", "/oauth/token")
+            .with_status(400)
+            .with_body(token_error("slow_down"))
+            .expect(1)
+            .create_async()
+            .await;
+        let approved = server
+            .mock("POST", "/oauth/token")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("grant_type".into(), DEVICE_CODE_GRANT.into()),
+                mockito::Matcher::UrlEncoded("device_code".into(), "dev-123".into()),
+            ]))
+            .with_body(r#"{"access_token":"at-1","token_type":"Bearer","expires_in":3600}"#)
+            .create_async()
+            .await;
+
+        let client = client_for(&server);
+        let code = client.request_device_code().await.unwrap();
+        assert_eq!(code.user_code, "ABCD-EFGH");
+        assert_eq!(code.interval, 5);
+
+        let token = client.poll_device_token(&code).await.unwrap();
+        assert_eq!(token.access_token, "at-1");
+        device.assert_async().await;
+        pending.assert_async().await;
+        slow_down.assert_async().await;
+        approved.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn device_flow_stops_when_denied() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/oauth/token")
+            .with_status(400)
+            .with_body(token_error("access_denied"))
+            .create_async()
+            .await;
+
+        let device = DeviceCodeResponse {
+            device_code: "dev-123".to_string(),
+            user_code: "ABCD-EFGH".to_string(),
+            verification_uri: "https://claude.ai/device".to_string(),
+            verification_uri_complete: None,
+            expires_in: 600,
+            interval: 1,
+        };
+        let err = client_for(&server)
+            .poll_device_token(&device)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("denied"), "{err}");
+    }
+}
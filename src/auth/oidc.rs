@@ -0,0 +1,392 @@
+//! Generic OAuth 2.0 / OpenID Connect accounts, for gateways that put an
+//! OpenAI-compatible API behind a company identity provider.
+//!
+//! Endpoints are discovered from the issuer's
+//! `.well-known/openid-configuration`. People log in with the authorization
+//! code flow and PKCE (or the device flow without a browser); CI uses the
+//! client-credentials grant with a secret taken from the environment.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::sync::{mpsc, OnceCell};
+
+use super::credentials::TokenRefresher;
+use super::oauth::{OAuthClient, TokenResponse};
+use crate::config::accounts::{OidcConfig, OidcGrant};
+
+/// Callback port for browser logins when the account does not set one.
+pub const DEFAULT_REDIRECT_PORT: u16 = 8990;
+
+/// Scopes requested by interactive logins when the account lists none;
+/// `offline_access` asks for a refresh token.
+const DEFAULT_LOGIN_SCOPES: &str = "openid offline_access";
+
+/// How long a browser login waits for the redirect.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The parts of an OpenID provider's metadata rco uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Discovery {
+    pub token_endpoint: String,
+    #[serde(default)]
+    pub authorization_endpoint: Option<String>,
+    #[serde(default)]
+    pub device_authorization_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// OAuth client for an account's configured issuer.
+pub struct OidcClient {
+    client: Client,
+    config: OidcConfig,
+    discovery: OnceCell<Discovery>,
+}
+
+impl OidcClient {
+    /// A client for the issuer in `config`, sending requests with `client`
+    /// (see [`crate::config::http::account_client`]).
+    pub fn new(config: OidcConfig, client: Client) -> Self {
+        Self {
+            client,
+            config,
+            discovery: OnceCell::new(),
+        }
+    }
+
+    /// The issuer's endpoints, fetched on first use.
+    pub async fn discovery(&self) -> Result<&Discovery> {
+        self.discovery
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim_end_matches('/')
+                );
+                let response = self
+                    .client
+                    .get(&url)
+                    .send()
+                    .await
+                    .with_context(|| format!("Failed to reach the OIDC issuer at {}", url))?;
+                if !response.status().is_success() {
+                    bail!("OIDC discovery failed ({}) for {}", response.status(), url);
+                }
+                response
+                    .json::<Discovery>()
+                    .await
+                    .with_context(|| format!("Invalid OpenID configuration at {}", url))
+            })
+            .await
+    }
+
+    /// Space-separated scopes to request.
+    fn scope(&self) -> Option<String> {
+        if !self.config.scopes.is_empty() {
+            Some(self.config.scopes.join(" "))
+        } else if self.config.grant == OidcGrant::AuthorizationCode {
+            Some(DEFAULT_LOGIN_SCOPES.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// The client secret from `client_secret_env`, if configured.
+    fn client_secret(&self) -> Result<Option<String>> {
+        let Some(name) = &self.config.client_secret_env else {
+            return Ok(None);
+        };
+        std::env::var(name)
+            .map(Some)
+            .with_context(|| format!("{} is not set; it should hold the OIDC client secret", name))
+    }
+
+    fn redirect_uri(&self) -> String {
+        format!(
+            "http://localhost:{}/callback",
+            self.config.redirect_port.unwrap_or(DEFAULT_REDIRECT_PORT)
+        )
+    }
+
+    /// Get tokens with the client-credentials grant.
+    pub async fn client_credentials(&self) -> Result<TokenResponse> {
+        if self.client_secret()?.is_none() {
+            bail!("The client-credentials grant needs client_secret_env set for the account");
+        }
+        let scope = self.scope();
+        let mut params = vec![("grant_type", "client_credentials")];
+        if let Some(scope) = &scope {
+            params.push(("scope", scope));
+        }
+        if let Some(audience) = &self.config.audience {
+            params.push(("audience", audience));
+        }
+        self.token_request(&params).await
+    }
+
+    /// Exchange a refresh token for new tokens.
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse> {
+        self.token_request(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ])
+        .await
+    }
+
+    /// The authorization URL for a PKCE login.
+    async fn authorization_url(&self, state: &str, challenge: &str) -> Result<String> {
+        let endpoint = self
+            .discovery()
+            .await?
+            .authorization_endpoint
+            .clone()
+            .context("The OIDC issuer does not advertise an authorization endpoint")?;
+
+        let redirect_uri = self.redirect_uri();
+        let scope = self.scope().unwrap_or_default();
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("scope", scope.as_str()),
+            ("state", state),
+            ("code_challenge", challenge),
+            ("code_challenge_method", "S256"),
+        ];
+        if let Some(audience) = &self.config.audience {
+            params.push(("audience", audience));
+        }
+        let query = serde_urlencoded::to_string(params).context("Failed to encode OAuth params")?;
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        Ok(format!("{endpoint}{separator}{query}"))
+    }
+
+    /// Log in with the authorization code flow and PKCE. `open` receives the
+    /// URL to visit; the redirect is caught on a local callback server.
+    pub async fn browser_login(&self, open: impl FnOnce(&str)) -> Result<TokenResponse> {
+        use warp::Filter;
+
+        let (verifier, challenge) = OAuthClient::generate_pkce()?;
+        let state = URL_SAFE_NO_PAD.encode(uuid::Uuid::new_v4().as_bytes());
+        let url = self.authorization_url(&state, &challenge).await?;
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let callback = warp::path("callback")
+            .and(warp::query::<HashMap<String, String>>())
+            .map(move |params: HashMap<String, String>| {
+                let _ = tx.send(params);
+                warp::reply::html(
+                    "<html><body><h1>Signed in</h1>\
+                     <p>You can close this window and return to your terminal.</p></body></html>",
+                )
+            });
+        let port = self.config.redirect_port.unwrap_or(DEFAULT_REDIRECT_PORT);
+        let server = tokio::spawn(warp::serve(callback).bind(([127, 0, 0, 1], port)));
+
+        open(&url);
+        let params = tokio::time::timeout(LOGIN_TIMEOUT, rx.recv()).await;
+        server.abort();
+        let params = params
+            .context("Authentication timeout - no response received")?
+            .context("The login callback server stopped")?;
+
+        if let Some(error) = params.get("error") {
+            bail!(
+                "Authorization failed: {} {}",
+                error,
+                params
+                    .get("error_description")
+                    .map(String::as_str)
+                    .unwrap_or("")
+            );
+        }
+        if params.get("state") != Some(&state) {
+            bail!("The login callback did not match this login attempt; try again");
+        }
+        let code = params
+            .get("code")
+            .context("The login callback carried no authorization code")?;
+
+        let redirect_uri = self.redirect_uri();
+        self.token_request(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &redirect_uri),
+            ("code_verifier", &verifier),
+        ])
+        .await
+    }
+
+    /// A device flow client for the issuer, if it supports RFC 8628.
+    pub async fn device_client(&self) -> Result<OAuthClient> {
+        let discovery = self.discovery().await?;
+        let device_endpoint = discovery
+            .device_authorization_endpoint
+            .as_deref()
+            .context("The OIDC issuer does not support the device flow; log in from a machine with a browser")?;
+        Ok(OAuthClient::new()
+            .with_http_client(self.client.clone())
+            .with_endpoints(device_endpoint, &discovery.token_endpoint)
+            .with_client(&self.config.client_id, &self.scope().unwrap_or_default()))
+    }
+
+    /// POST a form to the token endpoint, adding the client's credentials.
+    async fn token_request(&self, params: &[(&str, &str)]) -> Result<TokenResponse> {
+        let token_endpoint = self.discovery().await?.token_endpoint.clone();
+        let secret = self.client_secret()?;
+
+        let mut form: Vec<(&str, &str)> = params.to_vec();
+        form.push(("client_id", &self.config.client_id));
+        if let Some(secret) = &secret {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self
+            .client
+            .post(&token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .context("Failed to reach the OIDC token endpoint")?;
+
+        if response.status().is_success() {
+            response
+                .json::<TokenResponse>()
+                .await
+                .context("Failed to parse token response")
+        } else {
+            let status = response.status();
+            match response.json::<ErrorResponse>().await {
+                Ok(error) => bail!(
+                    "Token request failed: {} - {}",
+                    error.error,
+                    error.error_description.unwrap_or_default()
+                ),
+                Err(_) => bail!("Token request failed ({})", status),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TokenRefresher for OidcClient {
+    async fn refresh(&self, refresh_token: &str) -> Result<TokenResponse> {
+        self.refresh_token(refresh_token).await
+    }
+
+    async fn reacquire(&self) -> Result<Option<TokenResponse>> {
+        match self.config.grant {
+            OidcGrant::ClientCredentials => self.client_credentials().await.map(Some),
+            OidcGrant::AuthorizationCode => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn config(server: &mockito::Server, grant: OidcGrant) -> OidcConfig {
+        OidcConfig {
+            issuer: format!("{}/realms/dev/", server.url()),
+            client_id: "rco-cli".to_string(),
+            scopes: Vec::new(),
+            grant,
+            client_secret_env: None,
+            audience: None,
+            redirect_port: None,
+        }
+    }
+
+    async fn mock_discovery(server: &mut mockito::Server) -> mockito::Mock {
+        let body = format!(
+            r#"{{"issuer":"{url}/realms/dev","token_endpoint":"{url}/token",
+                "authorization_endpoint":"{url}/authorize",
+                "device_authorization_endpoint":"{url}/device"}}"#,
+            url = server.url()
+        );
+        server
+            .mock("GET", "/realms/dev/.well-known/openid-configuration")
+            .with_body(body)
+            .expect(1)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn client_credentials_use_the_discovered_token_endpoint() {
+        let mut server = mockito::Server::new_async().await;
+        let discovery = mock_discovery(&mut server).await;
+        let token = server
+            .mock("POST", "/token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()),
+                Matcher::UrlEncoded("client_id".into(), "rco-cli".into()),
+                Matcher::UrlEncoded("client_secret".into(), "s3cret".into()),
+                Matcher::UrlEncoded("audience".into(), "llm-gateway".into()),
+            ]))
+            .with_body(r#"{"access_token":"ci-token","token_type":"Bearer","expires_in":300}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        std::env::set_var("RCO_TEST_OIDC_SECRET", "s3cret");
+        let mut config = config(&server, OidcGrant::ClientCredentials);
+        config.client_secret_env = Some("RCO_TEST_OIDC_SECRET".to_string());
+        config.audience = Some("llm-gateway".to_string());
+        let client = OidcClient::new(config, Client::new());
+
+        assert_eq!(
+            client.client_credentials().await.unwrap().access_token,
+            "ci-token"
+        );
+        let again = client.reacquire().await.unwrap().unwrap();
+        assert_eq!(again.access_token, "ci-token");
+        discovery.assert_async().await;
+        token.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn login_urls_carry_pkce_and_default_scopes() {
+        let mut server = mockito::Server::new_async().await;
+        mock_discovery(&mut server).await;
+        let client = OidcClient::new(config(&server, OidcGrant::AuthorizationCode), Client::new());
+
+        let url = client.authorization_url("st", "ch").await.unwrap();
+        let url = url::Url::parse(&url).unwrap();
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(url.path(), "/authorize");
+        assert_eq!(query["client_id"], "rco-cli");
+        assert_eq!(query["scope"], "openid offline_access");
+        assert_eq!(query["code_challenge"], "ch");
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["redirect_uri"], "http://localhost:8990/callback");
+    }
+
+    #[tokio::test]
+    async fn refresh_reports_issuer_errors() {
+        let mut server = mockito::Server::new_async().await;
+        mock_discovery(&mut server).await;
+        server
+            .mock("POST", "/token")
+            .with_status(400)
+            .with_body(r#"{"error":"invalid_grant","error_description":"Token expired"}"#)
+            .create_async()
+            .await;
+
+        let client = OidcClient::new(config(&server, OidcGrant::AuthorizationCode), Client::new());
+        let err = client.refresh_token("rt").await.unwrap_err();
+        assert!(err.to_string().contains("invalid_grant"), "{err}");
+        assert!(client.reacquire().await.unwrap().is_none());
+    }
+}
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::config::accounts::{AccountConfig, AccountsConfig, AuthMethod};
+use crate::utils::fs::write_private;
 
 /// Get current Unix timestamp in seconds.
 ///
@@ -25,6 +29,17 @@ pub struct TokenStorage {
 }
 
 impl TokenStorage {
+    /// A placeholder with no access token, so the first use fetches one.
+    pub fn expired() -> Self {
+        Self {
+            access_token: String::new(),
+            refresh_token: None,
+            expires_at: Some(0),
+            token_type: "Bearer".to_string(),
+            scope: None,
+        }
+    }
+
     /// Get the path to the auth token file
     fn auth_file_path() -> Result<PathBuf> {
         let config_dir = if let Ok(config_home) = std::env::var("RCO_CONFIG_HOME") {
@@ -49,19 +64,8 @@ impl TokenStorage {
         // Serialize to JSON with pretty printing
         let json = serde_json::to_string_pretty(self).context("Failed to serialize token data")?;
 
-        // Write to file with restricted permissions
-        fs::write(&path, json).context("Failed to write auth token file")?;
-
-        // Set file permissions to 600 (user read/write only) on Unix
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = fs::metadata(&path)?.permissions();
-            perms.set_mode(0o600);
-            fs::set_permissions(&path, perms).context("Failed to set auth file permissions")?;
-        }
-
-        Ok(())
+        // Write atomically, readable only by the user (600 on Unix)
+        write_private(&path, json.as_bytes()).context("Failed to write auth token file")
     }
 
     /// Load tokens from file
@@ -102,7 +106,6 @@ impl TokenStorage {
     }
 
     /// Check if token will expire soon (within 5 minutes)
-    #[allow(dead_code)]
     pub fn expires_soon(&self) -> bool {
         if let Some(expires_at) = self.expires_at {
             let now = current_unix_timestamp().unwrap_or(u64::MAX);
@@ -120,45 +123,46 @@ pub fn store_tokens(
     expires_in: Option<u64>,
 ) -> Result<()> {
     // First try secure storage if available
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            // Attempt secure storage; on failure fall through to file storage
-            if let Err(e) =
-                crate::config::secure_storage::store_secret("claude_access_token", access_token)
-            {
-                eprintln!(
-                    "Note: Could not store access token in secure storage: {}",
-                    e
-                );
-            } else {
-                if let Some(refresh) = refresh_token {
-                    if let Err(e) =
-                        crate::config::secure_storage::store_secret("claude_refresh_token", refresh)
-                    {
-                        eprintln!(
-                            "Note: Could not store refresh token in secure storage: {}",
-                            e
-                        );
-                    }
+    crate::config::secure_storage::require_unlocked_vault()?;
+    if crate::config::secure_storage::is_available() {
+        // Attempt secure storage; on failure fall through to file storage
+        if let Err(e) =
+            crate::config::secure_storage::store_secret("claude_access_token", access_token)
+        {
+            if !crate::config::secure_storage::falls_back_to_plaintext() {
+                return Err(e);
+            }
+            crate::output::context::get().warning(format!(
+                "Note: Could not store access token in secure storage: {}",
+                e
+            ));
+        } else {
+            if let Some(refresh) = refresh_token {
+                if let Err(e) =
+                    crate::config::secure_storage::store_secret("claude_refresh_token", refresh)
+                {
+                    crate::output::context::get().warning(format!(
+                        "Note: Could not store refresh token in secure storage: {}",
+                        e
+                    ));
                 }
+            }
 
-                if let Some(expires_in) = expires_in {
-                    let expires_at = current_unix_timestamp().unwrap_or(u64::MAX) + expires_in;
-                    if let Err(e) = crate::config::secure_storage::store_secret(
-                        "claude_token_expires_at",
-                        &expires_at.to_string(),
-                    ) {
-                        eprintln!(
-                            "Note: Could not store token expiry in secure storage: {}",
-                            e
-                        );
-                    }
+            if let Some(expires_in) = expires_in {
+                let expires_at = current_unix_timestamp().unwrap_or(u64::MAX) + expires_in;
+                if let Err(e) = crate::config::secure_storage::store_secret(
+                    "claude_token_expires_at",
+                    &expires_at.to_string(),
+                ) {
+                    crate::output::context::get().warning(format!(
+                        "Note: Could not store token expiry in secure storage: {}",
+                        e
+                    ));
                 }
-
-                // If we successfully stored the access token, prefer secure storage and return
-                return Ok(());
             }
+
+            // If we successfully stored the access token, prefer secure storage and return
+            return Ok(());
         }
     }
 
@@ -180,31 +184,26 @@ pub fn store_tokens(
 /// Get stored OAuth tokens (tries secure storage first, then file)
 pub fn get_tokens() -> Result<Option<TokenStorage>> {
     // First try secure storage if available
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            if let Ok(Some(access_token)) =
-                crate::config::secure_storage::get_secret("claude_access_token")
-            {
-                let refresh_token =
-                    crate::config::secure_storage::get_secret("claude_refresh_token")
-                        .ok()
-                        .flatten();
-
-                let expires_at =
-                    crate::config::secure_storage::get_secret("claude_token_expires_at")
-                        .ok()
-                        .flatten()
-                        .and_then(|s| s.parse::<u64>().ok());
-
-                return Ok(Some(TokenStorage {
-                    access_token,
-                    refresh_token,
-                    expires_at,
-                    token_type: "Bearer".to_string(),
-                    scope: Some("openid profile email".to_string()),
-                }));
-            }
+    if crate::config::secure_storage::is_available() {
+        if let Ok(Some(access_token)) =
+            crate::config::secure_storage::get_secret("claude_access_token")
+        {
+            let refresh_token = crate::config::secure_storage::get_secret("claude_refresh_token")
+                .ok()
+                .flatten();
+
+            let expires_at = crate::config::secure_storage::get_secret("claude_token_expires_at")
+                .ok()
+                .flatten()
+                .and_then(|s| s.parse::<u64>().ok());
+
+            return Ok(Some(TokenStorage {
+                access_token,
+                refresh_token,
+                expires_at,
+                token_type: "Bearer".to_string(),
+                scope: Some("openid profile email".to_string()),
+            }));
         }
     }
 
@@ -215,12 +214,9 @@ pub fn get_tokens() -> Result<Option<TokenStorage>> {
 /// Delete stored OAuth tokens (from both secure storage and file)
 pub fn delete_tokens() -> Result<()> {
     // Delete from secure storage if available
-    #[cfg(feature = "secure-storage")]
-    {
-        let _ = crate::config::secure_storage::delete_secret("claude_access_token");
-        let _ = crate::config::secure_storage::delete_secret("claude_refresh_token");
-        let _ = crate::config::secure_storage::delete_secret("claude_token_expires_at");
-    }
+    let _ = crate::config::secure_storage::delete_secret("claude_access_token");
+    let _ = crate::config::secure_storage::delete_secret("claude_refresh_token");
+    let _ = crate::config::secure_storage::delete_secret("claude_token_expires_at");
 
     // Delete file storage
     TokenStorage::delete()?;
@@ -245,187 +241,337 @@ pub fn has_valid_token() -> bool {
 // Account-scoped token storage (for multi-account support)
 // ============================================
 
+/// Per-account credentials kept in `credentials.json` when secure storage is
+/// unavailable, keyed by account id and then by credential type.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CredentialsFile {
+    /// Set once legacy single-account credentials have been handed out.
+    #[serde(default)]
+    legacy_migrated: bool,
+    #[serde(default)]
+    accounts: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl CredentialsFile {
+    fn path() -> Result<PathBuf> {
+        Ok(TokenStorage::auth_file_path()?.with_file_name("credentials.json"))
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read credentials file")?;
+        serde_json::from_str(&contents).context("Failed to parse credentials file")
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize credentials")?;
+        write_private(path, json.as_bytes()).context("Failed to write credentials file")
+    }
+
+    fn get(&self, account_id: &str, key_type: &str) -> Option<String> {
+        self.accounts.get(account_id)?.get(key_type).cloned()
+    }
+
+    fn set(&mut self, account_id: &str, key_type: &str, value: &str) {
+        self.accounts
+            .entry(account_id.to_string())
+            .or_default()
+            .insert(key_type.to_string(), value.to_string());
+    }
+
+    fn remove(&mut self, account_id: &str, key_type: &str) -> bool {
+        let Some(entries) = self.accounts.get_mut(account_id) else {
+            return false;
+        };
+        let removed = entries.remove(key_type).is_some();
+        if entries.is_empty() {
+            self.accounts.remove(account_id);
+        }
+        removed
+    }
+}
+
 /// Generate storage key for an account
-#[allow(dead_code)]
 fn account_storage_key(account_id: &str, key_type: &str) -> String {
     format!("rco_account_{}_{}", account_id, key_type)
 }
 
+/// Store one credential for an account: in secure storage when available,
+/// otherwise (or if the keyring fails) in the credentials file. A locked
+/// vault is an error rather than a reason to write the file.
+fn put_account_secret(account_id: &str, key_type: &str, value: &str) -> Result<()> {
+    use crate::config::secure_storage;
+
+    secure_storage::require_unlocked_vault()?;
+    if secure_storage::is_available() {
+        let key = account_storage_key(account_id, key_type);
+        match secure_storage::store_secret(&key, value) {
+            Ok(()) => {
+                // Drop any stale copy so the two backends cannot disagree
+                remove_file_secret(account_id, key_type)?;
+                return Ok(());
+            }
+            Err(e) if !secure_storage::falls_back_to_plaintext() => return Err(e),
+            Err(e) => crate::output::context::get().warning(format!(
+                "Note: Could not store {} in secure storage, using the credentials file: {}",
+                key_type.replace('_', " "),
+                e
+            )),
+        }
+    }
+
+    let path = CredentialsFile::path()?;
+    let mut file = CredentialsFile::load_from(&path)?;
+    file.set(account_id, key_type, value);
+    file.save_to(&path)
+}
+
+/// Read one credential for an account from secure storage or the credentials file.
+fn get_account_secret(account_id: &str, key_type: &str) -> Result<Option<String>> {
+    use crate::config::secure_storage;
+
+    if secure_storage::is_available() {
+        let key = account_storage_key(account_id, key_type);
+        if let Some(value) = secure_storage::get_secret(&key)? {
+            return Ok(Some(value));
+        }
+    }
+
+    Ok(CredentialsFile::load_from(&CredentialsFile::path()?)?.get(account_id, key_type))
+}
+
+/// Delete one credential for an account from every backend.
+fn delete_account_secret(account_id: &str, key_type: &str) -> Result<()> {
+    let _ =
+        crate::config::secure_storage::delete_secret(&account_storage_key(account_id, key_type));
+    remove_file_secret(account_id, key_type)
+}
+
+fn remove_file_secret(account_id: &str, key_type: &str) -> Result<()> {
+    let path = CredentialsFile::path()?;
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut file = CredentialsFile::load_from(&path)?;
+    if file.remove(account_id, key_type) {
+        file.save_to(&path)?;
+    }
+    Ok(())
+}
+
 /// Store OAuth tokens for a specific account
-#[allow(dead_code)]
 pub fn store_tokens_for_account(
-    _account_id: &str,
+    account_id: &str,
     access_token: &str,
     refresh_token: Option<&str>,
     expires_in: Option<u64>,
 ) -> Result<()> {
-    // Use secure storage if available
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let access_key = account_storage_key(_account_id, "access_token");
-            if let Err(e) = crate::config::secure_storage::store_secret(&access_key, access_token) {
-                eprintln!(
-                    "Note: Could not store access token in secure storage: {}",
-                    e
-                );
-            } else {
-                // Store refresh token
-                if let Some(refresh) = refresh_token {
-                    let refresh_key = account_storage_key(_account_id, "refresh_token");
-                    let _ = crate::config::secure_storage::store_secret(&refresh_key, refresh);
-                }
-
-                // Store expiry
-                if let Some(expires_in) = expires_in {
-                    let expires_at = current_unix_timestamp().unwrap_or(u64::MAX) + expires_in;
-                    let expiry_key = account_storage_key(_account_id, "token_expires_at");
-                    let _ = crate::config::secure_storage::store_secret(
-                        &expiry_key,
-                        &expires_at.to_string(),
-                    );
-                }
+    let expires_at = expires_in.map(|exp| current_unix_timestamp().unwrap_or(u64::MAX) + exp);
+    store_token_set_for_account(account_id, access_token, refresh_token, expires_at)
+}
 
-                return Ok(());
-            }
+fn store_token_set_for_account(
+    account_id: &str,
+    access_token: &str,
+    refresh_token: Option<&str>,
+    expires_at: Option<u64>,
+) -> Result<()> {
+    put_account_secret(account_id, "access_token", access_token)?;
+    match refresh_token {
+        Some(refresh) => put_account_secret(account_id, "refresh_token", refresh)?,
+        None => delete_account_secret(account_id, "refresh_token")?,
+    }
+    match expires_at {
+        Some(expires_at) => {
+            put_account_secret(account_id, "token_expires_at", &expires_at.to_string())?
         }
+        None => delete_account_secret(account_id, "token_expires_at")?,
     }
-
-    // Fall back to file storage (not recommended for multi-account)
-    // For now, just store in the legacy location
-    store_tokens(access_token, refresh_token, expires_in)
+    Ok(())
 }
 
 /// Get OAuth tokens for a specific account
-#[allow(dead_code)]
-pub fn get_tokens_for_account(_account_id: &str) -> Result<Option<TokenStorage>> {
-    // Try secure storage first
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let access_key = account_storage_key(_account_id, "access_token");
-            if let Ok(Some(access_token)) = crate::config::secure_storage::get_secret(&access_key) {
-                let refresh_key = account_storage_key(_account_id, "refresh_token");
-                let refresh_token = crate::config::secure_storage::get_secret(&refresh_key)
-                    .ok()
-                    .flatten();
-
-                let expiry_key = account_storage_key(_account_id, "token_expires_at");
-                let expires_at = crate::config::secure_storage::get_secret(&expiry_key)
-                    .ok()
-                    .flatten()
-                    .and_then(|s| s.parse::<u64>().ok());
-
-                return Ok(Some(TokenStorage {
-                    access_token,
-                    refresh_token,
-                    expires_at,
-                    token_type: "Bearer".to_string(),
-                    scope: Some("openid profile email".to_string()),
-                }));
-            }
-        }
-    }
+pub fn get_tokens_for_account(account_id: &str) -> Result<Option<TokenStorage>> {
+    let Some(access_token) = get_account_secret(account_id, "access_token")? else {
+        return Ok(None);
+    };
+    let refresh_token = get_account_secret(account_id, "refresh_token")?;
+    let expires_at =
+        get_account_secret(account_id, "token_expires_at")?.and_then(|s| s.parse::<u64>().ok());
 
-    // Fall back to legacy storage for backward compatibility
-    get_tokens()
+    Ok(Some(TokenStorage {
+        access_token,
+        refresh_token,
+        expires_at,
+        token_type: "Bearer".to_string(),
+        scope: Some("openid profile email".to_string()),
+    }))
 }
 
 /// Delete OAuth tokens for a specific account
-#[allow(dead_code)]
-pub fn delete_tokens_for_account(_account_id: &str) -> Result<()> {
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            for key_type in ["access_token", "refresh_token", "token_expires_at"] {
-                let key = account_storage_key(_account_id, key_type);
-                let _ = crate::config::secure_storage::delete_secret(&key);
-            }
-        }
+pub fn delete_tokens_for_account(account_id: &str) -> Result<()> {
+    for key_type in ["access_token", "refresh_token", "token_expires_at"] {
+        delete_account_secret(account_id, key_type)?;
     }
-
     Ok(())
 }
 
 /// Store API key for a specific account
-#[allow(dead_code)]
-pub fn store_api_key_for_account(_account_id: &str, _api_key: &str) -> Result<()> {
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let key = account_storage_key(_account_id, "api_key");
-            crate::config::secure_storage::store_secret(&key, _api_key)?;
-            return Ok(());
-        }
-    }
-
-    // Fall back: can't store in file securely, warn user
-    anyhow::bail!(
-        "Secure storage not available. Cannot store API key for account '{}'.",
-        _account_id
-    )
+pub fn store_api_key_for_account(account_id: &str, api_key: &str) -> Result<()> {
+    put_account_secret(account_id, "api_key", api_key)
 }
 
 /// Get API key for a specific account
-#[allow(dead_code)]
-pub fn get_api_key_for_account(_account_id: &str) -> Result<Option<String>> {
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let key = account_storage_key(_account_id, "api_key");
-            return crate::config::secure_storage::get_secret(&key);
-        }
-    }
-
-    Ok(None)
+pub fn get_api_key_for_account(account_id: &str) -> Result<Option<String>> {
+    get_account_secret(account_id, "api_key")
 }
 
 /// Store bearer token for a specific account
-#[allow(dead_code)]
-pub fn store_bearer_token_for_account(_account_id: &str, _token: &str) -> Result<()> {
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let key = account_storage_key(_account_id, "bearer_token");
-            crate::config::secure_storage::store_secret(&key, _token)?;
-            return Ok(());
-        }
-    }
-
-    anyhow::bail!(
-        "Secure storage not available. Cannot store bearer token for account '{}'.",
-        _account_id
-    )
+pub fn store_bearer_token_for_account(account_id: &str, token: &str) -> Result<()> {
+    put_account_secret(account_id, "bearer_token", token)
 }
 
 /// Get bearer token for a specific account
-#[allow(dead_code)]
-pub fn get_bearer_token_for_account(_account_id: &str) -> Result<Option<String>> {
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            let key = account_storage_key(_account_id, "bearer_token");
-            return crate::config::secure_storage::get_secret(&key);
-        }
-    }
-
-    Ok(None)
+pub fn get_bearer_token_for_account(account_id: &str) -> Result<Option<String>> {
+    get_account_secret(account_id, "bearer_token")
 }
 
 /// Delete all stored credentials for an account
-#[allow(dead_code)]
-pub fn delete_all_for_account(_account_id: &str) -> Result<()> {
-    delete_tokens_for_account(_account_id)?;
-
-    #[cfg(feature = "secure-storage")]
-    {
-        if crate::config::secure_storage::is_available() {
-            for key_type in ["api_key", "bearer_token"] {
-                let key = account_storage_key(_account_id, key_type);
-                let _ = crate::config::secure_storage::delete_secret(&key);
+pub fn delete_all_for_account(account_id: &str) -> Result<()> {
+    delete_tokens_for_account(account_id)?;
+    for key_type in ["api_key", "bearer_token"] {
+        delete_account_secret(account_id, key_type)?;
+    }
+    Ok(())
+}
+
+/// Hand credentials from before multi-account storage to the accounts that
+/// were using them, once:
+///
+/// - The legacy OAuth tokens (`auth.json` or `claude_*` secrets) go to the
+///   active OAuth account, or the only one.
+/// - The global API key goes to the active account, or else the default
+///   one, if it is an API key account of the same provider without a key of
+///   its own. Other such accounts, which used to fall back to the global key
+///   too, are left without one rather than all sharing it.
+pub fn migrate_legacy_credentials(
+    accounts: &AccountsConfig,
+    global_api_key: Option<&str>,
+    global_provider: &str,
+) -> Result<LegacyMigration> {
+    let mut result = LegacyMigration::default();
+    if accounts.accounts.is_empty() {
+        return Ok(result);
+    }
+    let path = CredentialsFile::path()?;
+    if CredentialsFile::load_from(&path)?.legacy_migrated {
+        return Ok(result);
+    }
+
+    let mut sorted: Vec<&AccountConfig> = accounts.accounts.values().collect();
+    sorted.sort_by(|a, b| a.alias.cmp(&b.alias));
+    let migrated = &mut result.migrated;
+
+    let oauth: Vec<&AccountConfig> = sorted
+        .iter()
+        .copied()
+        .filter(|a| matches!(a.auth, AuthMethod::OAuth { .. }))
+        .collect();
+    let owner = accounts
+        .get_active_account()
+        .filter(|a| matches!(a.auth, AuthMethod::OAuth { .. }))
+        .or(if oauth.len() == 1 {
+            Some(oauth[0])
+        } else {
+            None
+        });
+    if let Some(owner) = owner {
+        if let AuthMethod::OAuth { account_id, .. } = &owner.auth {
+            if get_tokens_for_account(account_id)?.is_none() {
+                if let Some(legacy) = get_tokens()? {
+                    store_token_set_for_account(
+                        account_id,
+                        &legacy.access_token,
+                        legacy.refresh_token.as_deref(),
+                        legacy.expires_at,
+                    )?;
+                    migrated.push(owner.alias.clone());
+                }
             }
         }
     }
 
-    Ok(())
+    if let Some(api_key) = global_api_key {
+        let same_provider = |account: &AccountConfig| {
+            crate::providers::PROVIDER_REGISTRY
+                .get(&account.provider.to_lowercase())
+                .map(|entry| entry.matches(&global_provider.to_lowercase()))
+                .unwrap_or_else(|| account.provider.eq_ignore_ascii_case(global_provider))
+        };
+        let mut keyless = Vec::new();
+        for account in &sorted {
+            if let AuthMethod::ApiKey { key_id } = &account.auth {
+                if same_provider(account) && get_api_key_for_account(key_id)?.is_none() {
+                    keyless.push((*account, key_id));
+                }
+            }
+        }
+        let active = accounts.get_active_alias();
+        let owner = keyless
+            .iter()
+            .position(|(account, _)| Some(account.alias.as_str()) == active)
+            .or_else(|| keyless.iter().position(|(account, _)| account.is_default));
+        if let Some(owner) = owner {
+            let (account, key_id) = keyless.remove(owner);
+            store_api_key_for_account(key_id, api_key)?;
+            migrated.push(account.alias.clone());
+        }
+        result.without_key = keyless
+            .into_iter()
+            .map(|(account, _)| account.alias.clone())
+            .collect();
+    }
+
+    let mut file = CredentialsFile::load_from(&path)?;
+    file.legacy_migrated = true;
+    file.save_to(&path)?;
+    Ok(result)
+}
+
+/// What [`migrate_legacy_credentials`] did.
+#[derive(Debug, Default)]
+pub struct LegacyMigration {
+    /// Aliases that received credentials.
+    pub migrated: Vec<String>,
+    /// API key accounts that used to fall back to the global key and now
+    /// have none.
+    pub without_key: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_file_namespaces_by_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.json");
+
+        let mut file = CredentialsFile::load_from(&path).unwrap();
+        file.set("rco_work", "api_key", "sk-work");
+        file.set("rco_personal", "api_key", "sk-personal");
+        file.save_to(&path).unwrap();
+
+        let mut file = CredentialsFile::load_from(&path).unwrap();
+        assert_eq!(file.get("rco_work", "api_key").as_deref(), Some("sk-work"));
+        assert_eq!(
+            file.get("rco_personal", "api_key").as_deref(),
+            Some("sk-personal")
+        );
+        assert!(file.remove("rco_work", "api_key"));
+        assert!(!file.accounts.contains_key("rco_work"));
+        assert_eq!(file.get("rco_work", "api_key"), None);
+    }
 }
@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
 
-use crate::output::prelude::OutputFormat;
+use crate::config::schema::SchemaKind;
+use crate::output::prelude::{OutputFormat, OutputLevel};
+use crate::usage::ledger::GroupBy;
 
 #[derive(Parser)]
 #[command(
@@ -30,6 +32,12 @@ use crate::output::prelude::OutputFormat;
     # Open generated message in $EDITOR before committing
     rco --edit
 
+    # Sign the commit (uses gpg.format and user.signingkey from git config)
+    rco --sign
+
+    # Debug a provider issue (logs redacted request/response payloads)
+    rco -vv
+
     # Authenticate with Anthropic
     rco auth login
 
@@ -48,6 +56,15 @@ use crate::output::prelude::OutputFormat;
     # Use a skill for commit generation
     rco --skill my-template
 
+    # Show this month's token usage and cost per repository
+    rco usage --since month --by repo
+
+    # Commit message analytics for a release
+    rco stats v1.0..HEAD
+
+    # Share this repo's learned commit style with the team
+    rco style export
+
     # Generate shell completions
     rco completions bash
     rco completions zsh
@@ -112,8 +129,16 @@ pub struct GlobalOptions {
     #[arg(long = "print", default_value = "false")]
     pub print_message: bool,
 
+    /// Only print errors and the command's result
+    #[arg(short = 'q', long = "quiet", global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Increase verbosity (-v for details, -vv for debug logs and payload dumps)
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
     /// Output format (pretty, json, markdown)
-    #[arg(long = "output-format", default_value = "pretty")]
+    #[arg(long = "output-format", default_value = "pretty", global = true)]
     pub output_format: OutputFormat,
 
     /// Use a custom prompt template file
@@ -131,6 +156,37 @@ pub struct GlobalOptions {
     /// Use a specific skill for commit generation
     #[arg(long = "skill")]
     pub skill: Option<String>,
+
+    /// GPG/SSH-sign the commit, even if commit.gpgsign is not set
+    #[arg(
+        short = 'S',
+        long = "sign",
+        default_value = "false",
+        conflicts_with = "no_sign"
+    )]
+    pub sign: bool,
+
+    /// Do not sign the commit, even if commit.gpgsign is set
+    #[arg(long = "no-sign", default_value = "false")]
+    pub no_sign: bool,
+}
+
+impl GlobalOptions {
+    /// Verbosity selected by -q/-v/-vv
+    pub fn output_level(&self) -> OutputLevel {
+        OutputLevel::from_flags(self.quiet, self.verbose)
+    }
+
+    /// Signing override from --sign/--no-sign (`None` defers to git config)
+    pub fn sign_override(&self) -> Option<bool> {
+        if self.sign {
+            Some(true)
+        } else if self.no_sign {
+            Some(false)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Parser)]
@@ -143,12 +199,12 @@ pub struct SetupCommand {
     #[arg(long, default_value = "false")]
     pub advanced: bool,
 
-    /// Force TUI mode (terminal user interface)
-    #[arg(long, default_value = "false")]
+    /// Accepted for compatibility; setup always uses terminal prompts
+    #[arg(long, default_value = "false", hide = true)]
     pub tui: bool,
 
-    /// Force non-TUI mode (use dialoguer prompts)
-    #[arg(long, default_value = "false")]
+    /// Accepted for compatibility; setup always uses terminal prompts
+    #[arg(long, default_value = "false", hide = true)]
     pub no_tui: bool,
 }
 
@@ -187,6 +243,97 @@ pub enum Commands {
 
     /// Manage skills (custom templates, analyzers, formatters)
     Skills(SkillsCommand),
+
+    /// Show token usage and estimated cost
+    Usage(UsageCommand),
+
+    /// Show commit message analytics for a revision range
+    Stats(StatsCommand),
+
+    /// Manage the learned commit style profile for this repository
+    Style(StyleCommand),
+
+    /// Check config, accounts, secure storage, hooks and the tokenizer
+    Doctor(DoctorCommand),
+
+    /// List, rotate and export stored secrets (API keys and OAuth tokens)
+    Secrets(SecretsCommand),
+
+    /// Serve API keys to other tools over the git credential-helper protocol
+    Credential(CredentialCommand),
+}
+
+impl Commands {
+    /// Command path as used in structured output reports, e.g. "config get",
+    /// from the subcommands in `matches`
+    pub fn report_path(&self, matches: &clap::ArgMatches) -> String {
+        match self {
+            // These pick their action with flags or report it in the data
+            Commands::Model(cmd) if cmd.list => "model list".to_string(),
+            Commands::Model(_) => "model select".to_string(),
+            Commands::Update(cmd) if cmd.check => "update check".to_string(),
+            Commands::Style(_) => "style".to_string(),
+            _ => {
+                let mut path = Vec::new();
+                let mut current = matches;
+                while let Some((name, sub)) = current.subcommand() {
+                    path.push(name);
+                    current = sub;
+                }
+                path.join(" ")
+            }
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct DoctorCommand {
+    /// Also send a minimal request to the configured provider
+    #[arg(long)]
+    pub ping: bool,
+}
+
+#[derive(Parser)]
+pub struct CredentialCommand {
+    #[command(subcommand)]
+    pub action: CredentialAction,
+}
+
+#[derive(Subcommand)]
+pub enum CredentialAction {
+    /// Print the credential matching the attributes on stdin
+    Get,
+    /// Save the password on stdin for the matching account
+    Store,
+    /// Delete the stored credential for the matching account
+    Erase,
+}
+
+#[derive(Parser)]
+pub struct SecretsCommand {
+    #[command(subcommand)]
+    pub action: SecretsAction,
+}
+
+#[derive(Subcommand)]
+pub enum SecretsAction {
+    /// List the names of stored secrets
+    List,
+    /// Re-encrypt the vault under a new passphrase or key file
+    Rotate {
+        /// Switch to this key file (a new key is written to it)
+        #[arg(long, conflicts_with = "passphrase")]
+        key_file: Option<String>,
+        /// Switch to a passphrase (RCO_VAULT_NEW_PASSPHRASE or a prompt)
+        #[arg(long)]
+        passphrase: bool,
+    },
+    /// Print stored secrets as KEY='value' lines, in plaintext
+    Export {
+        /// Write to a file readable only by you instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 #[derive(Parser)]
@@ -230,6 +377,32 @@ pub enum ConfigAction {
         /// Configuration key
         key: String,
     },
+    /// Show which config layer each effective value comes from
+    Explain {
+        /// Configuration key (e.g. RCO_MODEL); all keys set by a layer if omitted
+        key: Option<String>,
+    },
+    /// Print the JSON Schema for config files, accounts or skill manifests
+    Schema {
+        /// File format to describe
+        #[arg(value_enum, default_value_t)]
+        kind: SchemaKind,
+        /// Write the schema to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Check config files, the merged config and the active account
+    Validate {
+        /// Also send a minimal request to the configured provider
+        #[arg(long)]
+        ping: bool,
+    },
+    /// Upgrade config files to the current schema version
+    Migrate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Reset configuration to defaults
     Reset {
         /// Reset all configuration
@@ -250,6 +423,21 @@ pub enum ConfigAction {
         /// Account alias (e.g., "work", "personal")
         #[arg(short, long)]
         alias: Option<String>,
+        /// Fetch the API key by running this command (e.g. "op read op://Private/OpenAI/key")
+        #[arg(long)]
+        auth_command: Option<String>,
+        /// Authenticate with this OAuth/OIDC issuer (an OpenAI-compatible gateway)
+        #[arg(long, requires = "oidc_client_id", conflicts_with = "auth_command")]
+        oidc_issuer: Option<String>,
+        /// OAuth client id registered with the issuer
+        #[arg(long, requires = "oidc_issuer")]
+        oidc_client_id: Option<String>,
+        /// Scopes to request (comma-separated)
+        #[arg(long, value_delimiter = ',', requires = "oidc_issuer")]
+        oidc_scopes: Vec<String>,
+        /// Use the client-credentials grant with the secret in this environment variable (for CI)
+        #[arg(long, requires = "oidc_issuer")]
+        oidc_client_secret_env: Option<String>,
     },
     /// List all configured accounts
     ListAccounts,
@@ -311,7 +499,19 @@ pub struct AuthCommand {
 #[derive(Subcommand)]
 pub enum AuthAction {
     /// Login with Claude OAuth
-    Login,
+    Login {
+        /// Use the device code flow (enter a code on another device); chosen
+        /// automatically over SSH, in containers and without a display
+        #[arg(long)]
+        device: bool,
+        /// Use the browser flow even where the device code flow would be
+        /// chosen, e.g. over SSH with the callback port forwarded
+        #[arg(long, conflicts_with = "device")]
+        browser: bool,
+        /// Log in to an account that uses a generic OIDC issuer
+        #[arg(long)]
+        account: Option<String>,
+    },
     /// Logout and remove stored tokens
     Logout,
     /// Check authentication status
@@ -346,8 +546,9 @@ pub struct UpdateCommand {
     #[arg(short, long)]
     pub force: bool,
 
-    /// Specify version to update to (e.g., "1.0.2")
-    #[arg(short, long)]
+    /// Specify version to update to (e.g., "1.0.2"). There is no short
+    /// form: `-v` is the global verbose flag
+    #[arg(long)]
     pub version: Option<String>,
 }
 
@@ -423,3 +624,59 @@ pub enum SkillsAction {
         source: String,
     },
 }
+
+#[derive(Parser)]
+pub struct UsageCommand {
+    /// Only include usage since a date (YYYY-MM-DD), an age (7d, 4w) or 'month'
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Group usage by provider, account, model, repo or user
+    #[arg(long, value_enum, default_value = "provider")]
+    pub by: GroupBy,
+}
+
+#[derive(Parser)]
+pub struct StatsCommand {
+    /// Revision range to analyze, e.g. v1.0..HEAD (defaults to all of HEAD)
+    pub range: Option<String>,
+
+    /// Only analyze the most recent N commits
+    #[arg(short = 'n', long)]
+    pub max_count: Option<usize>,
+
+    /// Trailer key that marks a commit as AI-generated
+    #[arg(long, default_value = crate::utils::commit_stats::DEFAULT_AI_TRAILER)]
+    pub ai_trailer: String,
+
+    /// Number of authors to list
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+}
+
+#[derive(Parser)]
+pub struct StyleCommand {
+    #[command(subcommand)]
+    pub action: StyleAction,
+}
+
+#[derive(Subcommand)]
+pub enum StyleAction {
+    /// Show the style profile used for this repository
+    Show,
+    /// Update the cached profile with commits made since the last analysis
+    Refresh {
+        /// Rebuild the profile from scratch instead of updating it
+        #[arg(long)]
+        full: bool,
+    },
+    /// Write the profile to a file that can be committed and shared
+    Export {
+        /// Output path (default: .rco/style.json in the repository root)
+        #[arg(short, long, conflicts_with = "stdout")]
+        output: Option<String>,
+        /// Print the profile to stdout instead of writing a file
+        #[arg(long)]
+        stdout: bool,
+    },
+}
@@ -1,76 +1,185 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use serde::Serialize;
 use std::path::Path;
-use std::process::Command;
+use std::time::Instant;
 
 use crate::cli::GlobalOptions;
 use crate::config::Config;
 use crate::git;
 use crate::output::progress;
+use crate::output::report::Report;
 use crate::output::styling::Styling;
+use crate::output::{self, context::OutputContext};
 use crate::providers;
+use crate::providers::error::ProviderError;
+use crate::providers::prompt::summarize_diff;
+use crate::usage;
 use crate::utils;
 use crate::utils::hooks::{run_hooks, write_temp_commit_file, HookOptions};
+use crate::utils::scope_resolver::{changed_paths, message_scope, replace_scope, ScopeResolver};
 
 /// Tokens reserved for prompt overhead when chunking diffs.
 /// This accounts for system prompts, user instructions, and response tokens
 /// that are sent alongside the diff content.
 const PROMPT_OVERHEAD_TOKENS: usize = 500;
 
+/// How many times a diff rejected for exceeding the model's context window is
+/// cut down before only a summary of the changed files is sent.
+const DIFF_REDUCTIONS: usize = 2;
+
+/// Smallest diff budget worth retrying with; below it only a summary of the
+/// changed files is sent.
+const MIN_DIFF_BUDGET_TOKENS: usize = 200;
+
 /// Execution context for commit message output.
-struct ExecContext;
+///
+/// Printing goes through the shared [`OutputContext`], so human-oriented
+/// output follows `-q/-v/-vv` and is suppressed for json/markdown formats,
+/// leaving stdout to the final [`Report`].
+struct ExecContext {
+    out: &'static OutputContext,
+}
 
 impl ExecContext {
-    fn new(_options: &GlobalOptions) -> Self {
-        Self
+    fn new() -> Self {
+        Self {
+            out: output::context::get(),
+        }
+    }
+
+    /// Print a line of human-oriented output.
+    fn line(&self, text: &str) {
+        self.out.line(text);
+    }
+
+    /// Print output that is the point of the command (shown even with `-q`).
+    fn result(&self, text: &str) {
+        self.out.result(text);
+    }
+
+    /// Print detail shown with `-v`.
+    fn verbose(&self, text: &str) {
+        self.out.verbose(text);
     }
 
     /// Print a success message.
     fn success(&self, message: &str) {
-        println!("{} {}", "✓".green(), message);
+        self.out.line(format_args!("{} {}", "✓".green(), message));
     }
 
     /// Print a warning message.
     fn warning(&self, message: &str) {
-        eprintln!("{} {}", "!".yellow().bold(), message);
+        self.out
+            .warning(format_args!("{} {}", "!".yellow().bold(), message));
     }
 
     /// Print an error message.
     fn error(&self, message: &str) {
-        eprintln!("{} {}", "✗".red(), message);
+        self.out.error(format_args!("{} {}", "✗".red(), message));
     }
 
     /// Print a header.
     fn header(&self, text: &str) {
-        println!("\n{}", text.bold());
+        self.line(&format!("\n{}", text.bold()));
     }
 
     /// Print a subheader.
     fn subheader(&self, text: &str) {
-        println!("{}", text.dimmed());
+        self.line(&text.dimmed().to_string());
     }
 
     /// Print a divider.
     fn divider(&self, length: Option<usize>) {
         let len = length.unwrap_or(50);
-        println!("{}", Styling::divider(len));
+        self.line(&Styling::divider(len).to_string());
     }
 
     /// Print a key-value pair.
     fn key_value(&self, key: &str, value: &str) {
-        println!("{}: {}", key.dimmed(), value);
+        self.line(&format!("{}: {}", key.dimmed(), value));
     }
+
+    /// Emit the structured report (no-op for pretty output).
+    fn emit(&self, report: &CommitReport) {
+        self.out.emit(&Report::ok("commit", report));
+    }
+}
+
+/// Structured result of a commit run, emitted as the `data` payload of the
+/// report for json/markdown output.
+#[derive(Debug, Default, Serialize)]
+struct CommitReport {
+    /// What happened: committed, cancelled, dry_run, clipboard, show_prompt.
+    action: &'static str,
+    provider: String,
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
+    /// All generated message variants.
+    messages: Vec<String>,
+    /// Index into `messages` of the variant that was used, if any.
+    chosen_variant: Option<usize>,
+    /// The message that was committed (after hooks and edits).
+    final_message: Option<String>,
+    commit_sha: Option<String>,
+    signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<String>,
+    /// Set when the diff had to be reduced to fit the model's context window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    degraded: Option<Degradation>,
+    /// Estimates, not the counts the provider bills; see `rco usage` for those.
+    estimated_tokens: TokenCounts,
+    timing: TimingBreakdown,
+}
+
+/// How the prompt was reduced after the provider rejected it as too long.
+#[derive(Debug, Serialize)]
+struct Degradation {
+    /// What was sent instead of the diff: reduced_diff or file_summary.
+    mode: &'static str,
+    /// Tokens of the diff (or summary) the messages were generated from.
+    diff_tokens: usize,
+    /// The context window the provider reported, if it did.
+    context_limit: Option<usize>,
+}
+
+/// Token counts for a commit run (estimated with the cl100k tokenizer).
+#[derive(Debug, Default, Serialize)]
+struct TokenCounts {
+    /// Tokens in the staged diff before chunking.
+    diff: usize,
+    /// Tokens across all generated messages.
+    output: usize,
+}
+
+/// Wall-clock timing for each phase of a commit run, in milliseconds.
+#[derive(Debug, Default, Serialize)]
+struct TimingBreakdown {
+    prepare_diff_ms: u64,
+    generation_ms: u64,
+    commit_ms: Option<u64>,
+    total_ms: u64,
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
 }
 
 pub async fn execute(options: GlobalOptions) -> Result<()> {
-    let ctx = ExecContext::new(&options);
+    let ctx = ExecContext::new();
+    let started = Instant::now();
+    let mut report = CommitReport::default();
 
     // Ensure we're in a git repository
     git::assert_git_repo()?;
 
     // Load and validate configuration
-    let config = load_and_validate_config(&options)?;
+    let config = load_and_validate_config(&options, &ctx)?;
+    report.provider = config.ai_provider.clone();
+    report.model = config.model.clone();
 
     // Determine effective generate count (CLI > config > default), clamped to 1-5
     let generate_count = options
@@ -79,11 +188,22 @@ pub async fn execute(options: GlobalOptions) -> Result<()> {
         .clamp(1, 5);
 
     // Prepare the diff for processing
+    let phase = Instant::now();
     let (final_diff, token_count) = prepare_diff(&config, &ctx)?;
+    report.timing.prepare_diff_ms = elapsed_ms(phase);
+    report.estimated_tokens.diff = token_count;
+    ctx.verbose(&format!(
+        "Diff: {} tokens (prepared in {}ms)",
+        token_count, report.timing.prepare_diff_ms
+    ));
 
     // If --show-prompt flag is set, just show the prompt and exit
     if options.show_prompt {
-        display_prompt(&config, &final_diff, options.context.as_deref(), &ctx);
+        let prompt = display_prompt(&config, &final_diff, options.context.as_deref(), &ctx);
+        report.action = "show_prompt";
+        report.prompt = Some(prompt);
+        report.timing.total_ms = elapsed_ms(started);
+        ctx.emit(&report);
         return Ok(());
     }
 
@@ -93,24 +213,39 @@ pub async fn execute(options: GlobalOptions) -> Result<()> {
     }
 
     // Generate commit message(s)
+    let phase = Instant::now();
     let messages = generate_commit_messages(
         &config,
         &final_diff,
-        options.context.as_deref(),
-        options.full_gitmoji,
+        &options,
         generate_count,
-        options.strip_thinking,
         &ctx,
+        &mut report,
     )
     .await?;
+    report.timing.generation_ms = elapsed_ms(phase);
+    ctx.verbose(&format!(
+        "Generated {} message(s) with {} ({}) in {}ms",
+        messages.len(),
+        report.provider,
+        report.model,
+        report.timing.generation_ms
+    ));
 
     if messages.is_empty() {
         anyhow::bail!("Failed to generate any commit messages");
     }
 
+    report.messages = messages.clone();
+    report.estimated_tokens.output = messages
+        .iter()
+        .map(|m| utils::token::estimate_tokens(m).unwrap_or(0))
+        .sum();
+
     // Handle clipboard mode
     if options.clipboard {
-        return handle_clipboard_mode(&messages, &ctx);
+        handle_clipboard_mode(&messages, &ctx, &mut report)?;
+        return finish(&options, &ctx, report, started);
     }
 
     // Handle print mode (for hooks compatibility)
@@ -121,7 +256,9 @@ pub async fn execute(options: GlobalOptions) -> Result<()> {
 
     // Handle dry-run mode - preview without committing
     if options.dry_run {
-        return handle_dry_run_mode(&messages, &ctx);
+        handle_dry_run_mode(&messages, &ctx);
+        report.action = "dry_run";
+        return finish(&options, &ctx, report, started);
     }
 
     // Run pre-commit hooks on first message
@@ -132,11 +269,49 @@ pub async fn execute(options: GlobalOptions) -> Result<()> {
 
     // Display messages and handle commit action
     display_commit_messages(&messages, &ctx);
-    handle_commit_action(&options, &config, &messages, &mut final_message, &ctx).await
+    let regenerated = handle_commit_action(
+        &options,
+        &config,
+        &messages,
+        &mut final_message,
+        &ctx,
+        &mut report,
+    )
+    .await?;
+
+    // A regenerated run has already reported for itself
+    if regenerated {
+        return Ok(());
+    }
+    finish(&options, &ctx, report, started)
+}
+
+/// Record total time, print the --timing breakdown and emit the report
+fn finish(
+    options: &GlobalOptions,
+    ctx: &ExecContext,
+    mut report: CommitReport,
+    started: Instant,
+) -> Result<()> {
+    report.timing.total_ms = elapsed_ms(started);
+
+    if options.timing {
+        let timing = &report.timing;
+        ctx.header("Timing");
+        ctx.key_value("Prepare diff", &format!("{}ms", timing.prepare_diff_ms));
+        ctx.key_value("Generation", &format!("{}ms", timing.generation_ms));
+        if let Some(commit_ms) = timing.commit_ms {
+            ctx.key_value("Commit", &format!("{}ms", commit_ms));
+        }
+        ctx.key_value("Total", &format!("{}ms", timing.total_ms));
+    }
+
+    ctx.emit(&report);
+    Ok(())
 }
 
 /// Load configuration and apply commitlint rules
-fn load_and_validate_config(options: &GlobalOptions) -> Result<Config> {
+fn load_and_validate_config(options: &GlobalOptions, ctx: &ExecContext) -> Result<Config> {
     let mut config = Config::load()?;
 
     // Apply CLI prompt-file override if provided
@@ -146,7 +321,7 @@ fn load_and_validate_config(options: &GlobalOptions) -> Result<Config> {
 
     // Apply skill if specified
     if let Some(ref skill_name) = options.skill {
-        apply_skill_to_config(&mut config, skill_name)?;
+        apply_skill_to_config(&mut config, skill_name, ctx)?;
     }
 
     config.load_with_commitlint()?;
@@ -155,7 +330,7 @@ fn load_and_validate_config(options: &GlobalOptions) -> Result<Config> {
 }
 
 /// Apply a skill's configuration to the config
-fn apply_skill_to_config(config: &mut Config, skill_name: &str) -> Result<()> {
+fn apply_skill_to_config(config: &mut Config, skill_name: &str, ctx: &ExecContext) -> Result<()> {
     use crate::skills::SkillsManager;
 
     let mut manager = SkillsManager::new()?;
@@ -174,7 +349,11 @@ fn apply_skill_to_config(config: &mut Config, skill_name: &str) -> Result<()> {
         tracing::info!("Loaded prompt template from skill: {}", skill_name);
     }
 
-    println!("{} Using skill: {}", "→".cyan(), skill_name.green());
+    ctx.line(&format!(
+        "{} Using skill: {}",
+        "→".cyan(),
+        skill_name.green()
+    ));
     Ok(())
 }
 
@@ -248,12 +427,13 @@ fn prepare_diff(config: &Config, ctx: &ExecContext) -> Result<(String, usize)> {
 }
 
 /// Display the prompt that would be sent to AI
-fn display_prompt(config: &Config, diff: &str, context: Option<&str>, ctx: &ExecContext) {
+fn display_prompt(config: &Config, diff: &str, context: Option<&str>, ctx: &ExecContext) -> String {
     let prompt = config.get_effective_prompt(diff, context, false);
     ctx.header("Prompt that would be sent to AI");
     ctx.divider(None);
-    println!("{}", prompt);
+    ctx.result(&prompt);
     ctx.divider(None);
+    prompt
 }
 
 /// Run pre-generation hooks
@@ -285,7 +465,11 @@ fn run_pre_gen_hooks(config: &Config, token_count: usize, context: Option<&str>)
 }
 
 /// Handle clipboard mode - copy message to clipboard and exit
-fn handle_clipboard_mode(messages: &[String], ctx: &ExecContext) -> Result<()> {
+fn handle_clipboard_mode(
+    messages: &[String],
+    ctx: &ExecContext,
+    report: &mut CommitReport,
+) -> Result<()> {
     let selected = if messages.len() == 1 {
         0
     } else {
@@ -293,29 +477,35 @@ fn handle_clipboard_mode(messages: &[String], ctx: &ExecContext) -> Result<()> {
     };
     copy_to_clipboard(&messages[selected])?;
     ctx.success("Commit message copied to clipboard!");
+    report.action = "clipboard";
+    report.chosen_variant = Some(selected);
+    report.final_message = Some(messages[selected].clone());
     Ok(())
 }
 
 /// Handle dry-run mode - preview message without committing
-fn handle_dry_run_mode(messages: &[String], ctx: &ExecContext) -> Result<()> {
+fn handle_dry_run_mode(messages: &[String], ctx: &ExecContext) {
     ctx.header("Dry Run Mode - Preview");
     ctx.divider(None);
     ctx.subheader("The following commit message would be generated:");
-    println!();
+    ctx.line("");
 
     if messages.len() == 1 {
-        println!("{}", messages[0].green());
+        ctx.result(&messages[0].green().to_string());
     } else {
         ctx.subheader("Multiple variations available:");
         for (i, msg) in messages.iter().enumerate() {
-            println!("\n{}. {}", i + 1, format!("Option {}", i + 1).cyan().bold());
-            println!("{}", msg.green());
+            ctx.line(&format!(
+                "\n{}. {}",
+                i + 1,
+                format!("Option {}", i + 1).cyan().bold()
+            ));
+            ctx.result(&msg.green().to_string());
         }
     }
 
     ctx.divider(None);
     ctx.subheader("No commit was made. Remove --dry-run to commit.");
-    Ok(())
 }
 
 /// Display the generated commit message(s)
@@ -323,13 +513,13 @@ fn display_commit_messages(messages: &[String], ctx: &ExecContext) {
     if messages.len() == 1 {
         ctx.header("Generated Commit Message");
         ctx.divider(None);
-        println!("{}", messages[0]);
+        ctx.line(&messages[0]);
         ctx.divider(None);
     } else {
         ctx.header("Generated Commit Message Variations");
         ctx.divider(None);
         for (i, msg) in messages.iter().enumerate() {
-            println!("{}. {}", i + 1, msg);
+            ctx.line(&format!("{}. {}", i + 1, msg));
         }
         ctx.divider(None);
     }
@@ -357,14 +547,18 @@ fn push_after_commit(config: &Config, ctx: &ExecContext) -> Result<()> {
     Ok(())
 }
 
-/// Handle the commit action (commit, edit, select, cancel, regenerate)
+/// Handle the commit action (commit, edit, select, cancel, regenerate).
+///
+/// Returns `true` when the user chose to regenerate, in which case the
+/// nested run has already produced its own output.
 async fn handle_commit_action(
     options: &GlobalOptions,
     config: &Config,
     messages: &[String],
     final_message: &mut str,
     ctx: &ExecContext,
-) -> Result<()> {
+    report: &mut CommitReport,
+) -> Result<bool> {
     let action = if options.skip_confirmation {
         CommitAction::Commit
     } else if options.edit {
@@ -378,7 +572,8 @@ async fn handle_commit_action(
 
     match action {
         CommitAction::Commit => {
-            perform_commit(final_message)?;
+            report.chosen_variant = Some(0);
+            perform_commit(final_message, options, ctx, report)?;
             run_post_commit_hooks(config, final_message).await?;
             ctx.success("Changes committed successfully!");
 
@@ -389,7 +584,8 @@ async fn handle_commit_action(
         }
         CommitAction::Edit => {
             let edited_message = edit_commit_message(final_message)?;
-            perform_commit(&edited_message)?;
+            report.chosen_variant = Some(0);
+            perform_commit(&edited_message, options, ctx, report)?;
             run_post_commit_hooks(config, &edited_message).await?;
             ctx.success("Changes committed successfully!");
 
@@ -403,9 +599,11 @@ async fn handle_commit_action(
             let edited_message = edit_in_external_editor(final_message)?;
             if edited_message.trim().is_empty() {
                 ctx.warning("Commit cancelled - empty message.");
-                return Ok(());
+                report.action = "cancelled";
+                return Ok(false);
             }
-            perform_commit(&edited_message)?;
+            report.chosen_variant = Some(0);
+            perform_commit(&edited_message, options, ctx, report)?;
             run_post_commit_hooks(config, &edited_message).await?;
             ctx.success("Changes committed successfully!");
 
@@ -421,7 +619,8 @@ async fn handle_commit_action(
             } else {
                 selected_message
             };
-            perform_commit(&final_msg)?;
+            report.chosen_variant = Some(index);
+            perform_commit(&final_msg, options, ctx, report)?;
             run_post_commit_hooks(config, &final_msg).await?;
             ctx.success("Changes committed successfully!");
 
@@ -432,14 +631,16 @@ async fn handle_commit_action(
         }
         CommitAction::Cancel => {
             ctx.warning("Commit cancelled.");
+            report.action = "cancelled";
         }
         CommitAction::Regenerate => {
             // Recursive call to regenerate
             Box::pin(execute(options.clone())).await?;
+            return Ok(true);
         }
     }
 
-    Ok(())
+    Ok(false)
 }
 
 fn select_files_to_stage(files: &[String]) -> Result<Vec<String>> {
@@ -582,18 +783,47 @@ fn edit_in_external_editor(original: &str) -> Result<String> {
     Ok(edited)
 }
 
-fn perform_commit(message: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["commit", "-m", message])
-        .output()
-        .context("Failed to execute git commit")?;
+/// Create the commit, honoring git signing config and --sign/--no-sign
+fn perform_commit(
+    message: &str,
+    options: &GlobalOptions,
+    ctx: &ExecContext,
+    report: &mut CommitReport,
+) -> Result<()> {
+    let phase = Instant::now();
+    let sha = git::commit(message, options.sign_override())?;
+    report.timing.commit_ms = Some(elapsed_ms(phase));
+    report.action = "committed";
+    report.final_message = Some(message.to_string());
+    report.signature = report_signature(&sha, ctx);
+    report.commit_sha = Some(sha);
+    Ok(())
+}
+
+/// Report the signature status of a freshly created commit, returning it
+fn report_signature(sha: &str, ctx: &ExecContext) -> Option<String> {
+    let signature = match git::get_commit_signature(sha) {
+        Ok(signature) => signature,
+        Err(e) => {
+            tracing::debug!("Could not read signature status: {}", e);
+            return None;
+        }
+    };
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Git commit failed: {}", stderr);
+    if !signature.status.is_signed() {
+        return Some(signature.status.description().to_string());
     }
 
-    Ok(())
+    let mut status = signature.status.description().to_string();
+    if let Some(signer) = signature.signer.as_deref().or(signature.key.as_deref()) {
+        status.push_str(&format!(" from {}", signer));
+    }
+
+    match signature.status {
+        git::SignatureStatus::Bad => ctx.warning(&format!("Commit signature: {}", status)),
+        _ => ctx.key_value("Signature", &status),
+    }
+    Some(status)
 }
 
 async fn run_post_commit_hooks(config: &Config, message: &str) -> Result<()> {
@@ -652,12 +882,13 @@ fn run_pre_commit_hooks(config: &Config, message: &str) -> Result<String> {
 async fn generate_commit_messages(
     config: &Config,
     diff: &str,
-    context: Option<&str>,
-    full_gitmoji: bool,
+    options: &GlobalOptions,
     count: u8,
-    strip_thinking: bool,
     ctx: &ExecContext,
+    report: &mut CommitReport,
 ) -> Result<Vec<String>> {
+    usage::check_budget(config)?;
+
     let pb = progress::spinner(&format!(
         "Generating {} commit message{}...",
         count,
@@ -669,26 +900,166 @@ async fn generate_commit_messages(
         if let Some(account) = config.get_active_account()? {
             tracing::info!("Using account: {}", account.alias);
             ctx.key_value("Using account", &account.alias);
+            report.provider = account.provider.clone();
+            if let Some(model) = &account.model {
+                report.model = model.clone();
+            }
+            report.account = Some(account.alias.clone());
             providers::create_provider_for_account(&account, config)?
         } else {
             providers::create_provider(config)?
         };
 
-    let mut messages = provider
-        .generate_commit_messages(diff, context, full_gitmoji, config, count)
-        .await?;
+    // A prompt over the context window is retried with less of the diff
+    let mut prompt_diff = diff.to_string();
+    let mut reductions = 0;
+    let mut summarized = false;
+    let mut messages = loop {
+        let error = match provider
+            .generate_commit_messages(
+                &prompt_diff,
+                options.context.as_deref(),
+                options.full_gitmoji,
+                config,
+                count,
+            )
+            .await
+        {
+            Ok(messages) => break messages,
+            Err(error) => error,
+        };
+        let Some(ProviderError::ContextLengthExceeded { limit, .. }) = ProviderError::find(&error)
+        else {
+            return Err(error);
+        };
+        let limit = *limit;
+        if summarized {
+            return Err(error.context("Even a summary of the changed files is too long"));
+        }
+        reductions += 1;
+
+        let window = limit.map_or(String::new(), |limit| format!(" of {} tokens", limit));
+        let budget = if reductions <= DIFF_REDUCTIONS {
+            reduced_budget(&prompt_diff, limit, config)
+        } else {
+            None
+        };
+        let degradation = if let Some(budget) = budget {
+            // prepare_diff's budget only splits the diff into chunks that
+            // are all sent, so cutting is what shortens the prompt
+            prompt_diff = utils::diff_chunking::truncate_diff(diff, budget);
+            pb.suspend(|| {
+                ctx.warning(&format!(
+                    "The prompt exceeds the model's context window{}. Retrying with the diff cut to {} tokens...",
+                    window, budget
+                ))
+            });
+            "reduced_diff"
+        } else {
+            prompt_diff = summarize_diff(diff);
+            summarized = true;
+            pb.suspend(|| {
+                ctx.warning(&format!(
+                    "The prompt still exceeds the model's context window{}. Retrying with only a summary of the changed files...",
+                    window
+                ))
+            });
+            "file_summary"
+        };
+        report.degraded = Some(Degradation {
+            mode: degradation,
+            diff_tokens: utils::token::estimate_tokens(&prompt_diff).unwrap_or(0),
+            context_limit: limit,
+        });
+    };
 
     // Strip thinking tags if requested
-    if strip_thinking {
+    if options.strip_thinking {
         for message in &mut messages {
             *message = utils::strip_thinking(message);
         }
     }
 
     pb.finish_with_message("Commit message(s) generated!");
+    if let Some(degraded) = &report.degraded {
+        ctx.warning(match degraded.mode {
+            "file_summary" => {
+                "Generated from a summary of the changed files only; review the message before committing"
+            }
+            _ => "Generated from part of the diff only; review the message before committing",
+        });
+    }
+    validate_scopes(config, diff, &mut messages, ctx);
     Ok(messages)
 }
 
+/// Token budget for the next attempt at a diff that exceeded the context
+/// window: half of what was sent, and below the reported window if known.
+/// `None` when that leaves too little of the diff to be worth sending.
+fn reduced_budget(sent: &str, limit: Option<usize>, config: &Config) -> Option<usize> {
+    let sent = utils::token::estimate_tokens(sent).unwrap_or(config.tokens_max_input);
+    let half = sent / 2;
+    let budget = match limit {
+        Some(limit) => half
+            .min(limit.saturating_sub(PROMPT_OVERHEAD_TOKENS + config.tokens_max_output as usize)),
+        None => half,
+    };
+    (budget >= MIN_DIFF_BUDGET_TOKENS).then_some(budget)
+}
+
+/// Check generated scopes against the scopes the repository defines.
+///
+/// Unknown scopes are reported, or with `RCO_SCOPE_STRICT` replaced by the
+/// best candidate for the changed files (or dropped when there is none).
+fn validate_scopes(config: &Config, diff: &str, messages: &mut [String], ctx: &ExecContext) {
+    if config.omit_scope {
+        return;
+    }
+    let Ok(repo_root) = git::get_repo_root() else {
+        return;
+    };
+    let resolver = ScopeResolver::load(Path::new(&repo_root), config);
+    if resolver.is_empty() {
+        return;
+    }
+
+    let allowed = resolver.allowed();
+    let best = resolver
+        .candidates(&changed_paths(diff))
+        .into_iter()
+        .next()
+        .map(|candidate| candidate.scope);
+
+    for message in messages.iter_mut() {
+        let Some(scope) = message_scope(message) else {
+            continue;
+        };
+        if allowed.contains(&scope) {
+            continue;
+        }
+
+        if config.scope_strict {
+            *message = replace_scope(message, best.as_deref());
+            ctx.verbose(&format!(
+                "Replaced undefined scope '{}' with {}",
+                scope,
+                best.as_deref()
+                    .map_or("no scope".to_string(), |b| format!("'{}'", b))
+            ));
+        } else {
+            let mut known: Vec<&str> = allowed.iter().map(String::as_str).take(10).collect();
+            if allowed.len() > known.len() {
+                known.push("…");
+            }
+            ctx.warning(&format!(
+                "Scope '{}' is not defined by this repository (known scopes: {})",
+                scope,
+                known.join(", ")
+            ));
+        }
+    }
+}
+
 /// Load and parse .rcoignore file
 fn load_rcoignore() -> Result<Vec<String>> {
     let repo_root = git::get_repo_root()?;
@@ -712,7 +1083,8 @@ fn filter_diff_by_rcoignore(diff: &str) -> Result<String> {
     let patterns = match patterns {
         Ok(p) => p,
         Err(e) => {
-            eprintln!("Warning: Failed to read .rcoignore: {}", e);
+            crate::output::context::get()
+                .warning(format!("Warning: Failed to read .rcoignore: {}", e));
             return Ok(diff.to_string());
         }
     };
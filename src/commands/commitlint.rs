@@ -6,6 +6,9 @@ use std::path::Path;
 
 use crate::cli::CommitLintCommand;
 use crate::git;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
 
 const COMMITLINT_CONFIG: &str = r#"module.exports = {
   extends: ['@commitlint/config-conventional'],
@@ -31,7 +34,8 @@ const COMMITLINT_CONFIG: &str = r#"module.exports = {
 };
 "#;
 
-pub async fn execute(cmd: CommitLintCommand) -> Result<()> {
+pub async fn execute(cmd: CommitLintCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
@@ -44,7 +48,15 @@ pub async fn execute(cmd: CommitLintCommand) -> Result<()> {
             .interact()?;
 
         if !overwrite {
-            println!("{}", "Commitlint configuration unchanged".yellow());
+            if format.is_structured() {
+                Report::ok(
+                    "commitlint",
+                    serde_json::json!({ "written": false, "path": config_path.display().to_string() }),
+                )
+                .emit(format);
+            } else {
+                out.line("Commitlint configuration unchanged".yellow());
+            }
             return Ok(());
         }
     }
@@ -53,17 +65,29 @@ pub async fn execute(cmd: CommitLintCommand) -> Result<()> {
     fs::write(&config_path, COMMITLINT_CONFIG)
         .context("Failed to write commitlint configuration")?;
 
-    println!("{}", "✅ Commitlint configuration created!".green());
-    println!("Configuration written to: {}", config_path.display());
+    if format.is_structured() {
+        Report::ok(
+            "commitlint",
+            serde_json::json!({ "written": true, "path": config_path.display().to_string() }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    out.line("✅ Commitlint configuration created!".green());
+    out.line(format!(
+        "Configuration written to: {}",
+        config_path.display()
+    ));
 
     // Check if package.json exists
     let package_json_path = Path::new(&repo_root).join("package.json");
     if package_json_path.exists() {
-        println!("\n{}", "📦 Next steps:".bold());
-        println!("1. Install commitlint dependencies:");
-        println!("   npm install --save-dev @commitlint/cli @commitlint/config-conventional");
-        println!("2. Add husky hook for commit-msg:");
-        println!("   npx husky add .husky/commit-msg 'npx --no -- commitlint --edit \"$1\"'");
+        out.line(format!("\n{}", "📦 Next steps:".bold()));
+        out.line("1. Install commitlint dependencies:");
+        out.line("   npm install --save-dev @commitlint/cli @commitlint/config-conventional");
+        out.line("2. Add husky hook for commit-msg:");
+        out.line("   npx husky add .husky/commit-msg 'npx --no -- commitlint --edit \"$1\"'");
     }
 
     Ok(())
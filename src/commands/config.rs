@@ -1,64 +1,376 @@
 use anyhow::Result;
 use colored::Colorize;
 use dialoguer::{Input, Select};
+use serde_json::json;
 
 use crate::cli::{ConfigAction, ConfigCommand};
+use crate::commands::doctor;
+use crate::config::layers::{self as config_layers, KeyExplanation, ResolvedConfig};
+use crate::config::migrations::{self, CURRENT_SCHEMA_VERSION, MIGRATIONS};
+use crate::config::schema::SchemaKind;
 use crate::config::{self, accounts, Config};
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+
+/// Configuration options shown by `rco config describe`, grouped by section.
+const CONFIG_OPTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "Core Settings",
+        &[
+            (
+                "RCO_AI_PROVIDER",
+                "AI provider to use (openai, anthropic, ollama, etc.)",
+            ),
+            ("RCO_MODEL", "Model name for the provider"),
+            ("RCO_API_KEY", "API key for the provider"),
+            ("RCO_API_URL", "Custom API endpoint URL"),
+        ],
+    ),
+    (
+        "Commit Style",
+        &[
+            ("RCO_COMMIT_TYPE", "Format: 'conventional' or 'gitmoji'"),
+            ("RCO_EMOJI", "Include emojis: true/false"),
+            ("RCO_LANGUAGE", "Output language (en, es, fr, etc.)"),
+            ("RCO_DESCRIPTION", "Include description: true/false"),
+            (
+                "RCO_SCOPE_MAP",
+                "Path to scope table, e.g. 'src/auth=auth;docs/=docs'",
+            ),
+            (
+                "RCO_SCOPE_STRICT",
+                "Rewrite scopes the repo does not define: true/false",
+            ),
+        ],
+    ),
+    (
+        "Behavior",
+        &[
+            ("RCO_TOKENS_MAX_INPUT", "Max input tokens (default: 4096)"),
+            ("RCO_TOKENS_MAX_OUTPUT", "Max output tokens (default: 500)"),
+            ("RCO_GITPUSH", "Auto-push after commit: true/false"),
+            ("RCO_REMOTE", "Remote to push to (default: origin)"),
+            ("RCO_ONE_LINE_COMMIT", "One-line format: true/false"),
+            (
+                "RCO_FEW_SHOT_COUNT",
+                "Similar past commits to show as examples (0 = off)",
+            ),
+            (
+                "RCO_FEW_SHOT_MAX_TOKENS",
+                "Token budget for few-shot examples (default: 800)",
+            ),
+        ],
+    ),
+    (
+        "Hooks",
+        &[
+            ("RCO_PRE_GEN_HOOK", "Command to run before generation"),
+            ("RCO_PRE_COMMIT_HOOK", "Command to run after generation"),
+            ("RCO_POST_COMMIT_HOOK", "Command to run after commit"),
+            ("RCO_HOOK_STRICT", "Fail on hook error: true/false"),
+            ("RCO_HOOK_TIMEOUT_MS", "Hook timeout in milliseconds"),
+        ],
+    ),
+    (
+        "Network",
+        &[
+            (
+                "RCO_RETRY_COUNT",
+                "Retries for rate limits and server errors (default: 5)",
+            ),
+            (
+                "RCO_RETRY_MAX_ELAPSED_SECS",
+                "Stop retrying after this many seconds (default: 120)",
+            ),
+            (
+                "RCO_REQUEST_TIMEOUT_SECS",
+                "Timeout for a single provider request (default: none)",
+            ),
+            (
+                "RCO_STREAM",
+                "Ask providers for a streamed answer (default: false)",
+            ),
+            (
+                "RCO_RATE_LIMIT_RPM",
+                "Requests per minute to send a provider (default: unlimited)",
+            ),
+            (
+                "RCO_RATE_LIMIT_TPM",
+                "Estimated tokens per minute to send a provider (default: unlimited)",
+            ),
+            (
+                "RCO_MAX_CONCURRENCY",
+                "Requests sent at once when generating variations (default: 4)",
+            ),
+        ],
+    ),
+    (
+        "Testing",
+        &[
+            (
+                "RCO_TEST_MOCK_TYPE",
+                "Mock provider mode: 'generate', 'replay' or 'record'",
+            ),
+            (
+                "RCO_MOCK_FIXTURES_DIR",
+                "Where fixtures are recorded and replayed (default: .rco/fixtures)",
+            ),
+        ],
+    ),
+    (
+        "Usage Budget",
+        &[
+            (
+                "RCO_BUDGET_MONTHLY_USD",
+                "Monthly spend limit in USD (see `rco usage`)",
+            ),
+            ("RCO_BUDGET_ACTION", "When over budget: 'warn' or 'block'"),
+        ],
+    ),
+    (
+        "Secret Storage",
+        &[
+            (
+                "RCO_SECRET_BACKEND",
+                "Where secrets are kept: 'keyring' or 'vault' (encrypted file)",
+            ),
+            (
+                "RCO_VAULT_KEY_FILE",
+                "Key file for the vault (default: ask for a passphrase)",
+            ),
+        ],
+    ),
+];
+
+/// Example invocations shown by `rco config describe`.
+const CONFIG_EXAMPLES: &[&str] = &[
+    "rco config set RCO_AI_PROVIDER=anthropic",
+    "rco config set RCO_MODEL=claude-3-5-haiku-20241022",
+    "rco config set RCO_EMOJI=true RCO_LANGUAGE=es",
+    "rco config set RCO_PRE_GEN_HOOK='just lint'",
+    "rco config set RCO_SCOPE_MAP='src/auth=auth;src/api=api'",
+    "rco config set RCO_RETRY_COUNT=2 RCO_REQUEST_TIMEOUT_SECS=60",
+    "rco config set RCO_RATE_LIMIT_RPM=50 RCO_RATE_LIMIT_TPM=40000",
+    "rco config set RCO_BUDGET_MONTHLY_USD=20 RCO_BUDGET_ACTION=block",
+    "rco config set RCO_SECRET_BACKEND=vault RCO_VAULT_KEY_FILE=~/.config/rustycommit/vault.key",
+    "rco config explain RCO_MODEL",
+    "rco config migrate --dry-run",
+    "rco config schema config -o rco.schema.json",
+];
+
+fn is_secret(key: &str) -> bool {
+    key.contains("API_KEY") || key.ends_with("TOKEN")
+}
 
-/// Unified output helper for config commands.
-#[allow(dead_code)]
-struct ConfigOutput;
-
-#[allow(dead_code)]
-impl ConfigOutput {
-    fn header(&self, text: &str) {
-        println!("\n{}", text.bold());
+/// Hide secret values from structured reports.
+fn report_value(key: &str, value: &str) -> String {
+    if is_secret(key) {
+        "********".to_string()
+    } else {
+        value.to_string()
     }
+}
 
-    fn subheader(&self, text: &str) {
-        println!("{}", text.dimmed());
+/// Hide secret values in an explanation before showing it.
+fn redact(explanation: &mut KeyExplanation) {
+    if !is_secret(&explanation.env_var) {
+        return;
     }
-
-    fn success(&self, message: &str) {
-        println!("{}", format!("✅ {}", message).green());
+    let mask = |value: &mut serde_json::Value| {
+        if !value.is_null() {
+            *value = json!("********");
+        }
+    };
+    mask(&mut explanation.value);
+    for assignment in &mut explanation.assignments {
+        if let Some(value) = assignment.value.as_mut() {
+            mask(value);
+        }
     }
+}
 
-    fn error(&self, message: &str) {
-        println!("{}", format!("❌ {}", message).red());
+fn display_value(value: &serde_json::Value) -> String {
+    if value.is_null() {
+        "(not set)".to_string()
+    } else {
+        config_layers::render(value)
     }
+}
 
-    fn warning(&self, message: &str) {
-        println!("{}", format!("⚠️  {}", message).yellow());
+fn print_explanation(explanation: &KeyExplanation) {
+    let out = output::context::get();
+    out.section(&explanation.env_var);
+    out.key_value("Value", &display_value(&explanation.value));
+    out.key_value("Source", &explanation.source.to_string());
+
+    out.header("Layers (lowest priority first)");
+    out.result(format!(
+        "  default: {}",
+        display_value(&explanation.default)
+    ));
+    let last = explanation.assignments.len().saturating_sub(1);
+    for (i, assignment) in explanation.assignments.iter().enumerate() {
+        let value = match &assignment.value {
+            Some(value) => display_value(value),
+            None => "unset".to_string(),
+        };
+        let line = format!("  {}: {}", assignment.source, value);
+        if i == last {
+            out.result(line);
+        } else {
+            out.result(format!("{} {}", line, "(overridden)".dimmed()));
+        }
     }
+    out.divider();
+}
 
-    fn info(&self, message: &str) {
-        println!("{}", message.cyan());
+/// Add the `rco config describe` text to config schema properties.
+fn describe_schema_properties(schema: &mut serde_json::Value) {
+    for (key, description) in CONFIG_OPTIONS.iter().flat_map(|(_, options)| *options) {
+        let field = key.trim_start_matches("RCO_").to_lowercase();
+        if let Some(property) = schema["properties"].get_mut(&field) {
+            if property.get("description").is_none() {
+                property["description"] = json!(description);
+            }
+        }
     }
+}
 
-    fn divider(&self) {
-        println!("{}", "─".repeat(50).dimmed());
+/// Check that an account's credentials resolve, so switching to an account
+/// without a key is reported now rather than at the next commit.
+fn account_credentials_status(config: &Config, alias: &str) -> Result<()> {
+    let Some(account) = config.get_account(alias)? else {
+        anyhow::bail!("Account '{}' not found", alias);
+    };
+    match crate::auth::account_credential(&account)? {
+        Some(_) => Ok(()),
+        None => match &account.auth {
+            accounts::AuthMethod::EnvVar { name } => {
+                anyhow::bail!("{} is not set; account '{}' reads its key from it", name, alias)
+            }
+            _ => anyhow::bail!(
+                "No credentials stored for account '{}'. Re-add it with: rco config add-provider --alias {}",
+                alias,
+                alias
+            ),
+        },
     }
+}
+
+/// Migrate every config layer file, or report what would change.
+fn migrate_files(dry_run: bool, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
+    let mut files = Vec::new();
+
+    for (kind, path) in config_layers::config_files()? {
+        if !path.exists() {
+            continue;
+        }
+        let mut table = match migrations::read_document(&path) {
+            Ok(table) => table,
+            Err(e) => {
+                out.warning(format!("⚠️  {e:#}"));
+                files.push(json!({ "layer": kind, "path": path, "error": format!("{e:#}") }));
+                continue;
+            }
+        };
+        let report = migrations::migrate(&mut table)?;
+        let unknown = migrations::unknown_keys(&table);
+        let steps: Vec<_> = MIGRATIONS
+            .iter()
+            .filter(|m| m.to > report.from && m.to <= report.to)
+            .map(|m| format!("v{}: {}", m.to, m.description))
+            .collect();
+
+        let backup = if !report.is_needed() {
+            None
+        } else if dry_run {
+            Some(migrations::backup_path(&path, report.from))
+        } else {
+            Some(migrations::write_migrated(&path, &table, report.from)?)
+        };
+
+        out.line(format!("\n📄 {} ({})", kind, path.display()).bold());
+        if report.from > CURRENT_SCHEMA_VERSION {
+            out.line(
+                format!(
+                    "   Schema v{} is newer than this release supports (v{}); skipped",
+                    report.from, CURRENT_SCHEMA_VERSION
+                )
+                .yellow(),
+            );
+        } else if report.is_needed() {
+            out.line(format!("   Schema v{} → v{}", report.from, report.to));
+            for step in &steps {
+                out.line(format!("   • {step}"));
+            }
+            for change in &report.changes {
+                out.line(format!("     - {change}").dimmed());
+            }
+        } else {
+            out.line(format!("   Up to date (schema v{})", report.from).green());
+        }
+        for key in &unknown {
+            out.line(format!("   ⚠️  {key}").yellow());
+        }
+        if let Some(backup) = &backup {
+            if dry_run {
+                out.line(format!(
+                    "   Would migrate; original kept as {}",
+                    backup.display()
+                ));
+            } else {
+                out.line(format!("   ✅ Migrated; original kept as {}", backup.display()).green());
+            }
+        }
 
-    fn section(&self, title: &str) {
-        self.divider();
-        println!("{}", title.cyan().bold());
-        self.divider();
+        files.push(json!({
+            "layer": kind,
+            "path": path,
+            "from": report.from,
+            "to": report.to,
+            "steps": steps,
+            "changes": report.changes,
+            "unknown_keys": unknown,
+            "backup": backup,
+            "migrated": backup.is_some() && !dry_run,
+        }));
     }
 
-    fn key_value(&self, key: &str, value: &str) {
-        println!("  {}: {}", key.dimmed(), value);
+    if files.is_empty() {
+        out.line("No config files found.".dimmed());
+    } else if dry_run {
+        out.line("\nDry run: no files were changed.".dimmed());
     }
+
+    Report::ok(
+        "config migrate",
+        json!({
+            "dry_run": dry_run,
+            "schema_version": CURRENT_SCHEMA_VERSION,
+            "files": files,
+        }),
+    )
+    .emit(format);
+    Ok(())
 }
 
-pub async fn execute(cmd: ConfigCommand) -> Result<()> {
+pub async fn execute(cmd: ConfigCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     let mut config = Config::load()?;
+    let structured = format.is_structured();
 
     match cmd.action {
         ConfigAction::Set { pairs } => {
+            let mut updated = Vec::new();
+            let mut failed = Vec::new();
+
             for pair in pairs {
                 let parts: Vec<&str> = pair.splitn(2, '=').collect();
                 if parts.len() != 2 {
-                    eprintln!("{}", format!("Invalid format: {pair}. Use KEY=value").red());
+                    out.error(format!("Invalid format: {pair}. Use KEY=value").red());
+                    failed.push(json!({ "key": pair, "error": "Invalid format. Use KEY=value" }));
                     continue;
                 }
 
@@ -67,35 +379,140 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
 
                 match config.set(key, value) {
                     Ok(_) => {
-                        println!("{}", format!("✅ {key} set to: {value}").green());
+                        out.line(format!("✅ {key} set to: {value}").green());
+                        updated.push(json!({ "key": key, "value": report_value(key, value) }));
                     }
                     Err(e) => {
-                        eprintln!("{}", format!("❌ Failed to set {key}: {e}").red());
+                        out.error(format!("❌ Failed to set {key}: {e}").red());
+                        failed.push(json!({ "key": key, "error": e.to_string() }));
                     }
                 }
             }
+
+            Report::ok(
+                "config set",
+                json!({ "updated": updated, "failed": failed }),
+            )
+            .emit(format);
         }
         ConfigAction::Get { key } => match config.get(&key) {
             Ok(value) => {
-                println!("{key}: {value}");
+                if structured {
+                    Report::ok("config get", json!({ "key": key, "value": value })).emit(format);
+                } else {
+                    out.result(format!("{key}: {value}"));
+                }
             }
+            Err(e) if structured => return Err(e),
             Err(e) => {
-                eprintln!("{}", format!("❌ {e}").red());
+                out.error(format!("❌ {e}").red());
             }
         },
+        ConfigAction::Explain { key } => {
+            let resolved = ResolvedConfig::resolve()?;
+            let mut explanations = match &key {
+                Some(key) => vec![resolved.explain(key)?],
+                None => resolved.explain_all(),
+            };
+            explanations.iter_mut().for_each(redact);
+
+            if structured {
+                let data = match &key {
+                    Some(_) => json!(explanations[0]),
+                    None => json!({ "layers": resolved.layers, "keys": explanations }),
+                };
+                Report::ok("config explain", data).emit(format);
+            } else if key.is_some() {
+                print_explanation(&explanations[0]);
+            } else {
+                out.section("Configuration layers (lowest priority first)");
+                out.result("  default");
+                for layer in &resolved.layers {
+                    out.result(format!("  {layer}"));
+                }
+                out.header("Values set by a layer");
+                if explanations.is_empty() {
+                    out.result("  (none)");
+                }
+                for explanation in &explanations {
+                    out.result(format!(
+                        "  {} = {}  {}",
+                        explanation.env_var,
+                        display_value(&explanation.value),
+                        format!("← {}", explanation.source).dimmed()
+                    ));
+                }
+                out.divider();
+            }
+        }
+        ConfigAction::Schema { kind, output } => {
+            let mut schema = config::schema::schema(kind);
+            if kind == SchemaKind::Config {
+                describe_schema_properties(&mut schema);
+            }
+            let rendered = serde_json::to_string_pretty(&schema)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, rendered + "\n")?;
+                    if structured {
+                        Report::ok("config schema", json!({ "path": path })).emit(format);
+                    } else {
+                        out.success(&format!("Schema written to {path}"));
+                    }
+                }
+                None if structured => {
+                    Report::ok("config schema", json!({ "schema": schema })).emit(format);
+                }
+                None => out.result(rendered),
+            }
+        }
+        ConfigAction::Validate { ping } => {
+            doctor::run("config validate", doctor::Scope::Config, ping, format).await?
+        }
+        ConfigAction::Migrate { dry_run } => migrate_files(dry_run, format)?,
         ConfigAction::Reset { all, keys } => {
             if all {
                 config.reset(None)?;
-                println!("{}", "✅ All configuration reset to defaults".green());
+                out.line("✅ All configuration reset to defaults".green());
             } else if !keys.is_empty() {
                 config.reset(Some(&keys))?;
-                println!("{}", format!("✅ Reset keys: {}", keys.join(", ")).green());
+                out.line(format!("✅ Reset keys: {}", keys.join(", ")).green());
+            } else if structured {
+                anyhow::bail!("Please specify --all or provide keys to reset");
             } else {
-                eprintln!("{}", "Please specify --all or provide keys to reset".red());
+                out.error("Please specify --all or provide keys to reset".red());
             }
+
+            Report::ok("config reset", json!({ "all": all, "keys": keys })).emit(format);
+        }
+        ConfigAction::Status if structured => {
+            let keychain_key = config::secure_storage::get_secret("RCO_API_KEY")?.is_some();
+            let backend = config::secure_storage::backend();
+            let storage = if config::secure_storage::is_available() && keychain_key {
+                Some(match backend {
+                    config::secure_storage::Backend::Keyring => "keychain",
+                    config::secure_storage::Backend::Vault => "vault",
+                })
+            } else if config.api_key.is_some() {
+                Some("config_file")
+            } else {
+                None
+            };
+
+            Report::ok(
+                "config status",
+                json!({
+                    "platform": config::secure_storage::get_platform_info(),
+                    "secure_storage_available": config::secure_storage::is_available(),
+                    "secret_backend": backend.as_str(),
+                    "api_key_configured": config.api_key.is_some() || keychain_key,
+                    "api_key_storage": storage,
+                    "provider": config.ai_provider,
+                }),
+            )
+            .emit(format);
         }
         ConfigAction::Status => {
-            let out = ConfigOutput;
             out.header("🔐 Secure Storage Status");
             out.divider();
 
@@ -106,18 +523,28 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
             out.key_value("Status", &status);
 
             if config::secure_storage::is_available() {
-                println!("\n{}", "✅ API keys will be stored securely".green());
+                out.line(format!(
+                    "\n{}",
+                    "✅ API keys will be stored securely".green()
+                ));
                 out.subheader("Your API keys are encrypted and protected by your system");
 
                 // Platform-specific information
-                #[cfg(target_os = "macos")]
-                out.subheader("Stored in: macOS Keychain (login keychain)");
+                if config::secure_storage::backend() == config::secure_storage::Backend::Vault {
+                    out.subheader(&format!(
+                        "Stored in: {}",
+                        config::secure_storage::get_platform_info()
+                    ));
+                } else {
+                    #[cfg(target_os = "macos")]
+                    out.subheader("Stored in: macOS Keychain (login keychain)");
 
-                #[cfg(target_os = "linux")]
-                out.subheader("Stored in: Secret Service (GNOME Keyring/KWallet)");
+                    #[cfg(target_os = "linux")]
+                    out.subheader("Stored in: Secret Service (GNOME Keyring/KWallet)");
 
-                #[cfg(target_os = "windows")]
-                out.subheader("Stored in: Windows Credential Manager");
+                    #[cfg(target_os = "windows")]
+                    out.subheader("Stored in: Windows Credential Manager");
+                }
             } else {
                 out.warning("API keys will be stored in the configuration file");
                 out.subheader("Location: ~/.config/rustycommit/config.toml");
@@ -133,75 +560,88 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                     out.subheader("Note: Secure storage is not available on this system");
                     out.subheader("Falling back to file-based storage");
                 }
+
+                out.subheader(
+                    "Or use the encrypted vault: rco config set RCO_SECRET_BACKEND=vault",
+                );
             }
 
             // Show current API key status
-            println!("\n{}", "Current Configuration:".bold());
+            out.line(format!("\n{}", "Current Configuration:".bold()));
             if config.api_key.is_some()
                 || config::secure_storage::get_secret("RCO_API_KEY")?.is_some()
             {
-                println!("{}", "🔑 API key is configured".green());
+                out.line("🔑 API key is configured".green());
 
                 // Show which storage method is being used
                 if config::secure_storage::is_available()
                     && config::secure_storage::get_secret("RCO_API_KEY")?.is_some()
                 {
-                    println!("{}", "   Stored securely in system keychain".dimmed());
+                    out.line(
+                        format!(
+                            "   Stored securely in {}",
+                            config::secure_storage::get_platform_info()
+                        )
+                        .dimmed(),
+                    );
                 } else if config.api_key.is_some() {
-                    println!("{}", "   Stored in configuration file".dimmed());
+                    out.line("   Stored in configuration file".dimmed());
                 }
             } else {
-                println!("{}", "❌ No API key configured".red());
-                println!(
-                    "{}",
-                    "   Run: rco config set RCO_API_KEY=<your_key>".dimmed()
-                );
+                out.line("❌ No API key configured".red());
+                out.line("   Run: rco config set RCO_API_KEY=<your_key>".dimmed());
             }
 
             // Show AI provider
-            println!("🤖 AI Provider: {}", config.ai_provider);
+            out.line(format!("🤖 AI Provider: {}", config.ai_provider));
+        }
+        ConfigAction::Describe if structured => {
+            let sections: Vec<_> = CONFIG_OPTIONS
+                .iter()
+                .map(|(section, options)| {
+                    let options: Vec<_> = options
+                        .iter()
+                        .map(|(key, description)| json!({ "key": key, "description": description }))
+                        .collect();
+                    json!({ "section": section, "options": options })
+                })
+                .collect();
+
+            Report::ok(
+                "config describe",
+                json!({ "sections": sections, "examples": CONFIG_EXAMPLES }),
+            )
+            .emit(format);
         }
         ConfigAction::Describe => {
-            println!("\n{}", "📖 Configuration Options".bold());
-            println!("{}", "═".repeat(60).dimmed());
-
-            println!("\n{}", "Core Settings:".bold().green());
-            println!("  RCO_AI_PROVIDER    AI provider to use (openai, anthropic, ollama, etc.)");
-            println!("  RCO_MODEL          Model name for the provider");
-            println!("  RCO_API_KEY        API key for the provider");
-            println!("  RCO_API_URL        Custom API endpoint URL");
-
-            println!("\n{}", "Commit Style:".bold().green());
-            println!("  RCO_COMMIT_TYPE    Format: 'conventional' or 'gitmoji'");
-            println!("  RCO_EMOJI          Include emojis: true/false");
-            println!("  RCO_LANGUAGE       Output language (en, es, fr, etc.)");
-            println!("  RCO_DESCRIPTION    Include description: true/false");
-
-            println!("\n{}", "Behavior:".bold().green());
-            println!("  RCO_TOKENS_MAX_INPUT   Max input tokens (default: 4096)");
-            println!("  RCO_TOKENS_MAX_OUTPUT  Max output tokens (default: 500)");
-            println!("  RCO_GITPUSH      Auto-push after commit: true/false");
-            println!("  RCO_REMOTE       Remote to push to (default: origin)");
-            println!("  RCO_ONE_LINE_COMMIT    One-line format: true/false");
-
-            println!("\n{}", "Hooks:".bold().green());
-            println!("  RCO_PRE_GEN_HOOK       Command to run before generation");
-            println!("  RCO_PRE_COMMIT_HOOK    Command to run after generation");
-            println!("  RCO_POST_COMMIT_HOOK   Command to run after commit");
-            println!("  RCO_HOOK_STRICT        Fail on hook error: true/false");
-            println!("  RCO_HOOK_TIMEOUT_MS    Hook timeout in milliseconds");
-
-            println!("\n{}", "Examples:".bold().green());
-            println!("  rco config set RCO_AI_PROVIDER=anthropic");
-            println!("  rco config set RCO_MODEL=claude-3-5-haiku-20241022");
-            println!("  rco config set RCO_EMOJI=true RCO_LANGUAGE=es");
-            println!("  rco config set RCO_PRE_GEN_HOOK='just lint'");
-
-            println!("\n{}", "═".repeat(60).dimmed());
+            out.line(format!("\n{}", "📖 Configuration Options".bold()));
+            out.line("═".repeat(60).dimmed());
+
+            for (section, options) in CONFIG_OPTIONS {
+                out.line(format!("\n{}", format!("{section}:").bold().green()));
+                for (key, description) in *options {
+                    out.line(format!("  {key:<22} {description}"));
+                }
+            }
+
+            out.line(format!("\n{}", "Examples:".bold().green()));
+            for example in CONFIG_EXAMPLES {
+                out.line(format!("  {example}"));
+            }
+
+            out.line(format!("\n{}", "═".repeat(60).dimmed()));
         }
-        ConfigAction::AddProvider { provider: _, alias } => {
-            println!("\n{}", "🔧 Add Provider Wizard".bold().green());
-            println!("{}", "═".repeat(50).dimmed());
+        ConfigAction::AddProvider {
+            provider: _,
+            alias,
+            auth_command,
+            oidc_issuer,
+            oidc_client_id,
+            oidc_scopes,
+            oidc_client_secret_env,
+        } => {
+            out.line(format!("\n{}", "🔧 Add Provider Wizard".bold().green()));
+            out.line("═".repeat(50).dimmed());
 
             // Select provider
             let provider_names = vec![
@@ -268,8 +708,24 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                 Some(api_url_input.trim().to_string())
             };
 
-            // Get API key (skip for Ollama)
-            let api_key = if provider_selection == 5 {
+            let oidc = oidc_issuer.map(|issuer| {
+                Box::new(accounts::OidcConfig {
+                    issuer,
+                    client_id: oidc_client_id.unwrap_or_default(),
+                    scopes: oidc_scopes,
+                    grant: if oidc_client_secret_env.is_some() {
+                        accounts::OidcGrant::ClientCredentials
+                    } else {
+                        accounts::OidcGrant::AuthorizationCode
+                    },
+                    client_secret_env: oidc_client_secret_env,
+                    audience: None,
+                    redirect_port: None,
+                })
+            });
+
+            // Get API key (skip for Ollama or when a command or issuer supplies it)
+            let api_key = if provider_selection == 5 || auth_command.is_some() || oidc.is_some() {
                 None
             } else {
                 let key_input: String = Input::new()
@@ -277,10 +733,7 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                     .interact()?;
 
                 if key_input.trim().is_empty() {
-                    eprintln!(
-                        "{}",
-                        "⚠ No API key entered. You'll need to set it later.".yellow()
-                    );
+                    out.warning("⚠ No API key entered. You'll need to set it later.".yellow());
                     None
                 } else {
                     Some(key_input.trim().to_string())
@@ -288,7 +741,19 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
             };
 
             // Create the account config
-            let auth = if api_key.is_some() {
+            let auth = if let Some(oidc) = oidc {
+                accounts::AuthMethod::OAuth {
+                    provider: "oidc".to_string(),
+                    account_id: format!("rco_{}", alias.to_lowercase().replace(' ', "_")),
+                    oidc: Some(oidc),
+                }
+            } else if let Some(command) = auth_command {
+                accounts::AuthMethod::Command {
+                    command,
+                    timeout_secs: None,
+                    cache_ttl_secs: None,
+                }
+            } else if api_key.is_some() {
                 // Generate a key_id for this account
                 let key_id = format!("rco_{}", alias.to_lowercase().replace(' ', "_"));
                 accounts::AuthMethod::ApiKey {
@@ -317,6 +782,12 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                 tokens_max_input: None,
                 tokens_max_output: None,
                 is_default: false,
+                http: None,
+                retry_count: None,
+                retry_max_elapsed_secs: None,
+                request_timeout_secs: None,
+                rate_limit_rpm: None,
+                rate_limit_tpm: None,
             };
 
             // Save the account
@@ -328,10 +799,10 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
 
             // Check if alias already exists
             if accounts_config.get_account(&account.alias).is_some() {
-                eprintln!(
-                    "{}",
-                    format!("❌ Account '{}' already exists", account.alias).red()
-                );
+                if structured {
+                    anyhow::bail!("Account '{}' already exists", account.alias);
+                }
+                out.error(format!("❌ Account '{}' already exists", account.alias).red());
             } else {
                 accounts_config.add_account(account.clone());
 
@@ -344,31 +815,47 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                     if let Err(e) =
                         crate::auth::token_storage::store_api_key_for_account(&key_id, &key)
                     {
-                        eprintln!(
-                            "{}",
-                            format!("⚠ Failed to store API key securely: {e}").yellow()
-                        );
+                        out.warning(format!("⚠ Failed to store API key securely: {e}").yellow());
                     }
                 }
 
                 accounts_config.save()?;
-                println!();
-                println!(
-                    "{}",
-                    format!("✅ Account '{}' added successfully!", account.alias).green()
-                );
-                println!();
-                println!(
+
+                if structured {
+                    Report::ok("config add-provider", &account).emit(format);
+                    return Ok(());
+                }
+
+                out.line("");
+                out.line(format!("✅ Account '{}' added successfully!", account.alias).green());
+                if let accounts::AuthMethod::OAuth { oidc: Some(_), .. } = &account.auth {
+                    out.line(format!(
+                        "{} Log in with: {}",
+                        "→".cyan(),
+                        format!("rco auth login --account {}", account.alias)
+                            .bold()
+                            .white()
+                    ));
+                }
+                out.line("");
+                out.line(format!(
                     "{} To use this account: {}",
                     "→".cyan(),
                     format!("rco config use-account {}", account.alias)
                         .bold()
                         .white()
-                );
+                ));
             }
         }
+        ConfigAction::ListAccounts if structured => {
+            let accounts = if config.has_accounts() {
+                config.list_accounts()?
+            } else {
+                Vec::new()
+            };
+            Report::ok("config list-accounts", json!({ "accounts": accounts })).emit(format);
+        }
         ConfigAction::ListAccounts => {
-            let out = ConfigOutput;
             out.header("📋 Configured Accounts");
             out.divider();
 
@@ -381,108 +868,141 @@ pub async fn execute(cmd: ConfigCommand) -> Result<()> {
                             } else {
                                 "".normal()
                             };
-                            println!(
+                            out.line(format!(
                                 "{}: {}{}",
                                 account.alias.yellow(),
                                 account.provider,
                                 default_marker
-                            );
+                            ));
                             if let Some(model) = &account.model {
-                                println!("   Model: {}", model.dimmed());
+                                out.line(format!("   Model: {}", model.dimmed()));
                             }
                             if let Some(api_url) = &account.api_url {
-                                println!("   URL: {}", api_url.dimmed());
+                                out.line(format!("   URL: {}", api_url.dimmed()));
                             }
                         }
                     }
                     Err(e) => {
-                        eprintln!("{}", format!("❌ Failed to list accounts: {e}").red());
+                        out.error(format!("❌ Failed to list accounts: {e}").red());
                     }
                 }
             } else {
-                println!("\n{}", "No accounts configured yet.".dimmed());
-                println!(
-                    "{}",
-                    "Use: rco config add-provider to add an account".dimmed()
-                );
+                out.line(format!("\n{}", "No accounts configured yet.".dimmed()));
+                out.line("Use: rco config add-provider to add an account".dimmed());
             }
         }
+        ConfigAction::UseAccount { alias } if structured => {
+            config.set_default_account(&alias)?;
+            let credentials = account_credentials_status(&config, &alias);
+            Report::ok(
+                "config use-account",
+                json!({ "alias": alias, "credentials": credentials.is_ok() }),
+            )
+            .emit(format);
+        }
         ConfigAction::UseAccount { alias } => {
-            println!(
+            out.line(format!(
                 "\n{}",
                 format!("🔄 Switching to account: {}", alias).bold().green()
-            );
+            ));
 
             match config.set_default_account(&alias) {
                 Ok(_) => {
-                    println!("{}", format!("✅ Now using account: {alias}").green());
-                    println!(
-                        "\n{}",
-                        "Note: Account switching requires restart of commands".dimmed()
-                    );
+                    out.line(format!("✅ Now using account: {alias}").green());
+                    if let Err(e) = account_credentials_status(&config, &alias) {
+                        out.warning(format!("⚠️  {e}").yellow());
+                    }
                 }
                 Err(e) => {
-                    eprintln!("{}", format!("❌ Failed to switch account: {e}").red());
+                    out.error(format!("❌ Failed to switch account: {e}").red());
                 }
             }
         }
+        ConfigAction::RemoveAccount { alias } if structured => {
+            config.remove_account(&alias)?;
+            Report::ok("config remove-account", json!({ "alias": alias })).emit(format);
+        }
         ConfigAction::RemoveAccount { alias } => {
-            println!(
+            out.line(format!(
                 "\n{}",
                 format!("🗑️  Removing account: {}", alias).bold().yellow()
-            );
+            ));
 
             match config.remove_account(&alias) {
                 Ok(_) => {
-                    println!("{}", format!("✅ Account '{alias}' removed").green());
+                    out.line(format!("✅ Account '{alias}' removed").green());
                 }
                 Err(e) => {
-                    eprintln!("{}", format!("❌ Failed to remove account: {e}").red());
+                    out.error(format!("❌ Failed to remove account: {e}").red());
                 }
             }
         }
+        ConfigAction::ShowAccount { alias } if structured => {
+            let alias = alias.as_deref().unwrap_or("default");
+            let account = config
+                .get_account(alias)?
+                .ok_or_else(|| anyhow::anyhow!("Account '{alias}' not found"))?;
+            Report::ok("config show-account", &account).emit(format);
+        }
         ConfigAction::ShowAccount { alias } => {
             let alias = alias.as_deref().unwrap_or("default");
 
-            println!("\n{}", format!("👤 Account: {}", alias).bold().green());
-            println!("{}", "═".repeat(50).dimmed());
+            out.line(format!(
+                "\n{}",
+                format!("👤 Account: {}", alias).bold().green()
+            ));
+            out.line("═".repeat(50).dimmed());
 
             match config.get_account(alias) {
                 Ok(Some(account)) => {
-                    println!("Alias: {}", account.alias.yellow());
-                    println!("Provider: {}", account.provider);
-                    println!("Default: {}", if account.is_default { "Yes" } else { "No" });
+                    out.line(format!("Alias: {}", account.alias.yellow()));
+                    out.line(format!("Provider: {}", account.provider));
+                    out.line(format!(
+                        "Default: {}",
+                        if account.is_default { "Yes" } else { "No" }
+                    ));
 
                     if let Some(model) = &account.model {
-                        println!("Model: {}", model);
+                        out.line(format!("Model: {}", model));
                     }
                     if let Some(api_url) = &account.api_url {
-                        println!("API URL: {}", api_url);
+                        out.line(format!("API URL: {}", api_url));
                     }
 
                     match &account.auth {
                         crate::config::accounts::AuthMethod::ApiKey { .. } => {
-                            println!("Auth: API Key 🔑");
+                            out.line("Auth: API Key 🔑");
                         }
                         crate::config::accounts::AuthMethod::OAuth {
                             provider,
                             account_id,
+                            oidc,
                         } => {
-                            println!("Auth: OAuth ({}) - Account: {}", provider, account_id);
+                            out.line(format!(
+                                "Auth: OAuth ({}) - Account: {}",
+                                provider, account_id
+                            ));
+                            if let Some(oidc) = oidc {
+                                out.line(format!("Issuer: {}", oidc.issuer));
+                                out.line(format!("Client ID: {}", oidc.client_id));
+                            }
                         }
                         crate::config::accounts::AuthMethod::EnvVar { name } => {
-                            println!("Auth: Environment Variable ({})", name);
+                            out.line(format!("Auth: Environment Variable ({})", name));
                         }
                         crate::config::accounts::AuthMethod::Bearer { .. } => {
-                            println!("Auth: Bearer Token 🔖");
+                            out.line("Auth: Bearer Token 🔖");
+                        }
+                        crate::config::accounts::AuthMethod::Command { command, .. } => {
+                            out.line(format!("Auth: Command ({})", command));
                         }
                     }
                 }
                 Ok(None) => {
-                    eprintln!("{}", format!("❌ Account '{alias}' not found").red());
+                    out.error(format!("❌ Account '{alias}' not found").red());
                 }
                 Err(e) => {
-                    eprintln!("{}", format!("❌ Failed to get account: {e}").red());
+                    out.error(format!("❌ Failed to get account: {e}").red());
                 }
             }
         }
@@ -0,0 +1,220 @@
+//! `rco credential`: serve rco's API keys to other tools over the
+//! git-credential-helper protocol.
+//!
+//! The caller writes `key=value` lines to stdin, ending with a blank line or
+//! EOF. rco reads `account`, `provider` and `host` to choose a credential:
+//!
+//! - `account=<alias>` selects that account.
+//! - `provider=<name>` selects the active account if it uses that provider,
+//!   else the first account that does, else the global config.
+//! - `host=<host[:port]>` selects the account (or global config) whose
+//!   `api_url` points at that host.
+//! - With none of these, the active account or the global config is used.
+//!
+//! `get` answers with `username`, `provider` and `password` lines; when
+//! nothing matches it prints nothing, so rco can sit in a helper chain.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+
+use anyhow::{bail, Result};
+
+use crate::auth::{self, token_storage};
+use crate::cli::{CredentialAction, CredentialCommand};
+use crate::config::accounts::{AccountConfig, AuthMethod};
+use crate::config::{secure_storage, Config};
+use crate::providers::PROVIDER_REGISTRY;
+
+/// The credential a request resolved to.
+enum Target {
+    Account(Box<AccountConfig>),
+    Global(Box<Config>),
+}
+
+pub async fn execute(cmd: CredentialCommand) -> Result<()> {
+    let attrs = read_attributes(io::stdin().lock())?;
+    let Some(target) = resolve(&attrs)? else {
+        return Ok(());
+    };
+
+    match cmd.action {
+        CredentialAction::Get => get(&target),
+        CredentialAction::Store => {
+            let Some(secret) = attrs.get("password") else {
+                bail!("credential store needs a password=<secret> line");
+            };
+            store(&target, secret)
+        }
+        CredentialAction::Erase => erase(&target),
+    }
+}
+
+fn get(target: &Target) -> Result<()> {
+    let (username, provider, secret) = match target {
+        Target::Account(account) => (
+            account.alias.clone(),
+            account.provider.clone(),
+            auth::account_credential(account)?,
+        ),
+        Target::Global(config) => (
+            "rco".to_string(),
+            config.ai_provider.clone(),
+            config.api_key.clone(),
+        ),
+    };
+    let Some(secret) = secret else {
+        return Ok(());
+    };
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "username={}", username)?;
+    writeln!(stdout, "provider={}", provider)?;
+    writeln!(stdout, "password={}", secret)?;
+    Ok(())
+}
+
+/// Save a credential the caller has confirmed works. Accounts whose key
+/// comes from outside rco (environment, command, OAuth) are left alone.
+fn store(target: &Target, secret: &str) -> Result<()> {
+    match target {
+        Target::Account(account) => match &account.auth {
+            AuthMethod::ApiKey { key_id } => {
+                token_storage::store_api_key_for_account(key_id, secret)
+            }
+            AuthMethod::Bearer { token_id } => {
+                token_storage::store_bearer_token_for_account(token_id, secret)
+            }
+            _ => Ok(()),
+        },
+        Target::Global(config) => {
+            let mut config = config.as_ref().clone();
+            config.set("RCO_API_KEY", secret)
+        }
+    }
+}
+
+/// Forget a credential the caller found to be rejected.
+fn erase(target: &Target) -> Result<()> {
+    match target {
+        Target::Account(account) => match &account.auth {
+            AuthMethod::ApiKey { key_id: id } | AuthMethod::Bearer { token_id: id } => {
+                token_storage::delete_all_for_account(id)?;
+                secure_storage::delete_secret(id)
+            }
+            _ => Ok(()),
+        },
+        Target::Global(config) => {
+            let mut config = config.as_ref().clone();
+            config.reset(Some(&["RCO_API_KEY".to_string()]))?;
+            secure_storage::delete_secret("RCO_API_KEY")
+        }
+    }
+}
+
+/// Parse `key=value` lines up to a blank line or EOF.
+fn read_attributes(input: impl BufRead) -> Result<BTreeMap<String, String>> {
+    let mut attrs = BTreeMap::new();
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            attrs.insert(key.to_string(), value.to_string());
+        }
+    }
+    Ok(attrs)
+}
+
+fn resolve(attrs: &BTreeMap<String, String>) -> Result<Option<Target>> {
+    let config = Config::load()?;
+    let accounts_config = config.load_accounts()?.unwrap_or_default();
+    let mut accounts: Vec<&AccountConfig> = accounts_config.accounts.values().collect();
+    accounts.sort_by(|a, b| a.alias.cmp(&b.alias));
+    let active = accounts_config.get_active_account();
+
+    if let Some(alias) = attrs.get("account") {
+        return Ok(accounts_config
+            .get_account(alias)
+            .map(|account| Target::Account(Box::new(account.clone()))));
+    }
+
+    if let Some(provider) = attrs.get("provider") {
+        let provider = canonical_provider(provider);
+        let uses = |account: &&AccountConfig| canonical_provider(&account.provider) == provider;
+        if let Some(account) = active
+            .filter(uses)
+            .or_else(|| accounts.into_iter().find(uses))
+        {
+            return Ok(Some(Target::Account(Box::new(account.clone()))));
+        }
+        if canonical_provider(&config.ai_provider) == provider {
+            return Ok(Some(Target::Global(Box::new(config))));
+        }
+        return Ok(None);
+    }
+
+    if let Some(host) = attrs.get("host") {
+        let serves =
+            |api_url: Option<&String>| api_url.and_then(|u| authority(u)).as_ref() == Some(host);
+        if let Some(account) = accounts.into_iter().find(|a| serves(a.api_url.as_ref())) {
+            return Ok(Some(Target::Account(Box::new(account.clone()))));
+        }
+        if serves(config.api_url.as_ref()) {
+            return Ok(Some(Target::Global(Box::new(config))));
+        }
+        return Ok(None);
+    }
+
+    Ok(Some(match active {
+        Some(account) => Target::Account(Box::new(account.clone())),
+        None => Target::Global(Box::new(config)),
+    }))
+}
+
+/// The registry name for a provider or one of its aliases.
+fn canonical_provider(name: &str) -> String {
+    let name = name.to_lowercase();
+    PROVIDER_REGISTRY
+        .get(&name)
+        .map(|entry| entry.name.to_string())
+        .unwrap_or(name)
+}
+
+/// `host[:port]` of a URL, as git sends it in `host=`.
+fn authority(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_attributes_until_a_blank_line() {
+        let input = "protocol=https\r\nhost=api.openai.com\npath=v1=x\n\nignored=1\n";
+        let attrs = read_attributes(input.as_bytes()).unwrap();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(attrs["host"], "api.openai.com");
+        assert_eq!(attrs["path"], "v1=x");
+    }
+
+    #[test]
+    fn authority_includes_non_default_ports() {
+        assert_eq!(
+            authority("https://api.openai.com/v1").as_deref(),
+            Some("api.openai.com")
+        );
+        assert_eq!(
+            authority("http://localhost:11434").as_deref(),
+            Some("localhost:11434")
+        );
+        assert_eq!(authority("not a url"), None);
+    }
+}
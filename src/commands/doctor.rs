@@ -0,0 +1,829 @@
+//! `rco doctor` and `rco config validate`: catch setup problems up front
+//! instead of when a commit message fails to generate.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::auth::{self, token_storage};
+use crate::cli::DoctorCommand;
+use crate::config::accounts::{AccountConfig, AccountsConfig, AuthMethod, OidcGrant};
+use crate::config::{layers, migrations, secure_storage, Config};
+use crate::git;
+use crate::output;
+use crate::output::error::{StructuredError, ToStructured};
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::output::styling::Theme;
+use crate::providers::error::ProviderError;
+use crate::providers::{self, PROVIDER_REGISTRY};
+use crate::utils::token::estimate_tokens;
+
+/// Model name prefixes served by each first-party provider's own endpoint.
+const MODEL_FAMILIES: &[(&str, &[&str])] = &[
+    ("anthropic", &["claude"]),
+    ("gemini", &["gemini", "gemma"]),
+    ("openai", &["gpt", "chatgpt", "o1", "o3", "o4", "codex"]),
+    ("xai", &["grok"]),
+];
+
+/// Hooks installed by `rco hook`.
+const HOOKS: [&str; 2] = ["prepare-commit-msg", "commit-msg"];
+
+/// Diff sent by `--ping`; small enough to cost a handful of tokens.
+const PING_DIFF: &str = "diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-# Project\n+# Project name\n";
+
+/// How long `--ping` waits for the provider.
+const PING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// A single diagnostic, with hints for anything that is not ok.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: Status,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<String>,
+}
+
+impl Check {
+    fn ok(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, Status::Ok, message, Vec::new())
+    }
+
+    fn warn(name: &str, message: impl Into<String>, hints: Vec<String>) -> Self {
+        Self::new(name, Status::Warn, message, hints)
+    }
+
+    fn fail(name: &str, message: impl Into<String>, hints: Vec<String>) -> Self {
+        Self::new(name, Status::Fail, message, hints)
+    }
+
+    fn new(name: &str, status: Status, message: impl Into<String>, hints: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+            hints,
+        }
+    }
+
+    /// Render a problem the way command errors are rendered.
+    pub fn to_error(&self) -> StructuredError {
+        StructuredError::new(&self.message)
+            .with_context("Check", &self.name)
+            .with_hints(self.hints.clone())
+    }
+}
+
+/// Which checks to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Config files, the merged config and the active account.
+    Config,
+    /// Everything, including secure storage, hooks and the tokenizer.
+    All,
+}
+
+pub async fn execute(cmd: DoctorCommand, format: OutputFormat) -> Result<()> {
+    run("doctor", Scope::All, cmd.ping, format).await
+}
+
+/// Run the checks for `scope` and report them. Fails when any check fails.
+pub async fn run(command: &str, scope: Scope, ping: bool, format: OutputFormat) -> Result<()> {
+    let mut checks = config_file_checks();
+    let config = match Config::load() {
+        Ok(config) => Some(config),
+        Err(e) => {
+            checks.push(Check::fail(
+                "config",
+                format!("Could not load config: {:#}", e),
+                vec!["Run 'rco config explain' to see which files are read".to_string()],
+            ));
+            None
+        }
+    };
+
+    if let Some(config) = &config {
+        checks.extend(validate_config(config));
+        checks.extend(account_checks(config));
+    }
+    if scope == Scope::All {
+        checks.push(secure_storage_check());
+        checks.push(hooks_check());
+        checks.push(tokenizer_check());
+    }
+    if ping {
+        if let Some(config) = &config {
+            checks.push(ping_check(config).await);
+        }
+    }
+
+    report(command, &checks, format)
+}
+
+/// Print the checks and turn failures into an error.
+fn report(command: &str, checks: &[Check], format: OutputFormat) -> Result<()> {
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let (passed, warnings, failed) = (count(Status::Ok), count(Status::Warn), count(Status::Fail));
+    let summary = StructuredError::new(&format!("{} of {} checks failed", failed, checks.len()))
+        .with_hints(
+            checks
+                .iter()
+                .filter(|c| c.status == Status::Fail)
+                .flat_map(|c| c.hints.iter().cloned()),
+        );
+
+    if format.is_structured() {
+        let mut report = Report::ok(
+            command,
+            json!({
+                "checks": checks,
+                "summary": { "passed": passed, "warnings": warnings, "failed": failed },
+            }),
+        );
+        if failed > 0 {
+            report.success = false;
+            report.error = Some(summary.to_value());
+        }
+        report.emit(format);
+        if failed > 0 {
+            std::process::exit(summary.exit_code());
+        }
+        return Ok(());
+    }
+
+    let out = output::context::get();
+    out.header(&format!("rco {}", command));
+    out.divider();
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in checks {
+        let symbol = match check.status {
+            Status::Ok => "✓".green(),
+            Status::Warn => "!".yellow(),
+            Status::Fail => "✗".red(),
+        };
+        out.result(format!(
+            "  {} {:<width$}  {}",
+            symbol,
+            check.name,
+            check.message,
+            width = width
+        ));
+        if check.status == Status::Warn {
+            for hint in &check.hints {
+                out.result(format!("    {} {}", "-".dimmed(), hint.dimmed()));
+            }
+        }
+    }
+    out.divider();
+    out.result(format!(
+        "{} passed, {} warnings, {} failed",
+        passed, warnings, failed
+    ));
+
+    if failed == 0 {
+        return Ok(());
+    }
+    let theme = Theme::new();
+    for check in checks.iter().filter(|c| c.status == Status::Fail) {
+        out.error(format!("\n{}", check.to_error().display(&theme).trim_end()));
+    }
+    anyhow::bail!("{} of {} checks failed", failed, checks.len())
+}
+
+/// Every config file that exists parses, is up to date and has no unknown keys.
+fn config_file_checks() -> Vec<Check> {
+    let files = match layers::config_files() {
+        Ok(files) => files,
+        Err(e) => return vec![Check::fail("config files", format!("{:#}", e), Vec::new())],
+    };
+
+    let mut checks = Vec::new();
+    for (kind, path) in files.iter().filter(|(_, path)| path.exists()) {
+        let name = format!("{} config", kind);
+        let mut table = match migrations::read_document(path) {
+            Ok(table) => table,
+            Err(e) => {
+                checks.push(Check::fail(
+                    &name,
+                    format!("{:#}", e),
+                    vec![
+                        format!("Fix the syntax in {}", path.display()),
+                        "Validate it in your editor with 'rco config schema'".to_string(),
+                    ],
+                ));
+                continue;
+            }
+        };
+
+        let migration = match migrations::migrate(&mut table) {
+            Ok(migration) => migration,
+            Err(e) => {
+                checks.push(Check::fail(&name, format!("{:#}", e), Vec::new()));
+                continue;
+            }
+        };
+        let unknown = migrations::unknown_keys(&table);
+        if !unknown.is_empty() {
+            checks.push(Check::warn(
+                &name,
+                format!("{} unknown key(s) in {}", unknown.len(), path.display()),
+                unknown.iter().map(ToString::to_string).collect(),
+            ));
+        } else if migration.is_needed() {
+            checks.push(Check::warn(
+                &name,
+                format!(
+                    "{} uses config schema v{} (current is v{})",
+                    path.display(),
+                    migration.from,
+                    migration.to
+                ),
+                vec!["Run 'rco config migrate' to upgrade it".to_string()],
+            ));
+        } else {
+            checks.push(Check::ok(&name, path.display().to_string()));
+        }
+    }
+    checks
+}
+
+/// Check the merged config for values that would make generation fail.
+pub fn validate_config(config: &Config) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let provider_ok = provider_check(&config.ai_provider, "provider", &mut checks);
+    if provider_ok {
+        checks.push(model_check(
+            &config.ai_provider,
+            &config.model,
+            config.api_url.is_some(),
+            "RCO_MODEL",
+        ));
+        if let Some(check) = api_key_check(config) {
+            checks.push(check);
+        }
+    }
+
+    if let Some(url) = &config.api_url {
+        checks.push(api_url_check(url));
+    }
+
+    checks.extend(limit_checks(config));
+    checks
+}
+
+/// Whether the provider is in the registry; pushes a check either way.
+fn provider_check(provider: &str, name: &str, checks: &mut Vec<Check>) -> bool {
+    if let Some(entry) = PROVIDER_REGISTRY.get(provider) {
+        checks.push(Check::ok(name, entry.name));
+        return true;
+    }
+
+    let known: Vec<String> = PROVIDER_REGISTRY
+        .all()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|e| std::iter::once(e.name).chain(e.aliases.iter().copied()))
+        .map(String::from)
+        .collect();
+    let mut hints = Vec::new();
+    if let Some(suggestion) = migrations::suggest(provider, &known) {
+        hints.push(format!("Did you mean '{}'?", suggestion));
+    }
+    let mut available = known.clone();
+    available.sort();
+    available.dedup();
+    hints.push(format!("Available providers: {}", available.join(", ")));
+    hints.push("Set one with: rco config set RCO_AI_PROVIDER=<provider>".to_string());
+
+    checks.push(Check::fail(
+        name,
+        format!("Unknown or disabled provider '{}'", provider),
+        hints,
+    ));
+    false
+}
+
+/// Whether `model` belongs to the provider. Only first-party endpoints are
+/// checked; gateways and custom `api_url`s serve whatever they like.
+fn model_check(provider: &str, model: &str, custom_url: bool, key: &str) -> Check {
+    if model.trim().is_empty() {
+        return Check::fail(
+            "model",
+            "No model configured",
+            vec![format!("Set one with: rco config set {}=<model>", key)],
+        );
+    }
+
+    let entry = PROVIDER_REGISTRY.get(provider);
+    let canonical = entry.as_ref().map_or(provider, |e| e.name);
+    let family_of = |model: &str| {
+        let model = model.to_lowercase();
+        let model = model.rsplit('/').next().unwrap_or(&model).to_string();
+        MODEL_FAMILIES
+            .iter()
+            .find(|(_, prefixes)| prefixes.iter().any(|p| model.starts_with(p)))
+            .map(|(family, _)| *family)
+    };
+    let Some(own) = MODEL_FAMILIES
+        .iter()
+        .find(|(family, _)| *family == canonical)
+    else {
+        return Check::ok("model", model);
+    };
+    if custom_url {
+        return Check::ok("model", model);
+    }
+
+    match family_of(model) {
+        Some(family) if family == own.0 => Check::ok("model", model),
+        other => {
+            let mut hints = Vec::new();
+            if let Some(default) = entry.and_then(|e| e.default_model) {
+                hints.push(format!(
+                    "Use a {} model: rco config set {}={}",
+                    canonical, key, default
+                ));
+            }
+            if let Some(family) = other {
+                hints.push(format!(
+                    "Or switch provider: rco config set RCO_AI_PROVIDER={}",
+                    family
+                ));
+                Check::fail(
+                    "model",
+                    format!("'{}' is a {} model, not a {} one", model, family, canonical),
+                    hints,
+                )
+            } else {
+                Check::warn(
+                    "model",
+                    format!("'{}' does not look like a {} model", model, canonical),
+                    hints,
+                )
+            }
+        }
+    }
+}
+
+/// Providers that need a key have one, unless an account or OAuth login
+/// supplies the credentials.
+fn api_key_check(config: &Config) -> Option<Check> {
+    let entry = PROVIDER_REGISTRY.get(&config.ai_provider)?;
+    if config.api_key.is_some() {
+        return Some(Check::ok("api key", "configured"));
+    }
+    let has_account = matches!(config.get_active_account(), Ok(Some(_)));
+    if !entry.requires_api_key || has_account {
+        return None;
+    }
+    if entry.name == "anthropic" && token_storage::has_valid_token() {
+        return Some(Check::ok("api key", "using OAuth login"));
+    }
+
+    Some(Check::fail(
+        "api key",
+        format!("No API key configured for {}", entry.name),
+        vec![
+            "Set one with: rco config set RCO_API_KEY=<your_key>".to_string(),
+            "Or export RCO_API_KEY in your environment".to_string(),
+        ],
+    ))
+}
+
+fn api_url_check(url: &str) -> Check {
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.has_host() => {
+            Check::ok("api url", url)
+        }
+        Ok(parsed) => Check::fail(
+            "api url",
+            format!("'{}' is not an http(s) URL ({})", url, parsed.scheme()),
+            vec!["Use a URL like https://api.example.com/v1".to_string()],
+        ),
+        Err(e) => Check::fail(
+            "api url",
+            format!("'{}' is not a valid URL: {}", url, e),
+            vec![
+                "Use a URL like https://api.example.com/v1".to_string(),
+                "Or remove it to use the provider default: rco config reset RCO_API_URL"
+                    .to_string(),
+            ],
+        ),
+    }
+}
+
+/// Numeric and enumerated settings are in range.
+fn limit_checks(config: &Config) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let set = |key: &str, example: &str| format!("Set it with: rco config set {}={}", key, example);
+
+    if config.description_max_length == 0 {
+        checks.push(Check::fail(
+            "description_max_length",
+            "RCO_DESCRIPTION_MAX_LENGTH is 0, so every description is truncated away",
+            vec![set("RCO_DESCRIPTION_MAX_LENGTH", "100")],
+        ));
+    }
+    if config.tokens_max_input == 0 {
+        checks.push(Check::fail(
+            "tokens_max_input",
+            "RCO_TOKENS_MAX_INPUT is 0, so no diff fits in the prompt",
+            vec![set("RCO_TOKENS_MAX_INPUT", "4096")],
+        ));
+    }
+    if config.tokens_max_output == 0 {
+        checks.push(Check::fail(
+            "tokens_max_output",
+            "RCO_TOKENS_MAX_OUTPUT is 0, so the model cannot reply",
+            vec![set("RCO_TOKENS_MAX_OUTPUT", "500")],
+        ));
+    } else if config.tokens_max_output as usize >= config.tokens_max_input {
+        checks.push(Check::warn(
+            "tokens_max_output",
+            format!(
+                "RCO_TOKENS_MAX_OUTPUT ({}) is not below RCO_TOKENS_MAX_INPUT ({})",
+                config.tokens_max_output, config.tokens_max_input
+            ),
+            vec![set("RCO_TOKENS_MAX_OUTPUT", "500")],
+        ));
+    }
+    if !(1..=5).contains(&config.generate_count) {
+        checks.push(Check::fail(
+            "generate_count",
+            format!(
+                "RCO_GENERATE_COUNT is {}, expected 1 to 5",
+                config.generate_count
+            ),
+            vec![set("RCO_GENERATE_COUNT", "1")],
+        ));
+    }
+    if !matches!(config.commit_type.as_str(), "conventional" | "gitmoji") {
+        checks.push(Check::warn(
+            "commit_type",
+            format!(
+                "Unknown RCO_COMMIT_TYPE '{}', expected conventional or gitmoji",
+                config.commit_type
+            ),
+            vec![set("RCO_COMMIT_TYPE", "conventional")],
+        ));
+    }
+    if let Some(action) = &config.budget_action {
+        if let Err(e) = crate::usage::BudgetAction::parse(action) {
+            checks.push(Check::fail(
+                "budget_action",
+                e.to_string(),
+                vec![set("RCO_BUDGET_ACTION", "warn")],
+            ));
+        }
+    }
+
+    if checks.is_empty() {
+        checks.push(Check::ok("limits", "all values in range"));
+    }
+    checks
+}
+
+/// The active account exists, uses a known provider and has credentials.
+fn account_checks(config: &Config) -> Vec<Check> {
+    let accounts = match AccountsConfig::load() {
+        Ok(Some(accounts)) => accounts,
+        Ok(None) => return Vec::new(),
+        Err(e) => {
+            return vec![Check::fail(
+                "account",
+                format!("Could not read accounts: {:#}", e),
+                vec!["Validate it with 'rco config schema accounts'".to_string()],
+            )]
+        }
+    };
+    let Some(alias) = accounts.get_active_alias() else {
+        return Vec::new();
+    };
+    let Some(account) = accounts.get_account(alias) else {
+        return vec![Check::warn(
+            "account",
+            format!(
+                "Active account '{}' does not exist; the default provider settings are used",
+                alias
+            ),
+            vec![
+                "List accounts with: rco config list-accounts".to_string(),
+                "Switch with: rco config use-account <alias>".to_string(),
+            ],
+        )];
+    };
+
+    let mut checks = vec![Check::ok("account", alias)];
+    if provider_check(&account.provider, "account provider", &mut checks) {
+        let model = account.model.as_deref().unwrap_or(&config.model);
+        let mut check = model_check(
+            &account.provider,
+            model,
+            account.api_url.is_some() || config.api_url.is_some(),
+            "RCO_MODEL",
+        );
+        check.name = "account model".to_string();
+        checks.push(check);
+    }
+    if let Some(url) = &account.api_url {
+        let mut check = api_url_check(url);
+        check.name = "account api url".to_string();
+        checks.push(check);
+    }
+    checks.push(account_credentials_check(account));
+    checks
+}
+
+fn account_credentials_check(account: &AccountConfig) -> Check {
+    if let AuthMethod::OAuth {
+        oidc: Some(oidc), ..
+    } = &account.auth
+    {
+        if oidc.grant == OidcGrant::ClientCredentials {
+            let name = oidc.client_secret_env.as_deref().unwrap_or_default();
+            return if !name.is_empty() && std::env::var_os(name).is_some() {
+                Check::ok(
+                    "account credentials",
+                    format!("client credentials (${})", name),
+                )
+            } else {
+                Check::fail(
+                    "account credentials",
+                    format!("No client secret for account '{}'", account.alias),
+                    vec![if name.is_empty() {
+                        "Set client_secret_env for the account in accounts.toml".to_string()
+                    } else {
+                        format!("Export {} in your environment", name)
+                    }],
+                )
+            };
+        }
+    }
+    let source = match &account.auth {
+        AuthMethod::ApiKey { .. } => "API key".to_string(),
+        AuthMethod::OAuth { .. } => "OAuth tokens".to_string(),
+        AuthMethod::EnvVar { name } => format!("${}", name),
+        AuthMethod::Bearer { .. } => "bearer token".to_string(),
+        AuthMethod::Command { command, .. } => format!("`{}`", command),
+    };
+    let hints = match &account.auth {
+        AuthMethod::EnvVar { name } => vec![format!("Export {} in your environment", name)],
+        AuthMethod::OAuth { oidc: Some(_), .. } => vec![format!(
+            "Log in with: rco auth login --account {}",
+            account.alias
+        )],
+        AuthMethod::OAuth { .. } => vec!["Log in again with: rco auth login".to_string()],
+        AuthMethod::Command { command, .. } => vec![
+            format!("Run `{}` yourself to check it prints the key", command),
+            "Raise timeout_secs for the account in accounts.toml if it needs longer".to_string(),
+        ],
+        _ => vec![format!(
+            "Re-add the account with: rco config add-provider --alias {}",
+            account.alias
+        )],
+    };
+
+    match auth::account_credential(account) {
+        Ok(Some(_)) => Check::ok("account credentials", source),
+        Ok(None) => Check::fail(
+            "account credentials",
+            format!("No {} found for account '{}'", source, account.alias),
+            hints,
+        ),
+        Err(e) => Check::fail("account credentials", format!("{:#}", e), hints),
+    }
+}
+
+fn secure_storage_check() -> Check {
+    if secure_storage::is_available() {
+        Check::ok("secure storage", secure_storage::get_platform_info())
+    } else {
+        Check::warn(
+            "secure storage",
+            secure_storage::status_message(),
+            vec![
+                "API keys are kept in the plaintext config file".to_string(),
+                "Export RCO_API_KEY instead to keep keys off disk".to_string(),
+            ],
+        )
+    }
+}
+
+/// Report which rco hooks are installed in the current repository.
+fn hooks_check() -> Check {
+    let Ok(root) = git::get_repo_root() else {
+        return Check::ok("git hooks", "not in a git repository");
+    };
+    let hooks_dir = Path::new(&root).join(".git").join("hooks");
+
+    let mut installed = Vec::new();
+    let mut not_executable = Vec::new();
+    for hook in HOOKS {
+        let path = hooks_dir.join(hook);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !content.contains("rco --hook") {
+            continue;
+        }
+        installed.push(hook);
+        if !is_executable(&path) {
+            not_executable.push(path.display().to_string());
+        }
+    }
+
+    if !not_executable.is_empty() {
+        return Check::warn(
+            "git hooks",
+            format!("{} installed but not executable", installed.join(", ")),
+            not_executable
+                .iter()
+                .map(|path| format!("Run: chmod +x {}", path))
+                .collect(),
+        );
+    }
+    if installed.is_empty() {
+        Check::ok(
+            "git hooks",
+            "not installed (optional; see 'rco hook --help')",
+        )
+    } else {
+        Check::ok("git hooks", format!("{} installed", installed.join(", ")))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+fn tokenizer_check() -> Check {
+    match estimate_tokens(PING_DIFF) {
+        Ok(_) => Check::ok("tokenizer", "cl100k_base loaded"),
+        Err(e) => Check::fail(
+            "tokenizer",
+            format!("Could not load the tokenizer: {:#}", e),
+            vec!["Reinstall rco; the tokenizer data ships with the binary".to_string()],
+        ),
+    }
+}
+
+/// Generate a message for a tiny diff with the configured provider.
+async fn ping_check(config: &Config) -> Check {
+    let started = Instant::now();
+    let account = config.get_active_account().ok().flatten();
+    let provider = match &account {
+        Some(account) => providers::create_provider_for_account(account, config),
+        None => providers::create_provider(config),
+    };
+    let result = match provider {
+        Ok(provider) => tokio::time::timeout(
+            PING_TIMEOUT,
+            provider.generate_commit_message(PING_DIFF, None, false, config),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(anyhow::anyhow!(
+                "No response within {}s",
+                PING_TIMEOUT.as_secs()
+            ))
+        }),
+        Err(e) => Err(e),
+    };
+
+    let provider = account
+        .as_ref()
+        .map_or(config.ai_provider.as_str(), |a| a.provider.as_str());
+    match result {
+        Ok(_) => Check::ok(
+            "ping",
+            format!(
+                "{} responded in {} ms",
+                provider,
+                started.elapsed().as_millis()
+            ),
+        ),
+        Err(e) => {
+            let url = account
+                .as_ref()
+                .and_then(|a| a.api_url.as_deref())
+                .or(config.api_url.as_deref());
+            let mut hints = Vec::new();
+            if ProviderError::find(&e).is_some() {
+                hints.extend(e.to_structured().hints().iter().cloned());
+            } else {
+                if let Some(url) = url {
+                    hints.push(format!("Check that {} is reachable", url));
+                }
+                hints.push("Check the API key and model with 'rco config explain'".to_string());
+            }
+            Check::fail(
+                "ping",
+                format!("{} request failed: {:#}", provider, e),
+                hints,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failing(checks: &[Check]) -> Vec<&str> {
+        checks
+            .iter()
+            .filter(|c| c.status == Status::Fail)
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_config_has_no_value_errors() {
+        let config = Config {
+            api_key: Some("sk-test".to_string()),
+            ..Config::default()
+        };
+        assert!(failing(&validate_config(&config)).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_values_fail_with_hints() {
+        let config = Config {
+            api_key: Some("sk-test".to_string()),
+            description_max_length: 0,
+            generate_count: 9,
+            api_url: Some("not a url".to_string()),
+            ..Config::default()
+        };
+        let checks = validate_config(&config);
+        let failed = failing(&checks);
+        assert!(failed.contains(&"description_max_length"));
+        assert!(failed.contains(&"generate_count"));
+        assert!(failed.contains(&"api url"));
+        assert!(checks
+            .iter()
+            .filter(|c| c.status == Status::Fail)
+            .all(|c| !c.hints.is_empty()));
+    }
+
+    #[test]
+    fn test_unknown_provider_suggests_closest() {
+        let mut checks = Vec::new();
+        assert!(!provider_check("opneai", "provider", &mut checks));
+        assert_eq!(checks[0].status, Status::Fail);
+        assert_eq!(checks[0].hints[0], "Did you mean 'openai'?");
+    }
+
+    #[cfg(feature = "anthropic")]
+    #[test]
+    fn test_model_mismatched_to_provider() {
+        let check = model_check("anthropic", "gpt-4o", false, "RCO_MODEL");
+        assert_eq!(check.status, Status::Fail);
+        assert!(check
+            .hints
+            .iter()
+            .any(|h| h.contains("RCO_AI_PROVIDER=openai")));
+
+        assert_eq!(
+            model_check("claude", "claude-sonnet-4", false, "RCO_MODEL").status,
+            Status::Ok
+        );
+        // A custom endpoint may serve any model
+        assert_eq!(
+            model_check("anthropic", "gpt-4o", true, "RCO_MODEL").status,
+            Status::Ok
+        );
+    }
+
+    #[test]
+    fn test_api_url_requires_http() {
+        assert_eq!(api_url_check("http://localhost:11434").status, Status::Ok);
+        assert_eq!(api_url_check("ftp://example.com").status, Status::Fail);
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use serde::Serialize;
 use std::fs;
 use std::io::Write;
 #[cfg(unix)]
@@ -8,6 +9,9 @@ use std::path::Path;
 
 use crate::cli::{HookAction, HookCommand};
 use crate::git;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
 
 const PREPARE_COMMIT_MSG_HOOK: &str = "prepare-commit-msg";
 const PREPARE_COMMIT_MSG_CONTENT: &str = r#"#!/bin/sh
@@ -27,25 +31,57 @@ const PRECOMMIT_HOOK_CONTENT: &str = r#"- repo: https://github.com/hongkongkiwi/
   hooks:
     - id: rusty-commit-msg"#;
 
-pub async fn execute(cmd: HookCommand) -> Result<()> {
-    match cmd.action {
-        HookAction::PrepareCommitMsg => install_prepare_commit_msg_hook(),
-        HookAction::CommitMsg => install_commit_msg_hook(),
-        HookAction::Unset => uninstall_all_hooks(),
+/// Result of a hook action, emitted as the structured report payload.
+#[derive(Debug, Serialize)]
+struct HookReport {
+    /// What happened: installed, already_installed, uninstalled or not_installed
+    status: &'static str,
+    /// Hooks affected by the action
+    hooks: Vec<&'static str>,
+    /// File that was written or updated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Backup of a pre-existing hook, if one was made
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup: Option<String>,
+}
+
+impl HookReport {
+    fn new(status: &'static str, hooks: Vec<&'static str>) -> Self {
+        Self {
+            status,
+            hooks,
+            path: None,
+            backup: None,
+        }
+    }
+}
+
+pub async fn execute(cmd: HookCommand, format: OutputFormat) -> Result<()> {
+    let (name, report) = match cmd.action {
+        HookAction::PrepareCommitMsg => (
+            "hook prepare-commit-msg",
+            install_prepare_commit_msg_hook()?,
+        ),
+        HookAction::CommitMsg => ("hook commit-msg", install_commit_msg_hook()?),
+        HookAction::Unset => ("hook unset", uninstall_all_hooks()?),
         HookAction::Precommit { set, unset } => {
             if set {
-                install_precommit_hook()?;
+                ("hook precommit", install_precommit_hook()?)
             } else if unset {
-                uninstall_precommit_hook()?;
+                ("hook precommit", uninstall_precommit_hook()?)
             } else {
                 anyhow::bail!("Please specify either --set or --unset for pre-commit hooks");
             }
-            Ok(())
         }
-    }
+    };
+
+    Report::ok(name, report).emit(format);
+    Ok(())
 }
 
-fn install_prepare_commit_msg_hook() -> Result<()> {
+fn install_prepare_commit_msg_hook() -> Result<HookReport> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
@@ -56,21 +92,24 @@ fn install_prepare_commit_msg_hook() -> Result<()> {
 
     let hook_path = hooks_dir.join(PREPARE_COMMIT_MSG_HOOK);
 
+    let mut backup = None;
+
     // Check if hook already exists
     if hook_path.exists() {
         let existing_content = fs::read_to_string(&hook_path)?;
         if existing_content.contains("rco --hook") {
-            println!("{}", "prepare-commit-msg hook already installed".yellow());
-            return Ok(());
+            out.line("prepare-commit-msg hook already installed".yellow());
+            return Ok(HookReport::new(
+                "already_installed",
+                vec![PREPARE_COMMIT_MSG_HOOK],
+            ));
         }
 
         // Backup existing hook
         let backup_path = hook_path.with_extension("backup");
         fs::copy(&hook_path, &backup_path).context("Failed to backup existing hook")?;
-        println!(
-            "{}",
-            format!("Backed up existing hook to {}", backup_path.display()).yellow()
-        );
+        out.line(format!("Backed up existing hook to {}", backup_path.display()).yellow());
+        backup = Some(backup_path.display().to_string());
     }
 
     // Write the hook file
@@ -84,17 +123,19 @@ fn install_prepare_commit_msg_hook() -> Result<()> {
         fs::set_permissions(&hook_path, perms).context("Failed to make hook executable")?;
     }
 
-    println!(
-        "{}",
-        "✅ prepare-commit-msg hook installed successfully!".green()
-    );
-    println!("The hook will run automatically when you use 'git commit'");
-    println!("Note: This hook is interactive (prompts for confirmation)");
+    out.line("✅ prepare-commit-msg hook installed successfully!".green());
+    out.line("The hook will run automatically when you use 'git commit'");
+    out.line("Note: This hook is interactive (prompts for confirmation)");
 
-    Ok(())
+    Ok(HookReport {
+        path: Some(hook_path.display().to_string()),
+        backup,
+        ..HookReport::new("installed", vec![PREPARE_COMMIT_MSG_HOOK])
+    })
 }
 
-fn install_commit_msg_hook() -> Result<()> {
+fn install_commit_msg_hook() -> Result<HookReport> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
@@ -105,21 +146,21 @@ fn install_commit_msg_hook() -> Result<()> {
 
     let hook_path = hooks_dir.join(COMMIT_MSG_HOOK);
 
+    let mut backup = None;
+
     // Check if hook already exists
     if hook_path.exists() {
         let existing_content = fs::read_to_string(&hook_path)?;
         if existing_content.contains("rco --hook") {
-            println!("{}", "commit-msg hook already installed".yellow());
-            return Ok(());
+            out.line("commit-msg hook already installed".yellow());
+            return Ok(HookReport::new("already_installed", vec![COMMIT_MSG_HOOK]));
         }
 
         // Backup existing hook
         let backup_path = hook_path.with_extension("backup");
         fs::copy(&hook_path, &backup_path).context("Failed to backup existing hook")?;
-        println!(
-            "{}",
-            format!("Backed up existing hook to {}", backup_path.display()).yellow()
-        );
+        out.line(format!("Backed up existing hook to {}", backup_path.display()).yellow());
+        backup = Some(backup_path.display().to_string());
     }
 
     // Write the hook file
@@ -133,13 +174,18 @@ fn install_commit_msg_hook() -> Result<()> {
         fs::set_permissions(&hook_path, perms).context("Failed to make hook executable")?;
     }
 
-    println!("{}", "✅ commit-msg hook installed successfully!".green());
-    println!("This hook generates commit messages without prompting (non-interactive)");
+    out.line("✅ commit-msg hook installed successfully!".green());
+    out.line("This hook generates commit messages without prompting (non-interactive)");
 
-    Ok(())
+    Ok(HookReport {
+        path: Some(hook_path.display().to_string()),
+        backup,
+        ..HookReport::new("installed", vec![COMMIT_MSG_HOOK])
+    })
 }
 
-fn uninstall_all_hooks() -> Result<()> {
+fn uninstall_all_hooks() -> Result<HookReport> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
@@ -181,15 +227,13 @@ fn uninstall_all_hooks() -> Result<()> {
     }
 
     if uninstalled.is_empty() {
-        println!("{}", "No Rusty Commit hooks installed".yellow());
-    } else {
-        println!(
-            "{}",
-            format!("✅ Uninstalled hooks: {}", uninstalled.join(", ")).green()
-        );
+        out.line("No Rusty Commit hooks installed".yellow());
+        return Ok(HookReport::new("not_installed", uninstalled));
     }
 
-    Ok(())
+    out.line(format!("✅ Uninstalled hooks: {}", uninstalled.join(", ")).green());
+
+    Ok(HookReport::new("uninstalled", uninstalled))
 }
 
 pub fn is_hook_called(args: &[String]) -> bool {
@@ -214,6 +258,7 @@ pub async fn prepare_commit_msg_hook(args: &[String]) -> Result<()> {
 
     // Generate commit message
     let config = crate::config::Config::load()?;
+    crate::usage::check_budget(&config)?;
     let provider = crate::providers::create_provider(&config)?;
     let message = provider
         .generate_commit_message(&diff, None, false, &config)
@@ -225,7 +270,8 @@ pub async fn prepare_commit_msg_hook(args: &[String]) -> Result<()> {
     Ok(())
 }
 
-fn install_precommit_hook() -> Result<()> {
+fn install_precommit_hook() -> Result<HookReport> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
@@ -235,9 +281,9 @@ fn install_precommit_hook() -> Result<()> {
     if config_path.exists() {
         let content = fs::read_to_string(&config_path)?;
         if content.contains("hongkongkiwi/precommit-rusty-commit") {
-            println!("{}", "Pre-commit hook already installed".yellow());
-            println!("To update, run: pre-commit autoupdate");
-            return Ok(());
+            out.line("Pre-commit hook already installed".yellow());
+            out.line("To update, run: pre-commit autoupdate");
+            return Ok(HookReport::new("already_installed", vec!["pre-commit"]));
         }
     }
 
@@ -250,30 +296,34 @@ fn install_precommit_hook() -> Result<()> {
         .and_then(|mut f| f.write_all(hook_entry.as_bytes()))
         .context("Failed to write to .pre-commit-config.yaml")?;
 
-    println!("{}", "✅ Pre-commit hook installed successfully!".green());
-    println!("Run 'pre-commit install' to activate the hook");
-    println!("Then use 'git commit' as normal - the hook will generate commit messages");
+    out.line("✅ Pre-commit hook installed successfully!".green());
+    out.line("Run 'pre-commit install' to activate the hook");
+    out.line("Then use 'git commit' as normal - the hook will generate commit messages");
 
-    Ok(())
+    Ok(HookReport {
+        path: Some(config_path.display().to_string()),
+        ..HookReport::new("installed", vec!["pre-commit"])
+    })
 }
 
-fn uninstall_precommit_hook() -> Result<()> {
+fn uninstall_precommit_hook() -> Result<HookReport> {
+    let out = output::context::get();
     git::assert_git_repo()?;
 
     let repo_root = git::get_repo_root()?;
     let config_path = Path::new(&repo_root).join(".pre-commit-config.yaml");
 
     if !config_path.exists() {
-        println!("{}", "No .pre-commit-config.yaml found".yellow());
-        return Ok(());
+        out.line("No .pre-commit-config.yaml found".yellow());
+        return Ok(HookReport::new("not_installed", vec!["pre-commit"]));
     }
 
     let content = fs::read_to_string(&config_path)?;
 
     // Check if our hook exists
     if !content.contains("hongkongkiwi/precommit-rusty-commit") {
-        println!("{}", "Pre-commit hook not found".yellow());
-        return Ok(());
+        out.line("Pre-commit hook not found".yellow());
+        return Ok(HookReport::new("not_installed", vec!["pre-commit"]));
     }
 
     // Remove the hook entry (lines containing our repo)
@@ -299,7 +349,10 @@ fn uninstall_precommit_hook() -> Result<()> {
     fs::write(&config_path, cleaned.join("\n") + "\n")
         .context("Failed to update .pre-commit-config.yaml")?;
 
-    println!("{}", "✅ Pre-commit hook uninstalled successfully!".green());
+    out.line("✅ Pre-commit hook uninstalled successfully!".green());
 
-    Ok(())
+    Ok(HookReport {
+        path: Some(config_path.display().to_string()),
+        ..HookReport::new("uninstalled", vec!["pre-commit"])
+    })
 }
@@ -338,6 +338,7 @@ async fn generate_commit_message_internal(
 ) -> Result<String> {
     use crate::providers;
 
+    crate::usage::check_budget(config)?;
     let provider = providers::create_provider(config)?;
     let message = provider
         .generate_commit_message(diff, context, full_gitmoji, config)
@@ -3,10 +3,16 @@ pub mod commit;
 pub mod commitlint;
 pub mod completions;
 pub mod config;
+pub mod credential;
+pub mod doctor;
 pub mod githook;
 pub mod mcp;
 pub mod model;
 pub mod pr;
+pub mod secrets;
 pub mod setup;
 pub mod skills;
+pub mod stats;
+pub mod style;
 pub mod update;
+pub mod usage;
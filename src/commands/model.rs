@@ -4,33 +4,68 @@ use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input};
 
 use crate::cli::ModelCommand;
 use crate::config::Config;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
 
-pub async fn execute(cmd: ModelCommand) -> Result<()> {
+pub async fn execute(cmd: ModelCommand, format: OutputFormat) -> Result<()> {
     let mut config = Config::load()?;
 
     if cmd.list {
-        list_models(&config, cmd.provider.as_deref()).await?;
+        list_models(&config, cmd.provider.as_deref(), format).await?;
         return Ok(());
     }
 
     // Interactive model selection
-    select_model_interactive(&mut config).await?;
+    select_model_interactive(&mut config, format).await?;
 
     Ok(())
 }
 
-async fn list_models(config: &Config, provider_override: Option<&str>) -> Result<()> {
+async fn list_models(
+    config: &Config,
+    provider_override: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let out = output::context::get();
     let provider = provider_override
         .unwrap_or(config.ai_provider.as_str())
         .to_lowercase();
 
-    println!(
-        "{}",
-        format!("Available models for provider: {}", provider).green()
-    );
-    println!("{}", "─".repeat(50).dimmed());
+    if format.is_structured() {
+        Report::ok(
+            "model list",
+            serde_json::json!({
+                "provider": provider,
+                "current_model": config.model,
+                "models": listed_models(&provider),
+            }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    out.line(format!("Available models for provider: {}", provider).green());
+    out.line("─".repeat(50).dimmed());
+
+    let models = listed_models(&provider);
+
+    for (i, model) in models.iter().enumerate() {
+        let marker = if config.model == *model { "✓" } else { " " };
+        out.result(format!("{}. {} {}", i + 1, marker, model));
+    }
+
+    out.line("");
+    out.line("To set a model:".yellow());
+    out.line("  rco config set RCO_MODEL=<model_name>");
+    out.line("  rco model  # interactive selection");
+
+    Ok(())
+}
 
-    let models = match provider.as_str() {
+/// Models shown by `rco model --list` for a provider
+fn listed_models(provider: &str) -> Vec<&'static str> {
+    match provider {
         "openai" | "deepseek" | "groq" | "openrouter" | "together" | "deepinfra"
         | "huggingface" | "mistral" | "fireworks" | "moonshot" | "qwen" | "qwen-coder"
         | "amazon-bedrock" | "github-models" => vec![
@@ -72,35 +107,17 @@ async fn list_models(config: &Config, provider_override: Option<&str>) -> Result
         "azure" | "azure-openai" => vec!["gpt-4o", "gpt-4o-mini", "gpt-4-turbo", "gpt-35-turbo"],
         "perplexity" => vec!["sonar-reasoning", "sonar", "r1-1776", "doctl"],
         _ => vec!["gpt-3.5-turbo", "gpt-4", "gpt-4o", "claude-3-5-sonnet"],
-    };
-
-    for (i, model) in models.iter().enumerate() {
-        let marker = if config.model == *model {
-            "✓"
-        } else {
-            " "
-        };
-        println!("{}. {} {}", i + 1, marker, model);
     }
-
-    println!();
-    println!("{}", "To set a model:".yellow());
-    println!("  rco config set RCO_MODEL=<model_name>");
-    println!("  rco model  # interactive selection");
-
-    Ok(())
 }
 
-async fn select_model_interactive(config: &mut Config) -> Result<()> {
-    let provider = config
-        .ai_provider
-        .as_str()
-        .to_lowercase();
+async fn select_model_interactive(config: &mut Config, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
+    let provider = config.ai_provider.as_str().to_lowercase();
 
-    println!("{}", "🤖 Interactive Model Selection".green().bold());
-    println!("Current provider: {}", provider.cyan());
-    println!("Current model: {}", config.model.cyan());
-    println!("{}", "─".repeat(50).dimmed());
+    out.line("🤖 Interactive Model Selection".green().bold());
+    out.line(format!("Current provider: {}", provider.cyan()));
+    out.line(format!("Current model: {}", config.model.cyan()));
+    out.line("─".repeat(50).dimmed());
 
     // Get model list for provider
     let models = get_provider_models(&provider);
@@ -129,8 +146,17 @@ async fn select_model_interactive(config: &mut Config) -> Result<()> {
     // Save config
     config.save()?;
 
-    println!();
-    println!("{}", format!("✅ Model set to: {}", config.model).green());
+    if format.is_structured() {
+        Report::ok(
+            "model select",
+            serde_json::json!({ "provider": provider, "model": config.model }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    out.line("");
+    out.line(format!("✅ Model set to: {}", config.model).green());
 
     Ok(())
 }
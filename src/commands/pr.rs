@@ -6,60 +6,34 @@ use std::process::Command;
 use crate::cli::PrCommand;
 use crate::config::Config;
 use crate::git;
+use crate::output;
+use crate::output::prelude::OutputFormat;
 use crate::output::progress;
+use crate::output::report::Report;
 use crate::providers;
+use crate::usage;
 
-/// Unified output helper for PR commands.
-#[allow(dead_code)]
-struct PrOutput;
-
-#[allow(dead_code)]
-impl PrOutput {
-    fn header(&self, text: &str) {
-        println!("\n{}", text.green().bold());
-    }
-
-    fn subheader(&self, text: &str) {
-        println!("{}", text.dimmed());
-    }
-
-    fn success(&self, message: &str) {
-        println!("{}", format!("✓ {}", message).green());
-    }
-
-    fn warning(&self, message: &str) {
-        println!("{}", message.yellow());
-    }
-
-    fn info(&self, message: &str) {
-        println!("{}", message.cyan());
-    }
-
-    fn divider(&self) {
-        println!("{}", "─".repeat(50).dimmed());
-    }
-
-    fn section(&self, title: &str) {
-        self.divider();
-        println!("{}", title.green().bold());
-        self.divider();
-    }
-}
-
-pub async fn execute(cmd: PrCommand) -> Result<()> {
+pub async fn execute(cmd: PrCommand, format: OutputFormat) -> Result<()> {
     let config = Config::load()?;
     let repo_root = git::get_repo_root()?;
 
     match cmd.action {
         crate::cli::PrAction::Generate { base } => {
-            generate_pr_description(&config, base.as_deref()).await
+            generate_pr_description(&config, base.as_deref(), format).await
+        }
+        crate::cli::PrAction::Browse { base } => {
+            browse_pr_page(&repo_root, base.as_deref(), format)
         }
-        crate::cli::PrAction::Browse { base } => browse_pr_page(&repo_root, base.as_deref()),
     }
 }
 
-async fn generate_pr_description(config: &Config, base_branch: Option<&str>) -> Result<()> {
-    let out = PrOutput;
+async fn generate_pr_description(
+    config: &Config,
+    base_branch: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let out = output::context::get();
+    let structured = format.is_structured();
     let current_branch = git::get_current_branch()?;
     let base = base_branch.unwrap_or("main");
 
@@ -73,10 +47,14 @@ async fn generate_pr_description(config: &Config, base_branch: Option<&str>) ->
     let diff = git::get_diff_between(base, &current_branch)?;
 
     if commits.is_empty() {
+        if structured {
+            anyhow::bail!("No commits found to generate PR description.");
+        }
         out.warning("No commits found to generate PR description.");
         return Ok(());
     }
 
+    usage::check_budget(config)?;
     let pb = progress::spinner("Generating PR description...");
 
     let provider = providers::create_provider(config)?;
@@ -86,10 +64,26 @@ async fn generate_pr_description(config: &Config, base_branch: Option<&str>) ->
 
     pb.finish_with_message("PR description generated!");
 
+    if structured {
+        Report::ok(
+            "pr generate",
+            serde_json::json!({
+                "branch": current_branch,
+                "base": base,
+                "provider": config.ai_provider,
+                "model": config.model,
+                "commits": commits,
+                "description": description,
+            }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
     // Display the description
     out.header("Generated PR Description");
     out.divider();
-    println!("{}", description);
+    out.result(&description);
     out.divider();
 
     // Copy to clipboard option
@@ -103,33 +97,34 @@ async fn generate_pr_description(config: &Config, base_branch: Option<&str>) ->
     match selection {
         0 => {
             copy_to_clipboard(&description)?;
-            println!("{}", "✅ PR description copied to clipboard!".green());
+            out.line("✅ PR description copied to clipboard!".green());
         }
         1 => {
             // Save to file for preview
             let preview_file = format!("PR_DESCRIPTION_{}.md", current_branch.replace('/', "_"));
             std::fs::write(&preview_file, &description)?;
-            println!(
-                "{}",
-                format!("PR description saved to: {}", preview_file).green()
-            );
+            out.line(format!("PR description saved to: {}", preview_file).green());
 
             // Try to open in editor
             if let Ok(editor) = std::env::var("EDITOR") {
                 if let Err(e) = Command::new(&editor).arg(&preview_file).status() {
-                    eprintln!("Warning: Failed to open editor '{}': {}", editor, e);
+                    out.error(format!(
+                        "Warning: Failed to open editor '{}': {}",
+                        editor, e
+                    ));
                 }
             }
         }
         _ => {
-            println!("{}", "Cancelled.".yellow());
+            out.line("Cancelled.".yellow());
         }
     }
 
     Ok(())
 }
 
-fn browse_pr_page(_repo_root: &str, base_branch: Option<&str>) -> Result<()> {
+fn browse_pr_page(_repo_root: &str, base_branch: Option<&str>, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     let current_branch = git::get_current_branch()?;
     let base = base_branch.unwrap_or("main");
 
@@ -137,12 +132,22 @@ fn browse_pr_page(_repo_root: &str, base_branch: Option<&str>) -> Result<()> {
     let remote_url = git::get_remote_url(None)?;
     let pr_url = convert_to_pr_url(&remote_url, &current_branch, base)?;
 
-    println!("{}", format!("Opening PR page: {}", pr_url).green());
+    // Scripts get the URL instead of a browser window
+    if format.is_structured() {
+        Report::ok(
+            "pr browse",
+            serde_json::json!({ "branch": current_branch, "base": base, "url": pr_url }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    out.result(format!("Opening PR page: {}", pr_url).green());
 
     if let Err(e) = webbrowser::open(&pr_url) {
-        eprintln!("Failed to open browser: {}", e);
-        println!("Please open the following URL manually:");
-        println!("{}", pr_url);
+        out.error(format!("Failed to open browser: {}", e));
+        out.line("Please open the following URL manually:");
+        out.result(pr_url);
     }
 
     Ok(())
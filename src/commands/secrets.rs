@@ -0,0 +1,273 @@
+//! `rco secrets`: list, rotate and export the API keys and OAuth tokens kept
+//! in secure storage.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use serde_json::json;
+
+use crate::cli::{SecretsAction, SecretsCommand};
+use crate::config::accounts::{self, AccountsConfig, AuthMethod};
+use crate::config::secure_storage::{self, Backend};
+use crate::config::vault::{self, KeySource, Vault};
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::utils::fs::write_private;
+
+/// Secrets rco stores outside of any account.
+const GLOBAL_SECRETS: [&str; 4] = [
+    "RCO_API_KEY",
+    "claude_access_token",
+    "claude_refresh_token",
+    "claude_token_expires_at",
+];
+
+/// Per-account secrets stored under [`accounts::account_storage_key`].
+const ACCOUNT_SECRETS: [&str; 5] = [
+    "api_key",
+    "bearer_token",
+    "access_token",
+    "refresh_token",
+    "token_expires_at",
+];
+
+pub async fn execute(cmd: SecretsCommand, format: OutputFormat) -> Result<()> {
+    match cmd.action {
+        SecretsAction::List => list(format),
+        SecretsAction::Rotate {
+            key_file,
+            passphrase,
+        } => rotate(key_file, passphrase, format),
+        SecretsAction::Export { output } => export(output, format),
+    }
+}
+
+fn list(format: OutputFormat) -> Result<()> {
+    let backend = secure_storage::backend();
+    let names = stored_names(backend)?;
+    let location = secure_storage::get_platform_info();
+
+    if format.is_structured() {
+        Report::ok(
+            "secrets list",
+            json!({
+                "backend": backend.as_str(),
+                "location": location,
+                "secrets": names,
+            }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    let out = output::context::get();
+    out.header("🔐 Stored Secrets");
+    out.key_value("Backend", &location);
+    out.divider();
+    if names.is_empty() {
+        out.line("No secrets stored.".dimmed());
+    } else {
+        for name in &names {
+            out.line(format!("  {}", name));
+        }
+    }
+    Ok(())
+}
+
+fn rotate(key_file: Option<String>, passphrase: bool, format: OutputFormat) -> Result<()> {
+    if secure_storage::backend() != Backend::Vault {
+        bail!(
+            "Rotation is only supported for the encrypted vault. \
+             Select it with: rco config set RCO_SECRET_BACKEND=vault"
+        );
+    }
+    let path = vault::vault_path()?;
+    if !path.exists() {
+        bail!("No secrets vault at {}; nothing to rotate", path.display());
+    }
+
+    let current = KeySource::configured();
+    let target = match (key_file, passphrase) {
+        (Some(key_file), _) => KeySource::KeyFile(expand_home(&key_file)),
+        (None, true) => KeySource::Passphrase,
+        (None, false) => current.clone(),
+    };
+
+    let mut vault = Vault::open(&path, &current)?;
+    vault.rotate(&target)?;
+
+    let out = output::context::get();
+    if target != current {
+        if std::env::var("RCO_VAULT_KEY_FILE").is_ok() {
+            out.warning(
+                "RCO_VAULT_KEY_FILE is set in the environment; update it to match the new key source",
+            );
+        } else {
+            let key_file = match &target {
+                KeySource::KeyFile(path) => Some(path.as_path()),
+                KeySource::Passphrase => None,
+            };
+            secure_storage::save_vault_key_file(key_file)?;
+        }
+    }
+
+    let (mode, key_file) = match &target {
+        KeySource::KeyFile(path) => ("key_file", Some(path.display().to_string())),
+        KeySource::Passphrase => ("passphrase", None),
+    };
+    let names = vault.names();
+    if format.is_structured() {
+        Report::ok(
+            "secrets rotate",
+            json!({
+                "vault": path.display().to_string(),
+                "mode": mode,
+                "key_file": key_file,
+                "secrets": names.len(),
+            }),
+        )
+        .emit(format);
+    } else {
+        out.success(&format!(
+            "Re-encrypted {} secret(s) in {}",
+            names.len(),
+            path.display()
+        ));
+        if let Some(key_file) = key_file {
+            out.key_value("Key file", &key_file);
+        }
+    }
+    Ok(())
+}
+
+fn export(output_path: Option<String>, format: OutputFormat) -> Result<()> {
+    let backend = secure_storage::backend();
+    let mut secrets = Vec::new();
+    match backend {
+        Backend::Vault => {
+            let path = vault::vault_path()?;
+            if path.exists() {
+                let vault = Vault::open(&path, &KeySource::configured())?;
+                for name in vault.names() {
+                    if let Some(value) = vault.get(&name)? {
+                        secrets.push((name, value));
+                    }
+                }
+            }
+        }
+        Backend::Keyring => {
+            for name in candidate_names() {
+                if let Some(value) = secure_storage::get_secret(&name)? {
+                    secrets.push((name, value));
+                }
+            }
+        }
+    }
+
+    let out = output::context::get();
+    out.warning("Exported secrets are not encrypted; handle the output with care");
+
+    if let Some(path) = &output_path {
+        let path = expand_home(path);
+        write_private(&path, dotenv(&secrets).as_bytes())?;
+        if format.is_structured() {
+            Report::ok(
+                "secrets export",
+                json!({ "path": path.display().to_string(), "secrets": secrets.len() }),
+            )
+            .emit(format);
+        } else {
+            out.success(&format!(
+                "Exported {} secret(s) to {}",
+                secrets.len(),
+                path.display()
+            ));
+        }
+    } else if format.is_structured() {
+        let values: serde_json::Map<_, _> = secrets
+            .into_iter()
+            .map(|(name, value)| (name, value.into()))
+            .collect();
+        Report::ok("secrets export", json!({ "secrets": values })).emit(format);
+    } else {
+        print!("{}", dotenv(&secrets));
+    }
+    Ok(())
+}
+
+/// Names of the secrets present in `backend`. The keyring cannot be
+/// enumerated, so every name rco might have used is probed.
+fn stored_names(backend: Backend) -> Result<Vec<String>> {
+    match backend {
+        Backend::Vault => vault::names(),
+        Backend::Keyring => {
+            if !secure_storage::is_available() {
+                return Ok(Vec::new());
+            }
+            let mut names = Vec::new();
+            for name in candidate_names() {
+                if secure_storage::get_secret(&name)?.is_some() {
+                    names.push(name);
+                }
+            }
+            Ok(names)
+        }
+    }
+}
+
+/// Every storage name rco may have written, given the configured accounts.
+fn candidate_names() -> Vec<String> {
+    let mut names: Vec<String> = GLOBAL_SECRETS.iter().map(|s| s.to_string()).collect();
+    if let Ok(Some(config)) = AccountsConfig::load() {
+        let mut aliases: Vec<_> = config.accounts.values().collect();
+        aliases.sort_by(|a, b| a.alias.cmp(&b.alias));
+        for account in aliases {
+            match &account.auth {
+                AuthMethod::ApiKey { key_id } => names.push(key_id.clone()),
+                AuthMethod::Bearer { token_id } => names.push(token_id.clone()),
+                AuthMethod::OAuth { .. }
+                | AuthMethod::EnvVar { .. }
+                | AuthMethod::Command { .. } => {}
+            }
+            for key_type in ACCOUNT_SECRETS {
+                names.push(accounts::account_storage_key(&account.alias, key_type));
+            }
+        }
+    }
+    names.dedup();
+    names
+}
+
+/// Render secrets as `NAME='value'` lines that a POSIX shell can source.
+fn dotenv(secrets: &[(String, String)]) -> String {
+    secrets
+        .iter()
+        .map(|(name, value)| format!("{}='{}'\n", name, value.replace('\'', "'\\''")))
+        .collect()
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotenv_quotes_values_for_the_shell() {
+        let secrets = vec![
+            ("RCO_API_KEY".to_string(), "sk-test".to_string()),
+            ("token".to_string(), "it's".to_string()),
+        ];
+        assert_eq!(
+            dotenv(&secrets),
+            "RCO_API_KEY='sk-test'\ntoken='it'\\''s'\n"
+        );
+    }
+}
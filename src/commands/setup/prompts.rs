@@ -5,24 +5,22 @@ use colored::Colorize;
 use dialoguer::{Input, Select};
 
 use super::providers::{CommitFormat, LanguageOption, ProviderCategory, ProviderOption};
+use crate::output;
 
 /// Prompt user for API key
 pub fn prompt_for_api_key(provider_name: &str) -> Result<String> {
-    println!();
-    println!("{}", "API Key Configuration".bold());
-    println!(
-        "{}",
+    let out = output::context::get();
+    out.line("");
+    out.line("API Key Configuration".bold());
+    out.line(
         format!(
             "   Get your API key from the {} dashboard",
             provider_name.bright_cyan()
         )
-        .dimmed()
+        .dimmed(),
     );
-    println!(
-        "{}",
-        "   Your key will be stored securely in your system's keychain.".dimmed()
-    );
-    println!();
+    out.line("   Your key will be stored securely in your system's keychain.".dimmed());
+    out.line("");
 
     let api_key: String = Input::new()
         .with_prompt(format!(
@@ -35,12 +33,12 @@ pub fn prompt_for_api_key(provider_name: &str) -> Result<String> {
     let trimmed = api_key.trim();
 
     if trimmed.is_empty() {
-        println!();
-        println!(
+        out.line("");
+        out.line(format!(
             "{} No API key provided. You can set it later with: {}",
             "⚠️".yellow(),
             "rco config set RCO_API_KEY=<your_key>".bright_cyan()
-        );
+        ));
     } else {
         // Show last 4 characters for confirmation
         let masked = if trimmed.len() > 4 {
@@ -48,8 +46,12 @@ pub fn prompt_for_api_key(provider_name: &str) -> Result<String> {
         } else {
             "****".to_string()
         };
-        println!();
-        println!("{} API key saved: {}", "✓".green(), masked.dimmed());
+        out.line("");
+        out.line(format!(
+            "{} API key saved: {}",
+            "✓".green(),
+            masked.dimmed()
+        ));
     }
 
     Ok(trimmed.to_string())
@@ -57,13 +59,11 @@ pub fn prompt_for_api_key(provider_name: &str) -> Result<String> {
 
 /// Select commit format
 pub fn select_commit_format() -> Result<CommitFormat> {
-    println!();
-    println!("{}", "Commit Message Format".bold());
-    println!(
-        "{}",
-        "   Choose how your commit messages should be formatted.".dimmed()
-    );
-    println!();
+    let out = output::context::get();
+    out.line("");
+    out.line("Commit Message Format".bold());
+    out.line("   Choose how your commit messages should be formatted.".dimmed());
+    out.line("");
 
     let formats = CommitFormat::all();
     let items: Vec<_> = formats.iter().map(|f| f.display()).collect();
@@ -76,12 +76,12 @@ pub fn select_commit_format() -> Result<CommitFormat> {
 
     let format = formats.into_iter().nth(selection).unwrap();
 
-    println!();
-    println!(
+    out.line("");
+    out.line(format!(
         "{} Selected: {}",
         "✓".green(),
         format.as_str().bright_cyan()
-    );
+    ));
 
     // Show example
     let example = match format {
@@ -89,20 +89,18 @@ pub fn select_commit_format() -> Result<CommitFormat> {
         CommitFormat::Gitmoji => "✨ feat(auth): Add login functionality",
         CommitFormat::Simple => "Add login functionality",
     };
-    println!("  Example: {}", example.dimmed());
+    out.line(format!("  Example: {}", example.dimmed()));
 
     Ok(format)
 }
 
 /// Select output language
 pub fn select_language() -> Result<String> {
-    println!();
-    println!("{}", "Output Language".bold());
-    println!(
-        "{}",
-        "   What language should commit messages be generated in?".dimmed()
-    );
-    println!();
+    let out = output::context::get();
+    out.line("");
+    out.line("Output Language".bold());
+    out.line("   What language should commit messages be generated in?".dimmed());
+    out.line("");
 
     let languages = LanguageOption::all();
     let items: Vec<_> = languages.iter().map(|l| l.display).collect();
@@ -127,13 +125,11 @@ pub fn select_language() -> Result<String> {
 
 /// Select provider with categorized display (quick mode)
 pub fn select_provider_quick() -> Result<ProviderOption> {
-    println!();
-    println!("{}", "Select your AI provider:".bold());
-    println!(
-        "{}",
-        "   This determines which AI will generate your commit messages.".dimmed()
-    );
-    println!();
+    let out = output::context::get();
+    out.line("");
+    out.line("Select your AI provider:".bold());
+    out.line("   This determines which AI will generate your commit messages.".dimmed());
+    out.line("");
 
     let providers = ProviderOption::all();
 
@@ -223,22 +219,23 @@ pub fn select_provider_quick() -> Result<ProviderOption> {
             .unwrap()
     };
 
-    println!();
-    println!(
+    out.line("");
+    out.line(format!(
         "{} Selected: {} {}",
         "✓".green(),
         provider.name.bright_cyan(),
         format!("(model: {})", provider.default_model).dimmed()
-    );
+    ));
 
     Ok(provider)
 }
 
 /// Select provider with simple list (advanced mode)
 pub fn select_provider_advanced() -> Result<ProviderOption> {
-    println!();
-    println!("{}", "Select your AI provider:".bold());
-    println!();
+    let out = output::context::get();
+    out.line("");
+    out.line("Select your AI provider:".bold());
+    out.line("");
 
     let providers = ProviderOption::all();
     let items: Vec<_> = providers.iter().map(|p| p.display).collect();
@@ -251,8 +248,12 @@ pub fn select_provider_advanced() -> Result<ProviderOption> {
 
     let provider = providers.into_iter().nth(selection).unwrap();
 
-    println!();
-    println!("{} Selected: {}", "✓".green(), provider.name.bright_cyan());
+    out.line("");
+    out.line(format!(
+        "{} Selected: {}",
+        "✓".green(),
+        provider.name.bright_cyan()
+    ));
 
     Ok(provider)
 }
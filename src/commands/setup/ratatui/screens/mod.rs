@@ -1,13 +0,0 @@
-//! Screen modules for TUI setup
-//!
-//! Each screen module provides rendering functions for
-/// a specific stage of the setup process.
-
-pub mod welcome;
-pub mod provider;
-pub mod model;
-pub mod auth;
-pub mod style;
-pub mod hooks;
-pub mod settings;
-pub mod summary;
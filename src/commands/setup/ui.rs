@@ -1,35 +1,29 @@
 //! UI utilities for setup wizard
 
+use crate::output;
 use colored::Colorize;
 
 /// Print a section header with decorative border
 pub fn print_section_header(title: &str) {
-    println!();
-    println!(
-        "{}",
-        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed()
-    );
-    println!("{}", title.bold());
-    println!(
-        "{}",
-        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed()
-    );
+    let out = output::context::get();
+    out.line("");
+    out.line("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
+    out.line(title.bold());
+    out.line("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
 }
 
 /// Print welcome header
 pub fn print_welcome_header() {
-    println!();
-    println!(
+    let out = output::context::get();
+    out.line("");
+    out.line(format!(
         "{} {}",
         "🚀".green(),
         "Welcome to Rusty Commit Setup!".bold().white()
-    );
-    println!();
-    println!(
-        "{}",
-        "   Let's get you set up with AI-powered commit messages.".dimmed()
-    );
-    println!();
+    ));
+    out.line("");
+    out.line("   Let's get you set up with AI-powered commit messages.".dimmed());
+    out.line("");
 }
 
 /// Print completion message with configuration summary
@@ -40,59 +34,68 @@ pub fn print_completion_message(
     language: &str,
     is_advanced: bool,
 ) {
-    println!();
-    println!(
-        "{}",
-        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed()
-    );
-    println!();
-    println!("{} Setup complete!", "✓".green().bold());
-    println!();
+    let out = output::context::get();
+    out.line("");
+    out.line("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
+    out.line("");
+    out.line(format!("{} Setup complete!", "✓".green().bold()));
+    out.line("");
 
     // Show summary
-    println!("{}", "Configuration Summary:".bold());
-    println!();
+    out.line("Configuration Summary:".bold());
+    out.line("");
 
-    println!("  {} Provider: {}", "•".cyan(), ai_provider.bright_white());
-    println!("  {} Model: {}", "•".cyan(), model.bright_white());
-    println!(
+    out.line(format!(
+        "  {} Provider: {}",
+        "•".cyan(),
+        ai_provider.bright_white()
+    ));
+    out.line(format!("  {} Model: {}", "•".cyan(), model.bright_white()));
+    out.line(format!(
         "  {} Commit format: {}",
         "•".cyan(),
         commit_type.bright_white()
-    );
+    ));
     if language != "en" {
-        println!("  {} Language: {}", "•".cyan(), language.bright_white());
+        out.line(format!(
+            "  {} Language: {}",
+            "•".cyan(),
+            language.bright_white()
+        ));
     }
 
-    println!();
-    println!("{} You're ready to go!", "→".cyan());
-    println!();
-    println!("   Try it now:  {}", "rco".bold().bright_cyan().underline());
-    println!();
+    out.line("");
+    out.line(format!("{} You're ready to go!", "→".cyan()));
+    out.line("");
+    out.line(format!(
+        "   Try it now:  {}",
+        "rco".bold().bright_cyan().underline()
+    ));
+    out.line("");
 
     if is_advanced {
-        println!("   Make a commit:  {}", "git add . && rco".dimmed());
-        println!();
-        println!(
+        out.line(format!(
+            "   Make a commit:  {}",
+            "git add . && rco".dimmed()
+        ));
+        out.line("");
+        out.line(format!(
             "{} Modify settings anytime: {}",
             "→".cyan(),
             "rco setup --advanced".bright_cyan()
-        );
-        println!(
+        ));
+        out.line(format!(
             "{} Or use: {}",
             "→".cyan(),
             "rco config set <key>=<value>".bright_cyan()
-        );
+        ));
     } else {
-        println!(
+        out.line(format!(
             "   Want more options? Run: {}",
             "rco setup --advanced".bright_cyan()
-        );
+        ));
     }
 
-    println!();
-    println!(
-        "{}",
-        "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed()
-    );
+    out.line("");
+    out.line("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".dimmed());
 }
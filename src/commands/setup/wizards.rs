@@ -6,12 +6,36 @@ use dialoguer::{Confirm, Input, Select};
 
 use crate::cli::SetupCommand;
 use crate::config::Config;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
 
 use super::providers::CommitFormat;
 use super::ui::{print_completion_message, print_section_header, print_welcome_header};
 
 /// Main entry point for setup command
-pub async fn execute(cmd: SetupCommand) -> Result<()> {
+pub async fn execute(cmd: SetupCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
+    // Structured output is for scripts, so only the non-interactive mode applies
+    if format.is_structured() {
+        if !cmd.defaults {
+            anyhow::bail!("Interactive setup does not support --output-format; use --defaults");
+        }
+        let config = save_default_config()?;
+        Report::ok(
+            "setup",
+            serde_json::json!({
+                "mode": "defaults",
+                "provider": config.ai_provider,
+                "model": config.model,
+                "commit_type": config.commit_type,
+                "language": config.language,
+            }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
     print_welcome_header();
 
     // Determine if we're doing quick or advanced setup
@@ -22,9 +46,9 @@ pub async fn execute(cmd: SetupCommand) -> Result<()> {
         true
     } else {
         // Ask user which mode they prefer
-        println!();
-        println!("{}", "Choose your setup mode:".bold());
-        println!();
+        out.line("");
+        out.line("Choose your setup mode:".bold());
+        out.line("");
 
         let modes = vec![
             "🚀 Quick Setup - Just the essentials (recommended)",
@@ -49,6 +73,7 @@ pub async fn execute(cmd: SetupCommand) -> Result<()> {
 
 /// Quick setup with essential options only
 async fn run_quick_setup() -> Result<()> {
+    let out = output::context::get();
     let mut config = Config::load()?;
 
     // Step 1: Provider selection
@@ -63,12 +88,12 @@ async fn run_quick_setup() -> Result<()> {
             config.api_key = Some(api_key);
         }
     } else {
-        println!();
-        println!(
+        out.line("");
+        out.line(format!(
             "{} {} doesn't require an API key - great for privacy!",
             "ℹ️".blue(),
             provider.name.bright_cyan()
-        );
+        ));
     }
 
     // Step 3: Commit format
@@ -307,6 +332,29 @@ async fn run_advanced_setup() -> Result<()> {
 
 /// Apply sensible defaults without prompting
 async fn apply_defaults() -> Result<()> {
+    let out = output::context::get();
+    save_default_config()?;
+
+    out.line("");
+    out.line(format!(
+        "{} Default configuration applied!",
+        "✓".green().bold()
+    ));
+    out.line("");
+    out.line("   Provider: openai (gpt-4o-mini)");
+    out.line("   Format: conventional commits");
+    out.line("");
+    out.line(format!(
+        "   Set your API key: {}",
+        "rco config set RCO_API_KEY=<your_key>".bright_cyan()
+    ));
+    out.line("");
+
+    Ok(())
+}
+
+/// Apply and save the default configuration
+fn save_default_config() -> Result<Config> {
     let mut config = Config::load()?;
 
     // Apply sensible defaults without prompting
@@ -331,17 +379,5 @@ async fn apply_defaults() -> Result<()> {
 
     config.save()?;
 
-    println!();
-    println!("{} Default configuration applied!", "✓".green().bold());
-    println!();
-    println!("   Provider: openai (gpt-4o-mini)");
-    println!("   Format: conventional commits");
-    println!();
-    println!(
-        "   Set your API key: {}",
-        "rco config set RCO_API_KEY=<your_key>".bright_cyan()
-    );
-    println!();
-
-    Ok(())
+    Ok(config)
 }
@@ -3,38 +3,96 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dirs;
+use serde_json::json;
 use std::fs;
 
 use crate::cli::{SkillsAction, SkillsCommand};
-use crate::skills::{SkillCategory, SkillsManager};
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::skills::{Skill, SkillCategory, SkillsManager};
 
-pub async fn execute(cmd: SkillsCommand) -> Result<()> {
+pub async fn execute(cmd: SkillsCommand, format: OutputFormat) -> Result<()> {
     match cmd.action {
-        SkillsAction::List { category } => list_skills(category).await,
+        SkillsAction::List { category } => list_skills(category, format).await,
         SkillsAction::Create {
             name,
             category,
             project,
-        } => create_skill(name, category, project).await,
-        SkillsAction::Show { name } => show_skill(name).await,
-        SkillsAction::Remove { name, force } => remove_skill(name, force).await,
-        SkillsAction::Open => open_skills_dir().await,
-        SkillsAction::Import { source, name } => import_skills(source, name).await,
-        SkillsAction::Available { source } => list_available_skills(source).await,
+        } => {
+            let path = create_skill(name.clone(), category.clone(), project).await?;
+            Report::ok(
+                "skills create",
+                json!({
+                    "name": name,
+                    "category": parse_category(&category).to_string(),
+                    "project": project,
+                    "path": path.display().to_string(),
+                }),
+            )
+            .emit(format);
+            Ok(())
+        }
+        SkillsAction::Show { name } => show_skill(name, format).await,
+        SkillsAction::Remove { name, force } => {
+            let removed = remove_skill(name.clone(), force).await?;
+            Report::ok("skills remove", json!({ "name": name, "removed": removed })).emit(format);
+            Ok(())
+        }
+        SkillsAction::Open => {
+            let path = open_skills_dir().await?;
+            Report::ok("skills open", json!({ "path": path.display().to_string() })).emit(format);
+            Ok(())
+        }
+        SkillsAction::Import { source, name } => {
+            let imported = import_skills(source.clone(), name).await?;
+            Report::ok(
+                "skills import",
+                json!({ "source": source, "imported": imported }),
+            )
+            .emit(format);
+            Ok(())
+        }
+        SkillsAction::Available { source } => list_available_skills(source, format).await,
     }
 }
 
-async fn list_skills(category_filter: Option<String>) -> Result<()> {
+/// Structured summary of a skill for reports
+fn skill_summary(skill: &Skill) -> serde_json::Value {
+    json!({
+        "name": skill.name(),
+        "description": skill.description(),
+        "category": skill.category().to_string(),
+        "source": skill.source().to_string(),
+        "version": skill.manifest.skill.version,
+        "author": skill.manifest.skill.author,
+        "tags": skill.manifest.skill.tags,
+        "path": skill.path.display().to_string(),
+    })
+}
+
+async fn list_skills(category_filter: Option<String>, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     let mut manager = SkillsManager::new()?;
     manager.discover()?;
 
     let skills = manager.skills();
 
+    if format.is_structured() {
+        let listed: Vec<_> = match category_filter {
+            Some(ref cat) => manager.by_category(&parse_category(cat)),
+            None => skills.iter().collect(),
+        };
+        let listed: Vec<_> = listed.into_iter().map(skill_summary).collect();
+        Report::ok("skills list", json!({ "skills": listed })).emit(format);
+        return Ok(());
+    }
+
     if skills.is_empty() {
-        println!("{}", "No skills found.".yellow());
-        println!();
-        println!("Create your first skill with:");
-        println!("  {}", "rco skills create my-skill".cyan());
+        out.line("No skills found.".yellow());
+        out.line("");
+        out.line("Create your first skill with:");
+        out.line(format!("  {}", "rco skills create my-skill".cyan()));
         return Ok(());
     }
 
@@ -51,15 +109,12 @@ async fn list_skills(category_filter: Option<String>) -> Result<()> {
     };
 
     if filtered_skills.is_empty() {
-        println!(
-            "{}",
-            format!("No skills found in category: {}", category_filter.unwrap()).yellow()
-        );
+        out.line(format!("No skills found in category: {}", category_filter.unwrap()).yellow());
         return Ok(());
     }
 
-    println!("{}", "Available Skills".bold().underline());
-    println!();
+    out.line("Available Skills".bold().underline());
+    out.line("");
 
     // Group by category
     let mut by_category: std::collections::HashMap<String, Vec<_>> =
@@ -76,7 +131,7 @@ async fn list_skills(category_filter: Option<String>) -> Result<()> {
     categories.sort();
 
     for category in categories {
-        println!("{}", format!("[{}]", category).cyan().bold());
+        out.line(format!("[{}]", category).cyan().bold());
         for skill in by_category.get(category).unwrap() {
             let source_marker = match skill.source() {
                 crate::skills::SkillSource::Builtin => format!(" {}", "[built-in]".dimmed()),
@@ -86,12 +141,12 @@ async fn list_skills(category_filter: Option<String>) -> Result<()> {
                 crate::skills::SkillSource::User => String::new(),
             };
 
-            println!(
+            out.line(format!(
                 "  {}{}\n    {}",
                 skill.name().green(),
                 source_marker,
                 skill.description().dimmed()
-            );
+            ));
 
             // Show tags if any
             if !skill.manifest.skill.tags.is_empty() {
@@ -102,22 +157,23 @@ async fn list_skills(category_filter: Option<String>) -> Result<()> {
                     .iter()
                     .map(|t| format!("#{}", t))
                     .collect();
-                println!("    {}", tags.join(" ").dimmed());
+                out.line(format!("    {}", tags.join(" ").dimmed()));
             }
         }
-        println!();
+        out.line("");
     }
 
-    println!(
+    out.line(format!(
         "Total: {} skill{}",
         filtered_skills.len(),
         if filtered_skills.len() == 1 { "" } else { "s" }
-    );
+    ));
 
     Ok(())
 }
 
-async fn create_skill(name: String, category: String, project: bool) -> Result<()> {
+async fn create_skill(name: String, category: String, project: bool) -> Result<std::path::PathBuf> {
+    let out = output::context::get();
     let manager = SkillsManager::new()?;
     let skill_category = parse_category(&category);
 
@@ -127,12 +183,12 @@ async fn create_skill(name: String, category: String, project: bool) -> Result<(
             anyhow::anyhow!("Not in a git repository. Cannot create project-level skill.")
         })?;
 
-        println!(
+        out.line(format_args!(
             "{} Creating new {} project skill '{}'...",
             "→".cyan(),
             skill_category.to_string().cyan(),
             name.green()
-        );
+        ));
 
         let skill_dir = project_dir.join(&name);
         if skill_dir.exists() {
@@ -148,53 +204,51 @@ async fn create_skill(name: String, category: String, project: bool) -> Result<(
         skill_dir
     } else {
         // Create user-level skill
-        println!(
+        out.line(format_args!(
             "{} Creating new {} user skill '{}'...",
             "→".cyan(),
             skill_category.to_string().cyan(),
             name.green()
-        );
+        ));
 
         manager.create_skill(&name, skill_category)?
     };
 
-    println!(
+    out.line(format_args!(
         "{} Skill created at: {}",
         "✓".green(),
         skill_path.display().to_string().cyan()
-    );
-    println!();
-    println!("Next steps:");
-    println!(
+    ));
+    out.line("");
+    out.line("Next steps:");
+    out.line(format_args!(
         "  1. Edit {} to customize your skill",
         skill_path.join("skill.toml").display().to_string().cyan()
-    );
-    println!(
+    ));
+    out.line(format_args!(
         "  2. Modify {} with your custom prompt",
         skill_path.join("prompt.md").display().to_string().cyan()
-    );
-    println!(
+    ));
+    out.line(format_args!(
         "  3. Use your skill: {}",
         format!("rco --skill {}", name).cyan()
-    );
+    ));
 
     if project {
-        println!();
-        println!(
-            "{}",
+        out.line("");
+        out.line(
             "Note: Project skills are shared with everyone who clones this repo."
                 .yellow()
-                .dimmed()
+                .dimmed(),
         );
-        println!(
-            "{}",
+        out.line(
             "      Make sure to commit the .rco/skills/ directory to version control."
                 .yellow()
-                .dimmed()
+                .dimmed(),
         );
     }
 
-    Ok(())
+    Ok(skill_path)
 }
 
 /// Create skill files (skill.toml and prompt.md)
@@ -250,7 +304,8 @@ Generate a commit message that:
     Ok(())
 }
 
-async fn show_skill(name: String) -> Result<()> {
+async fn show_skill(name: String, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     let mut manager = SkillsManager::new()?;
     manager.discover()?;
 
@@ -258,83 +313,100 @@ async fn show_skill(name: String) -> Result<()> {
         .find(&name)
         .ok_or_else(|| anyhow::anyhow!("Skill '{}' not found", name))?;
 
-    println!("{}", skill.name().bold().underline());
-    println!();
-    println!("{}: {}", "Description".dimmed(), skill.description());
-    println!(
+    if format.is_structured() {
+        let mut data = skill_summary(skill);
+        data["hooks"] = serde_json::to_value(&skill.manifest.hooks)?;
+        data["prompt_template"] = json!(skill.load_prompt_template()?);
+        Report::ok("skills show", data).emit(format);
+        return Ok(());
+    }
+
+    out.line(skill.name().bold().underline());
+    out.line("");
+    out.line(format!(
+        "{}: {}",
+        "Description".dimmed(),
+        skill.description()
+    ));
+    out.line(format!(
         "{}: {}",
         "Category".dimmed(),
         skill.category().to_string().cyan()
-    );
-    println!("{}: {}", "Version".dimmed(), skill.manifest.skill.version);
-    println!(
+    ));
+    out.line(format!(
+        "{}: {}",
+        "Version".dimmed(),
+        skill.manifest.skill.version
+    ));
+    out.line(format!(
         "{}: {}",
         "Source".dimmed(),
         skill.source().to_string().yellow()
-    );
+    ));
 
     if let Some(ref author) = skill.manifest.skill.author {
-        println!("{}: {}", "Author".dimmed(), author);
+        out.line(format!("{}: {}", "Author".dimmed(), author));
     }
 
     if !skill.manifest.skill.tags.is_empty() {
-        println!(
+        out.line(format!(
             "{}: {}",
             "Tags".dimmed(),
             skill.manifest.skill.tags.join(", ")
-        );
+        ));
     }
 
-    println!(
+    out.line(format!(
         "{}: {}",
         "Location".dimmed(),
         skill.path.display().to_string().dimmed()
-    );
+    ));
 
     // Show hooks
     if let Some(ref hooks) = skill.manifest.hooks {
-        println!();
-        println!("{}", "Hooks".dimmed());
+        out.line("");
+        out.line("Hooks".dimmed());
         if let Some(ref pre_gen) = hooks.pre_gen {
-            println!("  {}: {}", "pre_gen".cyan(), pre_gen);
+            out.line(format!("  {}: {}", "pre_gen".cyan(), pre_gen));
         }
         if let Some(ref post_gen) = hooks.post_gen {
-            println!("  {}: {}", "post_gen".cyan(), post_gen);
+            out.line(format!("  {}: {}", "post_gen".cyan(), post_gen));
         }
         if let Some(ref format) = hooks.format {
-            println!("  {}: {}", "format".cyan(), format);
+            out.line(format!("  {}: {}", "format".cyan(), format));
         }
     }
 
     // Show prompt template preview
     match skill.load_prompt_template() {
         Ok(Some(template)) => {
-            println!();
-            println!("{}", "Prompt Template Preview".dimmed());
-            println!();
+            out.line("");
+            out.line("Prompt Template Preview".dimmed());
+            out.line("");
             // Show first 10 lines
             let lines: Vec<_> = template.lines().take(10).collect();
             for line in lines {
-                println!("  {}", line.dimmed());
+                out.line(format!("  {}", line.dimmed()));
             }
             if template.lines().count() > 10 {
-                println!("  {} ...", "...".dimmed());
+                out.line(format!("  {} ...", "...".dimmed()));
             }
         }
         Ok(None) => {
-            println!();
-            println!("{}", "No prompt template".dimmed());
+            out.line("");
+            out.line("No prompt template".dimmed());
         }
         Err(e) => {
-            println!();
-            println!("{}: {}", "Error loading template".red(), e);
+            out.line("");
+            out.line(format!("{}: {}", "Error loading template".red(), e));
         }
     }
 
     Ok(())
 }
 
-async fn remove_skill(name: String, force: bool) -> Result<()> {
+async fn remove_skill(name: String, force: bool) -> Result<bool> {
+    let out = output::context::get();
     let mut manager = SkillsManager::new()?;
     manager.discover()?;
 
@@ -353,19 +425,20 @@ async fn remove_skill(name: String, force: bool) -> Result<()> {
             .interact()?;
 
         if !confirmed {
-            println!("{}", "Removal cancelled.".yellow());
-            return Ok(());
+            out.line("Removal cancelled.".yellow());
+            return Ok(false);
         }
     }
 
     manager.remove_skill(&name)?;
 
-    println!("{} Skill '{}' removed.", "✓".green(), name);
+    out.line(format_args!("{} Skill '{}' removed.", "✓".green(), name));
 
-    Ok(())
+    Ok(true)
 }
 
-async fn open_skills_dir() -> Result<()> {
+async fn open_skills_dir() -> Result<std::path::PathBuf> {
+    let out = output::context::get();
     let manager = SkillsManager::new()?;
     manager.ensure_skills_dir()?;
 
@@ -403,16 +476,17 @@ async fn open_skills_dir() -> Result<()> {
             .context("Failed to open skills directory")?;
     }
 
-    println!(
+    out.line(format_args!(
         "{} Opened skills directory: {}",
         "✓".green(),
         path.display()
-    );
+    ));
 
-    Ok(())
+    Ok(path.to_path_buf())
 }
 
-async fn import_skills(source: String, specific_name: Option<String>) -> Result<()> {
+async fn import_skills(source: String, specific_name: Option<String>) -> Result<Vec<String>> {
+    let out = output::context::get();
     use crate::skills::external::{
         import_from_claude_code, import_from_gist, import_from_github, import_from_url,
         parse_source,
@@ -429,12 +503,12 @@ async fn import_skills(source: String, specific_name: Option<String>) -> Result<
 
     let source = parse_source(&source)?;
 
-    println!(
+    out.line(format_args!(
         "{} Importing from {}...",
         "→".cyan(),
         source.to_string().cyan()
-    );
-    println!();
+    ));
+    out.line("");
 
     let imported = match source {
         crate::skills::external::ExternalSource::ClaudeCode => {
@@ -546,10 +620,9 @@ async fn import_skills(source: String, specific_name: Option<String>) -> Result<
         }
         crate::skills::external::ExternalSource::Gist { id } => {
             if specific_name.is_some() {
-                println!(
-                    "{}",
+                out.line(
                     "Note: Gist import doesn't support filtering by name. Importing all..."
-                        .yellow()
+                        .yellow(),
                 );
             }
             let name = import_from_gist(&id, target_dir)?;
@@ -562,62 +635,78 @@ async fn import_skills(source: String, specific_name: Option<String>) -> Result<
     };
 
     if imported.is_empty() {
-        println!(
-            "{}",
-            "No new skills were imported (they may already exist).".yellow()
-        );
+        out.line("No new skills were imported (they may already exist).".yellow());
     } else {
-        println!(
+        out.line(format_args!(
             "{} Successfully imported {} skill(s):",
             "✓".green(),
             imported.len()
-        );
+        ));
         for name in &imported {
-            println!("  • {}", name.green());
+            out.line(format_args!("  • {}", name.green()));
         }
-        println!();
-        println!(
+        out.line("");
+        out.line(format_args!(
             "Use {} to see all available skills.",
             "rco skills list".cyan()
-        );
+        ));
     }
 
-    Ok(())
+    Ok(imported)
 }
 
-async fn list_available_skills(source: String) -> Result<()> {
+async fn list_available_skills(source: String, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     use crate::skills::external::list_claude_code_skills;
 
+    if format.is_structured() {
+        let skills: Vec<_> = match source.as_str() {
+            "claude-code" | "claude" => list_claude_code_skills()?
+                .into_iter()
+                .map(|(name, description)| json!({ "name": name, "description": description }))
+                .collect(),
+            _ => anyhow::bail!(
+                "Listing available skills as structured output is only supported for claude-code"
+            ),
+        };
+        Report::ok(
+            "skills available",
+            json!({ "source": source, "skills": skills }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
     match source.as_str() {
         "claude-code" | "claude" => {
             let skills = list_claude_code_skills()?;
 
             if skills.is_empty() {
-                println!("{}", "No Claude Code skills found.".yellow());
-                println!();
-                println!("Claude Code skills are stored in: ~/.claude/skills/");
+                out.line("No Claude Code skills found.".yellow());
+                out.line("");
+                out.line("Claude Code skills are stored in: ~/.claude/skills/");
                 return Ok(());
             }
 
-            println!("{}", "Available Claude Code Skills".bold().underline());
-            println!();
-            println!(
-                "{}",
-                "Run 'rco skills import claude-code [name]' to import".dimmed()
-            );
-            println!();
+            out.line("Available Claude Code Skills".bold().underline());
+            out.line("");
+            out.line("Run 'rco skills import claude-code [name]' to import".dimmed());
+            out.line("");
 
             for (name, description) in skills {
-                println!("{} {}", "•".cyan(), name.green());
-                println!("  {}", description.dimmed());
+                out.line(format!("{} {}", "•".cyan(), name.green()));
+                out.line(format!("  {}", description.dimmed()));
             }
 
-            println!();
-            println!("To import all: {}", "rco skills import claude-code".cyan());
-            println!(
+            out.line("");
+            out.line(format!(
+                "To import all: {}",
+                "rco skills import claude-code".cyan()
+            ));
+            out.line(format!(
                 "To import one: {}",
                 "rco skills import claude-code --name <skill-name>".cyan()
-            );
+            ));
         }
         "cline" => {
             let clane_dir = dirs::home_dir()
@@ -626,9 +715,9 @@ async fn list_available_skills(source: String) -> Result<()> {
                 .join("skills");
 
             if !clane_dir.exists() {
-                println!("{}", "No Cline skills found.".yellow());
-                println!();
-                println!("Cline skills are stored in: ~/.cline/skills/");
+                out.line("No Cline skills found.".yellow());
+                out.line("");
+                out.line("Cline skills are stored in: ~/.cline/skills/");
                 return Ok(());
             }
 
@@ -639,35 +728,44 @@ async fn list_available_skills(source: String) -> Result<()> {
                 }
             }
 
-            println!("{}", "Available Cline Skills".bold().underline());
-            println!();
-            println!("Found {} skill(s) in ~/.cline/skills/", count);
-            println!();
-            println!("To import: {}", "rco skills import cline:/path/to/skills".cyan());
+            out.line("Available Cline Skills".bold().underline());
+            out.line("");
+            out.line(format!("Found {} skill(s) in ~/.cline/skills/", count));
+            out.line("");
+            out.line(format!(
+                "To import: {}",
+                "rco skills import cline:/path/to/skills".cyan()
+            ));
         }
         "codex" => {
-            println!("{}", "Codex Skills".bold().underline());
-            println!();
-            println!("To import from a .codex/ directory:");
-            println!("  {}", "rco skills import codex:/path/to/.codex".cyan());
+            out.line("Codex Skills".bold().underline());
+            out.line("");
+            out.line("To import from a .codex/ directory:");
+            out.line(format!(
+                "  {}",
+                "rco skills import codex:/path/to/.codex".cyan()
+            ));
         }
         "roo" => {
-            println!("{}", "Roo Code Skills".bold().underline());
-            println!();
-            println!("To import from a Roo Code repository:");
-            println!("  {}", "rco skills import roo:owner/repo".cyan());
-            println!();
-            println!("To import from a Roo Code gist:");
-            println!("  {}", "rco skills import roo:gist:gist-id".cyan());
+            out.line("Roo Code Skills".bold().underline());
+            out.line("");
+            out.line("To import from a Roo Code repository:");
+            out.line(format!("  {}", "rco skills import roo:owner/repo".cyan()));
+            out.line("");
+            out.line("To import from a Roo Code gist:");
+            out.line(format!("  {}", "rco skills import roo:gist:gist-id".cyan()));
         }
         "kilo" => {
-            println!("{}", "Kilo Skills".bold().underline());
-            println!();
-            println!("To import from a Kilo repository:");
-            println!("  {}", "rco skills import kilo:owner/repo".cyan());
-            println!();
-            println!("To import from a Kilo gist:");
-            println!("  {}", "rco skills import kilo:gist:gist-id".cyan());
+            out.line("Kilo Skills".bold().underline());
+            out.line("");
+            out.line("To import from a Kilo repository:");
+            out.line(format!("  {}", "rco skills import kilo:owner/repo".cyan()));
+            out.line("");
+            out.line("To import from a Kilo gist:");
+            out.line(format!(
+                "  {}",
+                "rco skills import kilo:gist:gist-id".cyan()
+            ));
         }
         _ => {
             anyhow::bail!(
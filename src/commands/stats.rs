@@ -0,0 +1,117 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+
+use crate::cli::StatsCommand;
+use crate::git;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::utils::commit_stats::{CommitStats, Count};
+
+/// Number of types, scopes and emojis shown in the pretty report.
+const TOP_ENTRIES: usize = 10;
+
+pub async fn execute(cmd: StatsCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
+    git::assert_git_repo()?;
+
+    let commits = git::get_commit_log(cmd.range.as_deref(), cmd.max_count)?;
+    let stats = CommitStats::from_commits(&commits, &cmd.ai_trailer, cmd.top);
+    let range = cmd.range.as_deref().unwrap_or("HEAD");
+
+    if format.is_structured() {
+        Report::ok("stats", json!({ "range": range, "stats": stats })).emit(format);
+        return Ok(());
+    }
+
+    if stats.commits == 0 {
+        out.line(format!("No commits to analyze in {}.", range).yellow());
+        return Ok(());
+    }
+
+    out.section(&format!("Commit message stats for {}", range));
+    let merges = if stats.merges_skipped > 0 {
+        format!(" ({} merges skipped)", stats.merges_skipped)
+    } else {
+        String::new()
+    };
+    out.result(format!(
+        "  Commits analyzed:      {}{}",
+        stats.commits, merges
+    ));
+    out.result(format!(
+        "  Conventional commits:  {} ({})",
+        stats.conventional_commits,
+        percent(stats.compliance_rate)
+    ));
+    out.result(format!(
+        "  Avg subject length:    {:.1} chars",
+        stats.avg_subject_length
+    ));
+    out.result(format!(
+        "  Gitmoji commits:       {} ({})",
+        stats.gitmoji_commits,
+        percent(stats.gitmoji_rate)
+    ));
+    out.result(format!(
+        "  AI-generated commits:  {} ({}, trailer '{}')",
+        stats.ai_generated_commits,
+        percent(stats.ai_generated_rate),
+        stats.ai_trailer
+    ));
+
+    print_distribution("Types", &stats.types);
+    print_distribution("Scopes", &stats.scopes);
+    print_distribution("Gitmoji", &stats.emojis);
+
+    if !stats.authors.is_empty() {
+        out.header("Top authors by compliance");
+        for author in &stats.authors {
+            out.result(format!(
+                "  {:>7}  {}/{} conventional  {} <{}>",
+                percent(author.compliance_rate),
+                author.conventional,
+                author.commits,
+                author.name,
+                author.email
+            ));
+        }
+    }
+    out.divider();
+
+    Ok(())
+}
+
+fn print_distribution(title: &str, counts: &[Count]) {
+    if counts.is_empty() {
+        return;
+    }
+    let out = output::context::get();
+    out.header(title);
+
+    let width = counts
+        .iter()
+        .take(TOP_ENTRIES)
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for count in counts.iter().take(TOP_ENTRIES) {
+        let bar = "█".repeat((count.share * 30.0).round().max(1.0) as usize);
+        out.result(format!(
+            "  {:<width$}  {:>5}  {:>7}  {}",
+            count.name,
+            count.count,
+            percent(count.share),
+            bar.cyan(),
+            width = width
+        ));
+    }
+    if counts.len() > TOP_ENTRIES {
+        out.line(format!("  … and {} more", counts.len() - TOP_ENTRIES).dimmed());
+    }
+}
+
+fn percent(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+
+use crate::cli::{StyleAction, StyleCommand};
+use crate::config::Config;
+use crate::git;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::utils::style_store::{self, ProfileSource, StoredStyleProfile};
+
+pub async fn execute(cmd: StyleCommand, format: OutputFormat) -> Result<()> {
+    git::assert_git_repo()?;
+    let config = Config::load()?;
+    let repo_root = git::get_repo_root()?;
+    let limit = config.history_commits_count;
+
+    match cmd.action {
+        StyleAction::Show => {
+            let (stored, source) = style_store::current(&repo_root, limit)?;
+            if format.is_structured() {
+                Report::ok(
+                    "style",
+                    json!({
+                        "action": "show",
+                        "source": source,
+                        "overridden_by_config": config.style_profile.is_some(),
+                        "profile": stored,
+                    }),
+                )
+                .emit(format);
+                return Ok(());
+            }
+            print_profile(&stored, &source, config.style_profile.is_some());
+        }
+        StyleAction::Refresh { full } => {
+            let stored = style_store::refresh(&repo_root, limit, full)?;
+            let path = style_store::cache_path(&repo_root)?;
+            if format.is_structured() {
+                Report::ok(
+                    "style",
+                    json!({
+                        "action": "refresh",
+                        "path": path.display().to_string(),
+                        "profile": stored,
+                    }),
+                )
+                .emit(format);
+                return Ok(());
+            }
+            output::context::get().success(&format!(
+                "Style profile updated ({} commits analyzed)",
+                stored.profile.commits_analyzed
+            ));
+            if style_store::shared_path(&repo_root).exists() {
+                output::context::get().line(
+                    format!(
+                        "Note: {} is committed to this repository and takes precedence.",
+                        style_store::SHARED_PROFILE_PATH
+                    )
+                    .yellow(),
+                );
+            }
+        }
+        StyleAction::Export { output, stdout } => {
+            let mut stored = style_store::refresh(&repo_root, limit, false)?;
+            // The repository root is machine specific
+            stored.repo_root = None;
+
+            if stdout {
+                output::context::get().result(serde_json::to_string_pretty(&stored)?);
+                return Ok(());
+            }
+
+            let path = match output {
+                Some(path) => PathBuf::from(path),
+                None => style_store::shared_path(&repo_root),
+            };
+            style_store::write_profile(&path, &stored)?;
+
+            if format.is_structured() {
+                Report::ok(
+                    "style",
+                    json!({ "action": "export", "path": path.display().to_string() }),
+                )
+                .emit(format);
+                return Ok(());
+            }
+            let out = output::context::get();
+            out.success(&format!("Style profile written to {}", path.display()));
+            out.line(
+                format!(
+                    "Commit it to share the profile with your team: git add {}",
+                    style_store::SHARED_PROFILE_PATH
+                )
+                .dimmed(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_profile(stored: &StoredStyleProfile, source: &ProfileSource, overridden: bool) {
+    let out = output::context::get();
+    let profile = &stored.profile;
+
+    out.section("Commit style profile");
+    let source_label = match source {
+        ProfileSource::Shared(_) => "shared (committed to repository)",
+        ProfileSource::Cached(_) => "learned from history",
+    };
+    out.key_value("Source", source_label);
+    out.key_value("Path", &source.path().display().to_string());
+    out.key_value("Commits analyzed", &profile.commits_analyzed.to_string());
+    if let Some(last) = &stored.last_commit {
+        out.key_value("Last commit", &last.chars().take(7).collect::<String>());
+    }
+    out.key_value("Prefix format", &format!("{:?}", profile.prefix_format));
+    out.key_value("Types", &top(&profile.type_frequencies));
+    if profile.uses_scopes {
+        out.key_value("Scopes", &top(&profile.scope_frequencies));
+    }
+    if profile.uses_gitmoji {
+        out.key_value("Gitmoji", &top(&profile.emoji_frequencies));
+    }
+    out.key_value(
+        "Avg description",
+        &format!("{:.1} chars", profile.avg_description_length),
+    );
+
+    if overridden {
+        out.line(
+            "\nRCO_STYLE_PROFILE is set and overrides this profile."
+                .yellow()
+                .to_string(),
+        );
+    }
+
+    out.header("Prompt guidance");
+    out.result(profile.to_prompt_guidance().trim_end());
+    out.divider();
+}
+
+/// The five most frequent entries as `name (count)`.
+fn top(frequencies: &HashMap<String, usize>) -> String {
+    let mut entries: Vec<_> = frequencies.iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    if entries.is_empty() {
+        return "-".to_string();
+    }
+    entries
+        .iter()
+        .take(5)
+        .map(|(name, count)| format!("{} ({})", name, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,10 +1,24 @@
 use crate::cli::UpdateCommand;
-use crate::update::{check_for_update, perform_update, InstallMethod};
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::update::{check_for_update, perform_update, InstallMethod, UpdateInfo};
 use anyhow::Result;
 use colored::*;
 use semver::Version;
 
-pub async fn execute(cmd: UpdateCommand) -> Result<()> {
+/// Structured report payload describing an update check.
+fn update_report(info: &UpdateInfo) -> serde_json::Value {
+    serde_json::json!({
+        "current_version": info.current_version,
+        "latest_version": info.latest_version,
+        "install_method": format!("{:?}", info.install_method),
+        "needs_update": info.needs_update,
+    })
+}
+
+pub async fn execute(cmd: UpdateCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
     // If a specific version is requested
     if let Some(target_version) = cmd.version {
         return update_to_specific_version(&target_version, cmd.force).await;
@@ -13,64 +27,70 @@ pub async fn execute(cmd: UpdateCommand) -> Result<()> {
     // Check for updates
     let update_info = check_for_update().await?;
 
-    println!("{}", "Checking for updates...".blue());
-    println!(
+    // Structured output reports availability; installing stays interactive
+    if format.is_structured() {
+        if !cmd.check {
+            anyhow::bail!("Installing updates is not supported with --output-format; use --check");
+        }
+        Report::ok("update check", update_report(&update_info)).emit(format);
+        return Ok(());
+    }
+
+    out.line("Checking for updates...".blue());
+    out.line(format!(
         "Current version: {}",
         format!("v{}", update_info.current_version).cyan()
-    );
-    println!(
+    ));
+    out.line(format!(
         "Latest version:  {}",
         format!("v{}", update_info.latest_version).cyan()
-    );
-    println!(
+    ));
+    out.line(format!(
         "Install method:  {}",
         format!("{:?}", update_info.install_method).cyan()
-    );
+    ));
 
     // If only checking
     if cmd.check {
         if update_info.needs_update {
-            println!(
+            out.line(format!(
                 "\n{}",
                 format!(
                     "Update available! Run 'rco update' to update to v{}",
                     update_info.latest_version
                 )
                 .yellow()
-            );
+            ));
         } else {
-            println!("\n{}", "You're running the latest version! 🎉".green());
+            out.line(format!(
+                "\n{}",
+                "You're running the latest version! 🎉".green()
+            ));
         }
         return Ok(());
     }
 
     // Check if update is needed
     if !update_info.needs_update && !cmd.force {
-        println!(
+        out.line(format!(
             "\n{}",
             "You're already running the latest version! 🎉".green()
-        );
-        println!(
-            "{}",
-            "Use --force to reinstall the current version.".dimmed()
-        );
+        ));
+        out.line("Use --force to reinstall the current version.".dimmed());
         return Ok(());
     }
 
     // Warn about unknown installation method
     if update_info.install_method == InstallMethod::Unknown {
-        eprintln!(
+        out.warning(format!(
             "\n{}",
             "Warning: Could not detect installation method.".yellow()
-        );
-        eprintln!(
-            "{}",
-            "Please update manually or use the install script:".yellow()
-        );
-        eprintln!(
+        ));
+        out.warning("Please update manually or use the install script:".yellow());
+        out.warning(format!(
             "  {}",
             "curl -fsSL https://raw.githubusercontent.com/hongkongkiwi/rusty-commit/main/install.sh | bash".cyan()
-        );
+        ));
         return Ok(());
     }
 
@@ -80,18 +100,19 @@ pub async fn execute(cmd: UpdateCommand) -> Result<()> {
         &update_info.latest_version,
         cmd.force,
     )? {
-        println!("{}", "Update cancelled.".yellow());
+        out.line("Update cancelled.".yellow());
         return Ok(());
     }
 
     // Perform update
-    println!();
+    out.line("");
     perform_update(&update_info).await?;
 
     Ok(())
 }
 
 async fn update_to_specific_version(version_str: &str, force: bool) -> Result<()> {
+    let out = output::context::get();
     // Clean version string (remove 'v' prefix if present)
     let version_str = version_str.trim_start_matches('v');
 
@@ -101,14 +122,8 @@ async fn update_to_specific_version(version_str: &str, force: bool) -> Result<()
 
     // Check if already on this version
     if target_version == current_version && !force {
-        println!(
-            "{}",
-            format!("Already running version v{}!", version_str).green()
-        );
-        println!(
-            "{}",
-            "Use --force to reinstall the current version.".dimmed()
-        );
+        out.line(format!("Already running version v{}!", version_str).green());
+        out.line("Use --force to reinstall the current version.".dimmed());
         return Ok(());
     }
 
@@ -116,18 +131,12 @@ async fn update_to_specific_version(version_str: &str, force: bool) -> Result<()
     let install_method = crate::update::detect_install_method()?;
 
     if install_method == InstallMethod::Unknown {
-        eprintln!(
-            "{}",
-            "Warning: Could not detect installation method.".yellow()
-        );
-        eprintln!(
-            "{}",
-            "Please update manually or use the install script:".yellow()
-        );
-        eprintln!(
+        out.warning("Warning: Could not detect installation method.".yellow());
+        out.warning("Please update manually or use the install script:".yellow());
+        out.warning(format!(
             "  {}",
             format!("curl -fsSL https://raw.githubusercontent.com/hongkongkiwi/rusty-commit/main/install.sh | bash -s -- --version v{}", version_str).cyan()
-        );
+        ));
         return Ok(());
     }
 
@@ -146,25 +155,26 @@ async fn update_to_specific_version(version_str: &str, force: bool) -> Result<()
         &update_info.latest_version,
         force,
     )? {
-        println!("{}", "Update cancelled.".yellow());
+        out.line("Update cancelled.".yellow());
         return Ok(());
     }
 
     // Perform update
-    println!();
+    out.line("");
     perform_update(&update_info).await?;
 
     Ok(())
 }
 
 fn confirm_update(current: &str, target: &str, force: bool) -> Result<bool> {
+    let out = output::context::get();
     use std::io::{self, Write};
 
     if force {
-        println!(
+        out.line(format!(
             "\n{}",
             format!("Force updating from v{} to v{}", current, target).yellow()
-        );
+        ));
         return Ok(true);
     }
 
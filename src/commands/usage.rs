@@ -0,0 +1,118 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+
+use crate::cli::UsageCommand;
+use crate::config::Config;
+use crate::output;
+use crate::output::prelude::OutputFormat;
+use crate::output::report::Report;
+use crate::usage::{self, ledger};
+
+pub async fn execute(cmd: UsageCommand, format: OutputFormat) -> Result<()> {
+    let out = output::context::get();
+    let config = Config::load()?;
+
+    let since = match cmd.since.as_deref() {
+        Some(value) => usage::parse_since(value, usage::now())?,
+        None => 0,
+    };
+    let records = ledger::load_since(since)?;
+    let groups = ledger::summarize(&records, cmd.by);
+    let total = ledger::total(&records);
+    let budget = usage::budget_status(&config)?;
+
+    if format.is_structured() {
+        Report::ok(
+            "usage",
+            json!({
+                "since": since,
+                "by": cmd.by,
+                "groups": groups,
+                "total": total,
+                "budget": budget,
+            }),
+        )
+        .emit(format);
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        out.line("No usage recorded yet.".yellow());
+        out.line(format!(
+            "Usage is logged to {}",
+            ledger::ledger_path()?.display()
+        ));
+        return Ok(());
+    }
+
+    let by = cmd.by.as_str();
+    let title = match cmd.since.as_deref() {
+        Some(value) => format!("Token usage by {} (since {})", by, value),
+        None => format!("Token usage by {}", by),
+    };
+    out.header(&title);
+    out.divider();
+
+    let width = groups
+        .iter()
+        .map(|g| g.key.len())
+        .chain([by.len(), "total".len()])
+        .max()
+        .unwrap_or(0);
+    out.line(
+        format!(
+            "{:<width$}  {:>8}  {:>12}  {:>12}  {:>10}",
+            by,
+            "requests",
+            "prompt",
+            "completion",
+            "cost",
+            width = width
+        )
+        .dimmed(),
+    );
+    for summary in groups.iter().chain(std::iter::once(&total)) {
+        let row = format!(
+            "{:<width$}  {:>8}  {:>12}  {:>12}  {:>10}",
+            summary.key,
+            summary.requests,
+            summary.prompt_tokens,
+            summary.completion_tokens,
+            format!("${:.4}", summary.cost_usd),
+            width = width
+        );
+        if std::ptr::eq(summary, &total) {
+            out.result(row.bold());
+        } else {
+            out.result(row);
+        }
+    }
+    out.divider();
+
+    if total.unpriced_requests > 0 {
+        out.line(
+            format!(
+                "{} request(s) used models without a known price and are not included in cost.",
+                total.unpriced_requests
+            )
+            .dimmed(),
+        );
+    }
+
+    if let Some(status) = budget {
+        let line = format!(
+            "Budget: ${:.2} of ${:.2} spent this month ({} when exceeded)",
+            status.spent_usd,
+            status.limit_usd,
+            status.action.as_str()
+        );
+        if status.exceeded() {
+            out.line(line.red());
+        } else {
+            out.line(line.green());
+        }
+    }
+
+    Ok(())
+}
@@ -1,17 +1,21 @@
 pub mod accounts;
 pub mod format;
+pub mod http;
+pub mod layers;
 pub mod migrations;
+pub mod schema;
 pub mod secure_storage;
-pub mod setup_config;
+pub mod vault;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dirs::home_dir;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Config {
     // API Configuration
     pub api_key: Option<String>,
@@ -42,6 +46,9 @@ pub struct Config {
     pub one_line_commit: bool,
     pub why: bool,
     pub omit_scope: bool,
+    pub scope_map: Option<Vec<String>>,
+    #[serde(default)]
+    pub scope_strict: bool,
     pub generate_count: u8,
     pub clipboard_on_timeout: bool,
 
@@ -50,6 +57,8 @@ pub struct Config {
 
     // Testing
     pub test_mock_type: Option<String>,
+    #[serde(default)]
+    pub mock_fixtures_dir: Option<String>,
 
     // Hooks
     pub hook_auto_uncomment: bool,
@@ -68,6 +77,10 @@ pub struct Config {
     pub learn_from_history: bool,
     pub history_commits_count: usize,
     pub style_profile: Option<String>,
+    #[serde(default)]
+    pub few_shot_count: usize,
+    #[serde(default = "default_few_shot_max_tokens")]
+    pub few_shot_max_tokens: usize,
 
     // Context and config reading
     pub read_context: bool,
@@ -77,6 +90,63 @@ pub struct Config {
 
     // Commit body support
     pub enable_commit_body: bool,
+
+    // Usage budget
+    pub budget_monthly_usd: Option<f64>,
+    pub budget_action: Option<String>,
+
+    // Secret storage
+    pub secret_backend: Option<String>,
+    pub vault_key_file: Option<String>,
+
+    // HTTP client (headers, proxy, TLS, timeouts)
+    #[serde(default)]
+    pub http: Option<http::HttpSettings>,
+
+    // Retries and timeouts for provider requests
+    #[serde(default = "default_retry_count")]
+    pub retry_count: u32,
+    #[serde(default = "default_retry_max_elapsed_secs")]
+    pub retry_max_elapsed_secs: u64,
+    #[serde(default)]
+    pub request_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub stream: bool,
+
+    // Client-side rate limits and concurrency
+    #[serde(default)]
+    pub rate_limit_rpm: Option<u32>,
+    #[serde(default)]
+    pub rate_limit_tpm: Option<u32>,
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+}
+
+fn default_few_shot_max_tokens() -> usize {
+    800
+}
+
+fn default_retry_count() -> u32 {
+    5
+}
+
+fn default_retry_max_elapsed_secs() -> u64 {
+    120
+}
+
+fn default_max_concurrency() -> usize {
+    4
+}
+
+/// A per-minute limit for `RCO_RATE_LIMIT_*`; zero would block every request.
+fn parse_rate_limit(value: &str, key: &str) -> Result<u32> {
+    let limit: u32 = value
+        .parse()
+        .with_context(|| format!("Invalid number for {}", key))?;
+    if limit == 0 {
+        anyhow::bail!("{} must be at least 1 (reset it to remove the limit)", key);
+    }
+    Ok(limit)
 }
 
 impl Default for Config {
@@ -102,10 +172,13 @@ impl Default for Config {
             one_line_commit: false,
             why: false,
             omit_scope: false,
+            scope_map: None,
+            scope_strict: false,
             generate_count: 1,
             clipboard_on_timeout: true,
             action_enabled: false,
             test_mock_type: None,
+            mock_fixtures_dir: None,
             hook_auto_uncomment: false,
             pre_gen_hook: None,
             pre_commit_hook: None,
@@ -118,12 +191,26 @@ impl Default for Config {
             learn_from_history: false,
             history_commits_count: 50,
             style_profile: None,
+            few_shot_count: 0,
+            few_shot_max_tokens: default_few_shot_max_tokens(),
             // Context and config reading (enabled by default)
             read_context: true,
             read_agent_files: true,
             read_commitlint: true,
             read_project_config: true,
             enable_commit_body: false,
+            budget_monthly_usd: None,
+            budget_action: None,
+            secret_backend: None,
+            vault_key_file: None,
+            http: None,
+            retry_count: default_retry_count(),
+            retry_max_elapsed_secs: default_retry_max_elapsed_secs(),
+            request_timeout_secs: None,
+            stream: false,
+            rate_limit_rpm: None,
+            rate_limit_tpm: None,
+            max_concurrency: default_max_concurrency(),
         }
     }
 }
@@ -142,8 +229,8 @@ impl Config {
 
     /// Load configuration with proper priority handling
     pub fn load() -> Result<Self> {
-        // Use the new format system to load with priority
-        format::ConfigLocations::load_merged()
+        // Resolve every layer (system, global, repo, package, branch, env)
+        Ok(layers::ResolvedConfig::resolve()?.config)
     }
 
     pub fn save(&self) -> Result<()> {
@@ -158,15 +245,19 @@ impl Config {
 
         // If we have an API key and secure storage is available, store it securely
         if let Some(ref api_key) = self.api_key {
+            secure_storage::require_unlocked_vault()?;
             if secure_storage::is_available() {
                 match secure_storage::store_secret("RCO_API_KEY", api_key) {
                     Ok(_) => {
                         // Don't save API key to file if stored securely
                         save_config.api_key = None;
                     }
+                    Err(e) if !secure_storage::falls_back_to_plaintext() => return Err(e),
                     Err(e) => {
                         // Fall back to file storage; keep api_key in file
-                        eprintln!("Warning: Secure storage unavailable, falling back to file: {e}");
+                        crate::output::context::get().warning(format!(
+                            "Warning: Secure storage unavailable, falling back to file: {e}"
+                        ));
                     }
                 }
             }
@@ -189,13 +280,19 @@ impl Config {
             return Ok(());
         }
 
+        let mut stored_securely = false;
         match key {
             // Support RCO_ prefix
             "RCO_API_KEY" => {
+                secure_storage::require_unlocked_vault()?;
                 self.api_key = Some(value.to_string());
                 // Also try to store in secure storage (use RCO_ key)
                 if secure_storage::is_available() {
-                    let _ = secure_storage::store_secret("RCO_API_KEY", value);
+                    match secure_storage::store_secret("RCO_API_KEY", value) {
+                        Ok(()) => stored_securely = true,
+                        Err(e) if !secure_storage::falls_back_to_plaintext() => return Err(e),
+                        Err(_) => {}
+                    }
                 }
             }
             "RCO_API_URL" => self.api_url = Some(value.to_string()),
@@ -266,9 +363,32 @@ impl Config {
             "RCO_OMIT_SCOPE" => {
                 self.omit_scope = value.parse().context("Invalid boolean for OMIT_SCOPE")?;
             }
+            "RCO_SCOPE_MAP" => {
+                let items: Vec<String> = value
+                    .split(';')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if let Some(item) = items.iter().find(|item| !item.contains('=')) {
+                    anyhow::bail!("Invalid SCOPE_MAP entry '{}': use path=scope", item);
+                }
+                self.scope_map = Some(items);
+            }
+            "RCO_SCOPE_STRICT" => {
+                self.scope_strict = value
+                    .parse()
+                    .context("Invalid boolean for SCOPE_STRICT")?;
+            }
             "RCO_TEST_MOCK_TYPE" => {
+                if !matches!(value, "generate" | "replay" | "record") {
+                    anyhow::bail!(
+                        "Invalid TEST_MOCK_TYPE '{}': use generate, replay or record",
+                        value
+                    );
+                }
                 self.test_mock_type = Some(value.to_string());
             }
+            "RCO_MOCK_FIXTURES_DIR" => self.mock_fixtures_dir = Some(value.to_string()),
             "RCO_HOOK_AUTO_UNCOMMENT" => {
                 self.hook_auto_uncomment = value
                     .parse()
@@ -338,11 +458,74 @@ impl Config {
             "RCO_STYLE_PROFILE" => {
                 self.style_profile = Some(value.to_string());
             }
+            "RCO_FEW_SHOT_COUNT" => {
+                self.few_shot_count = value
+                    .parse()
+                    .context("Invalid number for FEW_SHOT_COUNT")?;
+            }
+            "RCO_FEW_SHOT_MAX_TOKENS" => {
+                self.few_shot_max_tokens = value
+                    .parse()
+                    .context("Invalid number for FEW_SHOT_MAX_TOKENS")?;
+            }
             "RCO_ENABLE_COMMIT_BODY" => {
                 self.enable_commit_body = value
                     .parse()
                     .context("Invalid boolean for ENABLE_COMMIT_BODY")?;
             }
+            "RCO_BUDGET_MONTHLY_USD" => {
+                let limit: f64 = value
+                    .parse()
+                    .context("Invalid number for BUDGET_MONTHLY_USD")?;
+                if !limit.is_finite() || limit < 0.0 {
+                    anyhow::bail!("BUDGET_MONTHLY_USD must be a non-negative amount");
+                }
+                self.budget_monthly_usd = Some(limit);
+            }
+            "RCO_BUDGET_ACTION" => {
+                crate::usage::BudgetAction::parse(value)?;
+                self.budget_action = Some(value.to_lowercase());
+            }
+            "RCO_SECRET_BACKEND" => {
+                secure_storage::Backend::parse(value)?;
+                self.secret_backend = Some(value.to_lowercase());
+            }
+            "RCO_VAULT_KEY_FILE" => self.vault_key_file = Some(value.to_string()),
+            "RCO_RETRY_COUNT" => {
+                self.retry_count = value.parse().context("Invalid number for RETRY_COUNT")?;
+            }
+            "RCO_RETRY_MAX_ELAPSED_SECS" => {
+                self.retry_max_elapsed_secs = value
+                    .parse()
+                    .context("Invalid number for RETRY_MAX_ELAPSED_SECS")?;
+            }
+            "RCO_REQUEST_TIMEOUT_SECS" => {
+                let secs: u64 = value
+                    .parse()
+                    .context("Invalid number for REQUEST_TIMEOUT_SECS")?;
+                if secs == 0 {
+                    anyhow::bail!("REQUEST_TIMEOUT_SECS must be at least 1 second");
+                }
+                self.request_timeout_secs = Some(secs);
+            }
+            "RCO_STREAM" => {
+                self.stream = value.parse().context("Invalid boolean for STREAM")?;
+            }
+            "RCO_RATE_LIMIT_RPM" => {
+                self.rate_limit_rpm = Some(parse_rate_limit(value, "RATE_LIMIT_RPM")?);
+            }
+            "RCO_RATE_LIMIT_TPM" => {
+                self.rate_limit_tpm = Some(parse_rate_limit(value, "RATE_LIMIT_TPM")?);
+            }
+            "RCO_MAX_CONCURRENCY" => {
+                let max: usize = value
+                    .parse()
+                    .context("Invalid number for MAX_CONCURRENCY")?;
+                if max == 0 {
+                    anyhow::bail!("MAX_CONCURRENCY must be at least 1");
+                }
+                self.max_concurrency = max;
+            }
             // Ignore unsupported keys
             "RCO_API_CUSTOM_HEADERS" => {
                 // Silently ignore these legacy keys
@@ -351,8 +534,17 @@ impl Config {
             _ => anyhow::bail!("Unknown configuration key: {}", key),
         }
 
-        self.save()?;
-        Ok(())
+        // Only this key goes to the global file; the rest of `self` may come
+        // from the repo, branch or environment layers
+        let field = layers::normalize_key(key);
+        let value = self.to_table()?.remove(&field);
+        Self::edit_global_file(|document| {
+            match value {
+                // Don't save the API key to the file if it is stored securely
+                Some(value) if !stored_securely => document.insert(field, value),
+                _ => document.remove(&field),
+            };
+        })
     }
 
     pub fn get(&self, key: &str) -> Result<String> {
@@ -385,6 +577,23 @@ impl Config {
             "RCO_PROMPT_FILE" => self.prompt_file.as_ref().map(|s| s.to_string()),
             "RCO_GENERATE_COUNT" => Some(self.generate_count.to_string()),
             "RCO_CLIPBOARD_ON_TIMEOUT" => Some(self.clipboard_on_timeout.to_string()),
+            "RCO_BUDGET_MONTHLY_USD" => self.budget_monthly_usd.map(|v| v.to_string()),
+            "RCO_BUDGET_ACTION" => self.budget_action.clone(),
+            "RCO_SECRET_BACKEND" => self.secret_backend.clone(),
+            "RCO_VAULT_KEY_FILE" => self.vault_key_file.clone(),
+            "RCO_SCOPE_MAP" => self.scope_map.as_ref().map(|items| items.join(";")),
+            "RCO_SCOPE_STRICT" => Some(self.scope_strict.to_string()),
+            "RCO_FEW_SHOT_COUNT" => Some(self.few_shot_count.to_string()),
+            "RCO_FEW_SHOT_MAX_TOKENS" => Some(self.few_shot_max_tokens.to_string()),
+            "RCO_RETRY_COUNT" => Some(self.retry_count.to_string()),
+            "RCO_RETRY_MAX_ELAPSED_SECS" => Some(self.retry_max_elapsed_secs.to_string()),
+            "RCO_REQUEST_TIMEOUT_SECS" => self.request_timeout_secs.map(|v| v.to_string()),
+            "RCO_STREAM" => Some(self.stream.to_string()),
+            "RCO_RATE_LIMIT_RPM" => self.rate_limit_rpm.map(|v| v.to_string()),
+            "RCO_RATE_LIMIT_TPM" => self.rate_limit_tpm.map(|v| v.to_string()),
+            "RCO_MAX_CONCURRENCY" => Some(self.max_concurrency.to_string()),
+            "RCO_TEST_MOCK_TYPE" => self.test_mock_type.clone(),
+            "RCO_MOCK_FIXTURES_DIR" => self.mock_fixtures_dir.clone(),
             _ => None,
         };
 
@@ -437,6 +646,33 @@ impl Config {
                     "RCO_CLIPBOARD_ON_TIMEOUT" => {
                         self.clipboard_on_timeout = default.clipboard_on_timeout
                     }
+                    "RCO_BUDGET_MONTHLY_USD" => {
+                        self.budget_monthly_usd = default.budget_monthly_usd
+                    }
+                    "RCO_BUDGET_ACTION" => self.budget_action = default.budget_action.clone(),
+                    "RCO_SECRET_BACKEND" => self.secret_backend = default.secret_backend.clone(),
+                    "RCO_VAULT_KEY_FILE" => self.vault_key_file = default.vault_key_file.clone(),
+                    "RCO_SCOPE_MAP" => self.scope_map = default.scope_map.clone(),
+                    "RCO_SCOPE_STRICT" => self.scope_strict = default.scope_strict,
+                    "RCO_FEW_SHOT_COUNT" => self.few_shot_count = default.few_shot_count,
+                    "RCO_FEW_SHOT_MAX_TOKENS" => {
+                        self.few_shot_max_tokens = default.few_shot_max_tokens
+                    }
+                    "RCO_RETRY_COUNT" => self.retry_count = default.retry_count,
+                    "RCO_RETRY_MAX_ELAPSED_SECS" => {
+                        self.retry_max_elapsed_secs = default.retry_max_elapsed_secs
+                    }
+                    "RCO_REQUEST_TIMEOUT_SECS" => {
+                        self.request_timeout_secs = default.request_timeout_secs
+                    }
+                    "RCO_STREAM" => self.stream = default.stream,
+                    "RCO_RATE_LIMIT_RPM" => self.rate_limit_rpm = default.rate_limit_rpm,
+                    "RCO_RATE_LIMIT_TPM" => self.rate_limit_tpm = default.rate_limit_tpm,
+                    "RCO_MAX_CONCURRENCY" => self.max_concurrency = default.max_concurrency,
+                    "RCO_TEST_MOCK_TYPE" => self.test_mock_type = default.test_mock_type.clone(),
+                    "RCO_MOCK_FIXTURES_DIR" => {
+                        self.mock_fixtures_dir = default.mock_fixtures_dir.clone()
+                    }
                     _ => anyhow::bail!("Unknown configuration key: {}", key),
                 }
             }
@@ -444,8 +680,49 @@ impl Config {
             *self = Self::default();
         }
 
-        self.save()?;
-        Ok(())
+        // Drop the keys from the global file so the lower layers and the
+        // defaults apply again
+        Self::edit_global_file(|document| match keys {
+            Some(key_list) => {
+                for key in key_list {
+                    document.remove(&layers::normalize_key(key));
+                }
+            }
+            None => document.clear(),
+        })
+    }
+
+    /// The set fields of this config as a table of document values.
+    fn to_table(&self) -> Result<serde_json::Map<String, serde_json::Value>> {
+        // Going through TOML drops unset (`None`) fields
+        let table = toml::Table::try_from(self).context("Failed to serialize config")?;
+        Ok(serde_json::from_value(serde_json::to_value(table)?)?)
+    }
+
+    /// Apply an edit to the raw document of the user's global config file,
+    /// leaving the keys it doesn't touch as they are in that file.
+    fn edit_global_file(
+        edit: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>),
+    ) -> Result<()> {
+        let path = format::ConfigLocations::get()?.global;
+        let mut document = if path.exists() {
+            let mut document = migrations::read_document(&path)?;
+            migrations::migrate(&mut document)?;
+            document
+        } else {
+            serde_json::Map::new()
+        };
+        edit(&mut document);
+        document.insert(
+            "schema_version".to_string(),
+            migrations::CURRENT_SCHEMA_VERSION.into(),
+        );
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = format::ConfigFormat::from_path(&path).serialize_table(&document)?;
+        std::fs::write(&path, contents).context("Failed to write config file")
     }
 
     /// Load and merge global commitlint configuration
@@ -486,8 +763,9 @@ impl Config {
             if path.exists() {
                 // In a full implementation, we would parse the commitlint config
                 // and extract rules, but for now we'll use conventional commits
-                println!("📋 Found commitlint config at: {}", config_path);
-                println!("🔧 Using conventional commit format for consistency");
+                let out = crate::output::context::get();
+                out.verbose(format!("📋 Found commitlint config at: {}", config_path));
+                out.verbose("🔧 Using conventional commit format for consistency");
             }
         }
         Ok(())
@@ -508,13 +786,12 @@ impl Config {
                     Some(content)
                 }
                 Err(e) => {
-                    eprintln!(
-                        "{}",
+                    crate::output::context::get().warning(
                         format!(
                             "Warning: Failed to load prompt file '{}': {}. Using fallback.",
                             prompt_file, e
                         )
-                        .yellow()
+                        .yellow(),
                     );
                     self.custom_prompt.clone()
                 }
@@ -529,21 +806,17 @@ impl Config {
                 "SECURITY: Using custom prompt template - full diff content will be included in the prompt. \
                 Only use custom prompts from trusted sources. Malicious prompts could exfiltrate code."
             );
-            eprintln!(
-                "{}",
+            let out = crate::output::context::get();
+            out.warning(
                 "⚠️  SECURITY WARNING: Using custom prompt template."
                     .yellow()
-                    .bold()
+                    .bold(),
             );
-            eprintln!(
-                "{}",
+            out.warning(
                 "   Your diff content (potentially including sensitive code) will be sent to the AI provider."
-                    .yellow()
-            );
-            eprintln!(
-                "{}",
-                "   Only use custom prompts from trusted sources.".yellow()
+                    .yellow(),
             );
+            out.warning("   Only use custom prompts from trusted sources.".yellow());
 
             // Replace placeholders in custom prompt
             Self::replace_placeholders(&template, diff, context, self)
@@ -615,62 +888,6 @@ impl Config {
         self.prompt_file = path;
     }
 
-    /// Merge another config into this one (other takes priority over self)
-    pub fn merge(&mut self, other: Config) {
-        // For Option fields, only copy if other has Some value
-        macro_rules! merge_option {
-            ($field:ident) => {
-                if other.$field.is_some() {
-                    self.$field = other.$field;
-                }
-            };
-        }
-
-        // For non-Option fields, always copy from other
-        macro_rules! merge_field {
-            ($field:ident) => {
-                self.$field = other.$field;
-            };
-        }
-
-        merge_option!(api_key);
-        merge_option!(api_url);
-        merge_field!(ai_provider);
-        merge_field!(model);
-        merge_field!(tokens_max_input);
-        merge_field!(tokens_max_output);
-        merge_field!(commit_type);
-        merge_field!(emoji);
-        merge_field!(description);
-        merge_field!(description_capitalize);
-        merge_field!(description_add_period);
-        merge_field!(description_max_length);
-        merge_field!(language);
-        merge_field!(message_template_placeholder);
-        merge_field!(prompt_module);
-        merge_field!(gitpush);
-        merge_option!(remote);
-        merge_field!(one_line_commit);
-        merge_field!(why);
-        merge_field!(omit_scope);
-        merge_field!(action_enabled);
-        merge_option!(test_mock_type);
-        merge_field!(hook_auto_uncomment);
-        merge_option!(pre_gen_hook);
-        merge_option!(pre_commit_hook);
-        merge_option!(post_commit_hook);
-        merge_field!(hook_strict);
-        merge_field!(hook_timeout_ms);
-        merge_option!(commitlint_config);
-        merge_option!(custom_prompt);
-        merge_option!(prompt_file);
-        merge_field!(generate_count);
-        merge_field!(clipboard_on_timeout);
-        merge_field!(learn_from_history);
-        merge_field!(history_commits_count);
-        merge_option!(style_profile);
-    }
-
     /// Load configuration values from environment variables
     /// Uses RCO_ environment variables
     pub fn load_from_environment(&mut self) {
@@ -723,8 +940,19 @@ impl Config {
         load_env_var_parse!(one_line_commit, "ONE_LINE_COMMIT", bool);
         load_env_var_parse!(why, "WHY", bool);
         load_env_var_parse!(omit_scope, "OMIT_SCOPE", bool);
+        load_env_var_parse!(scope_strict, "SCOPE_STRICT", bool);
+        if let Some(value) = Self::get_env_var("SCOPE_MAP") {
+            self.scope_map = Some(
+                value
+                    .split(';')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            );
+        }
         load_env_var_parse!(action_enabled, "ACTION_ENABLED", bool);
         load_env_var!(test_mock_type, "TEST_MOCK_TYPE");
+        load_env_var!(mock_fixtures_dir, "MOCK_FIXTURES_DIR");
         load_env_var_parse!(hook_auto_uncomment, "HOOK_AUTO_UNCOMMENT", bool);
         load_env_var!(commitlint_config, "COMMITLINT_CONFIG");
         load_env_var!(custom_prompt, "CUSTOM_PROMPT");
@@ -734,7 +962,36 @@ impl Config {
         load_env_var_parse!(learn_from_history, "LEARN_FROM_HISTORY", bool);
         load_env_var_parse!(history_commits_count, "HISTORY_COMMITS_COUNT", usize);
         load_env_var!(style_profile, "STYLE_PROFILE");
+        load_env_var_parse!(few_shot_count, "FEW_SHOT_COUNT", usize);
+        load_env_var_parse!(few_shot_max_tokens, "FEW_SHOT_MAX_TOKENS", usize);
         load_env_var_parse!(enable_commit_body, "ENABLE_COMMIT_BODY", bool);
+        load_env_var!(budget_action, "BUDGET_ACTION");
+        load_env_var!(secret_backend, "SECRET_BACKEND");
+        load_env_var!(vault_key_file, "VAULT_KEY_FILE");
+        load_env_var_parse!(retry_count, "RETRY_COUNT", u32);
+        load_env_var_parse!(retry_max_elapsed_secs, "RETRY_MAX_ELAPSED_SECS", u64);
+        if let Some(value) = Self::get_env_var("REQUEST_TIMEOUT_SECS") {
+            if let Ok(secs) = value.parse::<u64>() {
+                self.request_timeout_secs = Some(secs);
+            }
+        }
+        load_env_var_parse!(stream, "STREAM", bool);
+        if let Some(value) = Self::get_env_var("RATE_LIMIT_RPM") {
+            if let Ok(rpm) = value.parse::<u32>() {
+                self.rate_limit_rpm = Some(rpm);
+            }
+        }
+        if let Some(value) = Self::get_env_var("RATE_LIMIT_TPM") {
+            if let Ok(tpm) = value.parse::<u32>() {
+                self.rate_limit_tpm = Some(tpm);
+            }
+        }
+        load_env_var_parse!(max_concurrency, "MAX_CONCURRENCY", usize);
+        if let Some(value) = Self::get_env_var("BUDGET_MONTHLY_USD") {
+            if let Ok(limit) = value.parse::<f64>() {
+                self.budget_monthly_usd = Some(limit);
+            }
+        }
     }
 }
 
@@ -744,9 +1001,40 @@ impl Config {
 
 #[allow(dead_code)]
 impl Config {
+    /// Load the accounts file, first handing credentials saved before
+    /// per-account storage to the accounts that were using them.
+    pub fn load_accounts(&self) -> Result<Option<accounts::AccountsConfig>> {
+        let Some(accounts_config) = accounts::AccountsConfig::load()? else {
+            return Ok(None);
+        };
+        match crate::auth::token_storage::migrate_legacy_credentials(
+            &accounts_config,
+            self.api_key.as_deref(),
+            &self.ai_provider,
+        ) {
+            Ok(migration) => {
+                let out = crate::output::context::get();
+                if !migration.migrated.is_empty() {
+                    out.warning(format!(
+                        "Moved existing credentials into account(s): {}",
+                        migration.migrated.join(", ")
+                    ));
+                }
+                for alias in &migration.without_key {
+                    out.warning(format!(
+                        "Account '{alias}' has no API key of its own and no longer uses the global one. Re-add it with: rco config add-provider --alias {alias}"
+                    ));
+                }
+            }
+            Err(e) => crate::output::context::get()
+                .warning(format!("⚠️  Could not migrate existing credentials: {e}")),
+        }
+        Ok(Some(accounts_config))
+    }
+
     /// Get the active account config, if available
     pub fn get_active_account(&self) -> Result<Option<accounts::AccountConfig>> {
-        if let Some(accounts_config) = accounts::AccountsConfig::load()? {
+        if let Some(accounts_config) = self.load_accounts()? {
             if let Some(account) = accounts_config.get_active_account() {
                 return Ok(Some(account.clone()));
             }
@@ -763,7 +1051,7 @@ impl Config {
 
     /// Get a specific account by alias
     pub fn get_account(&self, alias: &str) -> Result<Option<accounts::AccountConfig>> {
-        if let Some(accounts_config) = accounts::AccountsConfig::load()? {
+        if let Some(accounts_config) = self.load_accounts()? {
             if let Some(account) = accounts_config.get_account(alias) {
                 return Ok(Some(account.clone()));
             }
@@ -773,7 +1061,7 @@ impl Config {
 
     /// List all accounts
     pub fn list_accounts(&self) -> Result<Vec<accounts::AccountConfig>> {
-        if let Some(accounts_config) = accounts::AccountsConfig::load()? {
+        if let Some(accounts_config) = self.load_accounts()? {
             Ok(accounts_config
                 .list_accounts()
                 .into_iter()
@@ -786,17 +1074,31 @@ impl Config {
 
     /// Set an account as the default (active) account
     pub fn set_default_account(&mut self, alias: &str) -> Result<()> {
-        let mut accounts_config = accounts::AccountsConfig::load()?.unwrap_or_default();
+        let mut accounts_config = self.load_accounts()?.unwrap_or_default();
         accounts_config.set_active_account(alias)?;
         accounts_config.save()?;
         Ok(())
     }
 
-    /// Remove an account
+    /// Remove an account and its stored credentials
     pub fn remove_account(&mut self, alias: &str) -> Result<()> {
         let mut accounts_config = accounts::AccountsConfig::load()?.unwrap_or_default();
-        if accounts_config.remove_account(alias) {
+        if let Some(account) = accounts_config.get_account(alias).cloned() {
+            accounts_config.remove_account(alias);
+            if accounts_config.active_account.as_deref() == Some(alias) {
+                accounts_config.active_account = None;
+            }
             accounts_config.save()?;
+
+            use crate::auth::token_storage;
+            match &account.auth {
+                accounts::AuthMethod::ApiKey { key_id: id }
+                | accounts::AuthMethod::Bearer { token_id: id }
+                | accounts::AuthMethod::OAuth { account_id: id, .. } => {
+                    token_storage::delete_all_for_account(id)?;
+                }
+                accounts::AuthMethod::EnvVar { .. } | accounts::AuthMethod::Command { .. } => {}
+            }
         }
         Ok(())
     }
@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
 /// Authentication method for an account
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(tag = "type")]
 pub enum AuthMethod {
     #[serde(rename = "api_key")]
@@ -14,15 +15,67 @@ pub enum AuthMethod {
     OAuth {
         provider: String,
         account_id: String,
+        /// Settings for a generic OAuth/OIDC issuer; absent for the built-in
+        /// Claude and Codex logins.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        oidc: Option<Box<OidcConfig>>,
     },
     #[serde(rename = "env_var")]
     EnvVar { name: String },
     #[serde(rename = "bearer")]
     Bearer { token_id: String },
+    /// Run a command (e.g. `op read op://Private/OpenAI/key`) and use the
+    /// first line of its output as the API key.
+    #[serde(rename = "command")]
+    Command {
+        command: String,
+        /// Seconds before the command is killed (default 30).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        /// Seconds the fetched key is reused in memory (default 300).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_ttl_secs: Option<u64>,
+    },
+}
+
+/// How an OIDC account obtains tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OidcGrant {
+    /// Interactive login (browser with PKCE, or the device flow).
+    #[default]
+    AuthorizationCode,
+    /// Client id and secret only, for CI.
+    ClientCredentials,
+}
+
+/// A generic OAuth 2.0 / OpenID Connect issuer, e.g. a company gateway in
+/// front of an OpenAI-compatible API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct OidcConfig {
+    /// Issuer URL; endpoints are read from
+    /// `<issuer>/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    /// Scopes to request (default `openid offline_access` for logins).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub grant: OidcGrant,
+    /// Environment variable holding the client secret, for confidential
+    /// clients and the client-credentials grant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret_env: Option<String>,
+    /// `audience` parameter some issuers need to mint API tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audience: Option<String>,
+    /// Local port for the browser login callback (default 8990).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect_port: Option<u16>,
 }
 
 /// Single account configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccountConfig {
     pub alias: String,
     pub provider: String,
@@ -33,10 +86,28 @@ pub struct AccountConfig {
     pub tokens_max_output: Option<u32>,
     #[serde(default)]
     pub is_default: bool,
+    /// HTTP settings for this account, replacing the global `[http]` table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<super::http::HttpSettings>,
+    /// Overrides `RCO_RETRY_COUNT` for this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u32>,
+    /// Overrides `RCO_RETRY_MAX_ELAPSED_SECS` for this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_max_elapsed_secs: Option<u64>,
+    /// Overrides `RCO_REQUEST_TIMEOUT_SECS` for this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// Overrides `RCO_RATE_LIMIT_RPM` for this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_rpm: Option<u32>,
+    /// Overrides `RCO_RATE_LIMIT_TPM` for this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_tpm: Option<u32>,
 }
 
 /// All accounts configuration
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct AccountsConfig {
     pub active_account: Option<String>,
     pub accounts: HashMap<String, AccountConfig>,
@@ -159,15 +230,12 @@ pub fn account_storage_key(account_alias: &str, key_type: &str) -> String {
 
 /// Delete all storage keys for an account
 #[allow(dead_code)]
-pub fn delete_account_storage(_account_alias: &str) {
-    #[cfg(feature = "secure-storage")]
-    {
-        use crate::config::secure_storage;
-
-        for key_type in ["access_token", "refresh_token", "api_key", "bearer_token"] {
-            let key = account_storage_key(_account_alias, key_type);
-            let _ = secure_storage::delete_secret(&key);
-        }
+pub fn delete_account_storage(account_alias: &str) {
+    use crate::config::secure_storage;
+
+    for key_type in ["access_token", "refresh_token", "api_key", "bearer_token"] {
+        let key = account_storage_key(account_alias, key_type);
+        let _ = secure_storage::delete_secret(&key);
     }
     // For file-based storage, keys are stored in the accounts.toml itself
     // API keys in auth method are encrypted/obfuscated if needed
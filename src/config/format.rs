@@ -2,13 +2,36 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::migrations::CURRENT_SCHEMA_VERSION;
 use super::Config;
 
+/// Config file names looked up in a repository (and its package
+/// directories), in priority order.
+pub const REPO_CONFIG_NAMES: [&str; 8] = [
+    ".rustycommit.toml",
+    ".rustycommit.json",
+    ".rustycommit.yaml",
+    ".rustycommit.yml",
+    ".rco.toml",
+    ".rco.json",
+    ".rco.yaml",
+    ".rco.yml",
+];
+
+/// The first repo-level config file present in `dir`.
+pub fn find_repo_config(dir: &Path) -> Option<PathBuf> {
+    REPO_CONFIG_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
 /// Configuration file format
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConfigFormat {
     Toml,
     Json,
+    Yaml,
 }
 
 impl ConfigFormat {
@@ -17,28 +40,61 @@ impl ConfigFormat {
         match path.extension().and_then(|s| s.to_str()) {
             Some("toml") => ConfigFormat::Toml,
             Some("json") => ConfigFormat::Json,
+            Some("yaml" | "yml") => ConfigFormat::Yaml,
             _ => ConfigFormat::Toml, // Default to TOML
         }
     }
 
-    /// Parse config from string based on format
-    pub fn parse(&self, contents: &str) -> Result<Config> {
-        match self {
-            ConfigFormat::Toml => toml::from_str(contents).context("Failed to parse TOML config"),
+    /// Parse a possibly partial config file into a table of its values
+    pub fn parse_table(
+        &self,
+        contents: &str,
+    ) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let value = match self {
+            ConfigFormat::Toml => serde_json::to_value(
+                toml::from_str::<toml::Table>(contents).context("Failed to parse TOML config")?,
+            )?,
             ConfigFormat::Json => {
-                serde_json::from_str(contents).context("Failed to parse JSON config")
+                serde_json::from_str(contents).context("Failed to parse JSON config")?
+            }
+            ConfigFormat::Yaml => {
+                serde_yaml_ng::from_str(contents).context("Failed to parse YAML config")?
             }
+        };
+        match value {
+            serde_json::Value::Object(table) => Ok(table),
+            _ => anyhow::bail!("Config file must contain a table of settings"),
         }
     }
 
-    /// Serialize config to string based on format
+    /// Serialize config to string based on format, stamped with the
+    /// current schema version
     pub fn serialize(&self, config: &Config) -> Result<String> {
+        let mut table = config.to_table()?;
+        table.insert("schema_version".to_string(), CURRENT_SCHEMA_VERSION.into());
+        self.serialize_table(&table)
+    }
+
+    /// Serialize a raw config document, `schema_version` first
+    pub fn serialize_table(
+        &self,
+        table: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<String> {
         match self {
             ConfigFormat::Toml => {
-                toml::to_string_pretty(config).context("Failed to serialize to TOML")
+                let mut table = table.clone();
+                let header = match table.remove("schema_version") {
+                    Some(version) => format!("schema_version = {}\n", version),
+                    None => String::new(),
+                };
+                let body = toml::to_string_pretty(&table).context("Failed to serialize to TOML")?;
+                Ok(header + &body)
             }
             ConfigFormat::Json => {
-                serde_json::to_string_pretty(config).context("Failed to serialize to JSON")
+                serde_json::to_string_pretty(table).context("Failed to serialize to JSON")
+            }
+            ConfigFormat::Yaml => {
+                serde_yaml_ng::to_string(table).context("Failed to serialize to YAML")
             }
         }
     }
@@ -64,29 +120,14 @@ impl ConfigLocations {
             home.join(".config").join("rustycommit").join("config.toml")
         };
 
-        // Respect an opt-out for repo-level config (useful for tests/CI isolation)
-        let ignore_repo_config = std::env::var("RCO_IGNORE_REPO_CONFIG")
-            .ok()
-            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-            .unwrap_or(false);
-
         // Repository-specific config (if in a git repo and not ignored)
-        let repo = if ignore_repo_config {
+        let repo = if Self::ignore_repo_config() {
             None
         } else if let Ok(repo) = git2::Repository::open_from_env() {
             let workdir = repo
                 .workdir()
                 .context("Could not get repository working directory")?;
-
-            // Check for multiple possible config file names
-            let possible_configs = [
-                workdir.join(".rustycommit.toml"),
-                workdir.join(".rustycommit.json"),
-                workdir.join(".rco.toml"),
-                workdir.join(".rco.json"),
-            ];
-
-            possible_configs.into_iter().find(|p| p.exists())
+            find_repo_config(workdir)
         } else {
             None
         };
@@ -94,65 +135,12 @@ impl ConfigLocations {
         Ok(ConfigLocations { repo, global })
     }
 
-    /// Load config with proper priority: repo > global > default
-    pub fn load_merged() -> Result<Config> {
-        let locations = Self::get()?;
-
-        // Start with default config
-        let mut config = Config::default();
-
-        // Load global config if exists
-        if locations.global.exists() {
-            if let Ok(contents) = fs::read_to_string(&locations.global) {
-                let format = ConfigFormat::from_path(&locations.global);
-                match format.parse(&contents) {
-                    Ok(global_config) => config.merge(global_config),
-                    Err(e) => tracing::warn!(
-                        "Failed to parse global config at {}: {}",
-                        locations.global.display(),
-                        e
-                    ),
-                }
-            }
-        }
-
-        // Load repo-specific config if exists (highest priority)
-        if let Some(repo_path) = &locations.repo {
-            if let Ok(contents) = fs::read_to_string(repo_path) {
-                let format = ConfigFormat::from_path(repo_path);
-                match format.parse(&contents) {
-                    Ok(repo_config) => config.merge(repo_config),
-                    Err(e) => tracing::warn!(
-                        "Failed to parse repo config at {}: {}",
-                        repo_path.display(),
-                        e
-                    ),
-                }
-            }
-        }
-
-        // Load values from environment variables (RCO_ prefix)
-        config.load_from_environment();
-
-        // Try to load API key from secure storage if not in file or env
-        if config.api_key.is_none() {
-            if let Ok(Some(key)) = crate::config::secure_storage::get_secret("RCO_API_KEY") {
-                config.api_key = Some(key);
-            }
-        }
-
-        // Also check for OAuth tokens
-        if config.api_key.is_none() {
-            if let Some(_token) = crate::auth::token_storage::get_access_token()
-                .ok()
-                .flatten()
-            {
-                // Token is handled separately in auth module, but we can set a flag
-                // to indicate OAuth is available
-            }
-        }
-
-        Ok(config)
+    /// Respect an opt-out for repo-level config (useful for tests/CI isolation)
+    pub fn ignore_repo_config() -> bool {
+        std::env::var("RCO_IGNORE_REPO_CONFIG")
+            .ok()
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
     }
 
     /// Save config to appropriate location
@@ -0,0 +1,207 @@
+//! HTTP settings for provider requests: extra headers, proxies, custom CAs,
+//! client certificates and timeouts.
+//!
+//! Set globally as an `[http]` table in the config file, or per account as
+//! `[accounts.<alias>.http]`, which replaces the global table. Every
+//! provider builds its `reqwest::Client` through [`client`] or
+//! [`account_client`], which also apply `RCO_REQUEST_TIMEOUT_SECS` or the
+//! account's `request_timeout_secs`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, ClientBuilder, Identity, NoProxy, Proxy};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::accounts::AccountConfig;
+use super::Config;
+
+/// Environment variables consulted, in order, for the proxy when only
+/// `no_proxy` is configured.
+const PROXY_ENV_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct HttpSettings {
+    /// Headers added to every request, e.g. a gateway routing key.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Proxy URL for all requests. Without it `HTTPS_PROXY`/`HTTP_PROXY`
+    /// and `NO_PROXY` from the environment apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Comma-separated hosts, domains and CIDRs that bypass the proxy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// PEM files with extra root certificates to trust.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ca_certs: Vec<String>,
+    /// PEM file with the client certificate chain for mutual TLS; may also
+    /// hold the private key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+    /// PEM file with the client private key, if not in `client_cert`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// Longest wait for the next chunk of a response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout_secs: Option<u64>,
+}
+
+impl HttpSettings {
+    /// A client builder with these settings applied.
+    fn builder(&self) -> Result<ClientBuilder> {
+        let mut builder = Client::builder();
+
+        if !self.headers.is_empty() {
+            let mut headers = HeaderMap::new();
+            for (name, value) in &self.headers {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("Invalid HTTP header name '{}'", name))?;
+                let mut value = HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid value for HTTP header '{}'", name))?;
+                // Gateway headers often carry credentials; keep them out of debug output
+                value.set_sensitive(true);
+                headers.insert(name, value);
+            }
+            builder = builder.default_headers(headers);
+        }
+
+        let proxy = self.proxy.clone().or_else(|| {
+            self.no_proxy.as_ref()?;
+            PROXY_ENV_VARS
+                .iter()
+                .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+        });
+        if let Some(url) = proxy {
+            let no_proxy = match &self.no_proxy {
+                Some(list) => NoProxy::from_string(list),
+                None => NoProxy::from_env(),
+            };
+            let proxy = Proxy::all(&url).with_context(|| format!("Invalid proxy URL '{}'", url))?;
+            builder = builder.proxy(proxy.no_proxy(no_proxy));
+        }
+
+        for path in &self.ca_certs {
+            let pem = read(path, "CA bundle")?;
+            for cert in Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Invalid CA bundle {}", path))?
+            {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let Some(cert_path) = &self.client_cert {
+            let mut pem = read(cert_path, "client certificate")?;
+            if let Some(key_path) = &self.client_key {
+                pem.push(b'\n');
+                pem.extend(read(key_path, "client key")?);
+            }
+            let identity = Identity::from_pem(&pem)
+                .with_context(|| format!("Invalid client certificate or key in {}", cert_path))?;
+            builder = builder.identity(identity);
+        }
+
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.read_timeout_secs {
+            builder = builder.read_timeout(Duration::from_secs(secs));
+        }
+
+        Ok(builder)
+    }
+}
+
+/// A client for the global configuration.
+pub fn client(config: &Config) -> Result<Client> {
+    let settings = config.http.clone().unwrap_or_default();
+    build(&settings, config.request_timeout_secs)
+}
+
+/// A client for an account, using its `http` table and request timeout if
+/// it has them.
+pub fn account_client(account: &AccountConfig, config: &Config) -> Result<Client> {
+    let settings = account
+        .http
+        .clone()
+        .or_else(|| config.http.clone())
+        .unwrap_or_default();
+    build(
+        &settings,
+        account.request_timeout_secs.or(config.request_timeout_secs),
+    )
+}
+
+fn build(settings: &HttpSettings, request_timeout_secs: Option<u64>) -> Result<Client> {
+    let mut builder = settings.builder()?;
+    if let Some(secs) = request_timeout_secs {
+        builder = builder.timeout(Duration::from_secs(secs));
+    }
+    builder.build().context("Failed to build the HTTP client")
+}
+
+fn read(path: &str, what: &str) -> Result<Vec<u8>> {
+    let expanded = match path.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => path.into(),
+    };
+    fs::read(&expanded).with_context(|| format!("Failed to read {} {}", what, expanded.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sends_configured_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .match_header("x-gateway-route", "llm-prod")
+            .create_async()
+            .await;
+
+        let settings = HttpSettings {
+            headers: BTreeMap::from([("X-Gateway-Route".to_string(), "llm-prod".to_string())]),
+            connect_timeout_secs: Some(5),
+            read_timeout_secs: Some(5),
+            ..Default::default()
+        };
+        build(&settings, Some(5))
+            .unwrap()
+            .get(server.url())
+            .send()
+            .await
+            .unwrap();
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn reports_bad_settings() {
+        let bad_header = HttpSettings {
+            headers: BTreeMap::from([("bad header".to_string(), "x".to_string())]),
+            ..Default::default()
+        };
+        assert!(build(&bad_header, None).is_err());
+
+        let missing_ca = HttpSettings {
+            ca_certs: vec!["/nonexistent/ca.pem".to_string()],
+            ..Default::default()
+        };
+        let err = build(&missing_ca, None).unwrap_err();
+        assert!(err.to_string().contains("CA bundle"), "{err}");
+
+        let proxy = HttpSettings {
+            proxy: Some("http://proxy.internal:3128".to_string()),
+            no_proxy: Some("localhost,.corp.example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(build(&proxy, None).is_ok());
+    }
+}
@@ -0,0 +1,661 @@
+//! Layered configuration resolution.
+//!
+//! Values are resolved from these layers, later ones taking priority:
+//!
+//! 1. built-in defaults
+//! 2. the system-wide file (`/etc/rustycommit/config.toml`, or `$RCO_SYSTEM_CONFIG`)
+//! 3. the user's global `config.toml` (in `$RCO_CONFIG_HOME` or `~/.config/rustycommit`)
+//! 4. the repository root file (`.rustycommit.*` or `.rco.*`, as TOML, JSON
+//!    or YAML)
+//! 5. files with the same names in directories between the repository root
+//!    and the common directory of the staged files, outermost first, so a
+//!    monorepo package can carry its own settings
+//! 6. `[branch."<glob>"]` sections of any of the files above whose pattern
+//!    matches the current branch, file by file in the order above; within a
+//!    file, more specific patterns (more literal characters) win, and ties go
+//!    to the pattern that sorts last by name
+//! 7. `RCO_*` environment variables
+//! 8. secure storage, for the API key only
+//!
+//! Files only need the keys they change. A file can also clear a value set by
+//! an earlier layer with `unset = ["model"]` (or `null` in JSON), restoring the
+//! built-in default.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::format::{find_repo_config, ConfigFormat, ConfigLocations};
+use super::migrations::{self, CURRENT_SCHEMA_VERSION};
+use super::Config;
+
+/// Where a configuration value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerKind {
+    Default,
+    System,
+    Global,
+    Repo,
+    Directory,
+    Branch,
+    Environment,
+    SecureStorage,
+}
+
+impl fmt::Display for LayerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Default => "default",
+            Self::System => "system",
+            Self::Global => "global",
+            Self::Repo => "repo",
+            Self::Directory => "directory",
+            Self::Branch => "branch",
+            Self::Environment => "environment",
+            Self::SecureStorage => "secure storage",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A single layer: its kind, the file it was read from and, for branch
+/// sections, the pattern that matched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerSource {
+    pub kind: LayerKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+impl LayerSource {
+    fn new(kind: LayerKind) -> Self {
+        Self {
+            kind,
+            path: None,
+            branch: None,
+        }
+    }
+
+    fn file(kind: LayerKind, path: &Path) -> Self {
+        Self {
+            kind,
+            path: Some(path.to_path_buf()),
+            branch: None,
+        }
+    }
+}
+
+impl fmt::Display for LayerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(branch) = &self.branch {
+            write!(f, " \"{}\"", branch)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, " ({})", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// A value set (or unset, when `value` is `None`) by a layer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Assignment {
+    pub source: LayerSource,
+    pub value: Option<Value>,
+}
+
+/// The provenance of one configuration key.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyExplanation {
+    /// Field name as used in config files (e.g. `model`).
+    pub key: String,
+    /// Environment variable / `rco config set` name (e.g. `RCO_MODEL`).
+    pub env_var: String,
+    pub value: Value,
+    pub default: Value,
+    /// Layer the effective value came from.
+    pub source: LayerSource,
+    /// Every layer that set or unset the key, lowest priority first.
+    pub assignments: Vec<Assignment>,
+}
+
+/// A file's own values and its branch override sections.
+#[derive(Debug, Default)]
+struct FileValues {
+    values: Map<String, Value>,
+    unset: Vec<String>,
+    branches: Vec<(String, FileValues)>,
+}
+
+/// Applies layers in priority order while recording where each key was set.
+#[derive(Debug)]
+struct Resolver {
+    defaults: Map<String, Value>,
+    effective: Map<String, Value>,
+    assignments: BTreeMap<String, Vec<Assignment>>,
+    layers: Vec<LayerSource>,
+}
+
+impl Resolver {
+    fn new() -> Result<Self> {
+        let defaults = match serde_json::to_value(Config::default())? {
+            Value::Object(map) => map,
+            _ => unreachable!("Config serializes to an object"),
+        };
+        Ok(Self {
+            effective: defaults.clone(),
+            defaults,
+            assignments: BTreeMap::new(),
+            layers: Vec::new(),
+        })
+    }
+
+    /// Apply one layer. A layer whose values do not fit the config (e.g. a
+    /// string where a number is expected) is skipped as a whole.
+    fn apply(&mut self, source: LayerSource, values: &Map<String, Value>, unset: &[String]) {
+        let mut effective = self.effective.clone();
+        let mut applied = Vec::new();
+
+        for key in unset {
+            if let Some(default) = self.defaults.get(key) {
+                effective.insert(key.clone(), default.clone());
+                applied.push((key.clone(), None));
+            } else {
+                tracing::warn!("Unknown config key '{}' in {}", key, source);
+            }
+        }
+        for (key, value) in values {
+            // Unknown keys were already reported when the file was read
+            if !self.defaults.contains_key(key) {
+                continue;
+            }
+            effective.insert(key.clone(), value.clone());
+            applied.push((key.clone(), Some(value.clone())));
+        }
+
+        if let Err(e) = serde_json::from_value::<Config>(Value::Object(effective.clone())) {
+            tracing::warn!("Failed to apply config from {}: {}", source, e);
+            return;
+        }
+
+        self.effective = effective;
+        for (key, value) in applied {
+            self.assignments.entry(key).or_default().push(Assignment {
+                source: source.clone(),
+                value,
+            });
+        }
+        self.layers.push(source);
+    }
+
+    /// Apply a file and remember its branch sections for later.
+    fn apply_file(
+        &mut self,
+        source: LayerSource,
+        file: FileValues,
+        branch_sections: &mut Vec<(LayerSource, FileValues)>,
+    ) {
+        self.apply(source.clone(), &file.values, &file.unset);
+        for (pattern, section) in file.branches {
+            branch_sections.push((
+                LayerSource {
+                    kind: LayerKind::Branch,
+                    path: source.path.clone(),
+                    branch: Some(pattern),
+                },
+                section,
+            ));
+        }
+    }
+
+    fn record(&mut self, key: &str, source: LayerSource, value: Value) {
+        self.assignments
+            .entry(key.to_string())
+            .or_default()
+            .push(Assignment {
+                source: source.clone(),
+                value: Some(value),
+            });
+        if !self.layers.contains(&source) {
+            self.layers.push(source);
+        }
+    }
+
+    fn config(&self) -> Config {
+        serde_json::from_value(Value::Object(self.effective.clone()))
+            .expect("every applied layer was validated")
+    }
+}
+
+/// The effective configuration together with where each value came from.
+#[derive(Debug)]
+pub struct ResolvedConfig {
+    pub config: Config,
+    /// Layers that contributed values, lowest priority first.
+    pub layers: Vec<LayerSource>,
+    defaults: Map<String, Value>,
+    assignments: BTreeMap<String, Vec<Assignment>>,
+}
+
+impl ResolvedConfig {
+    /// Resolve the configuration for the current directory and repository.
+    pub fn resolve() -> Result<Self> {
+        let mut resolver = Resolver::new()?;
+        let mut branch_sections = Vec::new();
+
+        for (kind, path) in config_files()? {
+            if let Some(file) = read_file(&path) {
+                resolver.apply_file(LayerSource::file(kind, &path), file, &mut branch_sections);
+            }
+        }
+
+        if let Some(branch) = current_branch() {
+            for (source, section) in branch_sections {
+                let matches = source
+                    .branch
+                    .as_deref()
+                    .is_some_and(|pattern| branch_matches(pattern, &branch));
+                if matches {
+                    resolver.apply(source, &section.values, &section.unset);
+                }
+            }
+        }
+
+        let mut config = resolver.config();
+        let from_files = serde_json::to_value(&config)?;
+        config.load_from_environment();
+        // Only variables that were actually applied count as a layer
+        let with_env = serde_json::to_value(&config)?;
+        for key in resolver.defaults.keys().cloned().collect::<Vec<_>>() {
+            let Ok(raw) = std::env::var(env_var_name(&key)) else {
+                continue;
+            };
+            let value = with_env.get(&key).cloned().unwrap_or(Value::Null);
+            if from_files.get(&key) != Some(&value) || render(&value) == raw {
+                resolver.record(&key, LayerSource::new(LayerKind::Environment), value);
+            }
+        }
+
+        if config.api_key.is_none() {
+            match super::secure_storage::get_secret("RCO_API_KEY") {
+                Ok(Some(key)) => {
+                    resolver.record(
+                        "api_key",
+                        LayerSource::new(LayerKind::SecureStorage),
+                        Value::String(key.clone()),
+                    );
+                    config.api_key = Some(key);
+                }
+                Ok(None) => {}
+                // Only the vault reports errors, e.g. when it is locked
+                Err(e) => crate::output::context::get()
+                    .warning(format!("⚠️  Could not read the API key: {:#}", e)),
+            }
+        }
+
+        Ok(Self {
+            config,
+            layers: resolver.layers,
+            defaults: resolver.defaults,
+            assignments: resolver.assignments,
+        })
+    }
+
+    /// Explain a key, given as a field name (`model`) or as `RCO_MODEL`.
+    pub fn explain(&self, key: &str) -> Result<KeyExplanation> {
+        let key = normalize_key(key);
+        let default = self
+            .defaults
+            .get(&key)
+            .cloned()
+            .with_context(|| format!("Unknown configuration key: {}", env_var_name(&key)))?;
+        let value = serde_json::to_value(&self.config)?
+            .get(&key)
+            .cloned()
+            .unwrap_or(Value::Null);
+        let assignments = self.assignments.get(&key).cloned().unwrap_or_default();
+        let source = match assignments.last() {
+            Some(Assignment {
+                source,
+                value: Some(_),
+            }) => source.clone(),
+            _ => LayerSource::new(LayerKind::Default),
+        };
+
+        Ok(KeyExplanation {
+            env_var: env_var_name(&key),
+            key,
+            value,
+            default,
+            source,
+            assignments,
+        })
+    }
+
+    /// Explanations for every key set by some layer, in key order.
+    pub fn explain_all(&self) -> Vec<KeyExplanation> {
+        self.assignments
+            .keys()
+            .filter_map(|key| self.explain(key).ok())
+            .collect()
+    }
+}
+
+/// A value as it would be written on the command line.
+pub fn render(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Array(items) => items.iter().map(render).collect::<Vec<_>>().join(";"),
+        other => other.to_string(),
+    }
+}
+
+/// `RCO_` name of a config field.
+pub fn env_var_name(key: &str) -> String {
+    format!("RCO_{}", key.to_uppercase())
+}
+
+/// Field name for a key given as `model` or `RCO_MODEL`.
+pub(crate) fn normalize_key(key: &str) -> String {
+    let key = key.trim();
+    let key = key
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("RCO_"))
+        .map_or(key, |_| &key[4..]);
+    key.to_lowercase()
+}
+
+/// Path of the system-wide config file.
+pub(crate) fn system_config_path() -> PathBuf {
+    if let Ok(path) = std::env::var("RCO_SYSTEM_CONFIG") {
+        return PathBuf::from(path);
+    }
+    if cfg!(windows) {
+        let program_data =
+            std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string());
+        PathBuf::from(program_data)
+            .join("rustycommit")
+            .join("config.toml")
+    } else {
+        PathBuf::from("/etc/rustycommit/config.toml")
+    }
+}
+
+/// Config files to read, lowest priority first. Files that do not exist
+/// are included; callers skip them.
+pub fn config_files() -> Result<Vec<(LayerKind, PathBuf)>> {
+    let locations = ConfigLocations::get()?;
+    let mut files = vec![
+        (LayerKind::System, system_config_path()),
+        (LayerKind::Global, locations.global),
+    ];
+
+    if let Some(repo) = locations.repo {
+        files.push((LayerKind::Repo, repo));
+    }
+    if !ConfigLocations::ignore_repo_config() {
+        if let Ok(repo) = git2::Repository::open_from_env() {
+            if let Some(workdir) = repo.workdir() {
+                let mut dir = workdir.to_path_buf();
+                for component in package_dir(workdir).components() {
+                    dir.push(component);
+                    if let Some(path) = find_repo_config(&dir) {
+                        files.push((LayerKind::Directory, path));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Directory, relative to the repository root, whose configs apply: the
+/// common directory of the staged files, or the current directory when
+/// nothing is staged.
+fn package_dir(workdir: &Path) -> PathBuf {
+    let staged = crate::git::get_staged_files().unwrap_or_default();
+    if !staged.is_empty() {
+        return common_dir(&staged);
+    }
+    let (Ok(cwd), Ok(root)) = (
+        std::env::current_dir().and_then(|d| d.canonicalize()),
+        workdir.canonicalize(),
+    ) else {
+        return PathBuf::new();
+    };
+    cwd.strip_prefix(&root)
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Longest directory containing every path.
+fn common_dir<T: AsRef<str>>(paths: &[T]) -> PathBuf {
+    let mut common: Option<Vec<&str>> = None;
+    for path in paths {
+        let mut dirs: Vec<&str> = path.as_ref().split('/').collect();
+        dirs.pop();
+        common = Some(match common {
+            None => dirs,
+            Some(prefix) => prefix
+                .into_iter()
+                .zip(dirs)
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    common.unwrap_or_default().iter().collect()
+}
+
+fn current_branch() -> Option<String> {
+    let repo = git2::Repository::open_from_env().ok()?;
+    let head = repo.find_reference("HEAD").ok()?;
+    // Read the symbolic target so unborn branches resolve too
+    let target = head.symbolic_target()?;
+    target.strip_prefix("refs/heads/").map(str::to_string)
+}
+
+/// Whether a branch name matches a glob: `*` matches within one path
+/// segment, `**` across segments and `?` a single character.
+fn branch_matches(pattern: &str, branch: &str) -> bool {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex::Regex::new(&regex).is_ok_and(|re| re.is_match(branch))
+}
+
+/// Number of literal characters, used to order patterns by specificity.
+fn specificity(pattern: &str) -> usize {
+    pattern.chars().filter(|c| !matches!(c, '*' | '?')).count()
+}
+
+fn read_file(path: &Path) -> Option<FileValues> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut parsed = match ConfigFormat::from_path(path).parse_table(&contents) {
+        Ok(table) => table,
+        Err(e) => {
+            tracing::warn!("Failed to parse config at {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    match migrations::migrate(&mut parsed) {
+        Ok(report) if report.from > CURRENT_SCHEMA_VERSION => tracing::warn!(
+            "{} uses schema v{}, newer than this release supports (v{})",
+            path.display(),
+            report.from,
+            CURRENT_SCHEMA_VERSION
+        ),
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!("Failed to migrate config at {}: {}", path.display(), e);
+            return None;
+        }
+    }
+    migrations::warn_unknown_keys(path, &parsed);
+    Some(file_values(parsed))
+}
+
+fn file_values(table: Map<String, Value>) -> FileValues {
+    let mut file = FileValues::default();
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("unset", Value::Array(keys)) => file
+                .unset
+                .extend(keys.iter().filter_map(Value::as_str).map(normalize_key)),
+            ("branch", Value::Object(sections)) => {
+                for (pattern, section) in sections {
+                    if let Value::Object(section) = section {
+                        file.branches.push((pattern, file_values(section)));
+                    }
+                }
+            }
+            ("schema_version" | "$schema", _) => {}
+            (_, Value::Null) => file.unset.push(key),
+            (_, value) => {
+                file.values.insert(key, value);
+            }
+        }
+    }
+    // Parsed tables don't keep the file's order, so break ties by name
+    file.branches
+        .sort_by(|(a, _), (b, _)| specificity(a).cmp(&specificity(b)).then_with(|| a.cmp(b)));
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn test_branch_matches() {
+        assert!(branch_matches("release/*", "release/1.2"));
+        assert!(!branch_matches("release/*", "release/1.2/hotfix"));
+        assert!(branch_matches("release/**", "release/1.2/hotfix"));
+        assert!(branch_matches("v?", "v2"));
+        assert!(!branch_matches("main", "main-old"));
+    }
+
+    #[test]
+    fn test_common_dir() {
+        assert_eq!(
+            common_dir(&["packages/api/src/a.rs", "packages/api/Cargo.toml"]),
+            PathBuf::from("packages/api")
+        );
+        assert_eq!(
+            common_dir(&["packages/api/a.rs", "packages/web/b.ts"]),
+            PathBuf::from("packages")
+        );
+        assert_eq!(common_dir(&["README.md"]), PathBuf::new());
+    }
+
+    #[test]
+    fn test_file_values_splits_sections() {
+        let file = file_values(object(json!({
+            "model": "gpt-4",
+            "api_url": null,
+            "unset": ["RCO_EMOJI"],
+            "branch": {
+                "release/**": { "language": "de" },
+                "release/v*": { "language": "fr" },
+            },
+        })));
+        assert_eq!(file.values.get("model"), Some(&json!("gpt-4")));
+        let mut unset = file.unset.clone();
+        unset.sort();
+        assert_eq!(unset, vec!["api_url", "emoji"]);
+        let patterns: Vec<_> = file.branches.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(patterns, vec!["release/**", "release/v*"]);
+    }
+
+    #[test]
+    fn test_equally_specific_branch_sections_apply_by_name() {
+        let file = file_values(object(json!({
+            "branch": {
+                "release/v*": { "language": "fr" },
+                "release/*1": { "language": "de" },
+            },
+        })));
+        let patterns: Vec<_> = file.branches.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(patterns, vec!["release/*1", "release/v*"]);
+
+        // Both match; the later one wins
+        let mut resolver = Resolver::new().unwrap();
+        for (pattern, section) in &file.branches {
+            assert!(branch_matches(pattern, "release/v1"));
+            resolver.apply(LayerSource::new(LayerKind::Branch), &section.values, &[]);
+        }
+        assert_eq!(resolver.config().language, "fr");
+    }
+
+    #[test]
+    fn test_resolver_layers_partial_files_and_unset() {
+        let mut resolver = Resolver::new().unwrap();
+        let global = LayerSource::new(LayerKind::Global);
+        let repo = LayerSource::new(LayerKind::Repo);
+
+        resolver.apply(
+            global.clone(),
+            &object(json!({ "model": "gpt-4", "emoji": true })),
+            &[],
+        );
+        resolver.apply(
+            repo.clone(),
+            &object(json!({ "language": "de" })),
+            &["model".to_string()],
+        );
+        // Wrong types reject the whole layer
+        resolver.apply(
+            LayerSource::new(LayerKind::Directory),
+            &object(json!({ "emoji": "yes", "language": "fr" })),
+            &[],
+        );
+
+        let config = resolver.config();
+        assert_eq!(config.model, Config::default().model);
+        assert!(config.emoji);
+        assert_eq!(config.language, "de");
+
+        let model = &resolver.assignments["model"];
+        assert_eq!(model.len(), 2);
+        assert_eq!(model[0].source, global);
+        assert_eq!(model[1].value, None);
+        assert_eq!(resolver.layers, vec![global, repo]);
+    }
+
+    #[test]
+    fn test_normalize_key() {
+        assert_eq!(normalize_key("RCO_MODEL"), "model");
+        assert_eq!(normalize_key("rco_few_shot_count"), "few_shot_count");
+        assert_eq!(normalize_key("language"), "language");
+    }
+}
@@ -1,14 +1,434 @@
-use crate::config::Config;
-use anyhow::Result;
+//! Config file schema versions and migrations.
+//!
+//! Every config file carries a `schema_version`; files written before it
+//! existed are version 1. Migrations are ordered steps that each upgrade the
+//! raw document (TOML, JSON or YAML, including `[branch."..."]` sections) by one
+//! version. Files are migrated in memory whenever they are loaded; the user's
+//! global file is also rewritten on startup, and `rco config migrate` rewrites
+//! the other layers on request. The original is kept as `<file>.v<N>.bak`.
 
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::format::{ConfigFormat, ConfigLocations};
+use super::layers::normalize_key;
+use super::Config;
+
+/// Schema version written by this release.
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+/// Document keys that are not config fields.
+const DOCUMENT_KEYS: [&str; 4] = ["$schema", "schema_version", "unset", "branch"];
+
+/// A step that upgrades a document from version `to - 1` to `to`.
+pub struct Migration {
+    pub to: u32,
+    pub description: &'static str,
+    apply: fn(&mut Map<String, Value>, &mut Vec<String>),
+}
+
+/// All migrations, oldest first.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 2,
+        description: "Rename RCO_/OCO_-prefixed keys to field names",
+        apply: rename_prefixed_keys,
+    },
+    Migration {
+        to: 3,
+        description: "Derive commit_type from prompt_module and drop removed keys",
+        apply: migrate_prompt_module,
+    },
+];
+
+/// What migrating a document changed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub changes: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn is_needed(&self) -> bool {
+        self.from < self.to
+    }
+}
+
+/// A key no config field matches, with the closest known key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnknownKey {
+    pub key: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown config key '{}'", self.key)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean '{}'?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Schema version of a document; version 1 when it has none.
+pub fn document_version(table: &Map<String, Value>) -> Result<u32> {
+    match table.get("schema_version") {
+        None => Ok(1),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .with_context(|| format!("Invalid schema_version: {}", value)),
+    }
+}
+
+/// Upgrade a document to [`CURRENT_SCHEMA_VERSION`] in place.
+///
+/// Documents from a newer release are left untouched.
+pub fn migrate(table: &mut Map<String, Value>) -> Result<MigrationReport> {
+    let from = document_version(table)?;
+    if from >= CURRENT_SCHEMA_VERSION {
+        return Ok(MigrationReport {
+            from,
+            to: from,
+            changes: Vec::new(),
+        });
+    }
+
+    let mut changes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > from) {
+        (migration.apply)(table, &mut changes);
+        if let Some(Value::Object(sections)) = table.get_mut("branch") {
+            for (pattern, section) in sections.iter_mut() {
+                if let Value::Object(section) = section {
+                    let mut section_changes = Vec::new();
+                    (migration.apply)(section, &mut section_changes);
+                    changes.extend(
+                        section_changes
+                            .into_iter()
+                            .map(|change| format!("[branch.\"{}\"] {}", pattern, change)),
+                    );
+                }
+            }
+        }
+    }
+    table.insert(
+        "schema_version".to_string(),
+        Value::from(CURRENT_SCHEMA_VERSION),
+    );
+
+    Ok(MigrationReport {
+        from,
+        to: CURRENT_SCHEMA_VERSION,
+        changes,
+    })
+}
+
+fn rename_prefixed_keys(table: &mut Map<String, Value>, changes: &mut Vec<String>) {
+    let prefixed: Vec<String> = table
+        .keys()
+        .filter(|key| {
+            key.get(..4).is_some_and(|prefix| {
+                prefix.eq_ignore_ascii_case("RCO_") || prefix.eq_ignore_ascii_case("OCO_")
+            })
+        })
+        .cloned()
+        .collect();
+
+    for key in prefixed {
+        let field = key[4..].to_lowercase();
+        let Some(value) = table.remove(&key) else {
+            continue;
+        };
+        if table.contains_key(&field) {
+            changes.push(format!("removed {} ({} is already set)", key, field));
+        } else {
+            changes.push(format!("renamed {} to {}", key, field));
+            table.insert(field, value);
+        }
+    }
+}
+
+fn migrate_prompt_module(table: &mut Map<String, Value>, changes: &mut Vec<String>) {
+    if !table.contains_key("commit_type") {
+        let commit_type = match table.get("prompt_module").and_then(Value::as_str) {
+            Some("conventional-commit") => Some("conventional"),
+            Some("gitmoji") => Some("gitmoji"),
+            _ => None,
+        };
+        if let Some(commit_type) = commit_type {
+            table.insert("commit_type".to_string(), Value::from(commit_type));
+            changes.push(format!(
+                "set commit_type = \"{}\" from prompt_module",
+                commit_type
+            ));
+        }
+    }
+    if table.remove("api_custom_headers").is_some() {
+        changes.push("removed api_custom_headers (no longer supported)".to_string());
+    }
+}
+
+/// Keys of a document that match no config field, including those in
+/// branch sections and `unset` lists.
+pub fn unknown_keys(table: &Map<String, Value>) -> Vec<UnknownKey> {
+    let known = known_keys();
+    let mut unknown = Vec::new();
+    collect_unknown(table, "", &known, &mut unknown);
+    if let Some(Value::Object(sections)) = table.get("branch") {
+        for (pattern, section) in sections {
+            if let Value::Object(section) = section {
+                let prefix = format!("branch.\"{}\".", pattern);
+                collect_unknown(section, &prefix, &known, &mut unknown);
+            }
+        }
+    }
+    unknown
+}
+
+fn collect_unknown(
+    table: &Map<String, Value>,
+    prefix: &str,
+    known: &[String],
+    unknown: &mut Vec<UnknownKey>,
+) {
+    let unset = table
+        .get("unset")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(normalize_key);
+    let keys = table
+        .keys()
+        .filter(|key| !DOCUMENT_KEYS.contains(&key.as_str()))
+        .cloned()
+        .chain(unset);
+
+    for key in keys {
+        if !known.contains(&key) {
+            unknown.push(UnknownKey {
+                key: format!("{}{}", prefix, key),
+                suggestion: suggest(&key, known),
+            });
+        }
+    }
+}
+
+fn known_keys() -> Vec<String> {
+    match serde_json::to_value(Config::default()) {
+        Ok(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The known key closest to `key`, if it is a plausible typo.
+pub(crate) fn suggest(key: &str, known: &[String]) -> Option<String> {
+    let key = key.to_lowercase();
+    let max_distance = (key.chars().count() / 3).max(2);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(&key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Warn about unknown keys in a loaded file, once per file and key.
+pub fn warn_unknown_keys(path: &Path, table: &Map<String, Value>) {
+    static WARNED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+    let mut warned = WARNED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let warned = warned.get_or_insert_with(HashSet::new);
+
+    for unknown in unknown_keys(table) {
+        if warned.insert(format!("{}:{}", path.display(), unknown.key)) {
+            crate::output::context::get().warning(format!("⚠️  {} in {}", unknown, path.display()));
+        }
+    }
+}
+
+/// First free backup path for a file at the given version.
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut backup = path.with_file_name(format!("{}.v{}.bak", name, version));
+    let mut n = 1;
+    while backup.exists() {
+        backup = path.with_file_name(format!("{}.v{}.{}.bak", name, version, n));
+        n += 1;
+    }
+    backup
+}
+
+/// Read a config file as a raw document.
+pub fn read_document(path: &Path) -> Result<Map<String, Value>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config {}", path.display()))?;
+    ConfigFormat::from_path(path)
+        .parse_table(&contents)
+        .with_context(|| format!("Failed to parse config {}", path.display()))
+}
+
+/// Copy the original file to a backup and write the migrated document.
+/// Returns the backup path.
+pub fn write_migrated(path: &Path, table: &Map<String, Value>, from: u32) -> Result<PathBuf> {
+    let backup = backup_path(path, from);
+    fs::copy(path, &backup).with_context(|| format!("Failed to back up {}", path.display()))?;
+    let contents = ConfigFormat::from_path(path).serialize_table(table)?;
+    fs::write(path, contents)
+        .with_context(|| format!("Failed to write config {}", path.display()))?;
+    Ok(backup)
+}
+
+/// Upgrade the user's global config file to the current schema.
+///
+/// Repository files are only migrated in memory so the working tree is not
+/// modified behind the user's back; `rco config migrate` rewrites them.
 pub fn run_migrations() -> Result<()> {
-    // For now, just ensure config exists
-    let _config = Config::load()?;
+    let path = ConfigLocations::get()?.global;
+    if !path.exists() {
+        return Ok(());
+    }
 
-    // Future migrations can be added here
-    // Example:
-    // migrate_v1_to_v2(&mut config)?;
-    // migrate_v2_to_v3(&mut config)?;
+    let migrated = read_document(&path).and_then(|mut table| {
+        let report = migrate(&mut table)?;
+        if !report.is_needed() {
+            return Ok(None);
+        }
+        let backup = write_migrated(&path, &table, report.from)?;
+        Ok(Some((report, backup)))
+    });
 
+    let out = crate::output::context::get();
+    match migrated {
+        Ok(Some((report, backup))) => out.verbose(format!(
+            "Migrated {} from schema v{} to v{} (backup: {})",
+            path.display(),
+            report.from,
+            report.to,
+            backup.display()
+        )),
+        Ok(None) => {}
+        // Loading still works on the in-memory migration, so don't fail the command
+        Err(e) => out.warning(format!("⚠️  Could not migrate {}: {:#}", path.display(), e)),
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_ordered_and_end_at_current() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.to).collect();
+        let expected: Vec<u32> = (2..=CURRENT_SCHEMA_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_migrate_v1_document() {
+        let mut table = object(json!({
+            "RCO_MODEL": "gpt-4",
+            "OCO_LANGUAGE": "de",
+            "language": "fr",
+            "prompt_module": "gitmoji",
+            "api_custom_headers": "x",
+            "branch": { "release/*": { "RCO_EMOJI": true } },
+        }));
+        let report = migrate(&mut table).unwrap();
+
+        assert_eq!((report.from, report.to), (1, CURRENT_SCHEMA_VERSION));
+        assert_eq!(table["model"], "gpt-4");
+        assert_eq!(table["language"], "fr");
+        assert_eq!(table["commit_type"], "gitmoji");
+        assert_eq!(table["schema_version"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(table["branch"]["release/*"]["emoji"], true);
+        assert!(!table.contains_key("api_custom_headers"));
+        assert!(report
+            .changes
+            .contains(&"removed OCO_LANGUAGE (language is already set)".to_string()));
+        assert!(report
+            .changes
+            .contains(&"[branch.\"release/*\"] renamed RCO_EMOJI to emoji".to_string()));
+
+        // Already current: nothing to do
+        let again = migrate(&mut table).unwrap();
+        assert!(!again.is_needed());
+        assert!(again.changes.is_empty());
+    }
+
+    #[test]
+    fn test_newer_documents_are_left_alone() {
+        let mut table = object(json!({ "schema_version": 99, "RCO_MODEL": "x" }));
+        let report = migrate(&mut table).unwrap();
+        assert!(!report.is_needed());
+        assert!(table.contains_key("RCO_MODEL"));
+
+        let mut invalid = object(json!({ "schema_version": "two" }));
+        assert!(migrate(&mut invalid).is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_suggest_closest_field() {
+        let table = object(json!({
+            "modle": "gpt-4",
+            "emoji": true,
+            "unset": ["langauge"],
+            "branch": { "main": { "completely_unrelated": 1 } },
+        }));
+        let unknown = unknown_keys(&table);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownKey {
+                    key: "modle".to_string(),
+                    suggestion: Some("model".to_string()),
+                },
+                UnknownKey {
+                    key: "langauge".to_string(),
+                    suggestion: Some("language".to_string()),
+                },
+                UnknownKey {
+                    key: "branch.\"main\".completely_unrelated".to_string(),
+                    suggestion: None,
+                },
+            ]
+        );
+    }
+}
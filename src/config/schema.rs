@@ -0,0 +1,149 @@
+//! JSON Schemas for the files rusty-commit reads.
+//!
+//! Editors use them for completion and validation (a
+//! `# yaml-language-server: $schema=...` comment in YAML, `#:schema ...` in
+//! TOML with Taplo, or `"$schema"` in JSON), and CI can lint committed
+//! configs with any JSON Schema validator. `rco config schema` prints them.
+
+use clap::ValueEnum;
+use serde_json::{json, Map, Value};
+
+use super::accounts::AccountsConfig;
+use super::migrations::CURRENT_SCHEMA_VERSION;
+use super::Config;
+use crate::skills::SkillManifest;
+
+/// Which file format to describe.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SchemaKind {
+    /// Config files (`config.toml`, `.rco.toml`, `.rustycommit.yaml`, ...)
+    #[default]
+    Config,
+    /// The accounts file (`accounts.toml`)
+    Accounts,
+    /// Skill manifests (`skill.toml`)
+    Skill,
+}
+
+/// The JSON Schema for a file format.
+pub fn schema(kind: SchemaKind) -> Value {
+    match kind {
+        SchemaKind::Config => config_schema(),
+        SchemaKind::Accounts => {
+            let mut schema = schemars::schema_for!(AccountsConfig).to_value();
+            schema["title"] = json!("rusty-commit accounts");
+            schema
+        }
+        SchemaKind::Skill => {
+            let mut schema = schemars::schema_for!(SkillManifest).to_value();
+            schema["title"] = json!("rusty-commit skill manifest");
+            schema
+        }
+    }
+}
+
+/// Schema for a config file layer.
+///
+/// Unlike the [`Config`] struct, every key is optional since each layer only
+/// sets what it changes, and the document keys handled by the resolver
+/// (`schema_version`, `unset` and `[branch."<glob>"]` sections) are allowed.
+fn config_schema() -> Value {
+    let mut schema = schemars::schema_for!(Config).to_value();
+    let Some(object) = schema.as_object_mut() else {
+        return schema;
+    };
+    object.remove("required");
+    object.insert("title".to_string(), json!("rusty-commit config"));
+    object.insert("additionalProperties".to_string(), json!(false));
+
+    let defaults = match serde_json::to_value(Config::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => Map::new(),
+    };
+    let keys: Vec<String> = defaults.keys().cloned().collect();
+
+    let Some(Value::Object(properties)) = object.get_mut("properties") else {
+        return schema;
+    };
+    for (key, default) in &defaults {
+        if let (Some(Value::Object(property)), false) = (properties.get_mut(key), default.is_null())
+        {
+            property.insert("default".to_string(), default.clone());
+        }
+    }
+
+    let unset = json!({
+        "description": "Keys to reset to their defaults, overriding earlier layers",
+        "type": "array",
+        "items": { "type": "string", "enum": keys },
+    });
+    properties.insert("unset".to_string(), unset);
+
+    // Branch sections take the same settings as the file itself
+    let section = json!({
+        "type": "object",
+        "properties": properties.clone(),
+        "additionalProperties": false,
+    });
+    properties.insert(
+        "$schema".to_string(),
+        json!({ "description": "Schema for editor support", "type": "string" }),
+    );
+    properties.insert(
+        "schema_version".to_string(),
+        json!({
+            "description": "Config schema version; files without one are migrated from version 1",
+            "type": "integer",
+            "minimum": 1,
+            "maximum": CURRENT_SCHEMA_VERSION,
+        }),
+    );
+    properties.insert(
+        "branch".to_string(),
+        json!({
+            "description": "Overrides applied when the current branch matches the glob key",
+            "type": "object",
+            "additionalProperties": section,
+        }),
+    );
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_schema_allows_partial_layers() {
+        let schema = schema(SchemaKind::Config);
+        assert!(schema.get("required").is_none());
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["properties"]["model"]["default"], "gpt-3.5-turbo");
+        assert_eq!(
+            schema["properties"]["schema_version"]["maximum"],
+            CURRENT_SCHEMA_VERSION
+        );
+        assert!(schema["properties"]["unset"]["items"]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("few_shot_count")));
+    }
+
+    #[test]
+    fn test_config_schema_branch_sections() {
+        let schema = schema(SchemaKind::Config);
+        let section = &schema["properties"]["branch"]["additionalProperties"];
+        assert!(section["properties"]["model"].is_object());
+        assert!(section["properties"]["unset"].is_object());
+        assert!(section["properties"]["branch"].is_null());
+    }
+
+    #[test]
+    fn test_accounts_and_skill_schemas() {
+        let accounts = schema(SchemaKind::Accounts);
+        assert!(accounts["properties"]["accounts"].is_object());
+        let skill = schema(SchemaKind::Skill);
+        assert_eq!(skill["required"], json!(["skill"]));
+    }
+}
@@ -1,7 +1,8 @@
 //! Secure storage for sensitive configuration values.
 //!
 //! This module provides optional secure storage for API keys and other
-//! sensitive data using the system keychain when available.
+//! sensitive data using the system keychain when available, or an encrypted
+//! local vault (see [`super::vault`]) when `secret_backend = "vault"`.
 //!
 //! Platform support:
 //! - **macOS**: Uses the macOS Keychain via Security Framework
@@ -10,16 +11,114 @@
 //! - **iOS**: Uses iOS Keychain Services
 //! - **FreeBSD/OpenBSD**: Uses Secret Service if available
 
+use std::path::{Path, PathBuf};
+
 #[cfg(feature = "secure-storage")]
 use anyhow::anyhow;
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 #[cfg(feature = "secure-storage")]
 use keyring::Entry;
 
+use super::format::{ConfigFormat, ConfigLocations};
+use super::{layers, migrations, vault};
+
 #[allow(dead_code)]
 const SERVICE_NAME: &str = "rustycommit";
 
+/// Where secrets are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The system keyring (requires the `secure-storage` feature).
+    Keyring,
+    /// The encrypted local vault.
+    Vault,
+}
+
+impl Backend {
+    /// Parse `RCO_SECRET_BACKEND` (`keyring` or `vault`).
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "keyring" => Ok(Self::Keyring),
+            "vault" => Ok(Self::Vault),
+            other => anyhow::bail!(
+                "Invalid SECRET_BACKEND '{}': expected 'keyring' or 'vault'",
+                other
+            ),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keyring => "keyring",
+            Self::Vault => "vault",
+        }
+    }
+}
+
+/// The selected backend. Defaults to the keyring.
+pub fn backend() -> Backend {
+    setting("RCO_SECRET_BACKEND", "secret_backend")
+        .and_then(|value| Backend::parse(&value).ok())
+        .unwrap_or(Backend::Keyring)
+}
+
+/// Key file for the vault, if one is configured.
+pub fn vault_key_file() -> Option<PathBuf> {
+    setting("RCO_VAULT_KEY_FILE", "vault_key_file").map(|path| {
+        match path.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((rest, home)) => home.join(rest),
+            None => PathBuf::from(path),
+        }
+    })
+}
+
+/// Record the vault key file in the global config, or remove it to go back
+/// to a passphrase. Edits the file directly so secrets are not re-saved.
+pub fn save_vault_key_file(path: Option<&Path>) -> Result<()> {
+    let global = ConfigLocations::get()?.global;
+    let mut document = if global.exists() {
+        migrations::read_document(&global)?
+    } else {
+        serde_json::Map::new()
+    };
+    match path {
+        Some(path) => document.insert(
+            "vault_key_file".to_string(),
+            path.display().to_string().into(),
+        ),
+        None => document.remove("vault_key_file"),
+    };
+    if let Some(dir) = global.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let contents = ConfigFormat::from_path(&global).serialize_table(&document)?;
+    std::fs::write(&global, contents)
+        .with_context(|| format!("Failed to write config {}", global.display()))
+}
+
+/// A storage setting from the environment, the global config or the system
+/// config. Repository configs are ignored so a cloned repository cannot
+/// redirect where secrets are read from or written to.
+fn setting(env_var: &str, key: &str) -> Option<String> {
+    if let Ok(value) = std::env::var(env_var) {
+        if !value.is_empty() {
+            return Some(value);
+        }
+    }
+    let global = ConfigLocations::get()
+        .ok()
+        .map(|locations| locations.global);
+    global
+        .into_iter()
+        .chain(std::iter::once(layers::system_config_path()))
+        .filter(|path| path.exists())
+        .find_map(|path| {
+            let document = migrations::read_document(&path).ok()?;
+            document.get(key)?.as_str().map(String::from)
+        })
+}
+
 /// Store a secret securely in the system keyring.
 ///
 /// Platform behavior:
@@ -29,11 +128,19 @@ const SERVICE_NAME: &str = "rustycommit";
 ///
 /// If the secure-storage feature is not enabled or the system doesn't
 /// support keychain, this will return Ok(()) without storing anything.
-pub fn store_secret(_key: &str, _value: &str) -> Result<()> {
+/// With the vault backend the secret is encrypted into the vault instead.
+pub fn store_secret(key: &str, value: &str) -> Result<()> {
     // Respect explicit opt-out for tests/CI and deterministic behavior
     if is_disabled_via_env() {
         return Ok(());
     }
+    match backend() {
+        Backend::Vault => vault::store(key, value),
+        Backend::Keyring => store_in_keyring(key, value),
+    }
+}
+
+fn store_in_keyring(_key: &str, _value: &str) -> Result<()> {
     #[cfg(feature = "secure-storage")]
     {
         match Entry::new(SERVICE_NAME, _key) {
@@ -57,11 +164,18 @@ pub fn store_secret(_key: &str, _value: &str) -> Result<()> {
 /// Retrieve a secret from the system keyring.
 ///
 /// Returns None if secure-storage is not enabled or the key doesn't exist.
-pub fn get_secret(_key: &str) -> Result<Option<String>> {
+pub fn get_secret(key: &str) -> Result<Option<String>> {
     // Respect explicit opt-out for tests/CI and deterministic behavior
     if is_disabled_via_env() {
         return Ok(None);
     }
+    match backend() {
+        Backend::Vault => vault::get(key),
+        Backend::Keyring => get_from_keyring(key),
+    }
+}
+
+fn get_from_keyring(_key: &str) -> Result<Option<String>> {
     #[cfg(feature = "secure-storage")]
     {
         match Entry::new(SERVICE_NAME, _key) {
@@ -87,11 +201,18 @@ pub fn get_secret(_key: &str) -> Result<Option<String>> {
 /// Delete a secret from the system keyring.
 ///
 /// If secure-storage is not enabled, this is a no-op.
-pub fn delete_secret(_key: &str) -> Result<()> {
+pub fn delete_secret(key: &str) -> Result<()> {
     // Respect explicit opt-out for tests/CI and deterministic behavior
     if is_disabled_via_env() {
         return Ok(());
     }
+    match backend() {
+        Backend::Vault => vault::delete(key),
+        Backend::Keyring => delete_from_keyring(key),
+    }
+}
+
+fn delete_from_keyring(_key: &str) -> Result<()> {
     #[cfg(feature = "secure-storage")]
     {
         match Entry::new(SERVICE_NAME, _key) {
@@ -111,14 +232,41 @@ pub fn delete_secret(_key: &str) -> Result<()> {
 
 /// Check if secure storage is available on this system.
 ///
-/// Returns true only if the secure-storage feature is enabled AND
-/// the system has a working keychain.
+/// With the keyring backend, returns true only if the secure-storage
+/// feature is enabled AND the system has a working keychain. With the vault
+/// backend, returns true if the vault can be unlocked.
 pub fn is_available() -> bool {
     // Allow tests/CI to force-disable secure storage to ensure deterministic behavior
     if is_disabled_via_env() {
         return false;
     }
 
+    match backend() {
+        Backend::Vault => vault::is_unlockable(),
+        Backend::Keyring => keyring_available(),
+    }
+}
+
+/// Fail if the vault backend is selected but can't be unlocked here (no
+/// passphrase, key file or terminal), so callers about to store a secret
+/// don't fall back to writing it in plaintext.
+pub fn require_unlocked_vault() -> Result<()> {
+    if !is_disabled_via_env() && backend() == Backend::Vault && !vault::is_unlockable() {
+        anyhow::bail!(
+            "The secrets vault is locked; set RCO_VAULT_PASSPHRASE or RCO_VAULT_KEY_FILE"
+        );
+    }
+    Ok(())
+}
+
+/// Whether a secret that could not be stored securely may be written to a
+/// plain file instead. Only the keyring backend falls back; the vault is
+/// selected to keep secrets out of plain files.
+pub fn falls_back_to_plaintext() -> bool {
+    backend() == Backend::Keyring
+}
+
+fn keyring_available() -> bool {
     #[cfg(feature = "secure-storage")]
     {
         // Try to create a test entry to see if keyring is available
@@ -146,6 +294,16 @@ fn is_disabled_via_env() -> bool {
 
 /// Get detailed platform information for secure storage
 pub fn get_platform_info() -> String {
+    if backend() == Backend::Vault {
+        return match vault::vault_path() {
+            Ok(path) => format!("Encrypted vault ({})", path.display()),
+            Err(_) => "Encrypted vault".to_string(),
+        };
+    }
+    keyring_platform_info()
+}
+
+fn keyring_platform_info() -> String {
     #[cfg(all(feature = "secure-storage", target_os = "macos"))]
     return "macOS Keychain".to_string();
 
@@ -193,6 +351,15 @@ pub fn get_platform_info() -> String {
 
 /// Returns a user-friendly message about the secure storage status.
 pub fn status_message() -> String {
+    if backend() == Backend::Vault {
+        return if is_available() {
+            format!("Secrets are encrypted in the {}", get_platform_info())
+        } else {
+            "The vault backend is selected but locked: set RCO_VAULT_PASSPHRASE or RCO_VAULT_KEY_FILE"
+                .to_string()
+        };
+    }
+
     #[cfg(feature = "secure-storage")]
     {
         if is_available() {
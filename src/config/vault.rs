@@ -0,0 +1,585 @@
+//! Encrypted local vault for secrets.
+//!
+//! An alternative to the system keyring for machines without one (CI
+//! runners, SSH sessions, containers). Secrets live in `secrets.vault` in the
+//! config directory, each sealed with XChaCha20-Poly1305 under a 256-bit key
+//! that is either derived from a passphrase with Argon2id or read from a key
+//! file. Entry names are stored in the clear so they can be listed without
+//! unlocking the vault; values are bound to their names as associated data.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use super::secure_storage;
+use crate::utils::fs::{stage_private, write_private};
+
+/// Format version of the vault file.
+const VAULT_VERSION: u32 = 1;
+
+/// Associated data for the entry used to verify the key.
+const CHECK_NAME: &str = "rco-vault-check";
+
+/// Argon2id cost parameters for new passphrase vaults (OWASP minimums).
+const ARGON2_MEMORY_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Keys derived from passphrases this process, by salt, so the passphrase
+/// is asked for (and Argon2 run) once per command.
+static DERIVED_KEYS: Mutex<Option<HashMap<String, [u8; 32]>>> = Mutex::new(None);
+
+/// How the vault key is obtained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySource {
+    /// Derived from `RCO_VAULT_PASSPHRASE` or an interactive prompt.
+    Passphrase,
+    /// Read from a file holding 32 random bytes, base64-encoded.
+    KeyFile(PathBuf),
+}
+
+impl KeySource {
+    /// The configured key source: the key file if one is set, else a passphrase.
+    pub fn configured() -> Self {
+        match secure_storage::vault_key_file() {
+            Some(path) => Self::KeyFile(path),
+            None => Self::Passphrase,
+        }
+    }
+}
+
+/// Key derivation recorded in the vault file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Kdf {
+    Argon2id {
+        salt: String,
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+    KeyFile,
+}
+
+/// A value encrypted with the vault key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+/// On-disk layout of `secrets.vault`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    cipher: String,
+    kdf: Kdf,
+    check: Sealed,
+    secrets: BTreeMap<String, Sealed>,
+}
+
+/// An unlocked vault.
+pub struct Vault {
+    path: PathBuf,
+    file: VaultFile,
+    cipher: XChaCha20Poly1305,
+}
+
+impl Vault {
+    /// Open the vault at `path`, creating an empty one if it does not exist.
+    pub fn open_or_create(path: &Path, source: &KeySource) -> Result<Self> {
+        if path.exists() {
+            Self::open(path, source)
+        } else {
+            let (kdf, key) = new_key(source)?;
+            Self::create(path, kdf, &key)
+        }
+    }
+
+    /// Unlock an existing vault.
+    pub fn open(path: &Path, source: &KeySource) -> Result<Self> {
+        let file = read_file(path)?;
+        let key = unlock_key(&file.kdf, source)?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        open_sealed(&cipher, CHECK_NAME, &file.check).map_err(|_| match source {
+            KeySource::Passphrase => {
+                forget_key(&file.kdf);
+                anyhow!("Wrong passphrase for the secrets vault {}", path.display())
+            }
+            KeySource::KeyFile(key_file) => anyhow!(
+                "Key file {} does not unlock the secrets vault {}",
+                key_file.display(),
+                path.display()
+            ),
+        })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            cipher,
+        })
+    }
+
+    fn create(path: &Path, kdf: Kdf, key: &[u8; 32]) -> Result<Self> {
+        let cipher = XChaCha20Poly1305::new(key.into());
+        let check = seal(&cipher, CHECK_NAME, CHECK_NAME)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: VaultFile {
+                version: VAULT_VERSION,
+                cipher: "xchacha20poly1305".to_string(),
+                kdf,
+                check,
+                secrets: BTreeMap::new(),
+            },
+            cipher,
+        })
+    }
+
+    /// Decrypt a secret.
+    pub fn get(&self, name: &str) -> Result<Option<String>> {
+        self.file
+            .secrets
+            .get(name)
+            .map(|sealed| open_sealed(&self.cipher, name, sealed))
+            .transpose()
+    }
+
+    /// Encrypt and add or replace a secret. Call [`Vault::save`] to persist.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let sealed = seal(&self.cipher, name, value)?;
+        self.file.secrets.insert(name.to_string(), sealed);
+        Ok(())
+    }
+
+    /// Remove a secret, returning whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.file.secrets.remove(name).is_some()
+    }
+
+    /// Names of the stored secrets.
+    pub fn names(&self) -> Vec<String> {
+        self.file.secrets.keys().cloned().collect()
+    }
+
+    /// Re-encrypt every secret under a fresh key from `source`.
+    ///
+    /// For a key file source a new key is generated and staged next to the
+    /// key file, and only replaces it once the vault has been saved. If any
+    /// step fails, the old vault and key are left as they were.
+    pub fn rotate(&mut self, source: &KeySource) -> Result<()> {
+        let secrets = self
+            .names()
+            .into_iter()
+            .map(|name| {
+                let value = self.get(&name)?.unwrap_or_default();
+                Ok((name, value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (kdf, key) = match source {
+            KeySource::Passphrase => {
+                let passphrase = read_passphrase("New vault passphrase", true, true)?;
+                let salt: [u8; 16] = rand::random();
+                let kdf = argon2_kdf(&salt);
+                let key = derive_key(&kdf, &passphrase)?;
+                (kdf, key)
+            }
+            KeySource::KeyFile(_) => (Kdf::KeyFile, rand::random()),
+        };
+
+        let mut rotated = Self::create(&self.path, kdf, &key)?;
+        for (name, value) in &secrets {
+            rotated.set(name, value)?;
+        }
+
+        // The vault must never be saved under a key that isn't on disk; the
+        // staged key file is removed when dropped
+        let staged = match source {
+            KeySource::KeyFile(path) => Some((
+                path,
+                stage_private(path, key_file_contents(&key).as_bytes())?,
+            )),
+            KeySource::Passphrase => None,
+        };
+        rotated.save()?;
+        if let Some((path, staged)) = staged {
+            if let Err(e) = staged.persist(path) {
+                self.save()
+                    .context("Failed to restore the vault after rotating its key failed")?;
+                return Err(e.error)
+                    .with_context(|| format!("Failed to write vault key file {}", path.display()));
+            }
+        }
+        *self = rotated;
+        Ok(())
+    }
+
+    /// Write the vault atomically with owner-only permissions.
+    pub fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.file).context("Failed to serialize vault")?;
+        write_private(&self.path, json.as_bytes())
+    }
+}
+
+/// Path of the vault file in the config directory.
+pub fn vault_path() -> Result<PathBuf> {
+    let config_dir = if let Ok(config_home) = std::env::var("RCO_CONFIG_HOME") {
+        PathBuf::from(config_home)
+    } else {
+        let home = dirs::home_dir().context("Could not find home directory")?;
+        home.join(".config").join("rustycommit")
+    };
+    Ok(config_dir.join("secrets.vault"))
+}
+
+/// Names stored in the vault, read without unlocking it.
+pub fn names() -> Result<Vec<String>> {
+    let path = vault_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(read_file(&path)?.secrets.into_keys().collect())
+}
+
+/// Whether the vault can be unlocked without failing: a key file is
+/// configured, a passphrase is in the environment, or we can prompt.
+pub fn is_unlockable() -> bool {
+    match KeySource::configured() {
+        KeySource::KeyFile(_) => true,
+        KeySource::Passphrase => {
+            std::env::var("RCO_VAULT_PASSPHRASE").is_ok() || std::io::stdin().is_terminal()
+        }
+    }
+}
+
+/// Store a secret in the default vault.
+pub fn store(name: &str, value: &str) -> Result<()> {
+    let mut vault = Vault::open_or_create(&vault_path()?, &KeySource::configured())?;
+    vault.set(name, value)?;
+    vault.save()
+}
+
+/// Read a secret from the default vault. Does not unlock the vault (or
+/// prompt) unless the secret is there.
+pub fn get(name: &str) -> Result<Option<String>> {
+    if !names()?.iter().any(|n| n == name) {
+        return Ok(None);
+    }
+    Vault::open(&vault_path()?, &KeySource::configured())?.get(name)
+}
+
+/// Delete a secret from the default vault.
+pub fn delete(name: &str) -> Result<()> {
+    if !names()?.iter().any(|n| n == name) {
+        return Ok(());
+    }
+    let mut vault = Vault::open(&vault_path()?, &KeySource::configured())?;
+    vault.remove(name);
+    vault.save()
+}
+
+fn read_file(path: &Path) -> Result<VaultFile> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read secrets vault {}", path.display()))?;
+    let file: VaultFile = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse secrets vault {}", path.display()))?;
+    if file.version > VAULT_VERSION {
+        bail!(
+            "Secrets vault {} was written by a newer rco (format v{})",
+            path.display(),
+            file.version
+        );
+    }
+    Ok(file)
+}
+
+/// Key material for a new vault.
+fn new_key(source: &KeySource) -> Result<(Kdf, [u8; 32])> {
+    match source {
+        KeySource::Passphrase => {
+            let passphrase = read_passphrase("New vault passphrase", true, false)?;
+            let salt: [u8; 16] = rand::random();
+            let kdf = argon2_kdf(&salt);
+            let key = derive_key(&kdf, &passphrase)?;
+            Ok((kdf, key))
+        }
+        KeySource::KeyFile(path) if path.exists() => Ok((Kdf::KeyFile, read_key_file(path)?)),
+        KeySource::KeyFile(path) => {
+            let key: [u8; 32] = rand::random();
+            write_key_file(path, &key)?;
+            Ok((Kdf::KeyFile, key))
+        }
+    }
+}
+
+/// Key for an existing vault.
+fn unlock_key(kdf: &Kdf, source: &KeySource) -> Result<[u8; 32]> {
+    match (kdf, source) {
+        (Kdf::KeyFile, KeySource::KeyFile(path)) => read_key_file(path),
+        (Kdf::KeyFile, KeySource::Passphrase) => bail!(
+            "The secrets vault is encrypted with a key file. Set RCO_VAULT_KEY_FILE or \
+             'rco config set RCO_VAULT_KEY_FILE=<path>'"
+        ),
+        (Kdf::Argon2id { salt, .. }, _) => {
+            if let Some(key) = cached_key(salt) {
+                return Ok(key);
+            }
+            let passphrase = read_passphrase("Vault passphrase", false, false)?;
+            let key = derive_key(kdf, &passphrase)?;
+            DERIVED_KEYS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get_or_insert_with(HashMap::new)
+                .insert(salt.clone(), key);
+            Ok(key)
+        }
+    }
+}
+
+fn cached_key(salt: &str) -> Option<[u8; 32]> {
+    DERIVED_KEYS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()?
+        .get(salt)
+        .copied()
+}
+
+fn forget_key(kdf: &Kdf) {
+    if let Kdf::Argon2id { salt, .. } = kdf {
+        if let Some(keys) = DERIVED_KEYS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_mut()
+        {
+            keys.remove(salt);
+        }
+    }
+}
+
+fn argon2_kdf(salt: &[u8]) -> Kdf {
+    Kdf::Argon2id {
+        salt: BASE64.encode(salt),
+        memory_kib: ARGON2_MEMORY_KIB,
+        iterations: ARGON2_ITERATIONS,
+        parallelism: ARGON2_PARALLELISM,
+    }
+}
+
+fn derive_key(kdf: &Kdf, passphrase: &str) -> Result<[u8; 32]> {
+    let Kdf::Argon2id {
+        salt,
+        memory_kib,
+        iterations,
+        parallelism,
+    } = kdf
+    else {
+        bail!("Vault is not passphrase-protected");
+    };
+    let salt = BASE64.decode(salt).context("Invalid vault salt")?;
+    let params = Params::new(*memory_kib, *iterations, *parallelism, Some(32))
+        .map_err(|e| anyhow!("Invalid vault KDF parameters: {e}"))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive vault key: {e}"))?;
+    Ok(key)
+}
+
+/// Passphrase from the environment, or a prompt when interactive.
+fn read_passphrase(prompt: &str, confirm: bool, rotating: bool) -> Result<String> {
+    let env = if rotating {
+        "RCO_VAULT_NEW_PASSPHRASE"
+    } else {
+        "RCO_VAULT_PASSPHRASE"
+    };
+    if let Ok(passphrase) = std::env::var(env) {
+        if passphrase.is_empty() {
+            bail!("{} is empty", env);
+        }
+        return Ok(passphrase);
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!(
+            "The secrets vault is locked. Set {} or configure a key file with RCO_VAULT_KEY_FILE",
+            env
+        );
+    }
+
+    let mut password = dialoguer::Password::new().with_prompt(prompt);
+    if confirm {
+        password = password.with_confirmation("Confirm passphrase", "Passphrases do not match");
+    }
+    password.interact().context("Failed to read passphrase")
+}
+
+fn read_key_file(path: &Path) -> Result<[u8; 32]> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read vault key file {}", path.display()))?;
+    BASE64
+        .decode(contents.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .with_context(|| {
+            format!(
+                "Vault key file {} must hold 32 base64-encoded bytes",
+                path.display()
+            )
+        })
+}
+
+fn key_file_contents(key: &[u8; 32]) -> String {
+    format!("{}\n", BASE64.encode(key))
+}
+
+fn write_key_file(path: &Path, key: &[u8; 32]) -> Result<()> {
+    write_private(path, key_file_contents(key).as_bytes())
+}
+
+fn seal(cipher: &XChaCha20Poly1305, name: &str, value: &str) -> Result<Sealed> {
+    let nonce: [u8; 24] = rand::random();
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: value.as_bytes(),
+                aad: name.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow!("Failed to encrypt secret '{}'", name))?;
+    Ok(Sealed {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open_sealed(cipher: &XChaCha20Poly1305, name: &str, sealed: &Sealed) -> Result<String> {
+    let nonce = BASE64
+        .decode(&sealed.nonce)
+        .ok()
+        .filter(|nonce| nonce.len() == 24)
+        .with_context(|| format!("Invalid nonce for secret '{}'", name))?;
+    let ciphertext = BASE64
+        .decode(&sealed.ciphertext)
+        .with_context(|| format!("Invalid ciphertext for secret '{}'", name))?;
+    let plaintext = cipher
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: name.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow!("Failed to decrypt secret '{}'", name))?;
+    String::from_utf8(plaintext).with_context(|| format!("Secret '{}' is not UTF-8", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A passphrase KDF cheap enough for tests.
+    fn test_kdf() -> Kdf {
+        Kdf::Argon2id {
+            salt: BASE64.encode([7u8; 16]),
+            memory_kib: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn test_seal_round_trip_binds_name() {
+        let cipher = XChaCha20Poly1305::new(&[1u8; 32].into());
+        let sealed = seal(&cipher, "RCO_API_KEY", "sk-secret").unwrap();
+        assert_eq!(
+            open_sealed(&cipher, "RCO_API_KEY", &sealed).unwrap(),
+            "sk-secret"
+        );
+        // A value moved to another entry no longer decrypts
+        assert!(open_sealed(&cipher, "other", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_key_file_vault_round_trip_and_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.vault");
+        let key_file = dir.path().join("vault.key");
+        let source = KeySource::KeyFile(key_file.clone());
+
+        let mut vault = Vault::open_or_create(&path, &source).unwrap();
+        vault.set("RCO_API_KEY", "sk-one").unwrap();
+        vault.save().unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("sk-one"));
+
+        let old_key = fs::read_to_string(&key_file).unwrap();
+        let mut vault = Vault::open(&path, &source).unwrap();
+        vault.rotate(&source).unwrap();
+        assert_ne!(fs::read_to_string(&key_file).unwrap(), old_key);
+
+        let vault = Vault::open(&path, &source).unwrap();
+        assert_eq!(vault.get("RCO_API_KEY").unwrap().unwrap(), "sk-one");
+        assert_eq!(vault.names(), vec!["RCO_API_KEY".to_string()]);
+
+        fs::write(&key_file, BASE64.encode([9u8; 32])).unwrap();
+        assert!(Vault::open(&path, &source).is_err());
+    }
+
+    #[test]
+    fn test_failed_rotate_keeps_old_vault_and_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.vault");
+        let key_file = dir.path().join("vault.key");
+        let source = KeySource::KeyFile(key_file.clone());
+
+        let mut vault = Vault::open_or_create(&path, &source).unwrap();
+        vault.set("RCO_API_KEY", "sk-one").unwrap();
+        vault.save().unwrap();
+        let old_vault = fs::read_to_string(&path).unwrap();
+        let old_key = fs::read_to_string(&key_file).unwrap();
+
+        // The new key can't be staged: its directory is a file
+        fs::write(dir.path().join("file"), "").unwrap();
+        let unwritable = KeySource::KeyFile(dir.path().join("file").join("vault.key"));
+        assert!(vault.rotate(&unwritable).is_err());
+
+        // The new key is staged but can't replace the target, a directory
+        fs::create_dir(dir.path().join("occupied")).unwrap();
+        let occupied = KeySource::KeyFile(dir.path().join("occupied"));
+        assert!(vault.rotate(&occupied).is_err());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), old_vault);
+        assert_eq!(fs::read_to_string(&key_file).unwrap(), old_key);
+        let vault = Vault::open(&path, &source).unwrap();
+        assert_eq!(vault.get("RCO_API_KEY").unwrap().unwrap(), "sk-one");
+        // No staged key is left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 4);
+    }
+
+    #[test]
+    fn test_passphrase_kdf_is_deterministic() {
+        let kdf = test_kdf();
+        let key = derive_key(&kdf, "correct horse").unwrap();
+        assert_eq!(key, derive_key(&kdf, "correct horse").unwrap());
+        assert_ne!(key, derive_key(&kdf, "battery staple").unwrap());
+    }
+
+    #[test]
+    fn test_rejects_malformed_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("vault.key");
+        fs::write(&key_file, "too short").unwrap();
+        assert!(read_key_file(&key_file).is_err());
+    }
+}
@@ -206,6 +206,29 @@ pub fn get_repo_root() -> Result<String> {
     Ok(workdir.to_string_lossy().to_string())
 }
 
+/// Returns the configured `user.email` for the current repository.
+///
+/// # Errors
+///
+/// Returns an error if not in a Git repository or if `user.email` is not set.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let email = git::get_user_email().unwrap();
+/// println!("Committing as {}", email);
+/// ```
+pub fn get_user_email() -> Result<String> {
+    let repo = Repository::open_from_env()?;
+    let email = repo
+        .config()?
+        .get_string("user.email")
+        .context("user.email is not set in git config")?;
+    Ok(email)
+}
+
 /// Returns the current branch name.
 ///
 /// # Errors
@@ -230,6 +253,96 @@ pub fn get_current_branch() -> Result<String> {
     Ok(branch_name)
 }
 
+/// Files touched by a past commit, as returned by [`get_commit_changes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitChanges {
+    /// Abbreviated (7 character) commit id.
+    pub id: String,
+    /// Full commit message.
+    pub message: String,
+    /// Paths changed relative to the first parent.
+    pub files: Vec<String>,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// Returns the changed files and line stats of recent non-merge commits.
+///
+/// # Arguments
+///
+/// * `count` - Maximum number of commits to inspect, walking back from `HEAD`
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be accessed or has no commits.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// for commit in git::get_commit_changes(20).unwrap() {
+///     println!("{}: {} files", commit.id, commit.files.len());
+/// }
+/// ```
+pub fn get_commit_changes(count: usize) -> Result<Vec<CommitChanges>> {
+    let repo = Repository::open_from_env()?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    revwalk.push_head().context("Repository has no commits")?;
+
+    let mut commits = Vec::new();
+    for oid in revwalk.take(count) {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        let stats = diff.stats()?;
+        let files = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        commits.push(CommitChanges {
+            id: commit.id().to_string().chars().take(7).collect(),
+            message: commit.message().unwrap_or("").to_string(),
+            files,
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+        });
+    }
+
+    Ok(commits)
+}
+
+/// Returns the full object id of the commit at `HEAD`.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be accessed or has no commits.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let oid = git::get_head_commit_id().unwrap();
+/// println!("HEAD is at {}", oid);
+/// ```
+pub fn get_head_commit_id() -> Result<String> {
+    let repo = Repository::open_from_env()?;
+    let commit = repo.head()?.peel_to_commit()?;
+    Ok(commit.id().to_string())
+}
+
 /// Returns a list of commit hashes and messages between two branches.
 ///
 /// The output format is `"<hash> - <message>"` for each commit, with the hash
@@ -439,6 +552,7 @@ pub fn git_push_upstream() -> Result<()> {
 ///     println!("{}", msg);
 /// }
 /// ```
+#[allow(dead_code)]
 pub fn get_recent_commit_messages(count: usize) -> Result<Vec<String>> {
     let repo = Repository::open_from_env()?;
 
@@ -474,3 +588,369 @@ pub fn get_recent_commit_messages(count: usize) -> Result<Vec<String>> {
 
     Ok(commits)
 }
+
+/// A commit in the log, as returned by [`get_commit_log`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    /// Abbreviated (7 character) commit id.
+    pub id: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Full commit message, including body and trailers.
+    pub message: String,
+    /// Whether the commit has more than one parent.
+    pub is_merge: bool,
+}
+
+/// Returns the commits in a revision range, newest first.
+///
+/// # Arguments
+///
+/// * `range` - A range such as `v1.0..HEAD`, a single revision (all of its
+///   ancestors), or `None` for `HEAD`
+/// * `max_count` - Optional limit on the number of commits returned
+///
+/// # Errors
+///
+/// Returns an error if the range cannot be resolved or the repository cannot be accessed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let commits = git::get_commit_log(Some("v1.0..HEAD"), Some(100)).unwrap();
+/// for commit in commits {
+///     println!("{} {}", commit.id, commit.message.lines().next().unwrap_or(""));
+/// }
+/// ```
+pub fn get_commit_log(range: Option<&str>, max_count: Option<usize>) -> Result<Vec<CommitInfo>> {
+    let repo = Repository::open_from_env()?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    match range {
+        Some(range) if range.contains("..") => revwalk
+            .push_range(range)
+            .with_context(|| format!("Invalid revision range '{}'", range))?,
+        Some(rev) => {
+            let object = repo
+                .revparse_single(rev)
+                .with_context(|| format!("Unknown revision '{}'", rev))?;
+            revwalk.push(object.peel_to_commit()?.id())?;
+        }
+        None => revwalk.push_head().context("Repository has no commits")?,
+    }
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        if max_count.is_some_and(|max| commits.len() >= max) {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        let author = commit.author();
+        commits.push(CommitInfo {
+            id: commit.id().to_string().chars().take(7).collect(),
+            author_name: author.name().unwrap_or("").to_string(),
+            author_email: author.email().unwrap_or("").to_string(),
+            message: commit.message().unwrap_or("").to_string(),
+            is_merge: commit.parent_count() > 1,
+        });
+    }
+
+    Ok(commits)
+}
+
+/// Signature format used for signed commits, as set by `gpg.format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SigningFormat {
+    /// OpenPGP signatures via `gpg` (git's default).
+    #[default]
+    OpenPgp,
+    /// SSH signatures via `ssh-keygen`.
+    Ssh,
+    /// X.509 signatures via `gpgsm`.
+    X509,
+}
+
+impl SigningFormat {
+    /// Parse a `gpg.format` value. Returns `None` for unsupported formats.
+    pub fn from_git(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "openpgp" => Some(Self::OpenPgp),
+            "ssh" => Some(Self::Ssh),
+            "x509" => Some(Self::X509),
+            _ => None,
+        }
+    }
+
+    /// The `gpg.format` value for this format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpenPgp => "openpgp",
+            Self::Ssh => "ssh",
+            Self::X509 => "x509",
+        }
+    }
+}
+
+/// Commit signing settings resolved from git configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigningConfig {
+    /// Whether commits are signed by default (`commit.gpgsign`).
+    pub enabled: bool,
+    /// Signature format (`gpg.format`).
+    pub format: SigningFormat,
+    /// Signing key (`user.signingkey`), if configured.
+    pub key: Option<String>,
+}
+
+impl SigningConfig {
+    /// Whether a commit should be signed, given an optional CLI override.
+    #[allow(dead_code)]
+    pub fn should_sign(&self, sign_override: Option<bool>) -> bool {
+        sign_override.unwrap_or(self.enabled)
+    }
+
+    /// Check that signing can work before handing off to git.
+    ///
+    /// SSH signing has no implicit default key, so git fails late with a
+    /// terse message when `user.signingkey` is missing.
+    pub fn validate(&self) -> Result<()> {
+        if self.format == SigningFormat::Ssh && self.key.is_none() {
+            anyhow::bail!(
+                "SSH commit signing requires a key. Set one with 'git config user.signingkey ~/.ssh/id_ed25519.pub' or commit with --no-sign."
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Reads commit signing settings (`commit.gpgsign`, `gpg.format`,
+/// `user.signingkey`) from the repository's git configuration.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be accessed or `gpg.format`
+/// holds an unsupported value.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let signing = git::get_signing_config().unwrap();
+/// println!("Signing enabled: {}", signing.enabled);
+/// ```
+pub fn get_signing_config() -> Result<SigningConfig> {
+    let repo = Repository::open_from_env()?;
+    let config = repo.config()?;
+
+    let enabled = config.get_bool("commit.gpgsign").unwrap_or(false);
+    let format = match config.get_string("gpg.format") {
+        Ok(value) => SigningFormat::from_git(&value)
+            .with_context(|| format!("Unsupported gpg.format '{}'", value))?,
+        Err(_) => SigningFormat::default(),
+    };
+    let key = config
+        .get_string("user.signingkey")
+        .ok()
+        .filter(|k| !k.trim().is_empty());
+
+    Ok(SigningConfig {
+        enabled,
+        format,
+        key,
+    })
+}
+
+/// Signature verification status of a commit, as reported by git's `%G?`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Good, valid signature.
+    Good,
+    /// Good signature with unknown validity (key not trusted).
+    UnknownValidity,
+    /// Good signature that has expired.
+    Expired,
+    /// Good signature made by an expired key.
+    ExpiredKey,
+    /// Good signature made by a revoked key.
+    RevokedKey,
+    /// Bad signature.
+    Bad,
+    /// Signature present but cannot be checked (e.g. missing key or
+    /// no `gpg.ssh.allowedSignersFile`).
+    CannotCheck,
+    /// The commit is not signed.
+    Unsigned,
+}
+
+impl SignatureStatus {
+    /// Parse a `%G?` status letter.
+    pub fn from_code(code: &str) -> Self {
+        match code.trim() {
+            "G" => Self::Good,
+            "U" => Self::UnknownValidity,
+            "X" => Self::Expired,
+            "Y" => Self::ExpiredKey,
+            "R" => Self::RevokedKey,
+            "B" => Self::Bad,
+            "E" => Self::CannotCheck,
+            _ => Self::Unsigned,
+        }
+    }
+
+    /// Whether the commit carries a signature at all.
+    pub fn is_signed(&self) -> bool {
+        !matches!(self, Self::Unsigned)
+    }
+
+    /// Human-readable description of the status.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Good => "good signature",
+            Self::UnknownValidity => "good signature (unknown validity)",
+            Self::Expired => "good signature (expired)",
+            Self::ExpiredKey => "good signature (expired key)",
+            Self::RevokedKey => "good signature (revoked key)",
+            Self::Bad => "BAD signature",
+            Self::CannotCheck => "signed (signature could not be verified)",
+            Self::Unsigned => "not signed",
+        }
+    }
+}
+
+/// Signature details for a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSignature {
+    pub status: SignatureStatus,
+    /// Key fingerprint or ID used to sign (`%GK`).
+    pub key: Option<String>,
+    /// Signer identity (`%GS`).
+    pub signer: Option<String>,
+}
+
+/// Creates a commit from the staged changes and returns its SHA.
+///
+/// Signing follows git configuration (`commit.gpgsign`, `gpg.format`,
+/// `user.signingkey`) unless `sign` overrides it: `Some(true)` forces a
+/// signed commit and `Some(false)` disables signing.
+///
+/// # Errors
+///
+/// Returns an error if signing is requested but misconfigured, or if the
+/// git commit command fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let sha = git::commit("feat: add login page", None).unwrap();
+/// println!("Created {}", sha);
+/// ```
+pub fn commit(message: &str, sign: Option<bool>) -> Result<String> {
+    // Only look at the signing setup when a signature will be made, so a
+    // --no-sign commit isn't blocked by signing config it never uses.
+    let should_sign = match sign {
+        Some(sign) => sign,
+        None => Repository::open_from_env()?
+            .config()?
+            .get_bool("commit.gpgsign")
+            .unwrap_or(false),
+    };
+    let signing = if should_sign {
+        let signing = get_signing_config()?;
+        signing.validate()?;
+        Some(signing)
+    } else {
+        None
+    };
+
+    let mut cmd = Command::new("git");
+    cmd.args(["commit", "-m", message]);
+    match sign {
+        Some(true) => {
+            cmd.arg("-S");
+        }
+        Some(false) => {
+            cmd.arg("--no-gpg-sign");
+        }
+        None => {}
+    }
+
+    let output = cmd.output().context("Failed to execute git commit")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if let Some(signing) = &signing {
+            anyhow::bail!(
+                "Git commit failed while signing with {} (key: {}): {}",
+                signing.format.as_str(),
+                signing.key.as_deref().unwrap_or("default"),
+                stderr
+            );
+        }
+        anyhow::bail!("Git commit failed: {}", stderr);
+    }
+
+    let repo = Repository::open_from_env()?;
+    let head = repo.head()?.peel_to_commit()?;
+    Ok(head.id().to_string())
+}
+
+/// Returns the signature status of a commit.
+///
+/// # Arguments
+///
+/// * `rev` - Any revision git understands (e.g. "HEAD" or a SHA)
+///
+/// # Errors
+///
+/// Returns an error if the git log command fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rusty_commit::git;
+///
+/// let signature = git::get_commit_signature("HEAD").unwrap();
+/// println!("{}", signature.status.description());
+/// ```
+pub fn get_commit_signature(rev: &str) -> Result<CommitSignature> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%G?%n%GK%n%GS", rev])
+        .output()
+        .context("Failed to execute git log")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to read commit signature: {}", stderr);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let mut status = SignatureStatus::from_code(lines.next().unwrap_or("N"));
+
+    // git reports "N" for SSH signatures it cannot verify (no
+    // gpg.ssh.allowedSignersFile), so look for the signature header directly
+    if status == SignatureStatus::Unsigned {
+        let repo = Repository::open_from_env()?;
+        let oid = repo.revparse_single(rev)?.peel_to_commit()?.id();
+        if repo.extract_signature(&oid, None).is_ok() {
+            status = SignatureStatus::CannotCheck;
+        }
+    }
+    let non_empty = |s: Option<&str>| {
+        s.map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    Ok(CommitSignature {
+        status,
+        key: non_empty(lines.next()),
+        signer: non_empty(lines.next()),
+    })
+}
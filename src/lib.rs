@@ -81,4 +81,5 @@ pub mod output;
 pub mod providers;
 pub mod skills;
 pub mod update;
+pub mod usage;
 pub mod utils;
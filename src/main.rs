@@ -7,53 +7,101 @@ mod output;
 mod providers;
 mod skills;
 mod update;
+mod usage;
 mod utils;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
+use output::error::ToStructured;
+use output::prelude::OutputLevel;
 use std::env;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
-
     // Check if we're being called as a git hook
     let args: Vec<String> = env::args().collect();
     if commands::githook::is_hook_called(&args) {
+        init_tracing(OutputLevel::Normal);
         return commands::githook::prepare_commit_msg_hook(&args).await;
     }
 
     // Parse CLI arguments
-    let cli = cli::Cli::parse();
+    let matches = cli::Cli::command().get_matches();
+    let cli = cli::Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let format = cli.global.output_format;
+    let level = cli.global.output_level();
+    let command_path = cli
+        .command
+        .as_ref()
+        .map_or_else(|| "commit".to_string(), |c| c.report_path(&matches));
+
+    output::context::init(format, level);
+    init_tracing(level);
 
-    // Run migrations if needed
-    config::migrations::run_migrations()?;
+    // Run migrations if needed (`config migrate` reports and applies them itself)
+    let migrate_command = matches!(
+        &cli.command,
+        Some(cli::Commands::Config(cli::ConfigCommand {
+            action: cli::ConfigAction::Migrate { .. }
+        }))
+    );
+    if !migrate_command {
+        config::migrations::run_migrations()?;
+    }
 
-    // Check for updates
-    utils::version::check_is_latest_version().await?;
+    // Check for updates (skipped for machine-readable output)
+    if !format.is_structured() {
+        utils::version::check_is_latest_version().await?;
+    }
 
     // Execute the appropriate command
-    match cli.command {
-        Some(cli::Commands::Config(cmd)) => commands::config::execute(cmd).await,
-        Some(cli::Commands::Hook(cmd)) => commands::githook::execute(cmd).await,
-        Some(cli::Commands::CommitLint(cmd)) => commands::commitlint::execute(cmd).await,
-        Some(cli::Commands::Auth(cmd)) => commands::auth::execute(cmd).await,
+    let result = match cli.command {
+        Some(cli::Commands::Config(cmd)) => commands::config::execute(cmd, format).await,
+        Some(cli::Commands::Hook(cmd)) => commands::githook::execute(cmd, format).await,
+        Some(cli::Commands::CommitLint(cmd)) => commands::commitlint::execute(cmd, format).await,
+        Some(cli::Commands::Auth(cmd)) => commands::auth::execute(cmd, format).await,
         Some(cli::Commands::Mcp(cmd)) => commands::mcp::execute(cmd).await,
-        Some(cli::Commands::Update(cmd)) => commands::update::execute(cmd).await,
-        Some(cli::Commands::Pr(cmd)) => commands::pr::execute(cmd).await,
-        Some(cli::Commands::Model(cmd)) => commands::model::execute(cmd).await,
-        Some(cli::Commands::Setup(cmd)) => commands::setup::execute(cmd).await,
+        Some(cli::Commands::Update(cmd)) => commands::update::execute(cmd, format).await,
+        Some(cli::Commands::Pr(cmd)) => commands::pr::execute(cmd, format).await,
+        Some(cli::Commands::Model(cmd)) => commands::model::execute(cmd, format).await,
+        Some(cli::Commands::Setup(cmd)) => commands::setup::execute(cmd, format).await,
         Some(cli::Commands::Completions(cmd)) => commands::completions::execute(cmd).await,
-        Some(cli::Commands::Skills(cmd)) => commands::skills::execute(cmd).await,
+        Some(cli::Commands::Skills(cmd)) => commands::skills::execute(cmd, format).await,
+        Some(cli::Commands::Usage(cmd)) => commands::usage::execute(cmd, format).await,
+        Some(cli::Commands::Stats(cmd)) => commands::stats::execute(cmd, format).await,
+        Some(cli::Commands::Style(cmd)) => commands::style::execute(cmd, format).await,
+        Some(cli::Commands::Doctor(cmd)) => commands::doctor::execute(cmd, format).await,
+        Some(cli::Commands::Secrets(cmd)) => commands::secrets::execute(cmd, format).await,
+        Some(cli::Commands::Credential(cmd)) => commands::credential::execute(cmd).await,
         None => {
             // Default to commit command
             commands::commit::execute(cli.global).await
         }
+    };
+
+    // Report failures in the requested machine-readable format
+    if let Err(e) = &result {
+        if format.is_structured() {
+            let error = e.to_structured();
+            output::report::Report::error(&command_path, &error).emit(format);
+            std::process::exit(error.exit_code());
+        }
+        // Provider failures get their hints and exit code in text mode too
+        if providers::error::ProviderError::find(e).is_some() {
+            output::error::exit_with_error(&e.to_structured());
+        }
     }
+
+    result
+}
+
+/// Initialize tracing on stderr, filtered by verbosity unless `RUST_LOG` is set.
+fn init_tracing(level: OutputLevel) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(level.tracing_filter()));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
 }
@@ -0,0 +1,169 @@
+//! Process-wide output context.
+//!
+//! `main` initializes the context once from `--output-format` and `-q/-v/-vv`;
+//! commands print through it so verbosity and machine-readable output are
+//! honored everywhere without threading flags through every call.
+
+use std::fmt::Display;
+
+use colored::Colorize;
+use once_cell::sync::OnceCell;
+
+use super::prelude::{OutputFormat, OutputLevel};
+use super::report::Report;
+
+static CONTEXT: OnceCell<OutputContext> = OnceCell::new();
+
+/// Output settings shared by all commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputContext {
+    format: OutputFormat,
+    level: OutputLevel,
+}
+
+/// Install the process-wide output context. Later calls are ignored.
+pub fn init(format: OutputFormat, level: OutputLevel) -> &'static OutputContext {
+    CONTEXT.get_or_init(|| OutputContext::new(format, level))
+}
+
+/// The process-wide output context (pretty, normal verbosity if not initialized).
+pub fn get() -> &'static OutputContext {
+    CONTEXT.get_or_init(OutputContext::default)
+}
+
+impl OutputContext {
+    /// Create a context with the given format and verbosity.
+    pub fn new(format: OutputFormat, level: OutputLevel) -> Self {
+        Self { format, level }
+    }
+
+    /// The requested verbosity.
+    pub fn level(&self) -> OutputLevel {
+        self.level
+    }
+
+    /// Whether stdout is reserved for a structured report.
+    pub fn is_structured(&self) -> bool {
+        self.format.is_structured()
+    }
+
+    /// Whether regular human-readable output should be shown.
+    pub fn shows_normal(&self) -> bool {
+        !self.is_structured() && self.level != OutputLevel::Quiet
+    }
+
+    /// Whether progress spinners should be drawn.
+    pub fn shows_progress(&self) -> bool {
+        self.shows_normal()
+    }
+
+    /// Print a regular line (hidden in quiet and structured modes).
+    pub fn line(&self, text: impl Display) {
+        if self.shows_normal() {
+            println!("{}", text);
+        }
+    }
+
+    /// Print a command result that is the point of the command, e.g. a
+    /// config value. Shown even in quiet mode, but not in structured mode.
+    pub fn result(&self, text: impl Display) {
+        if !self.is_structured() {
+            println!("{}", text);
+        }
+    }
+
+    /// Print detail shown with `-v` and above.
+    pub fn verbose(&self, text: impl Display) {
+        if !self.is_structured() && self.level.is_verbose_or_higher() {
+            println!("{}", text.to_string().dimmed());
+        }
+    }
+
+    /// Print internal detail shown with `-vv`.
+    pub fn debug(&self, text: impl Display) {
+        if !self.is_structured() && self.level.is_debug() {
+            eprintln!("{} {}", "debug:".dimmed(), text);
+        }
+    }
+
+    /// Print a bold section header preceded by a blank line.
+    pub fn header(&self, text: &str) {
+        self.line(format!("\n{}", text.bold()));
+    }
+
+    /// Print dimmed secondary text.
+    pub fn subheader(&self, text: &str) {
+        self.line(text.dimmed());
+    }
+
+    /// Print a success message.
+    pub fn success(&self, message: &str) {
+        self.line(format!("✓ {}", message).green());
+    }
+
+    /// Print an informational message.
+    pub fn info(&self, message: &str) {
+        self.line(message.cyan());
+    }
+
+    /// Print a horizontal divider.
+    pub fn divider(&self) {
+        self.line("─".repeat(50).dimmed());
+    }
+
+    /// Print a title between two dividers.
+    pub fn section(&self, title: &str) {
+        self.divider();
+        self.line(title.cyan().bold());
+        self.divider();
+    }
+
+    /// Print an indented key-value pair.
+    pub fn key_value(&self, key: &str, value: &str) {
+        self.line(format!("  {}: {}", key.dimmed(), value));
+    }
+
+    /// Print a warning to stderr (hidden in quiet mode).
+    pub fn warning(&self, text: impl Display) {
+        if self.level != OutputLevel::Quiet {
+            eprintln!("{}", text);
+        }
+    }
+
+    /// Print an error to stderr. Always shown.
+    pub fn error(&self, text: impl Display) {
+        eprintln!("{}", text);
+    }
+
+    /// Emit a structured report (no-op in pretty mode).
+    pub fn emit(&self, report: &Report) {
+        report.emit(self.format);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hides_normal_output() {
+        let ctx = OutputContext::new(OutputFormat::Pretty, OutputLevel::Quiet);
+        assert!(!ctx.shows_normal());
+        assert!(!ctx.shows_progress());
+    }
+
+    #[test]
+    fn test_structured_hides_normal_output() {
+        let ctx = OutputContext::new(OutputFormat::Json, OutputLevel::Verbose);
+        assert!(ctx.is_structured());
+        assert!(!ctx.shows_normal());
+    }
+
+    #[test]
+    fn test_default_is_pretty_normal() {
+        let ctx = OutputContext::default();
+        assert!(!ctx.is_structured());
+        assert_eq!(ctx.level(), OutputLevel::Normal);
+        assert!(ctx.shows_normal());
+    }
+}
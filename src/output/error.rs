@@ -6,6 +6,7 @@ use anyhow::Result;
 use colored::Colorize;
 
 use super::styling::{Styling, Theme};
+use crate::providers::error::{ProviderError, RequestTarget};
 
 /// A structured error with context and hints.
 #[derive(Debug, Clone)]
@@ -59,7 +60,6 @@ impl StructuredError {
     }
 
     /// Add contextual information.
-    #[allow(dead_code)]
     pub fn with_context(mut self, key: &str, value: &str) -> Self {
         self.context.push((key.to_string(), value.to_string()));
         self
@@ -89,6 +89,11 @@ impl StructuredError {
         self.exit_code
     }
 
+    /// Get the hints.
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+
     /// Format the error for display.
     pub fn display(&self, _theme: &Theme) -> String {
         let mut output = String::new();
@@ -132,9 +137,8 @@ impl StructuredError {
         output
     }
 
-    /// Format as JSON.
-    #[allow(dead_code)]
-    pub fn to_json(&self) -> String {
+    /// Format as a JSON value.
+    pub fn to_value(&self) -> serde_json::Value {
         use serde_json::json;
 
         let hints_array: Vec<String> = self.hints.clone();
@@ -144,7 +148,7 @@ impl StructuredError {
             .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
             .collect();
 
-        let obj = json!({
+        json!({
             "error": self.message,
             "provider": self.provider,
             "model": self.model,
@@ -152,9 +156,13 @@ impl StructuredError {
             "context": context_obj,
             "hints": hints_array,
             "exit_code": self.exit_code,
-        });
+        })
+    }
 
-        serde_json::to_string_pretty(&obj).unwrap_or_else(|_| "{}".to_string())
+    /// Format as JSON.
+    #[allow(dead_code)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value()).unwrap_or_else(|_| "{}".to_string())
     }
 
     /// Format as markdown.
@@ -192,9 +200,14 @@ pub trait ToStructured {
     fn to_structured(&self) -> StructuredError;
 }
 
+/// Provider failures are reported through [`ProviderError::to_structured`],
+/// with the hints and exit code of their kind; anything else keeps exit code 1.
 impl ToStructured for anyhow::Error {
     fn to_structured(&self) -> StructuredError {
-        StructuredError::new(&self.to_string())
+        match ProviderError::find(self) {
+            Some(error) => error.to_structured(self.downcast_ref::<RequestTarget>()),
+            None => StructuredError::new(&self.to_string()),
+        }
     }
 }
 
@@ -308,7 +321,6 @@ pub mod patterns {
     }
 }
 
-#[allow(dead_code)]
 /// Print a structured error with the appropriate format.
 pub fn print_error(error: &StructuredError, theme: &Theme) {
     match theme.use_colors {
@@ -326,6 +338,9 @@ pub fn print_error(error: &StructuredError, theme: &Theme) {
             if let Some(ref underlying) = error.underlying {
                 eprintln!("Error: {}", underlying);
             }
+            for (key, value) in &error.context {
+                eprintln!("{}: {}", key, value);
+            }
             if !error.hints.is_empty() {
                 eprintln!("Suggestions:");
                 for hint in &error.hints {
@@ -336,7 +351,6 @@ pub fn print_error(error: &StructuredError, theme: &Theme) {
     }
 }
 
-#[allow(dead_code)]
 /// Exit with a structured error.
 pub fn exit_with_error(error: &StructuredError) -> ! {
     let theme = Theme::new();
@@ -427,6 +441,36 @@ mod tests {
         assert!(md.contains("## Suggestions"));
     }
 
+    #[test]
+    fn test_provider_errors_become_structured() {
+        let target = RequestTarget {
+            provider: "openai".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            account: Some("work".to_string()),
+        };
+        let error = anyhow::Error::new(ProviderError::classify(
+            401,
+            None,
+            "Invalid OpenAI API key".to_string(),
+        ))
+        .context(target)
+        .context("Failed to generate commit message");
+
+        let structured = error.to_structured();
+        assert_eq!(structured.message, "Authentication failed");
+        assert_eq!(structured.provider.as_deref(), Some("openai"));
+        assert_eq!(
+            structured.underlying.as_deref(),
+            Some("Invalid OpenAI API key")
+        );
+        assert_eq!(structured.exit_code(), 3);
+        assert!(structured.hints.iter().any(|h| h.contains("--alias work")));
+
+        let plain = anyhow::anyhow!("Not a git repository").to_structured();
+        assert_eq!(plain.message, "Not a git repository");
+        assert_eq!(plain.exit_code(), 1);
+    }
+
     #[test]
     fn test_structured_error_display() {
         let theme = Theme::new();
@@ -3,7 +3,9 @@
 //! Provides consistent styling, progress tracking, and error formatting
 //! across all commands.
 
+pub mod context;
 pub mod error;
 pub mod prelude;
 pub mod progress;
+pub mod report;
 pub mod styling;
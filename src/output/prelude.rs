@@ -3,7 +3,7 @@
 use clap::ValueEnum;
 
 /// Output format for commands.
-#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     /// Beautiful colored output (default).
     #[default]
@@ -14,9 +14,15 @@ pub enum OutputFormat {
     Markdown,
 }
 
+impl OutputFormat {
+    /// Whether this format is for machines (json/markdown) rather than humans.
+    pub fn is_structured(&self) -> bool {
+        !matches!(self, Self::Pretty)
+    }
+}
+
 /// Represents the verbosity level for output.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-#[allow(dead_code)]
 pub enum OutputLevel {
     /// Quiet mode - minimal output.
     Quiet,
@@ -29,8 +35,17 @@ pub enum OutputLevel {
     Debug,
 }
 
-#[allow(dead_code)]
 impl OutputLevel {
+    /// Level for `-q` and the number of `-v` flags.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            (false, _) => Self::Debug,
+        }
+    }
+
     pub fn is_verbose_or_higher(&self) -> bool {
         matches!(self, Self::Verbose | Self::Debug)
     }
@@ -38,4 +53,14 @@ impl OutputLevel {
     pub fn is_debug(&self) -> bool {
         matches!(self, Self::Debug)
     }
+
+    /// Default tracing filter for this level (`RUST_LOG` takes precedence).
+    pub fn tracing_filter(&self) -> &'static str {
+        match self {
+            Self::Quiet => "error",
+            Self::Normal => "warn",
+            Self::Verbose => "warn,rco=info,rusty_commit=info",
+            Self::Debug => "info,rco=debug,rusty_commit=debug",
+        }
+    }
 }
@@ -279,7 +279,13 @@ impl ProgressTracker {
 }
 
 /// Convenience function to create a simple spinner.
+///
+/// The spinner is hidden in quiet and structured output modes.
 pub fn spinner(message: &str) -> ProgressBar {
+    if !super::context::get().shows_progress() {
+        return ProgressBar::hidden();
+    }
+
     let pb = ProgressBar::new_spinner();
     pb.set_style(
         ProgressStyle::default_spinner()
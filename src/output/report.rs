@@ -0,0 +1,216 @@
+//! Versioned, machine-readable command reports.
+//!
+//! Every command emits a [`Report`] when `--output-format` is `json` or
+//! `markdown`, so editor plugins and scripts can consume rco without
+//! screen-scraping. The envelope is stable across commands; the `data`
+//! payload is command-specific.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::error::StructuredError;
+use super::prelude::OutputFormat;
+
+/// Version of the report schema. Bump when fields are removed or change meaning.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Envelope shared by all structured command output.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// Schema version of this report.
+    pub schema_version: u32,
+    /// The command that produced the report (e.g. "commit", "config get").
+    pub command: String,
+    /// Whether the command succeeded.
+    pub success: bool,
+    /// Command-specific payload.
+    pub data: Value,
+    /// Error details when `success` is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+impl Report {
+    /// Create a successful report with a serializable payload.
+    pub fn ok<T: Serialize>(command: &str, data: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            command: command.to_string(),
+            success: true,
+            data: serde_json::to_value(data).unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+
+    /// Create a failed report from a structured error.
+    pub fn error(command: &str, error: &StructuredError) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            command: command.to_string(),
+            success: false,
+            data: Value::Null,
+            error: Some(error.to_value()),
+        }
+    }
+
+    /// Render as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Render as markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut output = format!("# rco {}\n\n", self.command);
+        output.push_str(&format!("- **schema_version:** {}\n", self.schema_version));
+        output.push_str(&format!("- **success:** {}\n", self.success));
+
+        if let Some(Value::Object(error)) = &self.error {
+            output.push_str("\n## Error\n\n");
+            render_object(&mut output, error, 3);
+        }
+
+        match &self.data {
+            Value::Null => {}
+            Value::Object(map) if map.is_empty() => {}
+            Value::Object(map) => {
+                output.push('\n');
+                render_object(&mut output, map, 2);
+            }
+            other => {
+                output.push('\n');
+                render_value(&mut output, other);
+            }
+        }
+
+        output
+    }
+
+    /// Render in the given format. Returns `None` for pretty output, which
+    /// commands render themselves.
+    pub fn render(&self, format: OutputFormat) -> Option<String> {
+        match format {
+            OutputFormat::Pretty => None,
+            OutputFormat::Json => Some(self.to_json()),
+            OutputFormat::Markdown => Some(self.to_markdown()),
+        }
+    }
+
+    /// Print the report to stdout in the given format (no-op for pretty).
+    pub fn emit(&self, format: OutputFormat) {
+        if let Some(rendered) = self.render(format) {
+            println!("{}", rendered.trim_end());
+        }
+    }
+}
+
+/// Render an object's fields: scalars as a bullet list, nested values as sections.
+fn render_object(output: &mut String, map: &serde_json::Map<String, Value>, level: usize) {
+    let heading = "#".repeat(level.min(6));
+    let (scalars, nested): (Vec<_>, Vec<_>) = map.iter().partition(|(_, v)| is_inline(v));
+
+    for (key, value) in &scalars {
+        output.push_str(&format!("- **{}:** {}\n", key, inline(value)));
+    }
+
+    for (key, value) in nested {
+        output.push_str(&format!("\n{} {}\n\n", heading, key));
+        match value {
+            Value::Object(inner) => render_object(output, inner, level + 1),
+            other => render_value(output, other),
+        }
+    }
+}
+
+/// Render a non-object value as a block.
+fn render_value(output: &mut String, value: &Value) {
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    Value::String(s) if s.contains('\n') => {
+                        output.push_str(&format!("{}.\n\n```text\n{}\n```\n\n", i + 1, s));
+                    }
+                    Value::Object(inner) => {
+                        let fields: Vec<String> = inner
+                            .iter()
+                            .map(|(k, v)| format!("{}: {}", k, inline(v)))
+                            .collect();
+                        output.push_str(&format!("{}. {}\n", i + 1, fields.join(", ")));
+                    }
+                    other => output.push_str(&format!("{}. {}\n", i + 1, inline(other))),
+                }
+            }
+        }
+        Value::String(s) => output.push_str(&format!("```text\n{}\n```\n", s)),
+        other => output.push_str(&format!("{}\n", inline(other))),
+    }
+}
+
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::String(s) => !s.contains('\n'),
+        Value::Array(_) | Value::Object(_) => false,
+        _ => true,
+    }
+}
+
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_report_ok_envelope() {
+        let report = Report::ok("config get", json!({"key": "RCO_MODEL", "value": "gpt-4o"}));
+        let parsed: Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(parsed["schema_version"], SCHEMA_VERSION);
+        assert_eq!(parsed["command"], "config get");
+        assert_eq!(parsed["success"], true);
+        assert_eq!(parsed["data"]["value"], "gpt-4o");
+        assert!(parsed.get("error").is_none());
+    }
+
+    #[test]
+    fn test_report_error_envelope() {
+        let error = StructuredError::new("No changes to commit").with_exit_code(2);
+        let report = Report::error("commit", &error);
+        let parsed: Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(parsed["success"], false);
+        assert_eq!(parsed["error"]["error"], "No changes to commit");
+        assert_eq!(parsed["error"]["exit_code"], 2);
+    }
+
+    #[test]
+    fn test_report_markdown() {
+        let report = Report::ok(
+            "commit",
+            json!({
+                "provider": "openai",
+                "messages": ["feat: add login\n\nAdds the login page"],
+                "timing": {"total_ms": 12},
+            }),
+        );
+        let md = report.to_markdown();
+        assert!(md.starts_with("# rco commit"));
+        assert!(md.contains("- **provider:** openai"));
+        assert!(md.contains("## messages"));
+        assert!(md.contains("```text\nfeat: add login"));
+        assert!(md.contains("## timing"));
+        assert!(md.contains("- **total_ms:** 12"));
+    }
+
+    #[test]
+    fn test_report_pretty_renders_nothing() {
+        let report = Report::ok("commit", json!({}));
+        assert!(report.render(OutputFormat::Pretty).is_none());
+        assert!(report.render(OutputFormat::Json).is_some());
+    }
+}
@@ -2,17 +2,27 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
+use crate::auth::credentials::{CredentialProvider, Issuer};
 use crate::config::accounts::AccountConfig;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
+
+const DEFAULT_API_URL: &str = "https://api.anthropic.com";
 
 pub struct AnthropicProvider {
     client: Client,
-    api_key: String,
+    credentials: CredentialProvider,
+    api_url: String,
     model: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -21,6 +31,7 @@ struct AnthropicRequest {
     messages: Vec<Message>,
     max_tokens: u32,
     temperature: f32,
+    stream: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -32,56 +43,85 @@ struct Message {
 #[derive(Deserialize)]
 struct AnthropicResponse {
     content: Vec<Content>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
 }
 
+/// A content block; only `text` blocks are part of the answer, `thinking`
+/// blocks from extended thinking are left out.
 #[derive(Deserialize)]
 struct Content {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
     text: String,
 }
 
 impl AnthropicProvider {
     pub fn new(config: &Config) -> Result<Self> {
         // Try OAuth token first, then fall back to API key
-        let api_key = if let Some(token) = crate::auth::token_storage::get_access_token()? {
-            token
-        } else {
-            config
-                .api_key
-                .as_ref()
-                .context(
-                    "Not authenticated with Claude.\nRun: rco auth login (for OAuth)\nOr: rco config set RCO_API_KEY=<your_key>\nGet your API key from: https://console.anthropic.com/settings/keys",
-                )?
-                .clone()
+        let credentials = match CredentialProvider::stored_login(Issuer::Claude, config)? {
+            Some(credentials) => credentials,
+            None => CredentialProvider::fixed(
+                config
+                    .api_key
+                    .as_ref()
+                    .context(
+                        "Not authenticated with Claude.\nRun: rco auth login (for OAuth)\nOr: rco config set RCO_API_KEY=<your_key>\nGet your API key from: https://console.anthropic.com/settings/keys",
+                    )?
+                    .clone(),
+            ),
         };
 
-        let client = Client::new();
+        let client = http::client(config)?;
+        let api_url = config
+            .api_url
+            .as_deref()
+            .unwrap_or(DEFAULT_API_URL)
+            .trim_end_matches('/')
+            .to_string();
         let model = config.model.clone();
 
         Ok(Self {
             client,
-            api_key,
+            credentials,
+            api_url,
             model,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
     /// Create provider from account configuration
     #[allow(dead_code)]
-    pub fn from_account(account: &AccountConfig, _api_key: &str, config: &Config) -> Result<Self> {
-        let client = Client::new();
+    pub fn from_account(account: &AccountConfig, api_key: &str, config: &Config) -> Result<Self> {
+        let client = http::account_client(account, config)?;
+        let api_url = account
+            .api_url
+            .as_deref()
+            .or(config.api_url.as_deref())
+            .unwrap_or(DEFAULT_API_URL)
+            .trim_end_matches('/')
+            .to_string();
         let model = account
             .model
             .as_deref()
             .unwrap_or(&config.model)
             .to_string();
 
-        // For accounts, we'll use the api_key from the function parameter
-        // In a full implementation, this would extract from the account's auth method
-        let api_key = _api_key.to_string();
-
         Ok(Self {
             client,
-            api_key,
+            credentials: CredentialProvider::for_account(account, api_key, config)?,
+            api_url,
             model,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -111,62 +151,112 @@ impl AIProvider for AnthropicProvider {
             ],
             max_tokens: config.tokens_max_output,
             temperature: 0.7,
+            stream: config.stream,
         };
 
-        let anthropic_response: AnthropicResponse = retry_async(|| async {
+        let client = &self.client;
+        let oauth = self.credentials.is_oauth();
+        let url = format!("{}/v1/messages", self.api_url);
+        let (url, request) = (&url, &request);
+        let anthropic_response: AnthropicResponse = retry_async(self.retry, || self.credentials.with_token(|token| async move {
+            payload_log::request("anthropic", url, request);
+
             // Build request with appropriate auth header
-            let mut req = self
-                .client
-                .post("https://api.anthropic.com/v1/messages");
-
-            // Check if this is an OAuth token (starts with "ey") or API key (starts with "sk-")
-            if self.api_key.starts_with("ey") {
-                // OAuth token - use Authorization header
-                req = req.header(header::AUTHORIZATION, format!("Bearer {}", &self.api_key));
+            let mut req = client.post(url);
+
+            // OAuth access tokens use the Authorization header, API keys use x-api-key
+            if oauth || token.starts_with("ey") {
+                req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
             } else {
-                // API key - use x-api-key header
-                req = req.header("x-api-key", &self.api_key);
+                req = req.header("x-api-key", &token);
             }
 
             let response = req
                 .header("anthropic-version", "2023-06-01")
                 .header(header::CONTENT_TYPE, "application/json")
-                .json(&request)
+                .json(request)
                 .send()
                 .await
-                .context("Failed to connect to Anthropic")?;
+                .map_err(|e| ProviderError::network("Failed to connect to Anthropic", e))?;
 
             if !response.status().is_success() {
-                let status = response.status();
-                let error_text = response.text().await?;
-
-                if status.as_u16() == 401 {
-                    return Err(anyhow::anyhow!("Invalid Anthropic API key. Please check your API key configuration."));
-                } else if status.as_u16() == 403 {
-                    return Err(anyhow::anyhow!("Access forbidden. Please check your Anthropic API permissions."));
-                } else {
-                    return Err(anyhow::anyhow!("Anthropic API error ({}): {}", status, error_text));
+                let status = response.status().as_u16();
+                let error = ProviderError::from_response("anthropic", response).await?;
+
+                return Err(match status {
+                    401 => error.map_message(|_| "Invalid Anthropic API key. Please check your API key configuration.".to_string()),
+                    403 => error.map_message(|_| "Access forbidden. Please check your Anthropic API permissions.".to_string()),
+                    _ => error.map_message(|body| format!("Anthropic API error ({}): {}", status, body)),
                 }
+                .into());
             }
 
-            let anthropic_response: AnthropicResponse = response
-                .json()
+            let anthropic_response: AnthropicResponse = streaming::json_response("anthropic", response, fold_stream)
                 .await
-                .context("Failed to parse Anthropic response")?;
+                .map_err(|e| ProviderError::invalid_response(format!("Failed to parse Anthropic response: {:#}", e)))?;
 
             Ok(anthropic_response)
-        }).await.context("Failed to generate commit message from Anthropic after retries. Please check your internet connection and API configuration.")?;
+        })).await.context("Failed to generate commit message from Anthropic after retries. Please check your internet connection and API configuration.")?;
+
+        if let Some(u) = &anthropic_response.usage {
+            usage::record(
+                config,
+                &self.model,
+                TokenUsage::new(u.input_tokens, u.output_tokens),
+            );
+        }
 
         let message = anthropic_response
             .content
-            .first()
+            .iter()
+            .find(|c| c.kind == "text" || c.kind.is_empty())
             .map(|c| c.text.trim().to_string())
-            .context("Anthropic returned an empty response. The model may be overloaded - please try again.")?;
+            .ok_or_else(|| ProviderError::invalid_response("Anthropic returned an empty response. The model may be overloaded - please try again."))?;
 
         Ok(message)
     }
 }
 
+/// Fold the events of a streamed message back into the message: the text and
+/// thinking deltas of each content block, the stop reason and the usage.
+fn fold_stream(events: Vec<Value>) -> Result<Value> {
+    if let Some(error) = streaming::stream_error(&events) {
+        anyhow::bail!("The response stream reported an error: {}", error);
+    }
+
+    let mut message = serde_json::json!({});
+    let mut blocks: Vec<Value> = Vec::new();
+    for event in events {
+        match event.get("type").and_then(Value::as_str) {
+            Some("message_start") => message = event["message"].clone(),
+            Some("content_block_start") => blocks.push(event["content_block"].clone()),
+            Some("content_block_delta") => {
+                let index = event.get("index").and_then(Value::as_u64).unwrap_or(0);
+                let Some(block) = blocks.get_mut(index as usize) else {
+                    continue;
+                };
+                for key in ["text", "thinking"] {
+                    if let Some(piece) = event["delta"].get(key).and_then(Value::as_str) {
+                        let text = block.get(key).and_then(Value::as_str).unwrap_or_default();
+                        block[key] = Value::from(format!("{}{}", text, piece));
+                    }
+                }
+            }
+            Some("message_delta") => {
+                if let Some(reason) = event.pointer("/delta/stop_reason") {
+                    message["stop_reason"] = reason.clone();
+                }
+                if let Some(tokens) = event.pointer("/usage/output_tokens") {
+                    message["usage"]["output_tokens"] = tokens.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+    message["content"] = Value::Array(blocks);
+    Ok(message)
+}
+
 /// ProviderBuilder for Anthropic
 pub struct AnthropicProviderBuilder;
 
@@ -3,15 +3,21 @@ use async_trait::async_trait;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct AzureProvider {
     client: Client,
     api_key: String,
     endpoint: String,
     deployment: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -19,6 +25,7 @@ struct AzureRequest {
     messages: Vec<Message>,
     max_tokens: u32,
     temperature: f32,
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -30,6 +37,8 @@ struct Message {
 #[derive(Deserialize)]
 struct AzureResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Deserialize)]
@@ -60,13 +69,14 @@ impl AzureProvider {
 
         let deployment = config.model.clone();
 
-        let client = Client::new();
+        let client = http::client(config)?;
 
         Ok(Self {
             client,
             api_key,
             endpoint,
             deployment,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -91,13 +101,14 @@ impl AzureProvider {
             .unwrap_or(&config.model)
             .to_string();
 
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
 
         Ok(Self {
             client,
             api_key: api_key.to_string(),
             endpoint,
             deployment,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -126,6 +137,7 @@ impl AIProvider for AzureProvider {
             ],
             max_tokens: config.tokens_max_output,
             temperature: 0.7,
+            stream: config.stream,
         };
 
         let url = format!(
@@ -133,31 +145,47 @@ impl AIProvider for AzureProvider {
             self.endpoint, self.deployment
         );
 
-        let response = self
-            .client
-            .post(&url)
-            .header("api-key", &self.api_key)
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to connect to Azure OpenAI")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            anyhow::bail!("Azure OpenAI API error: {}", error_text);
-        }
+        payload_log::request("azure", &url, &request);
+
+        let azure_response: AzureResponse = retry_async(self.retry, || async {
+            let response = self
+                .client
+                .post(&url)
+                .header("api-key", &self.api_key)
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to Azure OpenAI", e))?;
+
+            if !response.status().is_success() {
+                let error = ProviderError::from_response("azure", response).await?;
+                return Err(error
+                    .map_message(|body| format!("Azure OpenAI API error: {}", body))
+                    .into());
+            }
+
+            streaming::json_response("azure", response, streaming::chat_completion)
+                .await
+                .map_err(|e| {
+                    ProviderError::invalid_response(format!(
+                        "Failed to parse Azure OpenAI response: {:#}",
+                        e
+                    ))
+                    .into()
+                })
+        })
+        .await?;
 
-        let azure_response: AzureResponse = response
-            .json()
-            .await
-            .context("Failed to parse Azure OpenAI response")?;
+        if let Some(token_usage) = azure_response.usage {
+            usage::record(config, &self.deployment, token_usage);
+        }
 
         let message = azure_response
             .choices
             .first()
             .map(|c| c.message.content.trim().to_string())
-            .context("No response from Azure OpenAI")?;
+            .ok_or_else(|| ProviderError::invalid_response("No response from Azure OpenAI"))?;
 
         Ok(message)
     }
@@ -1,18 +1,33 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use aws_config::retry::RetryConfig;
 use aws_config::Region;
 use aws_sdk_bedrockruntime as bedrock;
+use aws_sdk_bedrockruntime::error::{DisplayErrorContext, SdkError};
+use aws_sdk_bedrockruntime::operation::converse::ConverseError;
 use aws_sdk_bedrockruntime::types::{ContentBlock, SystemContentBlock};
+use aws_smithy_runtime_api::client::http::{
+    http_client_fn, HttpConnector, HttpConnectorFuture, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::result::ConnectorError;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
 use super::AIProvider;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct BedrockProvider {
     client: bedrock::Client,
     model: String,
     #[allow(dead_code)]
     region: String,
+    retry: RetryPolicy,
 }
 
 impl BedrockProvider {
@@ -24,35 +39,21 @@ impl BedrockProvider {
     async fn new_async(config: &Config) -> Result<Self> {
         let region = config
             .api_url
-            .as_ref()
-            .and_then(|url| {
-                url.split("bedrock.")
-                    .nth(1)
-                    .and_then(|s| s.split('.').next())
-                    .map(|s| s.to_string())
-            })
+            .as_deref()
+            .and_then(region_from_url)
             .unwrap_or_else(|| {
                 std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string())
             });
 
-        let region_provider = Region::new(region.clone());
-        let shared_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(region_provider)
-            .load()
-            .await;
-
-        let client = bedrock::Client::new(&shared_config);
+        let client = sdk_client(http::client(config)?, &region, config.api_url.as_deref()).await;
 
-        let model = config
-            .model
-            .as_deref()
-            .unwrap_or("anthropic.claude-3-5-sonnet-20241022-v2:0")
-            .to_string();
+        let model = config.model.clone();
 
         Ok(Self {
             client,
             model,
             region,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -72,34 +73,110 @@ impl BedrockProvider {
     ) -> Result<Self> {
         let region = account
             .api_url
-            .as_ref()
-            .and_then(|url| {
-                url.split("bedrock.")
-                    .nth(1)
-                    .and_then(|s| s.split('.').next())
-                    .map(|s| s.to_string())
-            })
+            .as_deref()
+            .and_then(region_from_url)
             .unwrap_or_else(|| "us-east-1".to_string());
 
-        let region_provider = Region::new(region.clone());
-        let shared_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(region_provider)
-            .load()
-            .await;
-
-        let client = bedrock::Client::new(&shared_config);
+        let client = sdk_client(
+            http::account_client(account, config)?,
+            &region,
+            account.api_url.as_deref(),
+        )
+        .await;
 
         let model = account
             .model
             .as_deref()
-            .or(config.model.as_deref())
-            .unwrap_or("anthropic.claude-3-5-sonnet-20241022-v2:0")
+            .unwrap_or(&config.model)
             .to_string();
 
         Ok(Self {
             client,
             model,
             region,
+            retry: RetryPolicy::for_account(account, config),
+        })
+    }
+}
+
+/// An SDK client that sends its requests with `client`. The SDK's own retries
+/// are off; the provider retries with the configured policy like the others.
+async fn sdk_client(
+    client: reqwest::Client,
+    region: &str,
+    api_url: Option<&str>,
+) -> bedrock::Client {
+    let connector = ReqwestConnector(client);
+    let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(Region::new(region.to_string()))
+        .http_client(http_client_fn(move |_, _| {
+            SharedHttpConnector::new(connector.clone())
+        }))
+        .retry_config(RetryConfig::disabled());
+    // A URL outside AWS is a proxy or gateway in front of Bedrock
+    if let Some(url) = api_url.filter(|url| !url.contains(".amazonaws.com")) {
+        loader = loader.endpoint_url(url.trim_end_matches('/'));
+    }
+    bedrock::Client::new(&loader.load().await)
+}
+
+/// The region in an AWS URL such as `https://bedrock.eu-west-1.amazonaws.com`.
+fn region_from_url(url: &str) -> Option<String> {
+    url.split("bedrock.")
+        .nth(1)
+        .and_then(|s| s.split('.').next())
+        .map(|s| s.to_string())
+}
+
+/// Sends the SDK's signed requests with reqwest, so Bedrock gets the same
+/// proxy, TLS and timeout settings as the other providers. The build has no
+/// HTTP client of the SDK's own.
+#[derive(Debug, Clone)]
+struct ReqwestConnector(reqwest::Client);
+
+impl HttpConnector for ReqwestConnector {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let client = self.0.clone();
+        HttpConnectorFuture::new(async move {
+            let method = reqwest::Method::from_bytes(request.method().as_bytes())
+                .map_err(|e| ConnectorError::user(e.into()))?;
+            let body = request.body().bytes().unwrap_or_default().to_vec();
+            if payload_log::is_enabled() {
+                let logged = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                payload_log::request("bedrock", request.uri(), &logged);
+            }
+
+            let mut builder = client.request(method, request.uri());
+            for (name, value) in request.headers() {
+                builder = builder.header(name, value);
+            }
+            let response = builder.body(body).send().await.map_err(|e| {
+                if e.is_timeout() {
+                    ConnectorError::timeout(e.into())
+                } else {
+                    ConnectorError::io(e.into())
+                }
+            })?;
+
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = response
+                .bytes()
+                .await
+                .map_err(|e| ConnectorError::io(e.into()))?;
+            payload_log::response("bedrock", status, &String::from_utf8_lossy(&body));
+
+            let status =
+                StatusCode::try_from(status).map_err(|e| ConnectorError::other(e.into(), None))?;
+            let mut result = HttpResponse::new(status, SdkBody::from(body));
+            for (name, value) in &headers {
+                if let Ok(value) = value.to_str() {
+                    result
+                        .headers_mut()
+                        .append(name.as_str().to_string(), value.to_string());
+                }
+            }
+            Ok(result)
         })
     }
 }
@@ -127,36 +204,60 @@ impl AIProvider for BedrockProvider {
             .context("Failed to build user message")?;
 
         let inference_config = bedrock::types::InferenceConfiguration::builder()
-            .max_tokens(config.tokens_max_output.unwrap_or(500) as i32)
+            .max_tokens(config.tokens_max_output as i32)
             .temperature(0.7)
             .build();
 
-        let converse_output = self
-            .client
-            .converse()
-            .model_id(&self.model)
-            .messages(user_message)
-            .system(system_block)
-            .inference_config(inference_config)
-            .send()
-            .await
-            .context("Failed to communicate with Bedrock")?;
+        let converse_output = retry_async(self.retry, || async {
+            self.client
+                .converse()
+                .model_id(&self.model)
+                .messages(user_message.clone())
+                .system(system_block.clone())
+                .inference_config(inference_config.clone())
+                .send()
+                .await
+                .map_err(converse_error)
+        })
+        .await?;
 
         let message = converse_output
             .output()
             .and_then(|o| o.as_message().ok())
-            .context("No response from Bedrock")?;
+            .ok_or_else(|| ProviderError::invalid_response("No response from Bedrock"))?;
 
+        // Reasoning models put a reasoning block before the text
         let content = message
             .content()
-            .first()
-            .and_then(|c| c.as_text().ok())
-            .context("Empty response from Bedrock")?;
+            .iter()
+            .find_map(|c| c.as_text().ok())
+            .ok_or_else(|| ProviderError::invalid_response("Empty response from Bedrock"))?;
 
         Ok(content.trim().to_string())
     }
 }
 
+/// Classify a failed Converse call like the HTTP providers' errors. Calls
+/// that never got a response keep the SDK error.
+fn converse_error(error: SdkError<ConverseError>) -> anyhow::Error {
+    match error
+        .raw_response()
+        .map(|response| response.status().as_u16())
+    {
+        // A success the SDK could not read
+        Some(200..=299) => ProviderError::invalid_response(format!(
+            "Failed to parse Bedrock response: {}",
+            DisplayErrorContext(&error)
+        ))
+        .into(),
+        Some(status) => {
+            let message = format!("Bedrock API error: {}", DisplayErrorContext(&error));
+            ProviderError::classify(status, None, message).into()
+        }
+        None => anyhow::Error::new(error).context("Failed to communicate with Bedrock"),
+    }
+}
+
 /// ProviderBuilder for Bedrock
 pub struct BedrockProviderBuilder;
 
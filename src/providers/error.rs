@@ -0,0 +1,554 @@
+//! Typed errors returned by providers.
+//!
+//! Providers turn failed responses into a [`ProviderError`] with
+//! [`ProviderError::from_response`], which reads the status, `Retry-After`
+//! and body. The variant decides whether the request is retried (see
+//! `utils::retry`) and how the failure is reported:
+//! [`ProviderError::to_structured`] picks the message, hints for the provider
+//! and a distinct exit code.
+
+use std::fmt;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::output::error::StructuredError;
+use crate::utils::payload_log;
+use crate::utils::retry::retry_after;
+
+/// Error bodies that mean the prompt did not fit the context window.
+const CONTEXT_LENGTH_MARKERS: &[&str] = &[
+    "context_length_exceeded",
+    "maximum context length",
+    "context window",
+    "prompt is too long",
+    "exceeds the maximum number of tokens",
+    "input is too long",
+];
+
+/// Error bodies that mean a safety system blocked the request.
+const CONTENT_FILTER_MARKERS: &[&str] = &[
+    "content_filter",
+    "content_policy",
+    "content management policy",
+];
+
+/// Error bodies of a 429 that waiting will not fix.
+const QUOTA_MARKERS: &[&str] = &["insufficient_quota", "billing", "credit balance"];
+
+/// Bodies of 400 responses that are really a rejected key (Google APIs).
+const INVALID_KEY_MARKERS: &[&str] = &["api_key_invalid", "api key not valid"];
+
+/// Where providers state the context window in their error text.
+static CONTEXT_LIMIT_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        // OpenAI: "This model's maximum context length is 8192 tokens"
+        r"maximum context length is (\d+)",
+        // Anthropic: "prompt is too long: 210000 tokens > 200000 maximum"
+        r">\s*(\d+) maximum",
+        // Gemini: "exceeds the maximum number of tokens allowed (1048576)"
+        r"maximum number of tokens allowed \((\d+)\)",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("valid context limit pattern"))
+    .collect()
+});
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProviderError {
+    /// The API key or token was rejected (401/403).
+    #[error("{message}")]
+    Auth { message: String },
+    /// Too many requests (429).
+    #[error("{message}")]
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// The account has no quota or credit left; waiting does not help.
+    #[error("{message}")]
+    QuotaExceeded { message: String },
+    /// The prompt does not fit the model's context window. `limit` is the
+    /// window in tokens, when the provider says.
+    #[error("{message}")]
+    ContextLengthExceeded {
+        limit: Option<usize>,
+        message: String,
+    },
+    #[error("{message}")]
+    ModelNotFound { message: String },
+    /// The provider's safety system blocked the prompt or the answer.
+    #[error("{message}")]
+    ContentFiltered { message: String },
+    /// No response at all: DNS, connection or timeout.
+    #[error("{message}")]
+    Network {
+        message: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    /// The provider failed (5xx) or timed out waiting for the request (408).
+    #[error("{message}")]
+    Server {
+        status: u16,
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    /// A response that could not be understood.
+    #[error("{message}")]
+    InvalidResponse { message: String },
+    /// Any other rejected request (4xx).
+    #[error("{message}")]
+    Rejected { status: u16, message: String },
+}
+
+impl ProviderError {
+    /// Read a failed response into the matching variant. The message is the
+    /// response body until replaced with [`Self::map_message`].
+    pub async fn from_response(
+        provider: &str,
+        response: reqwest::Response,
+    ) -> anyhow::Result<Self> {
+        let status = response.status().as_u16();
+        let retry_after = retry_after(response.headers());
+        let body = payload_log::error_text(provider, response).await?;
+        Ok(Self::classify(status, retry_after, body))
+    }
+
+    /// Pick the variant for a failed response from its status and body.
+    pub fn classify(status: u16, retry_after: Option<Duration>, body: String) -> Self {
+        let lower = body.to_lowercase();
+        let mentions = |markers: &[&str]| markers.iter().any(|m| lower.contains(m));
+        let message = body;
+
+        if status == 413 || mentions(CONTEXT_LENGTH_MARKERS) {
+            return Self::ContextLengthExceeded {
+                limit: context_limit(&message),
+                message,
+            };
+        }
+        if mentions(CONTENT_FILTER_MARKERS) {
+            return Self::ContentFiltered { message };
+        }
+        match status {
+            401 | 403 => Self::Auth { message },
+            400 if mentions(INVALID_KEY_MARKERS) => Self::Auth { message },
+            402 => Self::QuotaExceeded { message },
+            400 | 429 if mentions(QUOTA_MARKERS) => Self::QuotaExceeded { message },
+            429 => Self::RateLimited {
+                retry_after,
+                message,
+            },
+            404 if lower.contains("model") => Self::ModelNotFound { message },
+            _ if lower.contains("model_not_found") => Self::ModelNotFound { message },
+            408 | 500..=599 => Self::Server {
+                status,
+                retry_after,
+                message,
+            },
+            _ => Self::Rejected { status, message },
+        }
+    }
+
+    /// The provider error behind an error, if there is one in its chain.
+    pub fn find(error: &anyhow::Error) -> Option<&Self> {
+        error.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+
+    pub fn network(message: impl Into<String>, source: reqwest::Error) -> Self {
+        Self::Network {
+            message: message.into(),
+            source,
+        }
+    }
+
+    pub fn invalid_response(message: impl Into<String>) -> Self {
+        Self::InvalidResponse {
+            message: message.into(),
+        }
+    }
+
+    pub fn content_filtered(message: impl Into<String>) -> Self {
+        Self::ContentFiltered {
+            message: message.into(),
+        }
+    }
+
+    /// Replace the message, e.g. to put the provider name in front of the body.
+    pub fn map_message(mut self, f: impl FnOnce(&str) -> String) -> Self {
+        let message = self.message_mut();
+        *message = f(message);
+        self
+    }
+
+    fn message_mut(&mut self) -> &mut String {
+        match self {
+            Self::Auth { message }
+            | Self::RateLimited { message, .. }
+            | Self::QuotaExceeded { message }
+            | Self::ContextLengthExceeded { message, .. }
+            | Self::ModelNotFound { message }
+            | Self::ContentFiltered { message }
+            | Self::Network { message, .. }
+            | Self::Server { message, .. }
+            | Self::InvalidResponse { message }
+            | Self::Rejected { message, .. } => message,
+        }
+    }
+
+    /// Rate limits, server errors and requests that never got an answer are
+    /// worth another attempt.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimited { .. } | Self::Server { .. } => true,
+            Self::Network { source, .. } => !source.is_builder(),
+            _ => false,
+        }
+    }
+
+    /// How long the server asked us to wait before trying again.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } | Self::Server { retry_after, .. } => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+
+    /// Process exit code, distinct per kind of failure.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Auth { .. } => 3,
+            Self::RateLimited { .. } => 4,
+            Self::QuotaExceeded { .. } => 5,
+            Self::ContextLengthExceeded { .. } => 6,
+            Self::ModelNotFound { .. } => 7,
+            Self::ContentFiltered { .. } => 8,
+            Self::Network { .. } => 9,
+            Self::Server { .. } => 10,
+            Self::InvalidResponse { .. } => 11,
+            Self::Rejected { .. } => 12,
+        }
+    }
+
+    /// Report this error with hints for the provider it came from.
+    pub fn to_structured(&self, target: Option<&RequestTarget>) -> StructuredError {
+        let provider = target.map_or("", |t| t.provider.as_str());
+        let model = target.map_or("", |t| t.model.as_str());
+        let account = target.and_then(|t| t.account.as_deref());
+
+        let (message, hints) = match self {
+            Self::Auth { .. } => (
+                "Authentication failed".to_string(),
+                auth_hints(provider, account),
+            ),
+            Self::RateLimited { retry_after, .. } => {
+                let mut hints = Vec::new();
+                if let Some(delay) = retry_after {
+                    hints.push(format!(
+                        "The provider asked to wait {}s before the next request",
+                        delay.as_secs().max(1)
+                    ));
+                }
+                hints.push(
+                    "Raise RCO_RETRY_COUNT or RCO_RETRY_MAX_ELAPSED_SECS to keep retrying longer"
+                        .to_string(),
+                );
+                hints.push("Generate fewer variations with RCO_GENERATE_COUNT=1".to_string());
+                ("API rate limit exceeded".to_string(), hints)
+            }
+            Self::QuotaExceeded { .. } => {
+                let mut hints = Vec::new();
+                match family(provider) {
+                    Family::OpenAI => hints.push(
+                        "Check billing at https://platform.openai.com/settings/organization/billing"
+                            .to_string(),
+                    ),
+                    Family::Anthropic => hints.push(
+                        "Check billing at https://console.anthropic.com/settings/billing"
+                            .to_string(),
+                    ),
+                    _ => hints.push("Check the plan and billing of your account".to_string()),
+                }
+                hints.push("Switch to another account with 'rco config use-account'".to_string());
+                ("API quota exceeded".to_string(), hints)
+            }
+            Self::ContextLengthExceeded { limit, .. } => {
+                let mut hints = vec![match limit {
+                    Some(limit) => format!(
+                        "Set RCO_TOKENS_MAX_INPUT below the model's limit of {} tokens",
+                        limit
+                    ),
+                    None => "Lower RCO_TOKENS_MAX_INPUT so large diffs are split".to_string(),
+                }];
+                hints.push(
+                    "Stage fewer files, or exclude generated files in .rcoignore".to_string(),
+                );
+                hints.push("Use a model with a larger context window".to_string());
+                (
+                    "Prompt exceeds the model's context window".to_string(),
+                    hints,
+                )
+            }
+            Self::ModelNotFound { .. } => {
+                let mut hints = Vec::new();
+                if provider.eq_ignore_ascii_case("ollama") {
+                    hints.push(format!("Pull it first: ollama pull {}", model));
+                }
+                hints.push("Run 'rco model --list' to see available models".to_string());
+                hints.push("Choose another with: rco config set RCO_MODEL=<model>".to_string());
+                (format!("Model not found: {}", model), hints)
+            }
+            Self::ContentFiltered { .. } => (
+                "Blocked by the provider's content filter".to_string(),
+                vec![
+                    "Exclude the files that triggered it in .rcoignore".to_string(),
+                    "Try again, or use another model".to_string(),
+                ],
+            ),
+            Self::Network { source, .. } => {
+                let mut hints = Vec::new();
+                if family(provider) == Family::Local {
+                    hints.push(format!("Check that the {} server is running", provider));
+                } else {
+                    hints.push("Check your internet connection".to_string());
+                }
+                hints
+                    .push("Check RCO_API_URL and any proxy in the [http] config table".to_string());
+                if source.is_timeout() {
+                    hints.push("Raise RCO_REQUEST_TIMEOUT_SECS for slow models".to_string());
+                }
+                ("Network error".to_string(), hints)
+            }
+            Self::Server { status, .. } => {
+                let mut hints = vec!["Try again in a few minutes".to_string()];
+                match family(provider) {
+                    Family::OpenAI => hints.push("Check https://status.openai.com".to_string()),
+                    Family::Anthropic => {
+                        hints.push("Check https://status.anthropic.com".to_string())
+                    }
+                    _ => {}
+                }
+                (format!("Provider error (HTTP {})", status), hints)
+            }
+            Self::InvalidResponse { .. } => (
+                "Unexpected response from the provider".to_string(),
+                vec![
+                    "Check that RCO_API_URL points to an API of this provider".to_string(),
+                    "Run with -vv to log the raw response".to_string(),
+                ],
+            ),
+            Self::Rejected { status, .. } => (
+                format!("Request rejected (HTTP {})", status),
+                vec!["Run with -vv to log the request and response".to_string()],
+            ),
+        };
+
+        let mut error = StructuredError::new(&message)
+            .with_underlying(&self.to_string())
+            .with_hints(hints)
+            .with_exit_code(self.exit_code());
+        if !provider.is_empty() {
+            error = error.with_provider(provider);
+        }
+        if !model.is_empty() {
+            error = error.with_model(model);
+        }
+        if let Some(account) = account {
+            error = error.with_context("Account", account);
+        }
+        error
+    }
+}
+
+/// The provider, model and account a request went to. Attached as context to
+/// provider errors so they can be reported with that provider's hints.
+#[derive(Debug, Clone)]
+pub struct RequestTarget {
+    pub provider: String,
+    pub model: String,
+    pub account: Option<String>,
+}
+
+impl fmt::Display for RequestTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Request to {} ({}) failed", self.provider, self.model)
+    }
+}
+
+/// Provider groups that share hints.
+#[derive(Debug, PartialEq, Eq)]
+enum Family {
+    OpenAI,
+    Anthropic,
+    Gemini,
+    Local,
+    Other,
+}
+
+fn family(provider: &str) -> Family {
+    match provider.to_lowercase().as_str() {
+        "openai" | "codex" | "chatgpt" => Family::OpenAI,
+        "anthropic" | "claude" | "claude-code" => Family::Anthropic,
+        "gemini" | "vertex" | "google" => Family::Gemini,
+        "ollama" | "mlx" | "flowise" | "lmstudio" | "lm-studio" | "llamacpp" => Family::Local,
+        _ => Family::Other,
+    }
+}
+
+fn auth_hints(provider: &str, account: Option<&str>) -> Vec<String> {
+    let mut hints = Vec::new();
+    if let Some(alias) = account {
+        hints.push(format!(
+            "Update the credentials of account '{}' with: rco config add-provider --alias {}",
+            alias, alias
+        ));
+    }
+    match family(provider) {
+        Family::OpenAI if provider.eq_ignore_ascii_case("codex") => {
+            hints.push("Sign in with ChatGPT again: rco auth login".to_string())
+        }
+        Family::OpenAI => hints.push(
+            "Create a key at https://platform.openai.com/api-keys and run: rco config set RCO_API_KEY=<key>"
+                .to_string(),
+        ),
+        Family::Anthropic => {
+            hints.push("Sign in with Claude again: rco auth login".to_string());
+            hints.push(
+                "Or use a key from https://console.anthropic.com/settings/keys".to_string(),
+            );
+        }
+        Family::Gemini => hints.push(
+            "Create a key at https://aistudio.google.com/app/apikey and run: rco config set RCO_API_KEY=<key>"
+                .to_string(),
+        ),
+        _ => hints.push("Check the key with: rco config explain RCO_API_KEY".to_string()),
+    }
+    hints
+}
+
+/// The context window stated in a context-length error, if any.
+fn context_limit(message: &str) -> Option<usize> {
+    CONTEXT_LIMIT_PATTERNS
+        .iter()
+        .find_map(|pattern| pattern.captures(message)?.get(1)?.as_str().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(status: u16, body: &str) -> ProviderError {
+        ProviderError::classify(status, None, body.to_string())
+    }
+
+    #[test]
+    fn classifies_provider_responses() {
+        assert!(matches!(
+            classify(401, r#"{"error":{"type":"authentication_error"}}"#),
+            ProviderError::Auth { .. }
+        ));
+        assert!(matches!(
+            classify(
+                429,
+                r#"{"error":{"type":"requests","message":"Rate limit reached"}}"#
+            ),
+            ProviderError::RateLimited { .. }
+        ));
+        assert!(matches!(
+            classify(429, r#"{"error":{"type":"insufficient_quota"}}"#),
+            ProviderError::QuotaExceeded { .. }
+        ));
+        assert!(matches!(
+            classify(
+                400,
+                r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key."}}"#
+            ),
+            ProviderError::Auth { .. }
+        ));
+        assert!(matches!(
+            classify(
+                404,
+                r#"{"error":"model 'llama9' not found, try pulling it first"}"#
+            ),
+            ProviderError::ModelNotFound { .. }
+        ));
+        assert!(matches!(
+            classify(400, r#"{"error":{"code":"content_filter"}}"#),
+            ProviderError::ContentFiltered { .. }
+        ));
+        assert!(matches!(
+            classify(529, r#"{"type":"overloaded_error"}"#),
+            ProviderError::Server { status: 529, .. }
+        ));
+        assert!(matches!(
+            classify(422, "unprocessable"),
+            ProviderError::Rejected { status: 422, .. }
+        ));
+    }
+
+    #[test]
+    fn reads_context_limits() {
+        let limit = |status, body| match classify(status, body) {
+            ProviderError::ContextLengthExceeded { limit, .. } => limit,
+            other => panic!("not a context length error: {other:?}"),
+        };
+        assert_eq!(
+            limit(
+                400,
+                r#"{"error":{"message":"This model's maximum context length is 8192 tokens. However, your messages resulted in 9000 tokens.","code":"context_length_exceeded"}}"#
+            ),
+            Some(8192)
+        );
+        assert_eq!(
+            limit(
+                400,
+                r#"{"error":{"message":"prompt is too long: 210000 tokens > 200000 maximum"}}"#
+            ),
+            Some(200000)
+        );
+        assert_eq!(
+            limit(
+                400,
+                r#"{"error":{"message":"The input token count (1200000) exceeds the maximum number of tokens allowed (1048576)."}}"#
+            ),
+            Some(1048576)
+        );
+        assert_eq!(limit(413, "Request Entity Too Large"), None);
+    }
+
+    #[test]
+    fn structured_errors_have_distinct_exit_codes_and_hints() {
+        let target = RequestTarget {
+            provider: "ollama".to_string(),
+            model: "llama9".to_string(),
+            account: None,
+        };
+        let error = classify(404, "model 'llama9' not found").to_structured(Some(&target));
+        assert_eq!(error.exit_code(), 7);
+        let value = error.to_value();
+        assert_eq!(value["error"], "Model not found: llama9");
+        assert!(value["hints"][0]
+            .as_str()
+            .unwrap()
+            .contains("ollama pull llama9"));
+
+        let codes: std::collections::HashSet<i32> = [
+            classify(401, ""),
+            classify(429, ""),
+            classify(429, "insufficient_quota"),
+            classify(413, ""),
+            classify(404, "model"),
+            classify(400, "content_filter"),
+            classify(500, ""),
+            ProviderError::invalid_response(""),
+            classify(400, ""),
+        ]
+        .iter()
+        .map(ProviderError::exit_code)
+        .collect();
+        assert_eq!(codes.len(), 9);
+    }
+}
@@ -14,16 +14,21 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::build_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct FlowiseProvider {
     client: Client,
     api_url: String,
     api_key: Option<String>,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -31,6 +36,7 @@ struct FlowiseRequest {
     question: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     history: Option<Vec<FlowiseMessage>>,
+    streaming: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -50,7 +56,7 @@ struct FlowiseResponse {
 
 impl FlowiseProvider {
     pub fn new(config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::client(config)?;
         let api_url = config
             .api_url
             .as_deref()
@@ -62,6 +68,7 @@ impl FlowiseProvider {
             client,
             api_url,
             api_key,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -72,7 +79,7 @@ impl FlowiseProvider {
         _api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
         let api_url = account
             .api_url
             .as_deref()
@@ -84,6 +91,7 @@ impl FlowiseProvider {
             client,
             api_url,
             api_key: None,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -102,10 +110,12 @@ impl AIProvider for FlowiseProvider {
         let request = FlowiseRequest {
             question: prompt,
             history: None,
+            streaming: config.stream,
         };
 
-        let flowise_response: FlowiseResponse = retry_async(|| async {
+        let flowise_response: FlowiseResponse = retry_async(self.retry, || async {
             let url = format!("{}/api/v1/prediction/flowise", self.api_url);
+            payload_log::request("flowise", &url, &request);
             let mut req = self.client.post(&url).json(&request);
 
             // Add API key if available
@@ -113,25 +123,34 @@ impl AIProvider for FlowiseProvider {
                 req = req.header("Authorization", format!("Bearer {}", key));
             }
 
-            let response = req
-                .send()
-                .await
-                .context("Failed to connect to Flowise server. Is Flowise running?")?;
+            let response = req.send().await.map_err(|e| {
+                ProviderError::network(
+                    "Failed to connect to Flowise server. Is Flowise running?",
+                    e,
+                )
+            })?;
 
             if !response.status().is_success() {
-                let error_text = response.text().await?;
-                if error_text.contains("Unauthorized") || error_text.contains("401") {
-                    return Err(anyhow::anyhow!(
-                        "Invalid Flowise API key. Please check your configuration."
-                    ));
-                }
-                return Err(anyhow::anyhow!("Flowise API error: {}", error_text));
+                let error = ProviderError::from_response("flowise", response).await?;
+                let error = if let ProviderError::Auth { .. } = error {
+                    error.map_message(|_| {
+                        "Invalid Flowise API key. Please check your configuration.".to_string()
+                    })
+                } else {
+                    error.map_message(|body| format!("Flowise API error: {}", body))
+                };
+                return Err(error.into());
             }
 
-            let flowise_response: FlowiseResponse = response
-                .json()
-                .await
-                .context("Failed to parse Flowise response")?;
+            let flowise_response: FlowiseResponse =
+                streaming::json_response("flowise", response, fold_stream)
+                    .await
+                    .map_err(|e| {
+                        ProviderError::invalid_response(format!(
+                            "Failed to parse Flowise response: {:#}",
+                            e
+                        ))
+                    })?;
 
             Ok(flowise_response)
         })
@@ -148,6 +167,26 @@ impl AIProvider for FlowiseProvider {
     }
 }
 
+/// Fold streamed prediction events into a prediction: the text of the
+/// `token` events.
+fn fold_stream(events: Vec<Value>) -> Result<Value> {
+    let mut text = String::new();
+    for event in &events {
+        let data = &event["data"];
+        match event.get("event").and_then(Value::as_str) {
+            Some("token") => text.push_str(data.as_str().unwrap_or_default()),
+            Some("error") => anyhow::bail!(
+                "The response stream reported an error: {}",
+                data.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| data.to_string())
+            ),
+            _ => {}
+        }
+    }
+    Ok(serde_json::json!({ "text": text }))
+}
+
 /// ProviderBuilder for Flowise
 pub struct FlowiseProviderBuilder;
 
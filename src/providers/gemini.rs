@@ -3,14 +3,23 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
+
+const DEFAULT_API_URL: &str = "https://generativelanguage.googleapis.com";
 
 pub struct GeminiProvider {
     client: Client,
     api_key: String,
+    api_url: String,
     model: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -44,13 +53,38 @@ struct GenerationConfig {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GeminiResponse {
+    #[serde(default)]
     candidates: Vec<Candidate>,
+    #[serde(default)]
+    prompt_feedback: Option<PromptFeedback>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+}
+
+/// Set when the prompt itself was blocked; there are no candidates then.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    block_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct Candidate {
-    content: ResponseContent,
+    /// Missing when generation stopped for safety reasons.
+    content: Option<ResponseContent>,
+    finish_reason: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -71,25 +105,40 @@ impl GeminiProvider {
             .context("Gemini API key not configured. Run: rco config set RCO_API_KEY=<your_key>")?
             .clone();
 
-        let client = Client::new();
+        let client = http::client(config)?;
+        let api_url = config
+            .api_url
+            .as_deref()
+            .unwrap_or(DEFAULT_API_URL)
+            .trim_end_matches('/')
+            .to_string();
         let model = config.model.clone();
 
         Ok(Self {
             client,
             api_key,
+            api_url,
             model,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
     /// Create provider from account configuration
     #[allow(dead_code)]
     pub fn from_account(
-        _account: &crate::config::accounts::AccountConfig,
+        account: &crate::config::accounts::AccountConfig,
         api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
-        let model = _account
+        let client = http::account_client(account, config)?;
+        let api_url = account
+            .api_url
+            .as_deref()
+            .or(config.api_url.as_deref())
+            .unwrap_or(DEFAULT_API_URL)
+            .trim_end_matches('/')
+            .to_string();
+        let model = account
             .model
             .as_deref()
             .unwrap_or(&config.model)
@@ -98,7 +147,9 @@ impl GeminiProvider {
         Ok(Self {
             client,
             api_key: api_key.to_string(),
+            api_url,
             model,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -131,36 +182,83 @@ impl AIProvider for GeminiProvider {
             },
         };
 
-        let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
-            self.model
-        );
-
-        let response = self
-            .client
-            .post(&url)
-            .header("X-Goog-Api-Key", &self.api_key)
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to connect to Gemini")?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            anyhow::bail!("Gemini API error: {}", error_text);
+        let url = if config.stream {
+            format!(
+                "{}/v1beta/models/{}:streamGenerateContent?alt=sse",
+                self.api_url, self.model
+            )
+        } else {
+            format!(
+                "{}/v1beta/models/{}:generateContent",
+                self.api_url, self.model
+            )
+        };
+
+        payload_log::request("gemini", &url, &request);
+
+        let gemini_response: GeminiResponse = retry_async(self.retry, || async {
+            let response = self
+                .client
+                .post(&url)
+                .header("X-Goog-Api-Key", &self.api_key)
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to Gemini", e))?;
+
+            if !response.status().is_success() {
+                let error = ProviderError::from_response("gemini", response).await?;
+                return Err(error
+                    .map_message(|body| format!("Gemini API error: {}", body))
+                    .into());
+            }
+
+            streaming::json_response("gemini", response, streaming::generate_content)
+                .await
+                .map_err(|e| {
+                    ProviderError::invalid_response(format!(
+                        "Failed to parse Gemini response: {:#}",
+                        e
+                    ))
+                    .into()
+                })
+        })
+        .await?;
+
+        if let Some(u) = &gemini_response.usage_metadata {
+            usage::record(
+                config,
+                &self.model,
+                TokenUsage::new(u.prompt_token_count, u.candidates_token_count),
+            );
         }
 
-        let gemini_response: GeminiResponse = response
-            .json()
-            .await
-            .context("Failed to parse Gemini response")?;
+        if let Some(reason) = gemini_response
+            .prompt_feedback
+            .and_then(|feedback| feedback.block_reason)
+        {
+            return Err(ProviderError::content_filtered(format!(
+                "Gemini blocked the prompt ({})",
+                reason
+            ))
+            .into());
+        }
+
+        let candidate = gemini_response.candidates.first();
+        if let Some(reason @ ("SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST" | "SPII")) =
+            candidate.and_then(|c| c.finish_reason.as_deref())
+        {
+            return Err(ProviderError::content_filtered(format!(
+                "Gemini blocked the response ({})",
+                reason
+            ))
+            .into());
+        }
 
-        let message = gemini_response
-            .candidates
-            .first()
-            .and_then(|c| c.content.parts.first())
+        let message = candidate
+            .and_then(|c| c.content.as_ref()?.parts.first())
             .map(|p| p.text.trim().to_string())
-            .context("No response from Gemini")?;
+            .ok_or_else(|| ProviderError::invalid_response("No response from Gemini"))?;
 
         Ok(message)
     }
@@ -2,18 +2,23 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::build_prompt;
+use super::streaming;
 use super::AIProvider;
 use crate::config::accounts::AccountConfig;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct HuggingFaceProvider {
     client: Client,
     api_key: String,
     model: String,
     api_url: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -22,6 +27,7 @@ struct HFRequest {
     inputs: String,
     parameters: HFParameters,
     options: HFOptions,
+    stream: bool,
 }
 
 #[derive(Serialize)]
@@ -42,6 +48,24 @@ struct HFResponse {
     error: Option<String>,
 }
 
+/// Text generation answers with a list of generations; errors and some
+/// dedicated endpoints with a single object.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HFReply {
+    List(Vec<HFResponse>),
+    One(HFResponse),
+}
+
+impl HFReply {
+    fn first(self) -> Option<HFResponse> {
+        match self {
+            Self::List(responses) => responses.into_iter().next(),
+            Self::One(response) => Some(response),
+        }
+    }
+}
+
 impl HuggingFaceProvider {
     pub fn new(config: &Config) -> Result<Self> {
         let api_key = config
@@ -50,12 +74,8 @@ impl HuggingFaceProvider {
             .context("HuggingFace API key not configured.\nRun: rco config set RCO_API_KEY=<your_key>\nGet your token from: https://huggingface.co/settings/tokens")?
             .clone();
 
-        let client = Client::new();
-        let model = config
-            .model
-            .as_deref()
-            .unwrap_or("mistralai/Mistral-7B-Instruct-v0.2")
-            .to_string();
+        let client = http::client(config)?;
+        let model = config.model.clone();
 
         // Determine if this is an inference API call or dedicated endpoint
         let api_url = config
@@ -68,18 +88,18 @@ impl HuggingFaceProvider {
             api_key,
             model,
             api_url: api_url.to_string(),
+            retry: RetryPolicy::from_config(config),
         })
     }
 
     /// Create provider from account configuration
     #[allow(dead_code)]
     pub fn from_account(account: &AccountConfig, api_key: &str, config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
         let model = account
             .model
             .as_deref()
-            .or(config.model.as_deref())
-            .unwrap_or("mistralai/Mistral-7B-Instruct-v0.2")
+            .unwrap_or(&config.model)
             .to_string();
 
         let api_url = account
@@ -94,6 +114,7 @@ impl HuggingFaceProvider {
             api_key: api_key.to_string(),
             model,
             api_url,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -115,16 +136,18 @@ impl AIProvider for HuggingFaceProvider {
             inputs: prompt,
             parameters: HFParameters {
                 temperature: Some(0.7),
-                max_new_tokens: Some(config.tokens_max_output.unwrap_or(500)),
+                max_new_tokens: Some(config.tokens_max_output),
                 return_full_text: false,
             },
             options: HFOptions { use_cache: true },
+            stream: config.stream,
         };
 
         // Use Inference API endpoint
         let url = format!("{}/models/{}", self.api_url, self.model);
 
-        let hf_response: HFResponse = retry_async(|| async {
+        let hf_response: HFReply = retry_async(self.retry, || async {
+            payload_log::request("huggingface", &url, &request);
             let response = self
                 .client
                 .post(&url)
@@ -133,22 +156,32 @@ impl AIProvider for HuggingFaceProvider {
                 .json(&request)
                 .send()
                 .await
-                .context("Failed to connect to HuggingFace")?;
+                .map_err(|e| ProviderError::network("Failed to connect to HuggingFace", e))?;
 
             if !response.status().is_success() {
-                let error_text = response.text().await?;
-                return Err(anyhow::anyhow!("HuggingFace API error: {}", error_text));
+                let error = ProviderError::from_response("huggingface", response).await?;
+                return Err(error
+                    .map_message(|body| format!("HuggingFace API error: {}", body))
+                    .into());
             }
 
-            let hf_response: HFResponse = response
-                .json()
-                .await
-                .context("Failed to parse HuggingFace response")?;
+            let hf_response: HFReply =
+                streaming::json_response("huggingface", response, fold_stream)
+                    .await
+                    .map_err(|e| {
+                        ProviderError::invalid_response(format!(
+                            "Failed to parse HuggingFace response: {:#}",
+                            e
+                        ))
+                    })?;
 
             Ok(hf_response)
         })
         .await
         .context("Failed to generate commit message from HuggingFace after retries")?;
+        let hf_response = hf_response.first().ok_or_else(|| {
+            ProviderError::invalid_response("HuggingFace returned an empty response")
+        })?;
 
         // Handle error response
         if let Some(error) = hf_response.error {
@@ -157,7 +190,9 @@ impl AIProvider for HuggingFaceProvider {
 
         let message = hf_response
             .generated_text
-            .context("HuggingFace returned an empty response")?
+            .ok_or_else(|| {
+                ProviderError::invalid_response("HuggingFace returned an empty response")
+            })?
             .trim()
             .to_string();
 
@@ -167,6 +202,29 @@ impl AIProvider for HuggingFaceProvider {
     }
 }
 
+/// Fold streamed text generation events into a generation: the
+/// `generated_text` of the last event, or else the text of its tokens.
+fn fold_stream(events: Vec<Value>) -> Result<Value> {
+    if let Some(error) = streaming::stream_error(&events) {
+        return Ok(serde_json::json!({ "error": error }));
+    }
+
+    let generated = events
+        .iter()
+        .rev()
+        .find_map(|event| event.get("generated_text").and_then(Value::as_str))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            events
+                .iter()
+                .filter_map(|event| event.get("token"))
+                .filter(|token| !token["special"].as_bool().unwrap_or(false))
+                .filter_map(|token| token.get("text").and_then(Value::as_str))
+                .collect()
+        });
+    Ok(serde_json::json!({ "generated_text": generated }))
+}
+
 /// ProviderBuilder for HuggingFace
 pub struct HuggingFaceProviderBuilder;
 
@@ -13,15 +13,20 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use super::error::ProviderError;
 use super::prompt::build_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct MlxProvider {
     client: Client,
     api_url: String,
     model: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -42,6 +47,8 @@ struct MlxMessage {
 #[derive(Deserialize)]
 struct MlxResponse {
     choices: Vec<MlxChoice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Deserialize)]
@@ -51,18 +58,19 @@ struct MlxChoice {
 
 impl MlxProvider {
     pub fn new(config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::client(config)?;
         let api_url = config
             .api_url
             .as_deref()
             .unwrap_or("http://localhost:8080")
             .to_string();
-        let model = config.model.as_deref().unwrap_or("default").to_string();
+        let model = config.model.clone();
 
         Ok(Self {
             client,
             api_url,
             model,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -73,7 +81,7 @@ impl MlxProvider {
         _api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
         let api_url = account
             .api_url
             .as_deref()
@@ -83,14 +91,14 @@ impl MlxProvider {
         let model = account
             .model
             .as_deref()
-            .or(config.model.as_deref())
-            .unwrap_or("default")
+            .unwrap_or(&config.model)
             .to_string();
 
         Ok(Self {
             client,
             api_url,
             model,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -122,41 +130,53 @@ impl AIProvider for MlxProvider {
         let request = MlxRequest {
             model: self.model.clone(),
             messages,
-            max_tokens: config.tokens_max_output.unwrap_or(500) as i32,
+            max_tokens: config.tokens_max_output as i32,
             temperature: 0.7,
-            stream: false,
+            stream: config.stream,
         };
 
-        let mlx_response: MlxResponse = retry_async(|| async {
+        let mlx_response: MlxResponse = retry_async(self.retry, || async {
             let url = format!("{}/v1/chat/completions", self.api_url);
+            payload_log::request("mlx", &url, &request);
             let response = self
                 .client
                 .post(&url)
                 .json(&request)
                 .send()
                 .await
-                .context("Failed to connect to MLX server")?;
+                .map_err(|e| ProviderError::network("Failed to connect to MLX server", e))?;
 
             if !response.status().is_success() {
-                let error_text = response.text().await?;
-                return Err(anyhow::anyhow!("MLX API error: {}", error_text));
+                let error = ProviderError::from_response("mlx", response).await?;
+                return Err(error
+                    .map_message(|body| format!("MLX API error: {}", body))
+                    .into());
             }
 
-            let mlx_response: MlxResponse = response
-                .json()
-                .await
-                .context("Failed to parse MLX response")?;
+            let mlx_response: MlxResponse =
+                streaming::json_response("mlx", response, streaming::chat_completion)
+                    .await
+                    .map_err(|e| {
+                        ProviderError::invalid_response(format!(
+                            "Failed to parse MLX response: {:#}",
+                            e
+                        ))
+                    })?;
 
             Ok(mlx_response)
         })
         .await
         .context("Failed to generate commit message from MLX after retries")?;
 
+        if let Some(token_usage) = mlx_response.usage {
+            usage::record(config, &self.model, token_usage);
+        }
+
         let message = mlx_response
             .choices
             .first()
             .map(|choice| choice.message.content.trim().to_string())
-            .context("MLX returned an empty response")?;
+            .ok_or_else(|| ProviderError::invalid_response("MLX returned an empty response"))?;
 
         Ok(message)
     }
@@ -0,0 +1,584 @@
+//! Mock provider for offline testing and demos.
+//!
+//! `RCO_AI_PROVIDER=mock` never touches the network. What it returns depends
+//! on `RCO_TEST_MOCK_TYPE`:
+//!
+//! - `generate` (the default): a message derived from the diff alone, with the
+//!   type inferred from the changed paths and the files listed in the subject.
+//! - `replay`: the responses recorded for the same request in
+//!   `RCO_MOCK_FIXTURES_DIR` (default: `.rco/fixtures` in the repository).
+//!
+//! With `RCO_TEST_MOCK_TYPE=record`, any other provider works as usual and
+//! each request and its response are written to the fixtures directory, one
+//! JSON file per request, for the mock to replay later. Fixtures are keyed
+//! by the request alone, so recordings from any provider replay the same way.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::error::RequestTarget;
+use super::AIProvider;
+use crate::config::Config;
+use crate::git;
+
+/// Fixtures directory used when `RCO_MOCK_FIXTURES_DIR` is not set, relative
+/// to the repository root.
+pub const DEFAULT_FIXTURES_DIR: &str = ".rco/fixtures";
+
+/// A request as recorded in a fixture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FixtureRequest {
+    CommitMessage {
+        diff: String,
+        context: Option<String>,
+        full_gitmoji: bool,
+    },
+    PrDescription {
+        commits: Vec<String>,
+        diff: String,
+    },
+}
+
+impl FixtureRequest {
+    /// File name of the fixture for this request.
+    pub fn file_name(&self) -> String {
+        let kind = match self {
+            Self::CommitMessage { .. } => "commit",
+            Self::PrDescription { .. } => "pr",
+        };
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        let key: String = Sha256::digest(&json)
+            .iter()
+            .take(8)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("{}-{}.json", kind, key)
+    }
+}
+
+/// A recorded request with every response it got, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub provider: String,
+    pub model: String,
+    pub request: FixtureRequest,
+    pub responses: Vec<String>,
+}
+
+/// The fixtures directory for this config.
+pub fn fixtures_dir(config: &Config) -> PathBuf {
+    if let Some(dir) = &config.mock_fixtures_dir {
+        return PathBuf::from(dir);
+    }
+    match git::get_repo_root() {
+        Ok(root) => Path::new(&root).join(DEFAULT_FIXTURES_DIR),
+        Err(_) => PathBuf::from(DEFAULT_FIXTURES_DIR),
+    }
+}
+
+fn read_fixture(path: &Path) -> Result<Fixture> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse fixture {}", path.display()))
+}
+
+fn write_fixture(path: &Path, fixture: &Fixture) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(fixture)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write fixture {}", path.display()))
+}
+
+/// How a file is changed by the diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Added,
+    Deleted,
+    Modified,
+}
+
+/// Changed files and how, in diff order.
+fn changed_files(diff: &str) -> Vec<(String, Change)> {
+    let mut files: Vec<(String, Change)> = Vec::new();
+    let mut old_path: Option<&str> = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("--- ") {
+            old_path = Some(path.strip_prefix("a/").unwrap_or(path));
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let file = match (old_path.take(), path.strip_prefix("b/")) {
+                (Some("/dev/null"), Some(new)) => (new.to_string(), Change::Added),
+                (Some(old), None) if path == "/dev/null" => (old.to_string(), Change::Deleted),
+                (_, Some(new)) => (new.to_string(), Change::Modified),
+                _ => continue,
+            };
+            if !files.iter().any(|(p, _)| *p == file.0) {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
+
+fn is_docs(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.starts_with("docs/")
+        || [".md", ".mdx", ".rst", ".adoc", ".txt"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
+}
+
+fn is_test(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    lower.starts_with("tests/")
+        || lower.contains("/tests/")
+        || lower.contains("__tests__/")
+        || name.starts_with("test_")
+        || ["_test.", "_tests.", ".test.", ".spec.", "_spec.", "test.rs"]
+            .iter()
+            .any(|marker| name.contains(marker))
+}
+
+fn is_ci(path: &str) -> bool {
+    path.starts_with(".github/workflows/")
+        || path.starts_with(".circleci/")
+        || path == ".gitlab-ci.yml"
+        || path == ".travis.yml"
+        || path == "Jenkinsfile"
+}
+
+fn is_build(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    matches!(
+        name,
+        "Cargo.toml"
+            | "Cargo.lock"
+            | "build.rs"
+            | "package.json"
+            | "package-lock.json"
+            | "yarn.lock"
+            | "pnpm-lock.yaml"
+            | "go.mod"
+            | "go.sum"
+            | "pyproject.toml"
+            | "requirements.txt"
+            | "Makefile"
+            | "Dockerfile"
+    )
+}
+
+/// The conventional commit type the changed files suggest.
+fn infer_type(files: &[(String, Change)]) -> &'static str {
+    let all = |check: fn(&str) -> bool| files.iter().all(|(path, _)| check(path));
+    if files.is_empty() {
+        "chore"
+    } else if all(is_test) {
+        "test"
+    } else if all(is_ci) {
+        "ci"
+    } else if all(is_build) {
+        "build"
+    } else if all(is_docs) {
+        "docs"
+    } else if files.iter().any(|(_, change)| *change == Change::Added) {
+        "feat"
+    } else {
+        "chore"
+    }
+}
+
+fn gitmoji(commit_type: &str) -> &'static str {
+    match commit_type {
+        "feat" => "✨",
+        "test" => "✅",
+        "ci" => "👷",
+        "build" => "📦",
+        "docs" => "📝",
+        _ => "🔧",
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// "a.rs", "a.rs and b.rs", "a.rs, b.rs and c.rs" or "a.rs, b.rs and 3 more files".
+fn list_files(files: &[(String, Change)]) -> String {
+    let names: Vec<&str> = files.iter().map(|(path, _)| file_name(path)).collect();
+    match names.as_slice() {
+        [] => "files".to_string(),
+        [one] => one.to_string(),
+        [first, second] => format!("{} and {}", first, second),
+        [first, second, third] => format!("{}, {} and {}", first, second, third),
+        [first, second, rest @ ..] => {
+            format!("{}, {} and {} more files", first, second, rest.len())
+        }
+    }
+}
+
+/// A deterministic commit message for `diff`: the type inferred from the
+/// changed paths and a subject listing the files, following the commit
+/// format settings in `config`.
+pub fn commit_message(diff: &str, config: &Config) -> String {
+    let files = changed_files(diff);
+    let commit_type = infer_type(&files);
+
+    let verb = if files.iter().all(|(_, c)| *c == Change::Added) && !files.is_empty() {
+        "add"
+    } else if files.iter().all(|(_, c)| *c == Change::Deleted) && !files.is_empty() {
+        "remove"
+    } else {
+        "update"
+    };
+    let mut description = format!("{} {}", verb, list_files(&files));
+    if config.description_capitalize {
+        description[..1].make_ascii_uppercase();
+    }
+    if config.description_add_period {
+        description.push('.');
+    }
+
+    let mut message = match config.commit_type.as_str() {
+        "gitmoji" => format!("{} {}: {}", gitmoji(commit_type), commit_type, description),
+        _ => format!("{}: {}", commit_type, description),
+    };
+
+    if config.enable_commit_body && !config.one_line_commit && files.len() > 1 {
+        message.push_str("\n\n");
+        let lines: Vec<String> = files
+            .iter()
+            .map(|(path, change)| {
+                let verb = match change {
+                    Change::Added => "Add",
+                    Change::Deleted => "Remove",
+                    Change::Modified => "Update",
+                };
+                format!("- {} {}", verb, path)
+            })
+            .collect();
+        message.push_str(&lines.join("\n"));
+    }
+
+    message
+}
+
+/// A deterministic PR description from the commit subjects and changed files.
+pub fn pr_description(commits: &[String], diff: &str) -> String {
+    let files = changed_files(diff);
+    let summary = match commits {
+        [] => "No commits.".to_string(),
+        [one] => one.lines().next().unwrap_or_default().to_string(),
+        _ => format!("{} commits changing {}.", commits.len(), list_files(&files)),
+    };
+    let changes: Vec<String> = commits
+        .iter()
+        .filter_map(|commit| commit.lines().next())
+        .map(|subject| format!("- {}", subject))
+        .collect();
+    let files: Vec<String> = files
+        .iter()
+        .map(|(path, _)| format!("- `{}`", path))
+        .collect();
+
+    format!(
+        "## Summary\n{}\n\n## Changes\n{}\n\n## Files\n{}",
+        summary,
+        changes.join("\n"),
+        files.join("\n")
+    )
+}
+
+/// The mock provider: generated or replayed responses, no network.
+pub struct MockProvider {
+    replay: Option<PathBuf>,
+    /// Responses replayed so far, by fixture, to cycle through variations.
+    replayed: Mutex<HashMap<String, usize>>,
+}
+
+impl MockProvider {
+    pub fn new(config: &Config) -> Result<Self> {
+        let replay = match config.test_mock_type.as_deref() {
+            Some("replay") => Some(fixtures_dir(config)),
+            _ => None,
+        };
+        Ok(Self {
+            replay,
+            replayed: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The next recorded response for `request`, cycling through them.
+    fn replay(&self, dir: &Path, request: &FixtureRequest) -> Result<String> {
+        let name = request.file_name();
+        let path = dir.join(&name);
+        if !path.exists() {
+            anyhow::bail!(
+                "No fixture recorded for this request ({} in {}). Record one with \
+                 RCO_TEST_MOCK_TYPE=record and a real provider",
+                name,
+                dir.display()
+            );
+        }
+        let fixture = read_fixture(&path)?;
+        if fixture.responses.is_empty() {
+            anyhow::bail!("Fixture {} has no responses", path.display());
+        }
+
+        let mut replayed = self
+            .replayed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let count = replayed.entry(name).or_insert(0);
+        let response = fixture.responses[*count % fixture.responses.len()].clone();
+        *count += 1;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl AIProvider for MockProvider {
+    async fn generate_commit_message(
+        &self,
+        diff: &str,
+        context: Option<&str>,
+        full_gitmoji: bool,
+        config: &Config,
+    ) -> Result<String> {
+        match &self.replay {
+            Some(dir) => self.replay(
+                dir,
+                &FixtureRequest::CommitMessage {
+                    diff: diff.to_string(),
+                    context: context.map(str::to_string),
+                    full_gitmoji,
+                },
+            ),
+            None => Ok(commit_message(diff, config)),
+        }
+    }
+
+    async fn generate_pr_description(
+        &self,
+        commits: &[String],
+        diff: &str,
+        _config: &Config,
+    ) -> Result<String> {
+        match &self.replay {
+            Some(dir) => self.replay(
+                dir,
+                &FixtureRequest::PrDescription {
+                    commits: commits.to_vec(),
+                    diff: diff.to_string(),
+                },
+            ),
+            None => Ok(pr_description(commits, diff)),
+        }
+    }
+}
+
+/// A provider whose requests and responses are written to fixture files.
+struct Recording {
+    inner: Box<dyn AIProvider>,
+    dir: PathBuf,
+    provider: String,
+    model: String,
+    /// Fixtures written by this process; the first response replaces an
+    /// older recording and later ones (variations) are added to it.
+    written: Mutex<HashSet<String>>,
+}
+
+impl Recording {
+    fn record(&self, request: FixtureRequest, response: &str) -> Result<()> {
+        let name = request.file_name();
+        let path = self.dir.join(&name);
+
+        let mut written = self
+            .written
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut fixture = if written.contains(&name) && path.exists() {
+            read_fixture(&path)?
+        } else {
+            Fixture {
+                provider: self.provider.clone(),
+                model: self.model.clone(),
+                request,
+                responses: Vec::new(),
+            }
+        };
+        fixture.responses.push(response.to_string());
+        write_fixture(&path, &fixture)?;
+        written.insert(name);
+        tracing::debug!("Recorded fixture {}", path.display());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AIProvider for Recording {
+    async fn generate_commit_message(
+        &self,
+        diff: &str,
+        context: Option<&str>,
+        full_gitmoji: bool,
+        config: &Config,
+    ) -> Result<String> {
+        let message = self
+            .inner
+            .generate_commit_message(diff, context, full_gitmoji, config)
+            .await?;
+        let request = FixtureRequest::CommitMessage {
+            diff: diff.to_string(),
+            context: context.map(str::to_string),
+            full_gitmoji,
+        };
+        self.record(request, &message)?;
+        Ok(message)
+    }
+
+    async fn generate_pr_description(
+        &self,
+        commits: &[String],
+        diff: &str,
+        config: &Config,
+    ) -> Result<String> {
+        let description = self
+            .inner
+            .generate_pr_description(commits, diff, config)
+            .await?;
+        let request = FixtureRequest::PrDescription {
+            commits: commits.to_vec(),
+            diff: diff.to_string(),
+        };
+        self.record(request, &description)?;
+        Ok(description)
+    }
+}
+
+/// Wrap a real provider to record its requests when `RCO_TEST_MOCK_TYPE` is
+/// `record`; otherwise return it unchanged.
+pub fn recording(
+    inner: Box<dyn AIProvider>,
+    target: &RequestTarget,
+    config: &Config,
+) -> Box<dyn AIProvider> {
+    if config.test_mock_type.as_deref() != Some("record")
+        || target.provider.eq_ignore_ascii_case("mock")
+    {
+        return inner;
+    }
+    Box::new(Recording {
+        inner,
+        dir: fixtures_dir(config),
+        provider: target.provider.clone(),
+        model: target.model.clone(),
+        written: Mutex::new(HashSet::new()),
+    })
+}
+
+/// ProviderBuilder for the mock provider
+pub struct MockProviderBuilder;
+
+impl super::registry::ProviderBuilder for MockProviderBuilder {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn category(&self) -> super::registry::ProviderCategory {
+        super::registry::ProviderCategory::Local
+    }
+
+    fn create(&self, config: &Config) -> Result<Box<dyn AIProvider>> {
+        Ok(Box::new(MockProvider::new(config)?))
+    }
+
+    fn requires_api_key(&self) -> bool {
+        false
+    }
+
+    fn default_model(&self) -> Option<&'static str> {
+        Some("mock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1,2 @@
+ pub mod a;
++pub mod b;
+diff --git a/src/b.rs b/src/b.rs
+new file mode 100644
+--- /dev/null
++++ b/src/b.rs
+@@ -0,0 +1 @@
++pub fn b() {}
+diff --git a/old.md b/old.md
+deleted file mode 100644
+--- a/old.md
++++ /dev/null
+@@ -1 +0,0 @@
+-old
+";
+
+    #[test]
+    fn test_changed_files() {
+        assert_eq!(
+            changed_files(DIFF),
+            vec![
+                ("src/lib.rs".to_string(), Change::Modified),
+                ("src/b.rs".to_string(), Change::Added),
+                ("old.md".to_string(), Change::Deleted),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commit_message_from_paths() {
+        let mut config = Config {
+            commit_type: "conventional".to_string(),
+            description_capitalize: false,
+            description_add_period: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            commit_message(DIFF, &config),
+            "feat: update lib.rs, b.rs and old.md"
+        );
+
+        let docs = "--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(commit_message(docs, &config), "docs: update README.md");
+        let tests = "--- /dev/null\n+++ b/tests/cli_test.rs\n@@ -0,0 +1 @@\n+x\n";
+        assert_eq!(commit_message(tests, &config), "test: add cli_test.rs");
+
+        config.commit_type = "gitmoji".to_string();
+        config.description_capitalize = true;
+        assert_eq!(commit_message(docs, &config), "📝 docs: Update README.md");
+    }
+
+    #[test]
+    fn test_fixture_names_depend_on_the_request() {
+        let request = |diff: &str| FixtureRequest::CommitMessage {
+            diff: diff.to_string(),
+            context: None,
+            full_gitmoji: false,
+        };
+        assert_eq!(request("+a").file_name(), request("+a").file_name());
+        assert_ne!(request("+a").file_name(), request("+b").file_name());
+        assert!(request("+a").file_name().starts_with("commit-"));
+    }
+}
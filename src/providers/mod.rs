@@ -32,11 +32,24 @@ pub mod registry;
 // Prompt building utilities
 pub mod prompt;
 
-use crate::config::accounts::AccountConfig;
+// Typed provider errors
+pub mod error;
+
+// Offline mock provider and fixture recording
+pub mod mock;
+
+// Streamed responses (RCO_STREAM)
+pub mod streaming;
+
+use crate::config::accounts::{AccountConfig, AuthMethod, OidcGrant};
 use crate::config::Config;
+use crate::utils::rate_limit::{self, RateLimiter, RateLimits};
+use crate::utils::token::estimate_tokens;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use error::RequestTarget;
 use once_cell::sync::Lazy;
+use std::sync::Arc;
 
 #[async_trait]
 pub trait AIProvider: Send + Sync {
@@ -61,30 +74,31 @@ pub trait AIProvider: Send + Sync {
 
         if count <= 1 {
             // For single message, no parallelism needed
-            match self
-                .generate_commit_message(diff, context, full_gitmoji, config)
+            self.generate_commit_message(diff, context, full_gitmoji, config)
                 .await
-            {
-                Ok(msg) => Ok(vec![msg]),
-                Err(e) => {
-                    tracing::warn!("Failed to generate message: {}", e);
-                    Ok(vec![])
-                }
-            }
+                .map(|msg| vec![msg])
         } else {
-            // Generate messages in parallel using FuturesUnordered
-            let futures = (0..count)
-                .map(|_| self.generate_commit_message(diff, context, full_gitmoji, config));
-            let mut stream = futures::stream::FuturesUnordered::from_iter(futures);
+            // Generate messages in parallel, at most RCO_MAX_CONCURRENCY at a time
+            let mut stream = futures::stream::iter(0..count)
+                .map(|_| self.generate_commit_message(diff, context, full_gitmoji, config))
+                .buffer_unordered(config.max_concurrency.max(1));
 
             let mut messages = Vec::with_capacity(count as usize);
+            let mut last_error = None;
             while let Some(result) = stream.next().await {
                 match result {
                     Ok(msg) => messages.push(msg),
-                    Err(e) => tracing::warn!("Failed to generate message: {}", e),
+                    Err(e) => {
+                        tracing::warn!("Failed to generate message: {}", e);
+                        last_error = Some(e);
+                    }
                 }
             }
-            Ok(messages)
+            // Only fail when no variation made it
+            match last_error {
+                Some(e) if messages.is_empty() => Err(e),
+                _ => Ok(messages),
+            }
         }
     }
 
@@ -131,9 +145,20 @@ pub trait AIProvider: Send + Sync {
             .with_api_key(api_key)
             .with_api_base(api_url);
 
-        let client = async_openai::Client::with_config(openai_config);
+        let client = async_openai::Client::with_config(openai_config)
+            .with_http_client(crate::config::http::client(config)?);
 
         let response = client.chat().create(request).await?;
+        if let Some(usage) = &response.usage {
+            crate::usage::record(
+                config,
+                &config.model,
+                crate::usage::TokenUsage::new(
+                    usage.prompt_tokens.into(),
+                    usage.completion_tokens.into(),
+                ),
+            );
+        }
 
         let message = response
             .choices
@@ -231,16 +256,109 @@ pub static PROVIDER_REGISTRY: Lazy<registry::ProviderRegistry> = Lazy::new(|| {
         let _ = reg.register(Box::new(flowise::FlowiseProviderBuilder));
     }
 
+    // Always available, for tests and demos without network access
+    let _ = reg.register(Box::new(mock::MockProviderBuilder));
+
     reg
 });
 
+/// A provider whose errors carry the provider, model and account they came
+/// from, so they can be reported with matching hints, whose usage is recorded
+/// under them, and whose requests and retries wait for the client-side rate
+/// limits.
+struct Targeted {
+    inner: Box<dyn AIProvider>,
+    target: RequestTarget,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl Targeted {
+    /// The tokens a request counts against the token budget: the prompt and
+    /// the longest possible answer.
+    fn request_tokens(prompt: &[&str], config: &Config) -> usize {
+        let tokens: usize = prompt
+            .iter()
+            .map(|text| estimate_tokens(text).unwrap_or(text.len() / 4))
+            .sum();
+        tokens + config.tokens_max_output as usize
+    }
+}
+
+#[async_trait]
+impl AIProvider for Targeted {
+    async fn generate_commit_message(
+        &self,
+        diff: &str,
+        context: Option<&str>,
+        full_gitmoji: bool,
+        config: &Config,
+    ) -> Result<String> {
+        let tokens = Self::request_tokens(&[diff, context.unwrap_or_default()], config);
+        rate_limit::limited(
+            self.limiter.clone(),
+            tokens,
+            crate::usage::serving(
+                self.target.clone(),
+                self.inner
+                    .generate_commit_message(diff, context, full_gitmoji, config),
+            ),
+        )
+        .await
+        .map_err(|e| e.context(self.target.clone()))
+    }
+
+    async fn generate_pr_description(
+        &self,
+        commits: &[String],
+        diff: &str,
+        config: &Config,
+    ) -> Result<String> {
+        let tokens = Self::request_tokens(&[&commits.join("\n"), diff], config);
+        rate_limit::limited(
+            self.limiter.clone(),
+            tokens,
+            crate::usage::serving(
+                self.target.clone(),
+                self.inner.generate_pr_description(commits, diff, config),
+            ),
+        )
+        .await
+        .map_err(|e| e.context(self.target.clone()))
+    }
+}
+
+/// Wrap a provider so its errors name the request target and its requests
+/// share the rate limiter of that provider or account.
+fn targeted(
+    inner: Box<dyn AIProvider>,
+    target: RequestTarget,
+    limits: RateLimits,
+) -> Box<dyn AIProvider> {
+    let key = match &target.account {
+        Some(alias) => format!("account:{}", alias),
+        None => target.provider.clone(),
+    };
+    let limiter = rate_limit::shared(&key, limits);
+    Box::new(Targeted {
+        inner,
+        target,
+        limiter,
+    })
+}
+
 /// Create an AI provider instance from configuration
 pub fn create_provider(config: &Config) -> Result<Box<dyn AIProvider>> {
     let provider_name = config.ai_provider.as_str();
 
     // Try to create from registry
     if let Some(provider) = PROVIDER_REGISTRY.create(provider_name, config)? {
-        return Ok(provider);
+        let target = RequestTarget {
+            provider: config.ai_provider.clone(),
+            model: config.model.clone(),
+            account: None,
+        };
+        let provider = mock::recording(provider, &target, config);
+        return Ok(targeted(provider, target, RateLimits::from_config(config)));
     }
 
     // Provider not found - build error message with available providers
@@ -484,34 +602,62 @@ pub fn create_provider_for_account(
     account: &AccountConfig,
     config: &Config,
 ) -> Result<Box<dyn AIProvider>> {
-    use crate::auth::token_storage;
-    use crate::config::secure_storage;
+    let target = RequestTarget {
+        provider: account.provider.clone(),
+        model: account.model.clone().unwrap_or_else(|| config.model.clone()),
+        account: Some(account.alias.clone()),
+    };
+    let provider = mock::recording(build_account_provider(account, config)?, &target, config);
+    Ok(targeted(
+        provider,
+        target,
+        RateLimits::for_account(account, config),
+    ))
+}
 
+fn build_account_provider(
+    account: &AccountConfig,
+    config: &Config,
+) -> Result<Box<dyn AIProvider>> {
     let provider = account.provider.to_lowercase();
 
     // Extract credentials from the account's auth method
-    let credentials = match &account.auth {
-        crate::config::accounts::AuthMethod::ApiKey { key_id } => {
-            // Get API key from secure storage using the account's key_id
-            token_storage::get_api_key_for_account(key_id)?
-                .or_else(|| secure_storage::get_secret(key_id).ok().flatten())
-        }
-        crate::config::accounts::AuthMethod::OAuth {
-            provider: _oauth_provider,
-            account_id,
-        } => {
-            // Get OAuth access token from secure storage
-            token_storage::get_tokens_for_account(account_id)?.map(|t| t.access_token)
-        }
-        crate::config::accounts::AuthMethod::EnvVar { name } => std::env::var(name).ok(),
-        crate::config::accounts::AuthMethod::Bearer { token_id } => {
-            // Get bearer token from secure storage
-            token_storage::get_bearer_token_for_account(token_id)?
-                .or_else(|| secure_storage::get_secret(token_id).ok().flatten())
+    let credentials = crate::auth::account_credential(account)?;
+
+    // Falling back to the global API key here would make `use-account`
+    // switch models but not keys, so only env var accounts may omit one
+    if credentials.is_none() {
+        match &account.auth {
+            AuthMethod::EnvVar { .. } => {}
+            AuthMethod::OAuth {
+                oidc: Some(oidc), ..
+            } => {
+                if oidc.grant != OidcGrant::ClientCredentials {
+                    anyhow::bail!(
+                        "No OAuth tokens stored for account '{}'. Log in with: rco auth login --account {}",
+                        account.alias,
+                        account.alias
+                    );
+                }
+            }
+            AuthMethod::OAuth { .. } => anyhow::bail!(
+                "No OAuth tokens stored for account '{}'. Log in again with: rco auth login",
+                account.alias
+            ),
+            _ => anyhow::bail!(
+                "No credentials stored for account '{}'. Re-add it with: rco config add-provider --alias {}",
+                account.alias,
+                account.alias
+            ),
         }
-    };
+    }
 
     match provider.as_str() {
+        // Gateways behind a generic OIDC issuer speak the OpenAI API
+        #[cfg(feature = "openai")]
+        _ if matches!(&account.auth, AuthMethod::OAuth { oidc: Some(_), .. }) => Ok(Box::new(
+            openai::OpenAIProvider::from_account(account, "", config)?,
+        )),
         #[cfg(feature = "openai")]
         "openai" | "codex" => {
             if let Some(key) = credentials.as_ref() {
@@ -15,16 +15,21 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use super::error::ProviderError;
 use super::prompt::build_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct NvidiaProvider {
     client: Client,
     api_url: String,
     api_key: String,
     model: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -46,6 +51,8 @@ struct NvidiaMessage {
 #[derive(Deserialize)]
 struct NvidiaResponse {
     choices: Vec<NvidiaChoice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Deserialize)]
@@ -55,7 +62,7 @@ struct NvidiaChoice {
 
 impl NvidiaProvider {
     pub fn new(config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::client(config)?;
         let api_key = config
             .api_key
             .as_ref()
@@ -67,17 +74,14 @@ impl NvidiaProvider {
             .unwrap_or("https://integrate.api.nvidia.com/v1")
             .to_string();
 
-        let model = config
-            .model
-            .as_deref()
-            .unwrap_or("meta/llama-3.1-8b-instruct")
-            .to_string();
+        let model = config.model.clone();
 
         Ok(Self {
             client,
             api_url,
             api_key: api_key.clone(),
             model,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -88,7 +92,7 @@ impl NvidiaProvider {
         api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
         let api_url = account
             .api_url
             .as_deref()
@@ -99,8 +103,7 @@ impl NvidiaProvider {
         let model = account
             .model
             .as_deref()
-            .or(config.model.as_deref())
-            .unwrap_or("meta/llama-3.1-8b-instruct")
+            .unwrap_or(&config.model)
             .to_string();
 
         Ok(Self {
@@ -108,6 +111,7 @@ impl NvidiaProvider {
             api_url,
             api_key: api_key.to_string(),
             model,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -138,14 +142,15 @@ impl AIProvider for NvidiaProvider {
         let request = NvidiaRequest {
             model: self.model.clone(),
             messages,
-            max_tokens: config.tokens_max_output.unwrap_or(500) as i32,
+            max_tokens: config.tokens_max_output as i32,
             temperature: 0.7,
             top_p: 0.7,
-            stream: false,
+            stream: config.stream,
         };
 
-        let nvidia_response: NvidiaResponse = retry_async(|| async {
+        let nvidia_response: NvidiaResponse = retry_async(self.retry, || async {
             let url = format!("{}/chat/completions", self.api_url);
+            payload_log::request("nvidia", &url, &request);
             let response = self
                 .client
                 .post(&url)
@@ -153,33 +158,47 @@ impl AIProvider for NvidiaProvider {
                 .json(&request)
                 .send()
                 .await
-                .context("Failed to connect to NVIDIA NIM API")?;
+                .map_err(|e| ProviderError::network("Failed to connect to NVIDIA NIM API", e))?;
 
             if !response.status().is_success() {
-                let error_text = response.text().await?;
-                if error_text.contains("401") || error_text.contains("Unauthorized") {
-                    return Err(anyhow::anyhow!(
+                let error = ProviderError::from_response("nvidia", response).await?;
+                let error = if let ProviderError::Auth { .. } = error {
+                    error.map_message(|_| {
                         "Invalid NVIDIA API key. Please check your API key configuration."
-                    ));
-                }
-                return Err(anyhow::anyhow!("NVIDIA NIM API error: {}", error_text));
+                            .to_string()
+                    })
+                } else {
+                    error.map_message(|body| format!("NVIDIA NIM API error: {}", body))
+                };
+                return Err(error.into());
             }
 
-            let nvidia_response: NvidiaResponse = response
-                .json()
-                .await
-                .context("Failed to parse NVIDIA NIM response")?;
+            let nvidia_response: NvidiaResponse =
+                streaming::json_response("nvidia", response, streaming::chat_completion)
+                    .await
+                    .map_err(|e| {
+                        ProviderError::invalid_response(format!(
+                            "Failed to parse NVIDIA NIM response: {:#}",
+                            e
+                        ))
+                    })?;
 
             Ok(nvidia_response)
         })
         .await
         .context("Failed to generate commit message from NVIDIA NIM after retries")?;
 
+        if let Some(token_usage) = nvidia_response.usage {
+            usage::record(config, &self.model, token_usage);
+        }
+
         let message = nvidia_response
             .choices
             .first()
             .map(|choice| choice.message.content.trim().to_string())
-            .context("NVIDIA NIM returned an empty response")?;
+            .ok_or_else(|| {
+                ProviderError::invalid_response("NVIDIA NIM returned an empty response")
+            })?;
 
         Ok(message)
     }
@@ -2,17 +2,23 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::build_prompt;
+use super::streaming;
 use super::AIProvider;
 // Note: Ollama uses the combined prompt since its API doesn't support separate system messages
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct OllamaProvider {
     client: Client,
     api_url: String,
     model: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -32,11 +38,15 @@ struct OllamaOptions {
 #[derive(Deserialize)]
 struct OllamaResponse {
     response: String,
+    #[serde(default)]
+    prompt_eval_count: u64,
+    #[serde(default)]
+    eval_count: u64,
 }
 
 impl OllamaProvider {
     pub fn new(config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::client(config)?;
         let api_url = config
             .api_url
             .as_deref()
@@ -48,6 +58,7 @@ impl OllamaProvider {
             client,
             api_url,
             model,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -58,7 +69,7 @@ impl OllamaProvider {
         _api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
         let api_url = account
             .api_url
             .as_deref()
@@ -75,6 +86,7 @@ impl OllamaProvider {
             client,
             api_url,
             model,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -93,42 +105,75 @@ impl AIProvider for OllamaProvider {
         let request = OllamaRequest {
             model: self.model.clone(),
             prompt,
-            stream: false,
+            stream: config.stream,
             options: OllamaOptions {
                 temperature: 0.7,
                 num_predict: config.tokens_max_output as i32,
             },
         };
 
-        let ollama_response: OllamaResponse = retry_async(|| async {
+        let ollama_response: OllamaResponse = retry_async(self.retry, || async {
             let url = format!("{}/api/generate", self.api_url);
+            payload_log::request("ollama", &url, &request);
             let response = self
                 .client
                 .post(&url)
                 .json(&request)
                 .send()
                 .await
-                .context("Failed to connect to Ollama")?;
+                .map_err(|e| ProviderError::network("Failed to connect to Ollama", e))?;
 
             if !response.status().is_success() {
-                let error_text = response.text().await?;
-                return Err(anyhow::anyhow!("Ollama API error: {}", error_text));
+                let error = ProviderError::from_response("ollama", response).await?;
+                return Err(error
+                    .map_message(|body| format!("Ollama API error: {}", body))
+                    .into());
             }
 
-            let ollama_response: OllamaResponse = response
-                .json()
-                .await
-                .context("Failed to parse Ollama response")?;
+            let ollama_response: OllamaResponse =
+                streaming::json_response("ollama", response, fold_stream)
+                    .await
+                    .map_err(|e| {
+                        ProviderError::invalid_response(format!(
+                            "Failed to parse Ollama response: {:#}",
+                            e
+                        ))
+                    })?;
 
             Ok(ollama_response)
         })
         .await
         .context("Failed to generate commit message from Ollama after retries")?;
 
+        usage::record(
+            config,
+            &self.model,
+            TokenUsage::new(
+                ollama_response.prompt_eval_count,
+                ollama_response.eval_count,
+            ),
+        );
+
         Ok(ollama_response.response.trim().to_string())
     }
 }
 
+/// Fold the lines of a streamed generation into one: the response pieces,
+/// with the token counts of the final `done` line.
+fn fold_stream(events: Vec<Value>) -> Result<Value> {
+    if let Some(error) = streaming::stream_error(&events) {
+        anyhow::bail!("The response stream reported an error: {}", error);
+    }
+
+    let response: String = events
+        .iter()
+        .filter_map(|event| event.get("response").and_then(Value::as_str))
+        .collect();
+    let mut folded = events.last().cloned().unwrap_or_default();
+    folded["response"] = Value::from(response);
+    Ok(folded)
+}
+
 /// ProviderBuilder for Ollama
 pub struct OllamaProviderBuilder;
 
@@ -1,64 +1,85 @@
 use anyhow::{Context, Result};
 use async_openai::{
-    config::OpenAIConfig,
+    config::{Config as _, OpenAIConfig},
     types::chat::{
         ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
-        CreateChatCompletionRequestArgs,
+        ChatCompletionStreamOptions, CreateChatCompletionRequestArgs, CreateChatCompletionResponse,
+        FinishReason,
     },
-    Client,
 };
 use async_trait::async_trait;
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
+use crate::auth::credentials::{CredentialProvider, Issuer};
 use crate::config::accounts::AccountConfig;
-use crate::config::Config;
-use crate::utils::retry::retry_async;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct OpenAIProvider {
-    client: Client<OpenAIConfig>,
+    http: reqwest::Client,
+    api_base: String,
+    credentials: CredentialProvider,
     model: String,
+    retry: RetryPolicy,
 }
 
 impl OpenAIProvider {
     pub fn new(config: &Config) -> Result<Self> {
-        let api_key = config
-            .api_key
-            .as_ref()
-            .context("OpenAI API key not configured.\nRun: rco config set RCO_API_KEY=<your_key>\nGet your API key from: https://platform.openai.com/api-keys")?;
+        // Codex uses the ChatGPT login from `rco auth login` unless a key is set
+        let codex_login = if config.ai_provider == "codex" && config.api_key.is_none() {
+            CredentialProvider::stored_login(Issuer::Codex, config)?
+        } else {
+            None
+        };
+        let credentials = match codex_login {
+            Some(credentials) => credentials,
+            None => CredentialProvider::fixed(config
+                .api_key
+                .as_ref()
+                .context("OpenAI API key not configured.\nRun: rco config set RCO_API_KEY=<your_key>\nGet your API key from: https://platform.openai.com/api-keys")?
+                .clone()),
+        };
 
-        let openai_config = OpenAIConfig::new().with_api_key(api_key).with_api_base(
-            config
+        Ok(Self {
+            http: http::client(config)?,
+            api_base: config
                 .api_url
                 .as_deref()
-                .unwrap_or("https://api.openai.com/v1"),
-        );
-
-        let client = Client::with_config(openai_config);
-        let model = config.model.clone();
-
-        Ok(Self { client, model })
+                .unwrap_or("https://api.openai.com/v1")
+                .to_string(),
+            credentials,
+            model: config.model.clone(),
+            retry: RetryPolicy::from_config(config),
+        })
     }
 
     /// Create provider from account configuration
     #[allow(dead_code)]
     pub fn from_account(account: &AccountConfig, api_key: &str, config: &Config) -> Result<Self> {
-        let openai_config = OpenAIConfig::new().with_api_key(api_key).with_api_base(
-            account
-                .api_url
-                .as_deref()
-                .or(config.api_url.as_deref())
-                .unwrap_or("https://api.openai.com/v1"),
-        );
-
-        let client = Client::with_config(openai_config);
+        let api_base = account
+            .api_url
+            .as_deref()
+            .or(config.api_url.as_deref())
+            .unwrap_or("https://api.openai.com/v1")
+            .to_string();
         let model = account
             .model
             .as_deref()
             .unwrap_or(&config.model)
             .to_string();
 
-        Ok(Self { client, model })
+        Ok(Self {
+            http: http::account_client(account, config)?,
+            api_base,
+            credentials: CredentialProvider::for_account(account, api_key, config)?,
+            model,
+            retry: RetryPolicy::for_account(account, config),
+        })
     }
 }
 
@@ -79,7 +100,7 @@ impl AIProvider for OpenAIProvider {
         ];
 
         // Handle model-specific parameters
-        let request = if self.model.contains("gpt-5-nano") {
+        let mut request = if self.model.contains("gpt-5-nano") {
             // GPT-5-nano doesn't support temperature=0, use 1.0 (default)
             CreateChatCompletionRequestArgs::default()
                 .model(&self.model)
@@ -97,27 +118,65 @@ impl AIProvider for OpenAIProvider {
                 .build()?
         };
 
-        let response = retry_async(|| async {
-            match self.client.chat().create(request.clone()).await {
-                Ok(resp) => Ok(resp),
-                Err(e) => {
-                    let error_msg = e.to_string();
-                    if error_msg.contains("401") || error_msg.contains("invalid_api_key") {
-                        Err(anyhow::anyhow!("Invalid OpenAI API key. Please check your API key configuration."))
-                    } else if error_msg.contains("insufficient_quota") {
-                        Err(anyhow::anyhow!("OpenAI API quota exceeded. Please check your billing status."))
-                    } else {
-                        Err(anyhow::anyhow!(e).context("Failed to generate commit message from OpenAI"))
-                    }
+        if config.stream {
+            request.stream = Some(true);
+            request.stream_options = Some(ChatCompletionStreamOptions {
+                include_usage: Some(true),
+                include_obfuscation: None,
+            });
+        }
+
+        let url = OpenAIConfig::new()
+            .with_api_base(&self.api_base)
+            .url("/chat/completions");
+        payload_log::request("openai", &url, &request);
+
+        // Sent with our own client rather than async-openai's, which retries
+        // by itself and drops the status and `Retry-After` of failed requests
+        let (url, request) = (&url, &request);
+        let response: CreateChatCompletionResponse = retry_async(self.retry, || self.credentials.with_token(|token| async move {
+            let response = self
+                .http
+                .post(url)
+                .bearer_auth(&token)
+                .json(request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to OpenAI", e))?;
+
+            if !response.status().is_success() {
+                let error = ProviderError::from_response("openai", response).await?;
+                return Err(match error {
+                    ProviderError::Auth { .. } => error.map_message(|_| "Invalid OpenAI API key. Please check your API key configuration.".to_string()),
+                    ProviderError::QuotaExceeded { .. } => error.map_message(|_| "OpenAI API quota exceeded. Please check your billing status.".to_string()),
+                    _ => error.map_message(|body| format!("OpenAI API error: {}", body)),
                 }
+                .into());
             }
-        }).await.context("Failed to generate commit message from OpenAI after retries. Please check your internet connection and API configuration.")?;
 
-        let message = response
-            .choices
-            .first()
+            streaming::json_response("openai", response, streaming::chat_completion)
+                .await
+                .map_err(|e| ProviderError::invalid_response(format!("Failed to parse OpenAI response: {:#}", e)).into())
+        })).await.context("Failed to generate commit message from OpenAI after retries. Please check your internet connection and API configuration.")?;
+
+        if let Some(usage) = &response.usage {
+            usage::record(
+                config,
+                &self.model,
+                TokenUsage::new(usage.prompt_tokens.into(), usage.completion_tokens.into()),
+            );
+        }
+
+        let choice = response.choices.first();
+        if choice.and_then(|c| c.finish_reason) == Some(FinishReason::ContentFilter) {
+            return Err(ProviderError::content_filtered(
+                "OpenAI's content filter blocked the response",
+            )
+            .into());
+        }
+        let message = choice
             .and_then(|choice| choice.message.content.as_ref())
-            .context("OpenAI returned an empty response. The model may be overloaded - please try again.")?
+            .ok_or_else(|| ProviderError::invalid_response("OpenAI returned an empty response. The model may be overloaded - please try again."))?
             .trim()
             .to_string();
 
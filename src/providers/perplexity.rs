@@ -3,14 +3,20 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct PerplexityProvider {
     client: Client,
     model: String,
     api_key: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -31,6 +37,8 @@ struct Message {
 #[derive(Deserialize)]
 struct PerplexityResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Deserialize)]
@@ -50,25 +58,26 @@ impl PerplexityProvider {
             .as_ref()
             .context("Perplexity API key not configured.\nRun: rco config set RCO_API_KEY=<your_key>\nGet your API key from: https://www.perplexity.ai/settings/api")?;
 
-        let client = Client::new();
+        let client = http::client(config)?;
         let model = config.model.clone();
 
         Ok(Self {
             client,
             model,
             api_key: api_key.clone(),
+            retry: RetryPolicy::from_config(config),
         })
     }
 
     /// Create provider from account configuration
     #[allow(dead_code)]
     pub fn from_account(
-        _account: &crate::config::accounts::AccountConfig,
+        account: &crate::config::accounts::AccountConfig,
         api_key: &str,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
-        let model = _account
+        let client = http::account_client(account, config)?;
+        let model = account
             .model
             .as_deref()
             .unwrap_or(&config.model)
@@ -78,6 +87,7 @@ impl PerplexityProvider {
             client,
             model,
             api_key: api_key.to_string(),
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 }
@@ -109,7 +119,7 @@ impl AIProvider for PerplexityProvider {
             messages,
             max_tokens: config.tokens_max_output,
             temperature: 0.7,
-            stream: false,
+            stream: config.stream,
         };
 
         let api_url = config
@@ -117,54 +127,60 @@ impl AIProvider for PerplexityProvider {
             .as_deref()
             .unwrap_or("https://api.perplexity.ai/chat/completions");
 
-        let response = match self
-            .client
-            .post(api_url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-        {
-            Ok(resp) => resp,
-            Err(e) => {
-                anyhow::bail!("Failed to connect to Perplexity API: {}. Please check your internet connection.", e);
-            }
-        };
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-
-            match status.as_u16() {
-                401 => anyhow::bail!(
-                    "Invalid Perplexity API key. Please check your API key configuration."
-                ),
-                429 => anyhow::bail!(
-                    "Perplexity API rate limit exceeded. Please wait a moment and try again."
-                ),
-                400 => {
-                    if error_text.contains("insufficient_quota") {
-                        anyhow::bail!(
-                            "Perplexity API quota exceeded. Please check your billing status."
-                        );
+        payload_log::request("perplexity", api_url, &request);
+
+        let perplexity_response: PerplexityResponse = retry_async(self.retry, || async {
+            let response = self
+                .client
+                .post(api_url)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to Perplexity API. Please check your internet connection.", e))?;
+
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let error = ProviderError::from_response("perplexity", response).await?;
+
+                return Err(match error {
+                    ProviderError::Auth { .. } => error.map_message(|_| {
+                        "Invalid Perplexity API key. Please check your API key configuration."
+                            .to_string()
+                    }),
+                    ProviderError::RateLimited { .. } => error.map_message(|_| {
+                        "Perplexity API rate limit exceeded. Please wait a moment and try again."
+                            .to_string()
+                    }),
+                    ProviderError::QuotaExceeded { .. } => error.map_message(|_| {
+                        "Perplexity API quota exceeded. Please check your billing status."
+                            .to_string()
+                    }),
+                    ProviderError::Rejected { status: 400, .. } => {
+                        error.map_message(|body| format!("Bad request to Perplexity API: {}", body))
                     }
-                    anyhow::bail!("Bad request to Perplexity API: {}", error_text);
+                    _ => error
+                        .map_message(|body| format!("Perplexity API error ({}): {}", status, body)),
                 }
-                _ => anyhow::bail!("Perplexity API error ({}): {}", status, error_text),
+                .into());
             }
-        }
 
-        let perplexity_response: PerplexityResponse = response
-            .json()
-            .await
-            .context("Failed to parse Perplexity API response")?;
+            streaming::json_response("perplexity", response, streaming::chat_completion)
+                .await
+                .map_err(|e| ProviderError::invalid_response(format!("Failed to parse Perplexity API response: {:#}", e)).into())
+        })
+        .await?;
+
+        if let Some(token_usage) = perplexity_response.usage {
+            usage::record(config, &self.model, token_usage);
+        }
 
         let message = perplexity_response
             .choices
             .first()
             .map(|choice| &choice.message.content)
-            .context("Perplexity returned an empty response. The model may be overloaded - please try again.")?
+            .ok_or_else(|| ProviderError::invalid_response("Perplexity returned an empty response. The model may be overloaded - please try again."))?
             .trim()
             .to_string();
 
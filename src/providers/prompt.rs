@@ -5,7 +5,9 @@
 
 use crate::config::Config;
 use crate::git;
-use crate::utils::commit_style::CommitStyleProfile;
+use crate::utils::few_shot;
+use crate::utils::scope_resolver::{changed_paths, ScopeResolver};
+use crate::utils::style_store;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
@@ -17,7 +19,10 @@ pub fn split_prompt(
     config: &Config,
     full_gitmoji: bool,
 ) -> (String, String) {
-    let system_prompt = build_system_prompt(config, full_gitmoji);
+    let mut system_prompt = build_system_prompt(config, full_gitmoji);
+    if let Some(examples) = get_few_shot_examples(diff, config) {
+        system_prompt.push_str(&examples);
+    }
     let user_prompt = build_user_prompt(diff, context, full_gitmoji, config);
     (system_prompt, user_prompt)
 }
@@ -111,26 +116,20 @@ pub fn build_system_prompt(config: &Config, full_gitmoji: bool) -> String {
 
 /// Get style guidance from commit history analysis
 fn get_style_guidance(config: &Config) -> Option<String> {
-    // Get cached style profile or analyze fresh
+    // A manually configured profile always wins
     if let Some(cached) = &config.style_profile {
-        // Use cached profile if available
         return Some(cached.clone());
     }
 
-    // Analyze recent commits - default now 50 for better learning
-    let count = config.history_commits_count;
-
-    match git::get_recent_commit_messages(count) {
-        Ok(commits) => {
-            if commits.is_empty() {
-                return None;
-            }
-
-            let profile = CommitStyleProfile::analyze_from_commits(&commits);
+    // Use the shared repo profile, or the cached one updated with new commits
+    let repo_root = git::get_repo_root().ok()?;
+    match style_store::current(&repo_root, config.history_commits_count) {
+        Ok((stored, _)) => {
+            let profile = stored.profile;
 
             // Only use profile if we have enough confident data (at least 10 commits with patterns)
             // Increased from 5 to 10 for better confidence
-            if profile.is_empty() || commits.len() < 10 {
+            if profile.is_empty() || profile.commits_analyzed < 10 {
                 return None;
             }
 
@@ -143,6 +142,24 @@ fn get_style_guidance(config: &Config) -> Option<String> {
     }
 }
 
+/// Get examples from the past commits most similar to the current diff
+fn get_few_shot_examples(diff: &str, config: &Config) -> Option<String> {
+    if config.few_shot_count == 0 {
+        return None;
+    }
+
+    let history = match git::get_commit_changes(config.history_commits_count) {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::warn!("Failed to read commit history for few-shot examples: {}", e);
+            return None;
+        }
+    };
+    let examples = few_shot::select_similar(&changed_paths(diff), &history, config.few_shot_count);
+    let examples = few_shot::format_examples(&examples, config.few_shot_max_tokens)?;
+    Some(format!("\n{}", examples))
+}
+
 /// Build the user prompt part (actual task + diff)
 pub fn build_user_prompt(
     diff: &str,
@@ -163,6 +180,11 @@ pub fn build_user_prompt(
         prompt.push_str(&format!("Files Changed: {}\n\n", file_summary));
     }
 
+    // Suggest scopes derived from the repository layout
+    if let Some(scope_guidance) = get_scope_guidance(diff, _config) {
+        prompt.push_str(&scope_guidance);
+    }
+
     // Add chunk indicator with more detail if diff was chunked
     if diff.contains("---CHUNK") {
         let chunk_count = diff.matches("---CHUNK").count();
@@ -192,6 +214,30 @@ pub fn build_user_prompt(
     prompt
 }
 
+/// Get scope suggestions for the changed files from the repository layout
+fn get_scope_guidance(diff: &str, config: &Config) -> Option<String> {
+    if config.omit_scope {
+        return None;
+    }
+
+    let repo_root = git::get_repo_root().ok()?;
+    let resolver = ScopeResolver::load(Path::new(&repo_root), config);
+    let candidates = resolver.candidates(&changed_paths(diff));
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let scopes: Vec<&str> = candidates
+        .iter()
+        .take(3)
+        .map(|c| c.scope.as_str())
+        .collect();
+    Some(format!(
+        "Suggested scopes (from repository layout, best first): {}\nUse one of these as the commit scope.\n\n",
+        scopes.join(", ")
+    ))
+}
+
 /// Extract file type summary from diff
 pub fn extract_file_summary(diff: &str) -> String {
     let mut files: Vec<String> = Vec::new();
@@ -236,7 +282,7 @@ pub fn extract_file_summary(diff: &str) -> String {
     // Add file type categories
     if !file_types.is_empty() {
         let mut type_list: Vec<_> = file_types.into_iter().collect();
-        type_list.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by count descending
+        type_list.sort_by_key(|(_, count)| std::cmp::Reverse(*count)); // Sort by count descending
 
         let type_str: Vec<_> = type_list
             .iter()
@@ -259,6 +305,54 @@ pub fn extract_file_summary(diff: &str) -> String {
     summary
 }
 
+/// Replace a diff by a summary of the files it changes and how many lines,
+/// for when not even part of the diff fits the model's context window.
+///
+/// The `+++ b/` lines are kept so the prompt's file summary and scope
+/// suggestions still work on the result.
+pub fn summarize_diff(diff: &str) -> String {
+    const LISTED: usize = 200;
+
+    // (path, added, removed) per file, in diff order
+    let mut files: Vec<(String, usize, usize)> = Vec::new();
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            let path = header.rsplit_once(" b/").map_or(header, |(_, b)| b);
+            files.push((path.to_string(), 0, 0));
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            match files.last_mut() {
+                Some(file) => file.0 = path.to_string(),
+                None => files.push((path.to_string(), 0, 0)),
+            }
+        } else if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        } else if let Some(file) = files.last_mut() {
+            if line.starts_with('+') {
+                file.1 += 1;
+            } else if line.starts_with('-') {
+                file.2 += 1;
+            }
+        }
+    }
+
+    let mut summary =
+        String::from("[Summary only: the full diff does not fit the model's context window]\n");
+    let file_summary = extract_file_summary(diff);
+    if !file_summary.is_empty() {
+        summary.push_str(&format!("Files Changed: {}\n", file_summary));
+    }
+    for (path, added, removed) in files.iter().take(LISTED) {
+        summary.push_str(&format!(
+            "+++ b/{}\n  {} line(s) added, {} removed\n",
+            path, added, removed
+        ));
+    }
+    if files.len() > LISTED {
+        summary.push_str(&format!("[{} more file(s)]\n", files.len() - LISTED));
+    }
+    summary
+}
+
 /// Categorize file extension into a type
 fn categorize_file_type(ext: &str) -> String {
     match ext {
@@ -510,7 +604,7 @@ fn extract_section_content<'a>(content: &'a str, header_line: &str) -> &'a str {
     let after_header = &content[header_idx + header_line.len()..];
 
     // Find next header (## or ###) or end of section
-    if let Some(newline_idx) = after_header.find(|c| c == '\n') {
+    if let Some(newline_idx) = after_header.find('\n') {
         let potential_header = after_header[newline_idx + 1..].trim();
         if potential_header.starts_with("##") {
             return &after_header[..newline_idx];
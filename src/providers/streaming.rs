@@ -0,0 +1,241 @@
+//! Streamed provider responses.
+//!
+//! With `RCO_STREAM=true`, providers ask for their answer as a stream of
+//! server-sent events (`text/event-stream`), or of JSON lines for Ollama
+//! (`application/x-ndjson`). [`json_response`] reads the stream to the end and
+//! hands its events to the provider's fold, which puts them back together into
+//! the provider's non-streamed response, so the rest of a provider is the same
+//! either way. A response that is not a stream is read as plain JSON.
+
+use anyhow::{Context, Result};
+use reqwest::header;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::utils::payload_log;
+
+/// Read a provider's response, folding the events of a streamed one with
+/// `fold`, and deserialize it.
+pub async fn json_response<T: DeserializeOwned>(
+    provider: &str,
+    response: reqwest::Response,
+    fold: fn(Vec<Value>) -> Result<Value>,
+) -> Result<T> {
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let sse = content_type.starts_with("text/event-stream");
+    if !sse && !content_type.contains("ndjson") {
+        return payload_log::json_response(provider, response).await;
+    }
+
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+    payload_log::response(provider, status, &text);
+
+    let events = if sse {
+        sse_events(&text)?
+    } else {
+        json_lines(&text)?
+    };
+    if events.is_empty() {
+        anyhow::bail!("Response stream ended without any events");
+    }
+    let folded = fold(events)?;
+    serde_json::from_value(folded).context("Response stream is not valid for this provider")
+}
+
+/// The JSON `data` of each event in a server-sent event stream. Comments,
+/// events without data and OpenAI's closing `[DONE]` are skipped.
+pub fn sse_events(text: &str) -> Result<Vec<Value>> {
+    let mut events = Vec::new();
+    let mut data: Vec<&str> = Vec::new();
+    // A blank line ends an event; the last one may not have it
+    for line in text.lines().chain([""]) {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            let payload = data.join("\n");
+            data.clear();
+            if payload.trim().is_empty() || payload.trim() == "[DONE]" {
+                continue;
+            }
+            events.push(
+                serde_json::from_str(&payload)
+                    .with_context(|| format!("Stream event is not valid JSON: {}", payload))?,
+            );
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push(value.strip_prefix(' ').unwrap_or(value));
+        }
+    }
+    Ok(events)
+}
+
+/// Each non-empty line of a JSON lines stream.
+pub fn json_lines(text: &str) -> Result<Vec<Value>> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Stream line is not valid JSON: {}", line))
+        })
+        .collect()
+}
+
+/// The error a stream reported in place of (or after) its answer.
+pub fn stream_error(events: &[Value]) -> Option<String> {
+    events.iter().find_map(|event| {
+        let error = event.get("error")?;
+        Some(match error {
+            Value::String(message) => message.clone(),
+            error => error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string()),
+        })
+    })
+}
+
+/// Fold OpenAI-style `chat.completion.chunk` events into a chat completion:
+/// the content deltas of the first choice, its finish reason and the usage
+/// sent in the last chunk (with `stream_options.include_usage`).
+#[cfg(any(
+    feature = "openai",
+    feature = "xai",
+    feature = "azure",
+    feature = "perplexity",
+    feature = "mlx",
+    feature = "nvidia"
+))]
+pub fn chat_completion(events: Vec<Value>) -> Result<Value> {
+    if let Some(error) = stream_error(&events) {
+        anyhow::bail!("The response stream reported an error: {}", error);
+    }
+
+    let mut content = String::new();
+    let mut finish_reason = Value::Null;
+    let mut usage = Value::Null;
+    for event in &events {
+        let choice = event.pointer("/choices/0");
+        if let Some(text) = choice
+            .and_then(|c| c.pointer("/delta/content"))
+            .and_then(Value::as_str)
+        {
+            content.push_str(text);
+        }
+        if let Some(reason) = choice.and_then(|c| c.get("finish_reason")) {
+            if !reason.is_null() {
+                finish_reason = reason.clone();
+            }
+        }
+        if let Some(chunk_usage) = event.get("usage").filter(|u| !u.is_null()) {
+            usage = chunk_usage.clone();
+        }
+    }
+
+    let first = &events[0];
+    Ok(serde_json::json!({
+        "id": first.get("id").cloned().unwrap_or_else(|| Value::from("")),
+        "object": "chat.completion",
+        "created": first.get("created").cloned().unwrap_or_else(|| Value::from(0)),
+        "model": first.get("model").cloned().unwrap_or_else(|| Value::from("")),
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": finish_reason,
+        }],
+        "usage": usage,
+    }))
+}
+
+/// Fold Gemini `GenerateContentResponse` chunks (also Vertex AI's) into one
+/// response: the parts of the first candidate, with consecutive text parts of
+/// the same kind joined, its finish reason, and the last prompt feedback and
+/// usage metadata.
+#[cfg(any(feature = "gemini", feature = "vertex"))]
+pub fn generate_content(events: Vec<Value>) -> Result<Value> {
+    if let Some(error) = stream_error(&events) {
+        anyhow::bail!("The response stream reported an error: {}", error);
+    }
+
+    let mut response = serde_json::json!({});
+    let mut parts: Vec<Value> = Vec::new();
+    let mut finish_reason = Value::Null;
+    for event in &events {
+        for key in ["promptFeedback", "usageMetadata"] {
+            if let Some(value) = event.get(key) {
+                response[key] = value.clone();
+            }
+        }
+        let Some(candidate) = event.pointer("/candidates/0") else {
+            continue;
+        };
+        if let Some(reason) = candidate.get("finishReason") {
+            finish_reason = reason.clone();
+        }
+        let chunk_parts = candidate
+            .pointer("/content/parts")
+            .and_then(Value::as_array);
+        for part in chunk_parts.into_iter().flatten() {
+            // Thoughts are parts with `thought: true`; keep them apart
+            match (parts.last_mut(), part.get("text").and_then(Value::as_str)) {
+                (Some(last), Some(text))
+                    if last.get("text").is_some() && last.get("thought") == part.get("thought") =>
+                {
+                    let joined = format!("{}{}", last["text"].as_str().unwrap_or_default(), text);
+                    last["text"] = Value::from(joined);
+                }
+                _ => parts.push(part.clone()),
+            }
+        }
+    }
+    if !parts.is_empty() || !finish_reason.is_null() {
+        let mut candidate = serde_json::json!({ "content": { "role": "model", "parts": parts } });
+        if !finish_reason.is_null() {
+            candidate["finishReason"] = finish_reason;
+        }
+        response["candidates"] = Value::Array(vec![candidate]);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_events_skip_comments_and_done() {
+        let text =
+            ": keep-alive\r\n\r\nevent: message\r\ndata: {\"a\":1}\r\n\r\ndata: [DONE]\r\n\r\n";
+        assert_eq!(sse_events(text).unwrap(), vec![serde_json::json!({"a": 1})]);
+    }
+
+    #[test]
+    fn test_sse_events_join_data_lines() {
+        let text = "data: {\"a\":\ndata: 2}\n\ndata:{\"b\":3}";
+        assert_eq!(
+            sse_events(text).unwrap(),
+            vec![serde_json::json!({"a": 2}), serde_json::json!({"b": 3})]
+        );
+    }
+
+    #[test]
+    fn test_sse_events_reject_invalid_json() {
+        assert!(sse_events("data: {\"a\":\n\n").is_err());
+    }
+
+    #[test]
+    fn test_stream_error_reads_message() {
+        let events = vec![
+            serde_json::json!({"type": "ping"}),
+            serde_json::json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        ];
+        assert_eq!(stream_error(&events).as_deref(), Some("Overloaded"));
+    }
+}
@@ -4,9 +4,14 @@ use reqwest::{header, Client};
 use serde::Serialize;
 use serde_json::Value;
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 #[derive(Clone)]
 pub struct VertexProvider {
@@ -14,7 +19,10 @@ pub struct VertexProvider {
     model: String,
     project_id: String,
     location: String,
+    /// Base URL requests go to: the regional endpoint, or `RCO_API_URL`.
+    api_url: String,
     access_token: String,
+    retry: RetryPolicy,
 }
 
 #[derive(Serialize)]
@@ -55,7 +63,7 @@ impl VertexProvider {
     }
 
     async fn new_async(config: &Config) -> Result<Self> {
-        let client = Client::new();
+        let client = http::client(config)?;
 
         let project_id = std::env::var("GOOGLE_CLOUD_PROJECT")
             .or_else(|_| std::env::var("CLOUDSDK_CORE_PROJECT"))
@@ -63,22 +71,9 @@ impl VertexProvider {
                 "Google Cloud project ID not set. Set GOOGLE_CLOUD_PROJECT or run 'gcloud init'",
             )?;
 
-        let location = config
-            .api_url
-            .as_ref()
-            .and_then(|url| {
-                url.split(".googleapis.com")
-                    .next()
-                    .and_then(|s| s.split('-').next())
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_else(|| "us-central1".to_string());
-
-        let model = config
-            .model
-            .as_deref()
-            .unwrap_or("gemini-1.5-pro")
-            .to_string();
+        let (location, api_url) = endpoint(config.api_url.as_deref());
+
+        let model = config.model.clone();
 
         let access_token = Self::get_gcloud_token().await?;
 
@@ -87,7 +82,9 @@ impl VertexProvider {
             model,
             project_id,
             location,
+            api_url,
             access_token,
+            retry: RetryPolicy::from_config(config),
         })
     }
 
@@ -105,28 +102,19 @@ impl VertexProvider {
         account: &crate::config::accounts::AccountConfig,
         config: &Config,
     ) -> Result<Self> {
-        let client = Client::new();
+        let client = http::account_client(account, config)?;
 
         let project_id = std::env::var("GOOGLE_CLOUD_PROJECT")
             .or_else(|_| std::env::var("CLOUDSDK_CORE_PROJECT"))
             .context("Google Cloud project ID not set")?;
 
-        let location = account
-            .api_url
-            .as_ref()
-            .and_then(|url| {
-                url.split(".googleapis.com")
-                    .next()
-                    .and_then(|s| s.split('-').next())
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_else(|| "us-central1".to_string());
+        let (location, api_url) =
+            endpoint(account.api_url.as_deref().or(config.api_url.as_deref()));
 
         let model = account
             .model
             .as_deref()
-            .or(config.model.as_deref())
-            .unwrap_or("gemini-1.5-pro")
+            .unwrap_or(&config.model)
             .to_string();
 
         let access_token = Self::get_gcloud_token().await?;
@@ -136,11 +124,18 @@ impl VertexProvider {
             model,
             project_id,
             location,
+            api_url,
             access_token,
+            retry: RetryPolicy::for_account(account, config),
         })
     }
 
     async fn get_gcloud_token() -> Result<String> {
+        // gcloud's own override, also handy where gcloud is not installed
+        if let Ok(token) = std::env::var("CLOUDSDK_AUTH_ACCESS_TOKEN") {
+            return Ok(token);
+        }
+
         let output = tokio::process::Command::new("gcloud")
             .args(["auth", "print-access-token"])
             .output()
@@ -186,57 +181,132 @@ impl AIProvider for VertexProvider {
                 }],
             }),
             generation_config: VertexGenerationConfig {
-                max_output_tokens: config.tokens_max_output.unwrap_or(500),
+                max_output_tokens: config.tokens_max_output,
                 temperature: 0.7,
             },
         };
 
-        let url = format!(
-            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/{}:streamGenerateContent",
-            self.location, self.project_id, self.location, self.model
+        let mut url = format!(
+            "{}/v1/projects/{}/locations/{}/publishers/google/models/{}:streamGenerateContent",
+            self.api_url, self.project_id, self.location, self.model
         );
+        if config.stream {
+            url.push_str("?alt=sse");
+        }
 
-        let response = self
-            .client
-            .post(&url)
-            .header(
-                header::AUTHORIZATION,
-                format!("Bearer {}", self.access_token),
-            )
-            .header(header::CONTENT_TYPE, "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to connect to Vertex AI")?;
+        payload_log::request("vertex", &url, &request);
+
+        let json: Value = retry_async(self.retry, || async {
+            let response = self
+                .client
+                .post(&url)
+                .header(
+                    header::AUTHORIZATION,
+                    format!("Bearer {}", self.access_token),
+                )
+                .header(header::CONTENT_TYPE, "application/json")
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to Vertex AI", e))?;
+
+            if !response.status().is_success() {
+                let error = ProviderError::from_response("vertex", response).await?;
+                return Err(error
+                    .map_message(|body| format!("Vertex AI API error: {}", body))
+                    .into());
+            }
+
+            streaming::json_response("vertex", response, streaming::generate_content)
+                .await
+                .map_err(|e| {
+                    ProviderError::invalid_response(format!(
+                        "Failed to parse Vertex AI response: {:#}",
+                        e
+                    ))
+                    .into()
+                })
+        })
+        .await?;
+
+        // streamGenerateContent answers with an array of chunks, each with
+        // part of the text; usage comes with the last one. Streamed as events
+        // (`alt=sse`), the chunks are already folded into one
+        let chunks: Vec<&Value> = match &json {
+            Value::Array(chunks) => chunks.iter().collect(),
+            chunk => vec![chunk],
+        };
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            anyhow::bail!("Vertex AI API error: {}", error_text);
+        if let Some(metadata) = chunks.iter().rev().find_map(|c| c.get("usageMetadata")) {
+            let count = |key: &str| metadata.get(key).and_then(Value::as_u64).unwrap_or(0);
+            usage::record(
+                config,
+                &self.model,
+                TokenUsage::new(count("promptTokenCount"), count("candidatesTokenCount")),
+            );
         }
 
-        let json: Value = response
-            .json()
-            .await
-            .context("Failed to parse Vertex AI response")?;
-
-        // Extract text from response
-        let message = json
-            .get("candidates")
-            .and_then(|candidates| candidates.as_array())
-            .and_then(|candidates| candidates.first())
-            .and_then(|cand| cand.get("content"))
-            .and_then(|content| content.get("parts"))
-            .and_then(|parts| parts.as_array())
-            .and_then(|parts| parts.first())
-            .and_then(|part| part.get("text"))
-            .and_then(|text| text.as_str())
-            .map(|s| s.to_string())
-            .context("No response from Vertex AI")?;
+        let mut message = String::new();
+        for chunk in chunks {
+            if let Some(reason) = chunk
+                .pointer("/promptFeedback/blockReason")
+                .and_then(Value::as_str)
+            {
+                return Err(ProviderError::content_filtered(format!(
+                    "Vertex AI blocked the prompt ({})",
+                    reason
+                ))
+                .into());
+            }
+
+            let Some(candidate) = chunk.pointer("/candidates/0") else {
+                continue;
+            };
+            if let Some(reason @ ("SAFETY" | "PROHIBITED_CONTENT" | "BLOCKLIST" | "SPII")) =
+                candidate.get("finishReason").and_then(Value::as_str)
+            {
+                return Err(ProviderError::content_filtered(format!(
+                    "Vertex AI blocked the response ({})",
+                    reason
+                ))
+                .into());
+            }
+            let parts = candidate
+                .pointer("/content/parts")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten();
+            for text in parts.filter_map(|part| part.get("text").and_then(Value::as_str)) {
+                message.push_str(text);
+            }
+        }
+        if message.trim().is_empty() {
+            return Err(ProviderError::invalid_response("No response from Vertex AI").into());
+        }
 
         Ok(message.trim().to_string())
     }
 }
 
+/// The location and base URL for `api_url`: a regional endpoint such as
+/// `https://europe-west4-aiplatform.googleapis.com`, or any other URL (a
+/// proxy) used as is with the default location.
+fn endpoint(api_url: Option<&str>) -> (String, String) {
+    const DEFAULT_LOCATION: &str = "us-central1";
+
+    let Some(url) = api_url.map(|url| url.trim_end_matches('/')) else {
+        return (
+            DEFAULT_LOCATION.to_string(),
+            format!("https://{}-aiplatform.googleapis.com", DEFAULT_LOCATION),
+        );
+    };
+    let host = url.split("://").nth(1).unwrap_or(url);
+    let location = host
+        .split_once("-aiplatform.googleapis.com")
+        .map_or(DEFAULT_LOCATION, |(location, _)| location);
+    (location.to_string(), url.to_string())
+}
+
 /// ProviderBuilder for Vertex AI
 pub struct VertexProviderBuilder;
 
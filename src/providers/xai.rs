@@ -1,22 +1,29 @@
 use anyhow::{Context, Result};
 use async_openai::{
-    config::OpenAIConfig,
+    config::{Config as _, OpenAIConfig},
     types::chat::{
         ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
-        CreateChatCompletionRequestArgs,
+        ChatCompletionStreamOptions, CreateChatCompletionRequestArgs, CreateChatCompletionResponse,
+        FinishReason,
     },
-    Client,
 };
 use async_trait::async_trait;
 
+use super::error::ProviderError;
 use super::prompt::split_prompt;
+use super::streaming;
 use super::AIProvider;
 use crate::config::accounts::AccountConfig;
-use crate::config::Config;
+use crate::config::{http, Config};
+use crate::usage::{self, TokenUsage};
+use crate::utils::payload_log;
+use crate::utils::retry::{retry_async, RetryPolicy};
 
 pub struct XAIProvider {
-    client: Client<OpenAIConfig>,
+    http: reqwest::Client,
+    config: OpenAIConfig,
     model: String,
+    retry: RetryPolicy,
 }
 
 impl XAIProvider {
@@ -30,10 +37,12 @@ impl XAIProvider {
             .with_api_key(api_key)
             .with_api_base(config.api_url.as_deref().unwrap_or("https://api.x.ai/v1"));
 
-        let client = Client::with_config(openai_config);
-        let model = config.model.clone();
-
-        Ok(Self { client, model })
+        Ok(Self {
+            http: http::client(config)?,
+            config: openai_config,
+            model: config.model.clone(),
+            retry: RetryPolicy::from_config(config),
+        })
     }
 
     /// Create provider from account configuration
@@ -47,14 +56,18 @@ impl XAIProvider {
                 .unwrap_or("https://api.x.ai/v1"),
         );
 
-        let client = Client::with_config(openai_config);
         let model = account
             .model
             .as_deref()
             .unwrap_or(&config.model)
             .to_string();
 
-        Ok(Self { client, model })
+        Ok(Self {
+            http: http::account_client(account, config)?,
+            config: openai_config,
+            model,
+            retry: RetryPolicy::for_account(account, config),
+        })
     }
 }
 
@@ -74,25 +87,73 @@ impl AIProvider for XAIProvider {
             ChatCompletionRequestUserMessage::from(user_prompt).into(),
         ];
 
-        let request = CreateChatCompletionRequestArgs::default()
+        let mut request = CreateChatCompletionRequestArgs::default()
             .model(&self.model)
             .messages(messages)
             .temperature(0.7)
             .max_tokens(config.tokens_max_output as u16)
             .build()?;
-
-        let response = self
-            .client
-            .chat()
-            .create(request)
-            .await
-            .context("Failed to generate commit message from xAI")?;
-
-        let message = response
-            .choices
-            .first()
+        if config.stream {
+            request.stream = Some(true);
+            request.stream_options = Some(ChatCompletionStreamOptions {
+                include_usage: Some(true),
+                include_obfuscation: None,
+            });
+        }
+
+        let url = self.config.url("/chat/completions");
+        payload_log::request("xai", &url, &request);
+
+        // Sent with our own client for the same reason as OpenAIProvider:
+        // async-openai's retries drop the status of failed requests
+        let response: CreateChatCompletionResponse = retry_async(self.retry, || async {
+            let response = self
+                .http
+                .post(&url)
+                .headers(self.config.headers())
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| ProviderError::network("Failed to connect to xAI", e))?;
+
+            if !response.status().is_success() {
+                let error = ProviderError::from_response("xai", response).await?;
+                return Err(error
+                    .map_message(|body| format!("xAI API error: {}", body))
+                    .into());
+            }
+
+            streaming::json_response("xai", response, streaming::chat_completion)
+                .await
+                .map_err(|e| {
+                    ProviderError::invalid_response(format!(
+                        "Failed to parse xAI response: {:#}",
+                        e
+                    ))
+                    .into()
+                })
+        })
+        .await
+        .context("Failed to generate commit message from xAI")?;
+
+        if let Some(usage) = &response.usage {
+            usage::record(
+                config,
+                &self.model,
+                TokenUsage::new(usage.prompt_tokens.into(), usage.completion_tokens.into()),
+            );
+        }
+
+        let choice = response.choices.first();
+        if choice.and_then(|c| c.finish_reason) == Some(FinishReason::ContentFilter) {
+            return Err(ProviderError::content_filtered(
+                "xAI's content filter blocked the response",
+            )
+            .into());
+        }
+        let message = choice
             .and_then(|choice| choice.message.content.as_ref())
-            .context("xAI returned an empty response")?
+            .ok_or_else(|| ProviderError::invalid_response("xAI returned an empty response"))?
             .trim()
             .to_string();
 
@@ -41,6 +41,7 @@
 //! - **URL**: Direct download from any HTTP(S) URL
 
 use anyhow::{Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -53,7 +54,7 @@ pub const SKILLS_DIR: &str = "skills";
 pub const SKILL_MANIFEST: &str = "skill.toml";
 
 /// A skill manifest defining the skill's metadata and capabilities
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct SkillManifest {
     /// Skill metadata
     pub skill: SkillMeta,
@@ -66,7 +67,7 @@ pub struct SkillManifest {
 }
 
 /// Skill metadata
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct SkillMeta {
     /// Unique name for the skill
     pub name: String,
@@ -85,7 +86,7 @@ pub struct SkillMeta {
 }
 
 /// Skill categories
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum SkillCategory {
     /// Prompt templates and generators
@@ -114,7 +115,7 @@ impl std::fmt::Display for SkillCategory {
 }
 
 /// Skill hooks for pipeline integration
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct SkillHooks {
     /// Runs before AI generation (receives diff, can modify)
     pub pre_gen: Option<String>,
@@ -125,12 +126,13 @@ pub struct SkillHooks {
 }
 
 /// Configuration option schema
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct SkillConfigOption {
     /// Option type (string, bool, number)
     pub r#type: String,
     /// Default value
     #[serde(default)]
+    #[schemars(with = "Option<serde_json::Value>")]
     pub default: Option<toml::Value>,
     /// Description
     pub description: String,
@@ -702,21 +704,14 @@ pub mod external {
             Ok(ExternalSource::ClaudeCode)
         } else if let Some(path) = source.strip_prefix("cline:") {
             // Parse cline:path format
-            let path = if path.starts_with("/") || path.starts_with("./") {
-                PathBuf::from(path)
-            } else {
-                // Try relative to home directory or use as-is
-                PathBuf::from(path)
-            };
-            Ok(ExternalSource::Cline { path })
+            Ok(ExternalSource::Cline {
+                path: PathBuf::from(path),
+            })
         } else if let Some(path) = source.strip_prefix("codex:") {
             // Parse codex:path format
-            let path = if path.starts_with("/") || path.starts_with("./") {
-                PathBuf::from(path)
-            } else {
-                PathBuf::from(path)
-            };
-            Ok(ExternalSource::Codex { path })
+            Ok(ExternalSource::Codex {
+                path: PathBuf::from(path),
+            })
         } else if let Some(roo_ref) = source.strip_prefix("roo:") {
             // Parse roo:owner/repo or roo:gist:id format
             if roo_ref.contains('/') {
@@ -729,31 +724,17 @@ pub mod external {
                 } else {
                     anyhow::bail!("Invalid Roo reference. Use format: roo:owner/repo or roo:gist:id");
                 }
-            } else if roo_ref.starts_with("gist:") {
-                Ok(ExternalSource::Roo {
-                    source: roo_ref.to_string(),
-                })
             } else {
+                // gist:id or bare reference
                 Ok(ExternalSource::Roo {
                     source: roo_ref.to_string(),
                 })
             }
         } else if let Some(kilo_ref) = source.strip_prefix("kilo:") {
             // Parse kilo:owner/repo or kilo:gist:id format
-            if kilo_ref.contains('/') {
-                // Likely owner/repo format
-                Ok(ExternalSource::Kilo {
-                    source: kilo_ref.to_string(),
-                })
-            } else if kilo_ref.starts_with("gist:") {
-                Ok(ExternalSource::Kilo {
-                    source: kilo_ref.to_string(),
-                })
-            } else {
-                Ok(ExternalSource::Kilo {
-                    source: kilo_ref.to_string(),
-                })
-            }
+            Ok(ExternalSource::Kilo {
+                source: kilo_ref.to_string(),
+            })
         } else if let Some(github_ref) = source.strip_prefix("github:") {
             // Parse github:owner/repo or github:owner/repo/path
             let parts: Vec<&str> = github_ref.split('/').collect();
@@ -965,7 +946,7 @@ pub mod external {
         }
 
         // Clone the repository (shallow clone for speed)
-        println!("Cloning {}/{}...", owner, repo);
+        crate::output::context::get().line(format!("Cloning {}/{}...", owner, repo));
         let status = Command::new("git")
             .args([
                 "clone",
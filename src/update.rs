@@ -207,7 +207,7 @@ pub async fn check_for_update() -> Result<UpdateInfo> {
 
 /// Update using Homebrew (safer version)
 async fn update_homebrew() -> Result<()> {
-    println!("{}", "Updating via Homebrew...".blue());
+    crate::output::context::get().line("Updating via Homebrew...".blue());
 
     // Check if brew exists first
     which::which("brew").context("Homebrew not found in PATH")?;
@@ -234,19 +234,19 @@ async fn update_homebrew() -> Result<()> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if stderr.contains("already installed") {
-            println!("{}", "Already up to date!".green());
+            crate::output::context::get().line("Already up to date!".green());
             return Ok(());
         }
         bail!("brew upgrade failed: {}", stderr);
     }
 
-    println!("{}", "Successfully updated via Homebrew!".green());
+    crate::output::context::get().line("Successfully updated via Homebrew!".green());
     Ok(())
 }
 
 /// Update using Cargo (safer version)
 async fn update_cargo() -> Result<()> {
-    println!("{}", "Updating via Cargo...".blue());
+    crate::output::context::get().line("Updating via Cargo...".blue());
 
     // Check if cargo exists
     which::which("cargo").context("Cargo not found in PATH")?;
@@ -269,7 +269,7 @@ async fn update_cargo() -> Result<()> {
         );
     }
 
-    println!("{}", "Successfully updated via Cargo!".green());
+    crate::output::context::get().line("Successfully updated via Cargo!".green());
     Ok(())
 }
 
@@ -279,7 +279,7 @@ async fn download_with_verification(
     expected_checksum: Option<&str>,
     max_size: u64,
 ) -> Result<Vec<u8>> {
-    println!("{}", format!("Downloading from: {}", url).blue());
+    crate::output::context::get().line(format!("Downloading from: {}", url).blue());
 
     let client = create_http_client()?;
     let response = client
@@ -328,7 +328,7 @@ async fn download_with_verification(
             bail!("Checksum verification failed");
         }
 
-        println!("{}", "Checksum verified".green());
+        crate::output::context::get().line("Checksum verified".green());
     }
 
     // TODO: Add Cosign signature verification in future version
@@ -402,7 +402,7 @@ async fn atomic_replace_file(source: &Path, target: &Path) -> Result<()> {
 
 /// Update Debian package (secure version)
 async fn update_deb(version: &str) -> Result<()> {
-    println!("{}", "Updating via apt/dpkg...".blue());
+    crate::output::context::get().line("Updating via apt/dpkg...".blue());
 
     // Validate version
     validate_version(version)?;
@@ -450,7 +450,7 @@ async fn update_deb(version: &str) -> Result<()> {
 
     match result {
         Ok(output) if output.status.success() => {
-            println!("{}", "Successfully updated via package manager!".green());
+            crate::output::context::get().line("Successfully updated via package manager!".green());
             Ok(())
         }
         Ok(output) => bail!(
@@ -463,7 +463,7 @@ async fn update_deb(version: &str) -> Result<()> {
 
 /// Update RPM package (secure version)
 async fn update_rpm(version: &str) -> Result<()> {
-    println!("{}", "Updating via rpm/dnf/yum...".blue());
+    crate::output::context::get().line("Updating via rpm/dnf/yum...".blue());
 
     // Validate version
     validate_version(version)?;
@@ -515,7 +515,7 @@ async fn update_rpm(version: &str) -> Result<()> {
 
     match result {
         Ok(output) if output.status.success() => {
-            println!("{}", "Successfully updated via package manager!".green());
+            crate::output::context::get().line("Successfully updated via package manager!".green());
             Ok(())
         }
         Ok(output) => bail!(
@@ -528,7 +528,7 @@ async fn update_rpm(version: &str) -> Result<()> {
 
 /// Update binary installation (secure version)
 async fn update_binary(version: &str, exe_path: &Path) -> Result<()> {
-    println!("{}", "Updating binary installation...".blue());
+    crate::output::context::get().line("Updating binary installation...".blue());
 
     // Validate inputs
     validate_version(version)?;
@@ -644,16 +644,14 @@ async fn update_binary(version: &str, exe_path: &Path) -> Result<()> {
         Ok(_) => {
             // Success - remove backup
             let _ = fs::remove_file(&backup_path);
-            println!("{}", "Successfully updated binary!".green());
+            crate::output::context::get().line("Successfully updated binary!".green());
             Ok(())
         }
         Err(e) => {
             // Try to restore backup
             if let Err(restore_err) = fs::rename(&backup_path, &exe_path) {
-                eprintln!(
-                    "{}",
-                    format!("Critical: Failed to restore backup: {}", restore_err).red()
-                );
+                crate::output::context::get()
+                    .error(format!("Critical: Failed to restore backup: {}", restore_err).red());
             }
             Err(e)
         }
@@ -662,7 +660,7 @@ async fn update_binary(version: &str, exe_path: &Path) -> Result<()> {
 
 /// Update Snap package
 async fn update_snap() -> Result<()> {
-    println!("{}", "Updating via Snap...".blue());
+    crate::output::context::get().line("Updating via Snap...".blue());
 
     which::which("snap").context("Snap not found in PATH")?;
 
@@ -674,30 +672,29 @@ async fn update_snap() -> Result<()> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if stderr.contains("has no updates available") {
-            println!("{}", "Already up to date!".green());
+            crate::output::context::get().line("Already up to date!".green());
             return Ok(());
         }
         bail!("snap refresh failed: {}", stderr);
     }
 
-    println!("{}", "Successfully updated via Snap!".green());
+    crate::output::context::get().line("Successfully updated via Snap!".green());
     Ok(())
 }
 
 /// Perform the update based on installation method
 pub async fn perform_update(info: &UpdateInfo) -> Result<()> {
     if !info.needs_update {
-        println!("{}", "Already running the latest version!".green());
+        crate::output::context::get().line("Already running the latest version!".green());
         return Ok(());
     }
 
-    println!(
-        "{}",
+    crate::output::context::get().line(
         format!(
             "Updating from v{} to v{}...",
             info.current_version, info.latest_version
         )
-        .blue()
+        .blue(),
     );
 
     match info.install_method {
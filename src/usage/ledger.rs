@@ -0,0 +1,235 @@
+//! Append-only usage ledger.
+//!
+//! Each provider call is stored as one JSON line in `usage.jsonl` in the
+//! config directory (`$RCO_CONFIG_HOME` or `~/.config/rustycommit`). Lines are
+//! never rewritten, so the file can be shared, rotated or archived with
+//! ordinary tools.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use dirs::home_dir;
+use serde::{Deserialize, Serialize};
+
+/// A single provider call recorded in the ledger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Unix timestamp (seconds) of the call.
+    pub timestamp: u64,
+    pub provider: String,
+    /// Account alias the request was sent with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    pub model: String,
+    /// Repository root the call was made from, if any.
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// Git `user.email` of the developer, if configured.
+    #[serde(default)]
+    pub user: Option<String>,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Estimated cost in USD; `None` when the model has no known price.
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+}
+
+/// Dimension used to group usage in reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// Group by AI provider.
+    #[default]
+    Provider,
+    /// Group by account alias.
+    Account,
+    /// Group by model name.
+    Model,
+    /// Group by repository.
+    Repo,
+    /// Group by developer (git user.email).
+    User,
+}
+
+impl GroupBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Provider => "provider",
+            Self::Account => "account",
+            Self::Model => "model",
+            Self::Repo => "repo",
+            Self::User => "user",
+        }
+    }
+}
+
+/// Aggregated usage for one group.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageSummary {
+    pub key: String,
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+    /// Requests whose model had no known price (excluded from `cost_usd`).
+    pub unpriced_requests: u64,
+}
+
+impl UsageSummary {
+    fn add(&mut self, record: &UsageRecord) {
+        self.requests += 1;
+        self.prompt_tokens += record.prompt_tokens;
+        self.completion_tokens += record.completion_tokens;
+        match record.cost_usd {
+            Some(cost) => self.cost_usd += cost,
+            None => self.unpriced_requests += 1,
+        }
+    }
+}
+
+/// Path of the usage ledger.
+pub fn ledger_path() -> Result<PathBuf> {
+    let config_dir = if let Ok(config_home) = std::env::var("RCO_CONFIG_HOME") {
+        PathBuf::from(config_home)
+    } else {
+        let home = home_dir().context("Could not find home directory")?;
+        home.join(".config").join("rustycommit")
+    };
+    Ok(config_dir.join("usage.jsonl"))
+}
+
+/// Append a record to the ledger.
+pub fn append(record: &UsageRecord) -> Result<()> {
+    let path = ledger_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create config directory")?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open usage ledger {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?).context("Failed to write usage ledger")?;
+    Ok(())
+}
+
+/// Load all records made at or after `since` (Unix seconds).
+///
+/// Malformed lines are skipped so a partially written entry never breaks
+/// reporting.
+pub fn load_since(since: u64) -> Result<Vec<UsageRecord>> {
+    let path = ledger_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = fs::File::open(&path)
+        .with_context(|| format!("Failed to open usage ledger {}", path.display()))?;
+
+    let records = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<UsageRecord>(&line).ok())
+        .filter(|record| record.timestamp >= since)
+        .collect();
+    Ok(records)
+}
+
+/// Group records and total them, most expensive group first.
+pub fn summarize(records: &[UsageRecord], by: GroupBy) -> Vec<UsageSummary> {
+    let mut groups: BTreeMap<String, UsageSummary> = BTreeMap::new();
+    for record in records {
+        let key = match by {
+            GroupBy::Provider => record.provider.clone(),
+            GroupBy::Account => record
+                .account
+                .clone()
+                .unwrap_or_else(|| "(none)".to_string()),
+            GroupBy::Model => record.model.clone(),
+            GroupBy::Repo => record.repo.clone().unwrap_or_else(|| "(none)".to_string()),
+            GroupBy::User => record
+                .user
+                .clone()
+                .unwrap_or_else(|| "(unknown)".to_string()),
+        };
+        groups
+            .entry(key.clone())
+            .or_insert_with(|| UsageSummary {
+                key,
+                ..Default::default()
+            })
+            .add(record);
+    }
+
+    let mut summaries: Vec<UsageSummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+    summaries
+}
+
+/// Total across all records.
+pub fn total(records: &[UsageRecord]) -> UsageSummary {
+    let mut total = UsageSummary {
+        key: "total".to_string(),
+        ..Default::default()
+    };
+    records.iter().for_each(|record| total.add(record));
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(provider: &str, model: &str, cost: Option<f64>) -> UsageRecord {
+        UsageRecord {
+            timestamp: 0,
+            provider: provider.to_string(),
+            account: None,
+            model: model.to_string(),
+            repo: None,
+            user: Some("dev@example.com".to_string()),
+            prompt_tokens: 100,
+            completion_tokens: 10,
+            cost_usd: cost,
+        }
+    }
+
+    #[test]
+    fn test_summarize_by_provider() {
+        let records = vec![
+            record("openai", "gpt-4o-mini", Some(0.01)),
+            record("anthropic", "claude-3-5-haiku", Some(0.05)),
+            record("openai", "gpt-4o", Some(0.02)),
+            record("openai", "custom-model", None),
+        ];
+        let summaries = summarize(&records, GroupBy::Provider);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].key, "anthropic");
+        assert_eq!(summaries[1].key, "openai");
+        assert_eq!(summaries[1].requests, 3);
+        assert_eq!(summaries[1].prompt_tokens, 300);
+        assert_eq!(summaries[1].unpriced_requests, 1);
+        assert!((summaries[1].cost_usd - 0.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summarize_by_account() {
+        let mut work = record("openai", "gpt-4o", Some(0.02));
+        work.account = Some("work".to_string());
+        let records = vec![work, record("openai", "gpt-4o", Some(0.01))];
+        let summaries = summarize(&records, GroupBy::Account);
+        assert_eq!(summaries[0].key, "work");
+        assert_eq!(summaries[1].key, "(none)");
+    }
+
+    #[test]
+    fn test_summarize_missing_repo() {
+        let records = vec![record("openai", "gpt-4o", Some(0.02))];
+        let summaries = summarize(&records, GroupBy::Repo);
+        assert_eq!(summaries[0].key, "(none)");
+    }
+}
@@ -0,0 +1,281 @@
+//! Token usage and cost tracking.
+//!
+//! Providers hand the token counts returned with each response to [`record`],
+//! which prices them with [`pricing`] and appends them to the local
+//! [`ledger`] under the provider and account that served the request (see
+//! [`serving`]). `rco usage` reports on the ledger, and [`check_budget`]
+//! enforces the optional monthly budget (`RCO_BUDGET_MONTHLY_USD`) before
+//! new requests are made.
+
+pub mod ledger;
+pub mod pricing;
+
+use std::future::Future;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::git;
+use crate::output;
+use crate::providers::error::RequestTarget;
+use ledger::UsageRecord;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Token counts reported by a provider for one request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub prompt_tokens: u64,
+    #[serde(default)]
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+        }
+    }
+}
+
+tokio::task_local! {
+    /// The provider and account serving the current request.
+    static TARGET: RequestTarget;
+}
+
+/// Run `request`, recording its usage under `target`'s provider and account.
+pub async fn serving<F: Future>(target: RequestTarget, request: F) -> F::Output {
+    TARGET.scope(target, request).await
+}
+
+/// Record the usage of one provider call in the ledger, under the target set
+/// by [`serving`], or the configured provider outside of one.
+///
+/// Failures are logged rather than returned: losing a ledger entry must never
+/// fail the commit that produced it.
+pub fn record(config: &Config, model: &str, usage: TokenUsage) {
+    let (provider, account) = TARGET
+        .try_with(|target| (target.provider.clone(), target.account.clone()))
+        .unwrap_or_else(|_| (config.ai_provider.clone(), None));
+    let cost_usd = pricing::cost_usd(&provider, model, &usage);
+    let entry = UsageRecord {
+        timestamp: now(),
+        provider,
+        account,
+        model: model.to_string(),
+        repo: git::get_repo_root().ok(),
+        user: git::get_user_email().ok(),
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        cost_usd,
+    };
+    if let Err(e) = ledger::append(&entry) {
+        tracing::warn!("Failed to record token usage: {:#}", e);
+    }
+}
+
+/// What happens once the monthly budget is used up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetAction {
+    /// Print a warning and continue.
+    #[default]
+    Warn,
+    /// Refuse to make further provider calls.
+    Block,
+}
+
+impl BudgetAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Block => "block",
+        }
+    }
+
+    /// Parse `RCO_BUDGET_ACTION` (`warn` or `block`).
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "block" => Ok(Self::Block),
+            other => anyhow::bail!("Invalid budget action '{}': use 'warn' or 'block'", other),
+        }
+    }
+}
+
+/// Month-to-date spend against the configured budget.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BudgetStatus {
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+    pub action: BudgetAction,
+}
+
+impl BudgetStatus {
+    pub fn exceeded(&self) -> bool {
+        self.spent_usd >= self.limit_usd
+    }
+}
+
+/// Current budget status, or `None` when no monthly budget is configured.
+pub fn budget_status(config: &Config) -> Result<Option<BudgetStatus>> {
+    let Some(limit_usd) = config.budget_monthly_usd else {
+        return Ok(None);
+    };
+    let action = match config.budget_action.as_deref() {
+        Some(value) => BudgetAction::parse(value)?,
+        None => BudgetAction::default(),
+    };
+    let spent_usd = ledger::total(&ledger::load_since(month_start(now()))?).cost_usd;
+
+    Ok(Some(BudgetStatus {
+        limit_usd,
+        spent_usd,
+        action,
+    }))
+}
+
+/// Warn or fail, per `RCO_BUDGET_ACTION`, when the monthly budget is spent.
+pub fn check_budget(config: &Config) -> Result<()> {
+    let Some(status) = budget_status(config)? else {
+        return Ok(());
+    };
+    if !status.exceeded() {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Monthly budget of ${:.2} reached (${:.2} spent this month)",
+        status.limit_usd, status.spent_usd
+    );
+    match status.action {
+        BudgetAction::Block => anyhow::bail!(
+            "{}. Raise RCO_BUDGET_MONTHLY_USD or set RCO_BUDGET_ACTION=warn to continue.",
+            message
+        ),
+        BudgetAction::Warn => {
+            output::context::get().warning(format!("⚠ {}", message));
+            Ok(())
+        }
+    }
+}
+
+/// Parse a `--since` value into a Unix timestamp.
+///
+/// Accepts `YYYY-MM-DD`, a relative age such as `7d` or `4w`, or `month`
+/// for the start of the current calendar month (UTC).
+pub fn parse_since(value: &str, now: u64) -> Result<u64> {
+    let value = value.trim();
+    if value == "month" {
+        return Ok(month_start(now));
+    }
+    if let Some(days) = value.strip_suffix('d') {
+        let days: u64 = days.parse().context("Invalid number of days")?;
+        return Ok(now.saturating_sub(days * SECONDS_PER_DAY));
+    }
+    if let Some(weeks) = value.strip_suffix('w') {
+        let weeks: u64 = weeks.parse().context("Invalid number of weeks")?;
+        return Ok(now.saturating_sub(weeks * 7 * SECONDS_PER_DAY));
+    }
+
+    let parts: Vec<&str> = value.split('-').collect();
+    if let [year, month, day] = parts.as_slice() {
+        let year: i64 = year.parse().context("Invalid year")?;
+        let month: u32 = month.parse().context("Invalid month")?;
+        let day: u32 = day.parse().context("Invalid day")?;
+        if (1..=12).contains(&month) && (1..=31).contains(&day) {
+            let days = days_from_civil(year, month, day);
+            return Ok((days.max(0) as u64) * SECONDS_PER_DAY);
+        }
+    }
+    anyhow::bail!(
+        "Invalid --since value '{}': use YYYY-MM-DD, <N>d, <N>w or 'month'",
+        value
+    )
+}
+
+/// Unix timestamp of the first second of the month containing `timestamp` (UTC).
+pub fn month_start(timestamp: u64) -> u64 {
+    let (year, month, _) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    days_from_civil(year, month, 1) as u64 * SECONDS_PER_DAY
+}
+
+/// Current Unix timestamp in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Calendar date for a count of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-15T12:00:00Z
+    const MID_MARCH_2024: u64 = 1_710_504_000;
+
+    #[test]
+    fn test_civil_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2024, 3, 1), 19_783);
+        assert_eq!(civil_from_days(19_783), (2024, 3, 1));
+        assert_eq!(civil_from_days(days_from_civil(2000, 2, 29)), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_month_start() {
+        assert_eq!(month_start(MID_MARCH_2024), 19_783 * SECONDS_PER_DAY);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = MID_MARCH_2024;
+        assert_eq!(parse_since("7d", now).unwrap(), now - 7 * SECONDS_PER_DAY);
+        assert_eq!(parse_since("2w", now).unwrap(), now - 14 * SECONDS_PER_DAY);
+        assert_eq!(parse_since("month", now).unwrap(), month_start(now));
+        assert_eq!(
+            parse_since("2024-03-01", now).unwrap(),
+            19_783 * SECONDS_PER_DAY
+        );
+        assert!(parse_since("yesterday", now).is_err());
+        assert!(parse_since("2024-13-01", now).is_err());
+    }
+
+    #[test]
+    fn test_budget_action_parse() {
+        assert_eq!(BudgetAction::parse("warn").unwrap(), BudgetAction::Warn);
+        assert_eq!(BudgetAction::parse("BLOCK").unwrap(), BudgetAction::Block);
+        assert!(BudgetAction::parse("stop").is_err());
+    }
+}
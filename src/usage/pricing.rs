@@ -0,0 +1,125 @@
+//! Per-model price table used to estimate the cost of provider calls.
+//!
+//! Prices are USD per one million tokens and are matched against the model
+//! name by longest prefix, so dated snapshots (e.g. `gpt-4o-mini-2024-07-18`)
+//! resolve to their family price.
+
+use super::TokenUsage;
+
+/// Price of a model in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+/// Providers that run locally and never incur API charges.
+const LOCAL_PROVIDERS: &[&str] = &["ollama", "mlx"];
+
+/// `(model prefix, input, output)` in USD per million tokens.
+const PRICES: &[(&str, f64, f64)] = &[
+    // OpenAI
+    ("gpt-3.5-turbo", 0.50, 1.50),
+    ("gpt-4-turbo", 10.00, 30.00),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-5", 1.25, 10.00),
+    ("gpt-5-mini", 0.25, 2.00),
+    ("gpt-5-nano", 0.05, 0.40),
+    ("o1", 15.00, 60.00),
+    ("o1-mini", 1.10, 4.40),
+    ("o3", 2.00, 8.00),
+    ("o3-mini", 1.10, 4.40),
+    ("o4-mini", 1.10, 4.40),
+    // Anthropic
+    ("claude-3-haiku", 0.25, 1.25),
+    ("claude-3-5-haiku", 0.80, 4.00),
+    ("claude-haiku-4", 1.00, 5.00),
+    ("claude-3-5-sonnet", 3.00, 15.00),
+    ("claude-3-7-sonnet", 3.00, 15.00),
+    ("claude-sonnet-4", 3.00, 15.00),
+    ("claude-3-opus", 15.00, 75.00),
+    ("claude-opus-4", 15.00, 75.00),
+    // Google
+    ("gemini-1.5-flash", 0.075, 0.30),
+    ("gemini-1.5-pro", 1.25, 5.00),
+    ("gemini-2.0-flash", 0.10, 0.40),
+    ("gemini-2.5-flash", 0.30, 2.50),
+    ("gemini-2.5-pro", 1.25, 10.00),
+    // xAI
+    ("grok-2", 2.00, 10.00),
+    ("grok-3", 3.00, 15.00),
+    ("grok-3-mini", 0.30, 0.50),
+    ("grok-4", 3.00, 15.00),
+];
+
+/// Look up the price for a model, or `None` if it is not in the table.
+pub fn price_for(provider: &str, model: &str) -> Option<ModelPrice> {
+    if LOCAL_PROVIDERS.contains(&provider) {
+        return Some(ModelPrice {
+            input_per_mtok: 0.0,
+            output_per_mtok: 0.0,
+        });
+    }
+
+    // Strip routing prefixes such as "openai/gpt-4o" (OpenRouter) or
+    // "anthropic.claude-3-5-haiku" (Bedrock).
+    let model = model.to_lowercase();
+    let model = model
+        .rsplit_once('/')
+        .map(|(_, name)| name)
+        .unwrap_or(&model);
+    let model = model.strip_prefix("anthropic.").unwrap_or(model);
+
+    PRICES
+        .iter()
+        .filter(|(prefix, _, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|&(_, input, output)| ModelPrice {
+            input_per_mtok: input,
+            output_per_mtok: output,
+        })
+}
+
+/// Estimated cost in USD, or `None` for models without a known price.
+pub fn cost_usd(provider: &str, model: &str, usage: &TokenUsage) -> Option<f64> {
+    let price = price_for(provider, model)?;
+    Some(
+        usage.prompt_tokens as f64 * price.input_per_mtok / 1_000_000.0
+            + usage.completion_tokens as f64 * price.output_per_mtok / 1_000_000.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let mini = price_for("openai", "gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!(mini.input_per_mtok, 0.15);
+        let full = price_for("openai", "gpt-4o").unwrap();
+        assert_eq!(full.input_per_mtok, 2.50);
+    }
+
+    #[test]
+    fn test_routed_model_names() {
+        assert!(price_for("openrouter", "anthropic/claude-3-5-haiku").is_some());
+        assert!(price_for("bedrock", "anthropic.claude-3-5-haiku-20241022-v1:0").is_some());
+    }
+
+    #[test]
+    fn test_cost_calculation() {
+        let usage = TokenUsage {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 500_000,
+        };
+        let cost = cost_usd("openai", "gpt-4o-mini", &usage).unwrap();
+        assert!((cost - 0.45).abs() < 1e-9);
+        assert_eq!(cost_usd("ollama", "llama3.2", &usage), Some(0.0));
+        assert_eq!(cost_usd("openai", "some-unknown-model", &usage), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! Commit message analytics for `rco stats`.
+//!
+//! Aggregates the subjects of a range of commits into type/scope
+//! distributions, Conventional Commits compliance and gitmoji usage, and
+//! counts commits carrying an AI-generation trailer.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::commit_style::parse_subject;
+use crate::git::CommitInfo;
+
+/// Trailer key that marks a commit as AI-generated, unless overridden.
+pub const DEFAULT_AI_TRAILER: &str = "Generated-by";
+
+/// Occurrences of one value (a type, scope or emoji).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Count {
+    pub name: String,
+    pub count: usize,
+    /// Fraction of analyzed commits (0.0 - 1.0)
+    pub share: f64,
+}
+
+/// Per-author Conventional Commits compliance.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorStats {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    pub conventional: usize,
+    pub compliance_rate: f64,
+}
+
+/// Aggregated statistics over a set of commits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CommitStats {
+    /// Non-merge commits analyzed
+    pub commits: usize,
+    /// Merge commits excluded from the analysis
+    pub merges_skipped: usize,
+    pub types: Vec<Count>,
+    pub scopes: Vec<Count>,
+    pub avg_subject_length: f64,
+    pub conventional_commits: usize,
+    pub compliance_rate: f64,
+    pub gitmoji_commits: usize,
+    pub gitmoji_rate: f64,
+    pub emojis: Vec<Count>,
+    pub ai_trailer: String,
+    pub ai_generated_commits: usize,
+    pub ai_generated_rate: f64,
+    /// Authors ordered by compliance rate, then commit count
+    pub authors: Vec<AuthorStats>,
+}
+
+impl CommitStats {
+    /// Analyze `commits`, keeping at most `top_authors` authors.
+    pub fn from_commits(commits: &[CommitInfo], ai_trailer: &str, top_authors: usize) -> Self {
+        let mut stats = Self {
+            ai_trailer: ai_trailer.to_string(),
+            ..Default::default()
+        };
+
+        let mut types: HashMap<String, usize> = HashMap::new();
+        let mut scopes: HashMap<String, usize> = HashMap::new();
+        let mut emojis: HashMap<String, usize> = HashMap::new();
+        let mut authors: HashMap<String, AuthorStats> = HashMap::new();
+        let mut subject_chars = 0;
+
+        for commit in commits {
+            if commit.is_merge {
+                stats.merges_skipped += 1;
+                continue;
+            }
+            stats.commits += 1;
+
+            let subject = commit.message.lines().next().unwrap_or("").trim();
+            subject_chars += subject.chars().count();
+
+            let parsed = parse_subject(subject);
+            let conventional = parsed.as_ref().is_some_and(|p| p.is_conventional());
+            if let Some(parsed) = parsed {
+                *types.entry(parsed.commit_type.to_lowercase()).or_insert(0) += 1;
+                if let Some(scope) = parsed.scope {
+                    *scopes.entry(scope).or_insert(0) += 1;
+                }
+                if let Some(emoji) = parsed.emoji {
+                    stats.gitmoji_commits += 1;
+                    *emojis.entry(emoji).or_insert(0) += 1;
+                }
+            }
+            if conventional {
+                stats.conventional_commits += 1;
+            }
+            if has_trailer(&commit.message, ai_trailer) {
+                stats.ai_generated_commits += 1;
+            }
+
+            let author = authors
+                .entry(commit.author_email.to_lowercase())
+                .or_insert_with(|| AuthorStats {
+                    name: commit.author_name.clone(),
+                    email: commit.author_email.clone(),
+                    commits: 0,
+                    conventional: 0,
+                    compliance_rate: 0.0,
+                });
+            author.commits += 1;
+            if conventional {
+                author.conventional += 1;
+            }
+        }
+
+        if stats.commits == 0 {
+            return stats;
+        }
+
+        let total = stats.commits;
+        stats.types = ranked(types, total);
+        stats.scopes = ranked(scopes, total);
+        stats.emojis = ranked(emojis, total);
+        stats.avg_subject_length = subject_chars as f64 / total as f64;
+        stats.compliance_rate = rate(stats.conventional_commits, total);
+        stats.gitmoji_rate = rate(stats.gitmoji_commits, total);
+        stats.ai_generated_rate = rate(stats.ai_generated_commits, total);
+
+        let mut authors: Vec<AuthorStats> = authors
+            .into_values()
+            .map(|mut author| {
+                author.compliance_rate = rate(author.conventional, author.commits);
+                author
+            })
+            .collect();
+        authors.sort_by(|a, b| {
+            b.compliance_rate
+                .total_cmp(&a.compliance_rate)
+                .then(b.commits.cmp(&a.commits))
+                .then(a.email.cmp(&b.email))
+        });
+        authors.truncate(top_authors);
+        stats.authors = authors;
+
+        stats
+    }
+}
+
+/// Whether the trailer block (last paragraph) of `message` contains `key`.
+fn has_trailer(message: &str, key: &str) -> bool {
+    let mut paragraphs = message.trim_end().split("\n\n");
+    // The subject paragraph never holds trailers
+    paragraphs.next();
+    paragraphs.last().is_some_and(|block| {
+        block.lines().any(|line| {
+            line.split_once(':')
+                .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+        })
+    })
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+fn ranked(counts: HashMap<String, usize>, total: usize) -> Vec<Count> {
+    let mut ranked: Vec<Count> = counts
+        .into_iter()
+        .map(|(name, count)| Count {
+            name,
+            count,
+            share: rate(count, total),
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(email: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            id: "abc1234".to_string(),
+            author_name: email.split('@').next().unwrap().to_string(),
+            author_email: email.to_string(),
+            message: message.to_string(),
+            is_merge: false,
+        }
+    }
+
+    #[test]
+    fn test_from_commits() {
+        let mut merge = commit("a@example.com", "Merge branch 'dev'");
+        merge.is_merge = true;
+        let commits = vec![
+            commit("a@example.com", "feat(auth): add login"),
+            commit(
+                "a@example.com",
+                "fix(auth): handle expiry\n\nGenerated-by: rusty-commit",
+            ),
+            commit("b@example.com", "✨ feat: add search"),
+            commit("b@example.com", "Update readme"),
+            merge,
+        ];
+
+        let stats = CommitStats::from_commits(&commits, DEFAULT_AI_TRAILER, 10);
+
+        assert_eq!(stats.commits, 4);
+        assert_eq!(stats.merges_skipped, 1);
+        assert_eq!(stats.types[0].name, "feat");
+        assert_eq!(stats.types[0].count, 2);
+        assert_eq!(stats.scopes[0].name, "auth");
+        assert_eq!(stats.conventional_commits, 3);
+        assert_eq!(stats.gitmoji_commits, 1);
+        assert_eq!(stats.ai_generated_commits, 1);
+        assert!((stats.compliance_rate - 0.75).abs() < 1e-9);
+
+        assert_eq!(stats.authors[0].email, "a@example.com");
+        assert_eq!(stats.authors[0].compliance_rate, 1.0);
+        assert_eq!(stats.authors[1].compliance_rate, 0.5);
+    }
+
+    #[test]
+    fn test_has_trailer() {
+        assert!(has_trailer(
+            "feat: x\n\nBody text.\n\ngenerated-by: rco",
+            "Generated-by"
+        ));
+        // Only the trailer block counts, not the subject or body
+        assert!(!has_trailer("Generated-by: rco", "Generated-by"));
+        assert!(!has_trailer(
+            "feat: x\n\nGenerated-by: rco\n\nSigned-off-by: me",
+            "Generated-by"
+        ));
+    }
+
+    #[test]
+    fn test_empty() {
+        let stats = CommitStats::from_commits(&[], DEFAULT_AI_TRAILER, 10);
+        assert_eq!(stats.commits, 0);
+        assert_eq!(stats.compliance_rate, 0.0);
+    }
+}
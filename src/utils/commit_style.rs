@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
 /// Represents a learned style profile from commit history
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CommitStyleProfile {
     /// Most common commit types used (e.g., "feat", "fix")
     pub type_frequencies: HashMap<String, usize>,
@@ -21,9 +24,23 @@ pub struct CommitStyleProfile {
     pub adds_period: bool,
     /// Whether descriptions are typically capitalized
     pub capitalizes_description: bool,
+    /// Number of commits the profile was built from
+    pub commits_analyzed: usize,
+    /// Running totals kept so the profile can be updated incrementally
+    counters: StyleCounters,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct StyleCounters {
+    descriptions: usize,
+    description_chars: usize,
+    periods: usize,
+    capitalized: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum PrefixFormat {
     #[default]
@@ -39,18 +56,19 @@ impl CommitStyleProfile {
     /// Analyze commits and generate a style profile
     pub fn analyze_from_commits<T: AsRef<str>>(commits: &[T]) -> Self {
         let mut profile = Self::default();
+        profile.update_from_commits(commits);
+        profile
+    }
 
+    /// Fold additional commits into the profile
+    pub fn update_from_commits<T: AsRef<str>>(&mut self, commits: &[T]) {
         if commits.is_empty() {
-            return profile;
+            return;
         }
 
-        let total = commits.len() as f64;
-
-        // Count types, scopes, and analyze each commit
-        let mut total_desc_len = 0;
-        let mut desc_count = 0;
-        let mut periods = 0;
-        let mut capitalized = 0;
+        let profile = self;
+        profile.commits_analyzed += commits.len();
+        let total = profile.commits_analyzed as f64;
 
         for commit in commits {
             let commit_str = commit.as_ref();
@@ -119,32 +137,33 @@ impl CommitStyleProfile {
             // Analyze description
             if let Some(desc) = commit_str.split_once(':').map(|x| x.1) {
                 let desc = desc.trim();
-                total_desc_len += desc.len();
-                desc_count += 1;
+                let counters = &mut profile.counters;
+                counters.description_chars += desc.len();
+                counters.descriptions += 1;
 
                 // Check for period at end
                 if desc.ends_with('.') {
-                    periods += 1;
+                    counters.periods += 1;
                 }
 
                 // Check if first char is capitalized
                 if let Some(first) = desc.chars().next() {
                     if first.is_ascii_uppercase() {
-                        capitalized += 1;
+                        counters.capitalized += 1;
                     }
                 }
             }
         }
 
         // Calculate averages and percentages
-        if desc_count > 0 {
-            profile.avg_description_length = total_desc_len as f64 / desc_count as f64;
-            profile.adds_period = (periods as f64 / total) > 0.3; // 30% threshold
-            profile.capitalizes_description = (capitalized as f64 / desc_count as f64) > 0.5;
+        let counters = &profile.counters;
+        if counters.descriptions > 0 {
+            let desc_count = counters.descriptions as f64;
+            profile.avg_description_length = counters.description_chars as f64 / desc_count;
+            profile.adds_period = (counters.periods as f64 / total) > 0.3; // 30% threshold
+            profile.capitalizes_description = (counters.capitalized as f64 / desc_count) > 0.5;
             // 50% threshold
         }
-
-        profile
     }
 
     /// Generate style guidance text for the AI prompt
@@ -241,6 +260,77 @@ impl CommitStyleProfile {
     }
 }
 
+/// The parts of a single commit subject line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSubject {
+    /// Leading gitmoji, if any
+    pub emoji: Option<String>,
+    /// Commit type (e.g., "feat")
+    pub commit_type: String,
+    /// Scope between parentheses, if any
+    pub scope: Option<String>,
+    /// Whether the subject is marked as breaking with `!`
+    pub breaking: bool,
+    pub description: String,
+}
+
+impl ParsedSubject {
+    /// Whether the subject follows the Conventional Commits format with a known type
+    pub fn is_conventional(&self) -> bool {
+        is_valid_commit_type(&self.commit_type)
+            && self.commit_type.chars().all(|c| c.is_ascii_lowercase())
+            && !self.description.is_empty()
+    }
+}
+
+/// Parse a subject line of the form `[emoji] type[(scope)][!]: description`.
+///
+/// Returns `None` when the subject has no `type:` prefix at all.
+pub fn parse_subject(subject: &str) -> Option<ParsedSubject> {
+    let subject = subject.trim();
+    let (prefix, description) = subject.split_once(':')?;
+
+    let (emoji, prefix) = match prefix.chars().next() {
+        Some(c) if is_emoji(c) => {
+            let emoji: String = prefix
+                .chars()
+                .take_while(|c| !c.is_ascii_alphanumeric())
+                .collect();
+            let rest = &prefix[emoji.len()..];
+            (Some(emoji.trim().to_string()), rest)
+        }
+        _ => (None, prefix),
+    };
+
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(rest) => (rest, true),
+        None => (prefix, false),
+    };
+
+    let (commit_type, scope) = match prefix.split_once('(') {
+        Some((commit_type, rest)) => {
+            let scope = rest.strip_suffix(')')?;
+            (
+                commit_type,
+                Some(scope.to_string()).filter(|s| !s.is_empty()),
+            )
+        }
+        None => (prefix, None),
+    };
+
+    if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    Some(ParsedSubject {
+        emoji,
+        commit_type: commit_type.to_string(),
+        scope,
+        breaking,
+        description: description.trim().to_string(),
+    })
+}
+
 /// Check if a character is an emoji
 fn is_emoji(c: char) -> bool {
     // Basic check for common emoji ranges
@@ -329,6 +419,56 @@ mod tests {
         assert!(profile.uses_gitmoji);
     }
 
+    #[test]
+    fn test_incremental_update_matches_full_analysis() {
+        let older = vec!["feat(auth): add login", "fix(api): Resolve issue."];
+        let newer = vec!["docs: update readme", "feat(ui): Add dark mode"];
+
+        let mut incremental = CommitStyleProfile::analyze_from_commits(&older);
+        incremental.update_from_commits(&newer);
+
+        let all: Vec<&str> = older.iter().chain(newer.iter()).copied().collect();
+        let full = CommitStyleProfile::analyze_from_commits(&all);
+
+        assert_eq!(incremental.commits_analyzed, 4);
+        assert_eq!(incremental.type_frequencies, full.type_frequencies);
+        assert_eq!(incremental.scope_frequencies, full.scope_frequencies);
+        assert_eq!(
+            incremental.avg_description_length,
+            full.avg_description_length
+        );
+        assert_eq!(
+            incremental.capitalizes_description,
+            full.capitalizes_description
+        );
+    }
+
+    #[test]
+    fn test_profile_round_trips_through_json() {
+        let profile = CommitStyleProfile::analyze_from_commits(&["✨ feat(auth): add login"]);
+        let json = serde_json::to_string(&profile).unwrap();
+        let restored: CommitStyleProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(profile, restored);
+    }
+
+    #[test]
+    fn test_parse_subject() {
+        let parsed = parse_subject("feat(auth)!: drop legacy tokens").unwrap();
+        assert_eq!(parsed.commit_type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("auth"));
+        assert!(parsed.breaking);
+        assert!(parsed.is_conventional());
+
+        let parsed = parse_subject("✨ feat: add login").unwrap();
+        assert_eq!(parsed.emoji.as_deref(), Some("✨"));
+        assert_eq!(parsed.commit_type, "feat");
+        assert!(parsed.is_conventional());
+
+        assert!(!parse_subject("Update: readme").unwrap().is_conventional());
+        assert!(parse_subject("Fix the build").is_none());
+        assert!(parse_subject("Merge branch 'main' into dev: sync").is_none());
+    }
+
     #[test]
     fn test_generate_prompt_guidance() {
         let commits = vec!["feat(auth): add login", "fix(api): resolve issue"];
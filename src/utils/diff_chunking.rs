@@ -283,6 +283,88 @@ pub fn chunk_diff(diff: &str, max_tokens: usize) -> String {
         .join("\n\n")
 }
 
+/// Cuts a diff down to `max_tokens`, for when even the chunked diff does not
+/// fit the model's context window.
+///
+/// Whole files are kept in order while they fit. The first file that does not
+/// fit is cut off at the budget, and files left out entirely are listed at the
+/// end so the model still knows they changed.
+pub fn truncate_diff(diff: &str, max_tokens: usize) -> String {
+    let mut kept = String::new();
+    let mut used = 0;
+    let mut cut = false;
+    let mut omitted = Vec::new();
+
+    for section in split_by_file(diff) {
+        let tokens = estimate_tokens(section).unwrap_or(usize::MAX);
+        if used + tokens <= max_tokens {
+            kept.push_str(section);
+            used += tokens;
+            continue;
+        }
+        if !cut && used < max_tokens {
+            cut = true;
+            for line in section.lines() {
+                let line_tokens = estimate_tokens(line).unwrap_or(1) + 1;
+                if used + line_tokens > max_tokens {
+                    break;
+                }
+                kept.push_str(line);
+                kept.push('\n');
+                used += line_tokens;
+            }
+            kept.push_str("[... rest of this file omitted]\n");
+            continue;
+        }
+        omitted.push(section_path(section));
+    }
+
+    if !omitted.is_empty() {
+        const LISTED: usize = 20;
+        let mut names = omitted.iter().take(LISTED).cloned().collect::<Vec<_>>();
+        if omitted.len() > LISTED {
+            names.push(format!("and {} more", omitted.len() - LISTED));
+        }
+        kept.push_str(&format!(
+            "[Diff of {} more file(s) omitted: {}]\n",
+            omitted.len(),
+            names.join(", ")
+        ));
+    }
+    kept
+}
+
+/// Splits a diff at its `diff --git` headers, keeping each header with its file.
+fn split_by_file(diff: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = diff
+        .match_indices("diff --git ")
+        .map(|(i, _)| i)
+        .filter(|&i| i == 0 || diff.as_bytes()[i - 1] == b'\n')
+        .collect();
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain(std::iter::once(&diff.len())))
+        .map(|(&start, &end)| &diff[start..end])
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+/// The path of a file section, from its `+++ b/` line or `diff --git` header.
+fn section_path(section: &str) -> String {
+    section
+        .lines()
+        .find_map(|line| line.strip_prefix("+++ b/"))
+        .or_else(|| {
+            let header = section.lines().next()?.strip_prefix("diff --git ")?;
+            Some(header.rsplit_once(" b/").map_or(header, |(_, b)| b))
+        })
+        .unwrap_or("(unknown)")
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +400,33 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, "old.txt");
     }
+
+    fn file_diff(path: &str, lines: usize) -> String {
+        let mut diff = format!(
+            "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,{lines} +1,{lines} @@\n"
+        );
+        for i in 0..lines {
+            diff.push_str(&format!("+let value_{i} = compute_something({i});\n"));
+        }
+        diff
+    }
+
+    #[test]
+    fn test_truncate_diff_keeps_what_fits() {
+        let diff = [
+            file_diff("a.rs", 5),
+            file_diff("big.rs", 400),
+            file_diff("c.rs", 5),
+        ]
+        .concat();
+        assert_eq!(truncate_diff(&diff, 100_000), diff);
+
+        let truncated = truncate_diff(&diff, 300);
+        assert!(estimate_tokens(&truncated).unwrap() < 350);
+        assert!(truncated.contains("+++ b/a.rs"));
+        assert!(truncated.contains("+++ b/big.rs\n"));
+        assert!(truncated.contains("[... rest of this file omitted]"));
+        assert!(!truncated.contains("+++ b/c.rs"));
+        assert!(truncated.ends_with("[Diff of 1 more file(s) omitted: c.rs]\n"));
+    }
 }
@@ -0,0 +1,191 @@
+//! Few-shot examples from the repository's own history.
+//!
+//! Past commits are ranked by how much their changes overlap with the
+//! current diff (touched paths, directories and file types), and the best
+//! matches are rendered as examples for the system prompt within a token
+//! budget.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::commit_style::parse_subject;
+use super::token::estimate_tokens;
+use crate::git::CommitChanges;
+
+/// Relative weight of exact path, directory and file type overlap.
+const PATH_WEIGHT: f64 = 3.0;
+const DIR_WEIGHT: f64 = 2.0;
+const EXT_WEIGHT: f64 = 1.0;
+
+/// Number of file names listed per example.
+const MAX_LISTED_FILES: usize = 5;
+
+/// Paths, ancestor directories and extensions of a set of changes.
+#[derive(Debug, Default)]
+struct Features {
+    paths: HashSet<String>,
+    dirs: HashSet<String>,
+    exts: HashSet<String>,
+}
+
+impl Features {
+    fn from_paths<T: AsRef<str>>(paths: &[T]) -> Self {
+        let mut features = Self::default();
+        for path in paths {
+            let path = path.as_ref();
+            features.paths.insert(path.to_string());
+            let mut dir = Path::new(path).parent();
+            while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+                features.dirs.insert(d.to_string_lossy().to_string());
+                dir = d.parent();
+            }
+            if let Some(ext) = Path::new(path).extension() {
+                features.exts.insert(ext.to_string_lossy().to_lowercase());
+            }
+        }
+        features
+    }
+
+    fn similarity(&self, other: &Self) -> f64 {
+        PATH_WEIGHT * jaccard(&self.paths, &other.paths)
+            + DIR_WEIGHT * jaccard(&self.dirs, &other.dirs)
+            + EXT_WEIGHT * jaccard(&self.exts, &other.exts)
+    }
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// The `count` past commits most similar to a change touching `paths`.
+///
+/// Commits with Conventional Commits subjects are preferred when the
+/// history has any, so examples show the repository at its best.
+pub fn select_similar<'a, T: AsRef<str>>(
+    paths: &[T],
+    history: &'a [CommitChanges],
+    count: usize,
+) -> Vec<&'a CommitChanges> {
+    let current = Features::from_paths(paths);
+    let is_conventional = |commit: &CommitChanges| {
+        let subject = commit.message.lines().next().unwrap_or("");
+        parse_subject(subject).is_some_and(|parsed| parsed.is_conventional())
+    };
+    let prefer_conventional = history.iter().any(is_conventional);
+
+    let mut scored: Vec<(f64, &CommitChanges)> = history
+        .iter()
+        .filter(|commit| !prefer_conventional || is_conventional(commit))
+        .map(|commit| {
+            (
+                current.similarity(&Features::from_paths(&commit.files)),
+                commit,
+            )
+        })
+        .filter(|(score, _)| *score > 0.0)
+        .collect();
+    // Stable sort keeps newer commits first among equal scores
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(count)
+        .map(|(_, commit)| commit)
+        .collect()
+}
+
+/// Render examples for the system prompt, adding them in order until
+/// `max_tokens` would be exceeded. Returns `None` if none fit.
+pub fn format_examples(examples: &[&CommitChanges], max_tokens: usize) -> Option<String> {
+    let header = "EXAMPLES (past commits in this repository that changed similar files; \
+                  match their style, not their content):\n";
+    let mut prompt = header.to_string();
+    let mut used = estimate_tokens(header).unwrap_or(0);
+    let mut added = 0;
+
+    for commit in examples {
+        let mut files: Vec<&str> = commit
+            .files
+            .iter()
+            .take(MAX_LISTED_FILES)
+            .map(String::as_str)
+            .collect();
+        if commit.files.len() > MAX_LISTED_FILES {
+            files.push("…");
+        }
+        let example = format!(
+            "\nExample {} ({} file{} changed, +{} -{}: {}):\n{}\n",
+            added + 1,
+            commit.files.len(),
+            if commit.files.len() == 1 { "" } else { "s" },
+            commit.insertions,
+            commit.deletions,
+            files.join(", "),
+            commit.message.trim()
+        );
+
+        let tokens = estimate_tokens(&example).unwrap_or(example.len() / 4);
+        if used + tokens > max_tokens {
+            break;
+        }
+        used += tokens;
+        added += 1;
+        prompt.push_str(&example);
+    }
+
+    (added > 0).then_some(prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(message: &str, files: &[&str]) -> CommitChanges {
+        CommitChanges {
+            id: "abc1234".to_string(),
+            message: message.to_string(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+            insertions: 10,
+            deletions: 2,
+        }
+    }
+
+    #[test]
+    fn test_select_similar_ranks_by_overlap() {
+        let history = vec![
+            commit("docs: update readme", &["README.md"]),
+            commit(
+                "feat(auth): add login",
+                &["src/auth/login.rs", "src/auth/mod.rs"],
+            ),
+            commit("fix(api): handle timeout", &["src/api/client.rs"]),
+            commit("Tweak auth", &["src/auth/login.rs"]),
+        ];
+        let selected = select_similar(&["src/auth/login.rs"], &history, 2);
+
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].message, "feat(auth): add login");
+        assert_eq!(selected[1].message, "fix(api): handle timeout");
+    }
+
+    #[test]
+    fn test_format_examples_respects_budget() {
+        let history = [
+            commit("feat(auth): add login", &["src/auth/login.rs"]),
+            commit("fix(auth): handle expiry", &["src/auth/token.rs"]),
+        ];
+        let examples: Vec<&CommitChanges> = history.iter().collect();
+
+        let all = format_examples(&examples, 1000).unwrap();
+        assert!(all.contains("Example 2 (1 file changed, +10 -2: src/auth/token.rs)"));
+
+        let one = format_examples(&examples, 60).unwrap();
+        assert!(one.contains("feat(auth): add login"));
+        assert!(!one.contains("Example 2"));
+
+        assert!(format_examples(&examples, 10).is_none());
+    }
+}
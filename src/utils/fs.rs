@@ -0,0 +1,38 @@
+//! File helpers for secrets and credentials.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Write a file readable only by the owner, replacing it atomically.
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    stage_private(path, contents)?
+        .persist(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Write `contents` to an owner-only temporary file next to `path`, to be
+/// persisted over it.
+pub fn stage_private(path: &Path, contents: &[u8]) -> Result<tempfile::NamedTempFile> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+    let mut file = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Failed to create a temporary file in {}", dir.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(file.path(), fs::Permissions::from_mode(0o600))
+            .context("Failed to set file permissions")?;
+    }
+    file.write_all(contents)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(file)
+}
@@ -68,7 +68,11 @@ pub fn run_hooks(opts: HookOptions) -> Result<()> {
         let (executable, args) = match parse_command(cmd) {
             Some(parts) => parts,
             None => {
-                eprintln!("Warning: Empty command in {} hook {}", opts.name, idx + 1);
+                crate::output::context::get().warning(format!(
+                    "Warning: Empty command in {} hook {}",
+                    opts.name,
+                    idx + 1
+                ));
                 continue;
             }
         };
@@ -80,13 +84,13 @@ pub fn run_hooks(opts: HookOptions) -> Result<()> {
             || executable_lower == "cmd"
             || executable_lower == "powershell"
         {
-            eprintln!(
+            crate::output::context::get().warning(format!(
                 "Warning: Shell execution in {} hook {} is deprecated for security reasons. \
                 Consider using direct command execution instead: {}",
                 opts.name,
                 idx + 1,
                 cmd
-            );
+            ));
         }
 
         let mut command = Command::new(&executable);
@@ -131,7 +135,7 @@ pub fn run_hooks(opts: HookOptions) -> Result<()> {
             if opts.strict {
                 return Err(anyhow::anyhow!(msg));
             } else {
-                eprintln!("Warning: {}", msg);
+                crate::output::context::get().warning(format!("Warning: {}", msg));
             }
         }
     }
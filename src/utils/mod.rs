@@ -3,10 +3,17 @@
 //! This module organizes various utility functions and helpers used throughout
 //! the application. Each submodule focuses on a specific responsibility.
 
+pub mod commit_stats;
 pub mod commit_style;
 pub mod diff_chunking;
+pub mod few_shot;
+pub mod fs;
 pub mod hooks;
+pub mod payload_log;
+pub mod rate_limit;
 pub mod retry;
+pub mod scope_resolver;
+pub mod style_store;
 pub mod thinking_strip;
 pub mod token;
 pub mod version;
@@ -0,0 +1,275 @@
+//! Debug log of provider request/response payloads.
+//!
+//! With `-vv`, every request sent to an AI provider and every response body
+//! received is appended as a JSON line to `logs/debug.log` in the config
+//! directory (`$RCO_CONFIG_HOME` or `~/.config/rustycommit`). Credentials are
+//! redacted from both bodies and URLs before anything is written.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Once;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use dirs::home_dir;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::output;
+
+/// Placeholder written in place of redacted values.
+const REDACTED: &str = "[REDACTED]";
+
+/// Object keys whose values are always redacted, lowercase and without `_`
+/// or `-`. Whole names only, so e.g. `maxOutputTokens` is kept.
+const SECRET_KEYS: &[&str] = &[
+    "apikey",
+    "xapikey",
+    "xgoogapikey",
+    "token",
+    "accesstoken",
+    "refreshtoken",
+    "idtoken",
+    "authtoken",
+    "sessiontoken",
+    "authorization",
+    "proxyauthorization",
+    "secret",
+    "clientsecret",
+    "secretaccesskey",
+    "privatekey",
+    "password",
+    "credential",
+    "credentials",
+];
+
+/// URL query parameters whose values are redacted.
+const SECRET_PARAMS: &[&str] = &["key", "api_key", "apikey", "token", "access_token"];
+
+static ANNOUNCE: Once = Once::new();
+
+/// Whether payload logging is enabled (`-vv`).
+pub fn is_enabled() -> bool {
+    output::context::get().level().is_debug()
+}
+
+/// Path of the payload log file.
+pub fn log_path() -> Option<PathBuf> {
+    let config_dir = if let Ok(config_home) = std::env::var("RCO_CONFIG_HOME") {
+        PathBuf::from(config_home)
+    } else {
+        home_dir()?.join(".config").join("rustycommit")
+    };
+    Some(config_dir.join("logs").join("debug.log"))
+}
+
+/// Log a request body about to be sent to `url`.
+pub fn request(provider: &str, url: &str, body: &impl Serialize) {
+    if !is_enabled() {
+        return;
+    }
+    let body = serde_json::to_value(body).unwrap_or(Value::Null);
+    write_entry(json!({
+        "direction": "request",
+        "provider": provider,
+        "url": redact_url(url),
+        "body": body,
+    }));
+}
+
+/// Log a raw response body.
+pub fn response(provider: &str, status: u16, body: &str) {
+    if !is_enabled() {
+        return;
+    }
+    let body = serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.to_string()));
+    write_entry(json!({
+        "direction": "response",
+        "provider": provider,
+        "status": status,
+        "body": body,
+    }));
+}
+
+/// Read an error response body and log it.
+pub async fn error_text(provider: &str, response: reqwest::Response) -> Result<String> {
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+    self::response(provider, status, &text);
+    Ok(text)
+}
+
+/// Read a successful response body, log it, and deserialize it.
+///
+/// Drop-in replacement for `response.json::<T>()` that keeps the exact
+/// payload available in the debug log.
+pub async fn json_response<T: DeserializeOwned>(
+    provider: &str,
+    response: reqwest::Response,
+) -> Result<T> {
+    let status = response.status().as_u16();
+    let text = response
+        .text()
+        .await
+        .context("Failed to read response body")?;
+    self::response(provider, status, &text);
+    serde_json::from_str(&text).context("Response body is not valid JSON for this provider")
+}
+
+/// Replace the values of credential-like keys with a placeholder, recursively.
+pub fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *entry = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(entry);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// Redact credential query parameters (e.g. Gemini's `?key=`) from a URL.
+pub fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if SECRET_PARAMS.contains(&name.to_lowercase().as_str()) => {
+                format!("{}={}", name, REDACTED)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", base, query)
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .collect::<String>()
+        .to_lowercase();
+    SECRET_KEYS.contains(&key.as_str())
+}
+
+fn write_entry(mut entry: Value) {
+    redact_value(&mut entry);
+    if let Some(map) = entry.as_object_mut() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        map.insert("timestamp".to_string(), json!(timestamp));
+    }
+
+    let Some(path) = log_path() else {
+        return;
+    };
+    if let Err(e) = append_line(&path, &entry.to_string()) {
+        tracing::debug!("Failed to write payload log {}: {}", path.display(), e);
+        return;
+    }
+    ANNOUNCE.call_once(|| {
+        output::context::get().debug(format!(
+            "Writing request/response payloads to {}",
+            path.display()
+        ));
+    });
+}
+
+fn append_line(path: &PathBuf, line: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    // Prompts contain the user's code, so the log is private like secrets,
+    // including logs created before it was
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if let Ok(metadata) = fs::metadata(path) {
+            if metadata.permissions().mode() & 0o077 != 0 {
+                fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+            }
+        }
+    }
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_value_nested_secrets() {
+        let mut value = json!({
+            "model": "gpt-4o-mini",
+            "api_key": "sk-secret",
+            "headers": { "Authorization": "Bearer abc" },
+            "messages": [{ "role": "user", "access_token": "t" }],
+            "usage": { "prompt_tokens": 12, "max_tokens": 100 },
+            "generationConfig": { "maxOutputTokens": 500 },
+            "x-api-key": "sk-ant",
+            "client_secret": "s",
+            "tokenizer": "cl100k"
+        });
+        redact_value(&mut value);
+
+        assert_eq!(value["model"], "gpt-4o-mini");
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["headers"]["Authorization"], REDACTED);
+        assert_eq!(value["messages"][0]["access_token"], REDACTED);
+        assert_eq!(value["messages"][0]["role"], "user");
+        assert_eq!(value["usage"]["prompt_tokens"], 12);
+        assert_eq!(value["usage"]["max_tokens"], 100);
+        assert_eq!(value["generationConfig"]["maxOutputTokens"], 500);
+        assert_eq!(value["x-api-key"], REDACTED);
+        assert_eq!(value["client_secret"], REDACTED);
+        assert_eq!(value["tokenizer"], "cl100k");
+    }
+
+    #[test]
+    fn test_redact_url_query_key() {
+        assert_eq!(
+            redact_url("https://example.com/v1/models/x:generate?key=abc&alt=json"),
+            "https://example.com/v1/models/x:generate?key=[REDACTED]&alt=json"
+        );
+        assert_eq!(
+            redact_url("https://example.com/v1/chat"),
+            "https://example.com/v1/chat"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_log_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("debug.log");
+        append_line(&path, "{}").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        append_line(&path, "{}").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
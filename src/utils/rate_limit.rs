@@ -0,0 +1,263 @@
+//! Client-side rate limiting for provider requests.
+//!
+//! Every provider, or account, gets a token bucket for requests per minute
+//! and one for estimated tokens per minute, sized by `RCO_RATE_LIMIT_RPM` and
+//! `RCO_RATE_LIMIT_TPM` or the account's overrides. The buckets are shared by
+//! the whole process, so parallel variations and bursts of MCP calls wait for
+//! capacity instead of running into the provider's own limits.
+//!
+//! A request counts against the limits on every attempt: [`limited`] takes
+//! capacity for the first one and [`acquire_for_retry`], called by
+//! `retry_async`, for each retry.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::accounts::AccountConfig;
+use crate::config::Config;
+
+type LimiterKey = (String, RateLimits);
+
+/// Limiters in use this process, by provider or account and their limits.
+static LIMITERS: Lazy<Mutex<HashMap<LimiterKey, Arc<RateLimiter>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+tokio::task_local! {
+    /// The limiter and estimated tokens of the request being sent.
+    static REQUEST: (Arc<RateLimiter>, usize);
+}
+
+/// Wait for capacity for a request of about `tokens` tokens, then run it.
+/// Retries inside `request` wait for capacity again.
+pub async fn limited<F: Future>(
+    limiter: Option<Arc<RateLimiter>>,
+    tokens: usize,
+    request: F,
+) -> F::Output {
+    match limiter {
+        Some(limiter) => {
+            limiter.acquire(tokens).await;
+            REQUEST.scope((limiter, tokens), request).await
+        }
+        None => request.await,
+    }
+}
+
+/// Wait for capacity to retry the request started by [`limited`], if any.
+pub async fn acquire_for_retry() {
+    if let Ok((limiter, tokens)) = REQUEST.try_with(|request| request.clone()) {
+        limiter.acquire(tokens).await;
+    }
+}
+
+/// Requests and tokens per minute allowed for one provider; `None` is no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RateLimits {
+    pub requests_per_minute: Option<u32>,
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimits {
+    /// The limits from `RCO_RATE_LIMIT_RPM` and `RCO_RATE_LIMIT_TPM`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            requests_per_minute: config.rate_limit_rpm,
+            tokens_per_minute: config.rate_limit_tpm,
+        }
+    }
+
+    /// The limits for an account, using its overrides where set.
+    pub fn for_account(account: &AccountConfig, config: &Config) -> Self {
+        Self {
+            requests_per_minute: account.rate_limit_rpm.or(config.rate_limit_rpm),
+            tokens_per_minute: account.rate_limit_tpm.or(config.rate_limit_tpm),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.requests_per_minute.is_none() && self.tokens_per_minute.is_none()
+    }
+}
+
+/// The limiter shared by every request to `key` (a provider name or account
+/// alias) with these limits, or `None` when there are no limits.
+pub fn shared(key: &str, limits: RateLimits) -> Option<Arc<RateLimiter>> {
+    if limits.is_unlimited() {
+        return None;
+    }
+    let limiter = LIMITERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry((key.to_string(), limits))
+        .or_insert_with(|| Arc::new(RateLimiter::new(limits)))
+        .clone();
+    Some(limiter)
+}
+
+/// A bucket holding up to a minute's worth of units, refilled continuously.
+#[derive(Debug)]
+struct Bucket {
+    per_minute: f64,
+    available: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        Self {
+            per_minute: f64::from(per_minute),
+            available: f64::from(per_minute),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.available = (self.available + elapsed * self.per_minute / 60.0).min(self.per_minute);
+        self.updated = now;
+    }
+
+    /// How long until `amount` units are available. Requests larger than the
+    /// bucket wait for a full one rather than forever.
+    fn wait_for(&self, amount: f64) -> Duration {
+        let missing = amount.min(self.per_minute) - self.available;
+        if missing <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(missing * 60.0 / self.per_minute)
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available -= amount.min(self.per_minute);
+    }
+}
+
+#[derive(Debug)]
+struct Buckets {
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+}
+
+/// Request and token buckets for one provider.
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        let now = Instant::now();
+        Self {
+            buckets: Mutex::new(Buckets {
+                requests: limits.requests_per_minute.map(|rpm| Bucket::new(rpm, now)),
+                tokens: limits.tokens_per_minute.map(|tpm| Bucket::new(tpm, now)),
+            }),
+        }
+    }
+
+    /// Wait until a request of about `tokens` tokens fits both limits, then
+    /// count it against them.
+    pub async fn acquire(&self, tokens: usize) {
+        loop {
+            let wait = self.try_acquire(tokens as f64, Instant::now());
+            if wait.is_zero() {
+                return;
+            }
+            tracing::debug!("Client-side rate limit reached, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take capacity for one request if both buckets have it, otherwise
+    /// return how long to wait before trying again.
+    fn try_acquire(&self, tokens: f64, now: Instant) -> Duration {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Buckets {
+            requests,
+            tokens: token_bucket,
+        } = &mut *buckets;
+
+        let mut wait = Duration::ZERO;
+        if let Some(bucket) = requests.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(1.0));
+        }
+        if let Some(bucket) = token_bucket.as_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.wait_for(tokens));
+        }
+        if wait.is_zero() {
+            if let Some(bucket) = requests.as_mut() {
+                bucket.take(1.0);
+            }
+            if let Some(bucket) = token_bucket.as_mut() {
+                bucket.take(tokens);
+            }
+        }
+        wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(rpm: Option<u32>, tpm: Option<u32>) -> RateLimits {
+        RateLimits {
+            requests_per_minute: rpm,
+            tokens_per_minute: tpm,
+        }
+    }
+
+    #[test]
+    fn test_requests_per_minute() {
+        let limiter = RateLimiter::new(limits(Some(2), None));
+        let start = Instant::now();
+        assert!(limiter.try_acquire(0.0, start).is_zero());
+        assert!(limiter.try_acquire(0.0, start).is_zero());
+
+        // The third request waits for a refill: one request every 30s
+        let wait = limiter.try_acquire(0.0, start);
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+        assert!(limiter
+            .try_acquire(0.0, start + Duration::from_secs(30))
+            .is_zero());
+    }
+
+    #[test]
+    fn test_tokens_per_minute() {
+        let limiter = RateLimiter::new(limits(Some(100), Some(6000)));
+        let start = Instant::now();
+        assert!(limiter.try_acquire(4000.0, start).is_zero());
+
+        // 3000 more need 1000 refilled tokens, 10s at 100 tokens a second
+        let wait = limiter.try_acquire(3000.0, start);
+        assert_eq!(wait.as_secs(), 10);
+        // Waiting for tokens did not use up a request
+        assert!(limiter
+            .try_acquire(3000.0, start + Duration::from_secs(10))
+            .is_zero());
+
+        // A request larger than the bucket waits for a full bucket
+        let wait = limiter.try_acquire(10_000.0, start + Duration::from_secs(10));
+        assert_eq!(wait.as_secs(), 60);
+    }
+
+    #[test]
+    fn test_shared_limiters() {
+        assert!(shared("openai", RateLimits::default()).is_none());
+
+        let a = shared("test-shared", limits(Some(10), None)).unwrap();
+        let b = shared("test-shared", limits(Some(10), None)).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let other = shared("test-shared", limits(Some(20), None)).unwrap();
+        assert!(!Arc::ptr_eq(&a, &other));
+    }
+}
@@ -1,77 +1,134 @@
+//! Retries for provider requests.
+//!
+//! Providers report failures as [`ProviderError`], so whether to retry is
+//! decided by its kind: rate limits and server errors are retried, waiting as
+//! long as the server's `Retry-After` asks, and so are timeouts and connection
+//! failures. Everything else fails on the first attempt. How many retries and
+//! for how long comes from [`RetryPolicy`].
+
 use anyhow::Result;
 use backoff::{future::retry, ExponentialBackoff, ExponentialBackoffBuilder};
-use std::time::Duration;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::accounts::AccountConfig;
+use crate::config::Config;
+use crate::providers::error::ProviderError;
+use crate::utils::rate_limit;
+
+/// The delay requested by `Retry-After` (seconds or an HTTP date), or by the
+/// `retry-after-ms` header some OpenAI-compatible APIs send instead.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
 
-/// Maximum total time to spend retrying before giving up
-const MAX_RETRY_TIMEOUT_SECS: u64 = 120;
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.parse::<f64>().ok()) {
+        if let Ok(delay) = Duration::try_from_secs_f64(ms / 1000.0) {
+            return Some(delay);
+        }
+    }
 
-/// Determines if an error is retryable
+    let value = header(RETRY_AFTER.as_str())?;
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Whether an error is worth another attempt: a retryable [`ProviderError`],
+/// or a request that timed out or could not connect.
 pub fn is_retryable_error(error: &anyhow::Error) -> bool {
-    let error_msg = error.to_string().to_lowercase();
-
-    // Retryable errors: network issues, timeouts, rate limits, server errors
-    error_msg.contains("429") ||  // Rate limit
-    error_msg.contains("rate_limit") ||
-    error_msg.contains("rate limit") ||
-    error_msg.contains("500") ||  // Internal server error
-    error_msg.contains("502") ||  // Bad gateway
-    error_msg.contains("503") ||  // Service unavailable
-    error_msg.contains("504") ||  // Gateway timeout
-    error_msg.contains("timeout") ||
-    error_msg.contains("connection") ||
-    error_msg.contains("network") ||
-    error_msg.contains("dns") ||
-    error_msg.contains("overloaded")
+    for cause in error.chain() {
+        if let Some(provider_error) = cause.downcast_ref::<ProviderError>() {
+            return provider_error.is_retryable();
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_timeout() || e.is_connect() || e.is_request();
+        }
+    }
+    false
 }
 
-/// Determines if an error is permanent (should not retry)
-pub fn is_permanent_error(error: &anyhow::Error) -> bool {
-    let error_msg = error.to_string().to_lowercase();
-
-    // Permanent errors: auth issues, invalid requests, quota exceeded
-    error_msg.contains("401") ||  // Unauthorized
-    error_msg.contains("403") ||  // Forbidden
-    error_msg.contains("invalid api key") ||
-    error_msg.contains("insufficient quota") ||
-    error_msg.contains("quota exceeded") ||
-    error_msg.contains("invalid request") ||
-    error_msg.contains("model not found") ||
-    error_msg.contains("400") // Bad request
+/// How often and for how long to retry a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub retries: u32,
+    /// Time after which no further attempt is started, waits included.
+    pub max_elapsed: Duration,
 }
 
-/// Create a backoff policy for API retries
-pub fn create_backoff() -> ExponentialBackoff {
-    ExponentialBackoffBuilder::new()
-        .with_initial_interval(Duration::from_millis(500))
-        .with_max_interval(Duration::from_secs(30))
-        .with_multiplier(2.0)
-        .with_max_elapsed_time(Some(Duration::from_secs(MAX_RETRY_TIMEOUT_SECS)))
-        .build()
+impl RetryPolicy {
+    /// The policy from `RCO_RETRY_COUNT` and `RCO_RETRY_MAX_ELAPSED_SECS`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            retries: config.retry_count,
+            max_elapsed: Duration::from_secs(config.retry_max_elapsed_secs),
+        }
+    }
+
+    /// The policy for an account, using its overrides where set.
+    pub fn for_account(account: &AccountConfig, config: &Config) -> Self {
+        Self {
+            retries: account.retry_count.unwrap_or(config.retry_count),
+            max_elapsed: Duration::from_secs(
+                account
+                    .retry_max_elapsed_secs
+                    .unwrap_or(config.retry_max_elapsed_secs),
+            ),
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(Duration::from_millis(500))
+            .with_max_interval(Duration::from_secs(30))
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(Some(self.max_elapsed))
+            .build()
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
 }
 
 /// Retry an async operation with exponential backoff
-pub async fn retry_async<F, Fut, T>(operation: F) -> Result<T>
+pub async fn retry_async<F, Fut, T>(policy: RetryPolicy, operation: F) -> Result<T>
 where
     F: Fn() -> Fut + Send + Sync,
     Fut: std::future::Future<Output = Result<T>> + Send,
 {
-    let backoff = create_backoff();
-
-    retry(backoff, || async {
-        match operation().await {
-            Ok(result) => Ok(result),
-            Err(error) => {
-                if is_permanent_error(&error) {
-                    // Don't retry permanent errors
-                    Err(backoff::Error::permanent(error))
-                } else if is_retryable_error(&error) {
-                    // Retry transient errors
-                    Err(backoff::Error::transient(error))
-                } else {
-                    // Unknown errors - treat as permanent to be safe
-                    Err(backoff::Error::permanent(error))
-                }
+    let started = Instant::now();
+    let attempts = AtomicU32::new(0);
+
+    retry(policy.backoff(), || async {
+        if attempts.load(Ordering::Relaxed) > 0 {
+            rate_limit::acquire_for_retry().await;
+        }
+        let error = match operation().await {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+        let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        if attempt > policy.retries || !is_retryable_error(&error) {
+            return Err(backoff::Error::permanent(error));
+        }
+
+        let requested = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ProviderError>())
+            .and_then(ProviderError::retry_after);
+        match requested {
+            // backoff does not check the elapsed time for server-chosen delays
+            Some(delay) if started.elapsed() + delay > policy.max_elapsed => {
+                Err(backoff::Error::permanent(error))
             }
+            Some(delay) => Err(backoff::Error::retry_after(error, delay)),
+            None => Err(backoff::Error::transient(error)),
         }
     })
     .await
@@ -81,43 +138,61 @@ where
 mod tests {
     use super::*;
     use anyhow::anyhow;
+    use std::sync::{Arc, Mutex};
+
+    fn http_error(status: u16, retry_after: Option<Duration>) -> anyhow::Error {
+        ProviderError::classify(status, retry_after, format!("API error ({status})")).into()
+    }
+
+    fn policy(retries: u32, max_elapsed_secs: u64) -> RetryPolicy {
+        RetryPolicy {
+            retries,
+            max_elapsed: Duration::from_secs(max_elapsed_secs),
+        }
+    }
 
     #[test]
     fn test_is_retryable_error() {
-        assert!(is_retryable_error(&anyhow!("429 Rate limit exceeded")));
-        assert!(is_retryable_error(&anyhow!("500 Internal server error")));
-        assert!(is_retryable_error(&anyhow!("Connection timeout")));
-        assert!(is_retryable_error(&anyhow!("Network error")));
-        assert!(is_retryable_error(&anyhow!("Model overloaded")));
-
-        assert!(!is_retryable_error(&anyhow!("401 Unauthorized")));
-        assert!(!is_retryable_error(&anyhow!("Invalid API key")));
+        assert!(is_retryable_error(&http_error(429, None)));
+        assert!(is_retryable_error(&http_error(503, None)));
+        assert!(is_retryable_error(
+            &http_error(500, None).context("Failed to generate commit message")
+        ));
+
+        assert!(!is_retryable_error(&http_error(401, None)));
+        assert!(!is_retryable_error(&http_error(400, None)));
+        // The text no longer decides anything
+        assert!(!is_retryable_error(&anyhow!("500 connection timeout")));
     }
 
     #[test]
-    fn test_is_permanent_error() {
-        assert!(is_permanent_error(&anyhow!("401 Unauthorized")));
-        assert!(is_permanent_error(&anyhow!("Invalid API key")));
-        assert!(is_permanent_error(&anyhow!("Insufficient quota")));
-        assert!(is_permanent_error(&anyhow!("400 Bad request")));
-
-        assert!(!is_permanent_error(&anyhow!("429 Rate limit")));
-        assert!(!is_permanent_error(&anyhow!("500 Server error")));
+    fn test_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        let later = SystemTime::now() + Duration::from_secs(30);
+        headers.insert(RETRY_AFTER, httpdate::fmt_http_date(later).parse().unwrap());
+        let delay = retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(25) && delay <= Duration::from_secs(30));
+
+        headers.insert("retry-after-ms", "250".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(250)));
     }
 
     #[tokio::test]
     async fn test_retry_success() {
-        use std::sync::{Arc, Mutex};
-
         let attempts = Arc::new(Mutex::new(0));
 
-        let result = retry_async(|| {
+        let result = retry_async(policy(3, 10), || {
             let attempts = attempts.clone();
             async move {
                 let mut attempts_lock = attempts.lock().unwrap();
                 *attempts_lock += 1;
                 if *attempts_lock < 3 {
-                    Err(anyhow!("429 Rate limit"))
+                    Err(http_error(429, Some(Duration::ZERO)))
                 } else {
                     Ok("success".to_string())
                 }
@@ -132,16 +207,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_retry_permanent_error() {
-        use std::sync::{Arc, Mutex};
-
         let attempts = Arc::new(Mutex::new(0));
 
-        let result: Result<String, _> = retry_async(|| {
+        let result: Result<String, _> = retry_async(policy(3, 10), || {
             let attempts = attempts.clone();
             async move {
                 let mut attempts_lock = attempts.lock().unwrap();
                 *attempts_lock += 1;
-                Err(anyhow!("401 Unauthorized"))
+                Err(http_error(401, None))
             }
         })
         .await;
@@ -149,4 +222,64 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(*attempts.lock().unwrap(), 1); // Should not retry permanent errors
     }
+
+    #[tokio::test]
+    async fn test_retry_limits() {
+        let attempts = Arc::new(Mutex::new(0));
+        let result: Result<String, _> = retry_async(policy(2, 10), || {
+            let attempts = attempts.clone();
+            async move {
+                *attempts.lock().unwrap() += 1;
+                Err(http_error(503, Some(Duration::ZERO)))
+            }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 3);
+
+        // A Retry-After beyond the time left gives up instead of waiting
+        let attempts = Arc::new(Mutex::new(0));
+        let started = Instant::now();
+        let result: Result<String, _> = retry_async(policy(5, 10), || {
+            let attempts = attempts.clone();
+            async move {
+                *attempts.lock().unwrap() += 1;
+                Err(http_error(429, Some(Duration::from_secs(60))))
+            }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_retries_wait_for_the_rate_limit() {
+        let limiter = Arc::new(rate_limit::RateLimiter::new(rate_limit::RateLimits {
+            requests_per_minute: Some(3),
+            tokens_per_minute: None,
+        }));
+        let attempts = Arc::new(Mutex::new(0));
+        let result = rate_limit::limited(
+            Some(limiter.clone()),
+            0,
+            retry_async(policy(2, 10), || {
+                let attempts = attempts.clone();
+                async move {
+                    *attempts.lock().unwrap() += 1;
+                    if *attempts.lock().unwrap() < 3 {
+                        Err(http_error(429, Some(Duration::ZERO)))
+                    } else {
+                        Ok("success")
+                    }
+                }
+            }),
+        )
+        .await;
+        assert_eq!(result.unwrap(), "success");
+
+        // All three attempts took a request, so a fourth has to wait
+        let next = tokio::time::timeout(Duration::from_millis(100), limiter.acquire(0)).await;
+        assert!(next.is_err());
+    }
 }
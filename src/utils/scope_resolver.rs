@@ -0,0 +1,439 @@
+//! Scope inference from repository layout.
+//!
+//! Maps changed paths to commit scopes using, in order of priority:
+//!
+//! 1. The `RCO_SCOPE_MAP` table (`path=scope` entries)
+//! 2. Cargo workspace members and npm workspace packages (by package name)
+//! 3. Sections of a GitLab-style `CODEOWNERS` file (`[Section]` headers)
+//!
+//! The scopes found for a diff are suggested to the model, and the scope of
+//! the generated message can be checked against every scope the repository
+//! defines.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::commit_style::parse_subject;
+use crate::config::Config;
+
+/// Where a scope rule came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopeSource {
+    Config,
+    Workspace,
+    Codeowners,
+}
+
+/// A path pattern mapped to a scope.
+#[derive(Debug, Clone)]
+pub struct ScopeRule {
+    pub pattern: String,
+    pub scope: String,
+    pub source: ScopeSource,
+    regex: Regex,
+}
+
+impl ScopeRule {
+    /// Build a rule from a gitignore/CODEOWNERS style pattern.
+    pub fn new(pattern: &str, scope: &str, source: ScopeSource) -> Option<Self> {
+        let regex = Regex::new(&pattern_to_regex(pattern)).ok()?;
+        Some(Self {
+            pattern: pattern.to_string(),
+            scope: scope.to_string(),
+            source,
+            regex,
+        })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+/// A scope suggested for the current changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScopeCandidate {
+    pub scope: String,
+    /// Number of changed files mapped to this scope
+    pub files: usize,
+    pub source: ScopeSource,
+}
+
+/// Resolves changed paths to scopes.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeResolver {
+    rules: Vec<ScopeRule>,
+}
+
+impl ScopeResolver {
+    /// Collect rules from the config and the repository at `repo_root`.
+    pub fn load(repo_root: &Path, config: &Config) -> Self {
+        let mut rules = Vec::new();
+
+        for entry in config.scope_map.iter().flatten() {
+            if let Some((pattern, scope)) = entry.split_once('=') {
+                rules.extend(ScopeRule::new(
+                    pattern.trim(),
+                    scope.trim(),
+                    ScopeSource::Config,
+                ));
+            }
+        }
+        rules.extend(cargo_workspace_rules(repo_root));
+        rules.extend(npm_workspace_rules(repo_root));
+        rules.extend(codeowners_rules(repo_root));
+
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Every scope the repository defines.
+    pub fn allowed(&self) -> BTreeSet<String> {
+        self.rules.iter().map(|rule| rule.scope.clone()).collect()
+    }
+
+    /// The rule that decides the scope of `path`, if any.
+    ///
+    /// The highest-priority source with a match wins. Within the config table
+    /// and workspaces the most specific (longest) pattern wins; within
+    /// `CODEOWNERS` the last matching line wins, as it does for ownership.
+    pub fn resolve(&self, path: &str) -> Option<&ScopeRule> {
+        let matching = |source: ScopeSource| {
+            self.rules
+                .iter()
+                .filter(move |rule| rule.source == source && rule.matches(path))
+        };
+
+        matching(ScopeSource::Config)
+            .max_by_key(|rule| rule.pattern.len())
+            .or_else(|| matching(ScopeSource::Workspace).max_by_key(|rule| rule.pattern.len()))
+            .or_else(|| matching(ScopeSource::Codeowners).next_back())
+    }
+
+    /// Scopes for the changed `paths`, most files first.
+    pub fn candidates(&self, paths: &[String]) -> Vec<ScopeCandidate> {
+        let mut counts: HashMap<&str, (usize, ScopeSource)> = HashMap::new();
+        for rule in paths.iter().filter_map(|path| self.resolve(path)) {
+            let entry = counts.entry(&rule.scope).or_insert((0, rule.source));
+            entry.0 += 1;
+            entry.1 = entry.1.min(rule.source);
+        }
+
+        let mut candidates: Vec<ScopeCandidate> = counts
+            .into_iter()
+            .map(|(scope, (files, source))| ScopeCandidate {
+                scope: scope.to_string(),
+                files,
+                source,
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.files
+                .cmp(&a.files)
+                .then(a.source.cmp(&b.source))
+                .then(a.scope.cmp(&b.scope))
+        });
+        candidates
+    }
+}
+
+/// Paths touched by a unified diff (new and deleted files included).
+pub fn changed_paths(diff: &str) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for line in diff.lines() {
+        let path = line
+            .strip_prefix("+++ b/")
+            .or_else(|| line.strip_prefix("--- a/"));
+        if let Some(path) = path {
+            if !paths.iter().any(|p| p == path) {
+                paths.push(path.to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// Scope of a generated message's subject line, if it has one.
+pub fn message_scope(message: &str) -> Option<String> {
+    parse_subject(message.lines().next()?)?.scope
+}
+
+/// Replace (or remove, with `None`) the scope in a message's subject line.
+pub fn replace_scope(message: &str, scope: Option<&str>) -> String {
+    let (subject, rest) = match message.split_once('\n') {
+        Some((subject, rest)) => (subject, Some(rest)),
+        None => (message, None),
+    };
+
+    let subject = match (subject.find('('), subject.find(')')) {
+        (Some(open), Some(close)) if open < close && !subject[..open].contains(':') => {
+            match scope {
+                Some(scope) => format!("{}({}){}", &subject[..open], scope, &subject[close + 1..]),
+                None => format!("{}{}", &subject[..open], &subject[close + 1..]),
+            }
+        }
+        _ => subject.to_string(),
+    };
+
+    match rest {
+        Some(rest) => format!("{}\n{}", subject, rest),
+        None => subject,
+    }
+}
+
+/// Convert a gitignore/CODEOWNERS style pattern to an anchored regex.
+fn pattern_to_regex(pattern: &str) -> String {
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.starts_with('/') || trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+
+    let mut regex = String::new();
+    let mut chars = trimmed.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // "**/" also matches zero directories
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    let prefix = if anchored { "^" } else { "^(?:.*/)?" };
+    // A pattern also matches everything below a matching directory
+    format!("{}{}(?:/.*)?$", prefix, regex)
+}
+
+/// Rules for `[workspace] members` of a root `Cargo.toml`.
+fn cargo_workspace_rules(repo_root: &Path) -> Vec<ScopeRule> {
+    let Some(manifest) = read_toml(&repo_root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let members: Vec<String> = manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    expand_members(repo_root, &members)
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = read_toml(&repo_root.join(&dir).join("Cargo.toml"))?;
+            let name = manifest.get("package")?.get("name")?.as_str()?.to_string();
+            ScopeRule::new(&format!("/{}/", dir), &name, ScopeSource::Workspace)
+        })
+        .collect()
+}
+
+/// Rules for the `workspaces` of a root `package.json`.
+fn npm_workspace_rules(repo_root: &Path) -> Vec<ScopeRule> {
+    let Some(package) = read_json(&repo_root.join("package.json")) else {
+        return Vec::new();
+    };
+    // Either an array or `{ "packages": [...] }` (Yarn)
+    let workspaces = package.get("workspaces");
+    let members: Vec<String> = workspaces
+        .and_then(|w| w.as_array().or_else(|| w.get("packages")?.as_array()))
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    expand_members(repo_root, &members)
+        .into_iter()
+        .filter_map(|dir| {
+            let package = read_json(&repo_root.join(&dir).join("package.json"))?;
+            let name = package.get("name")?.as_str()?;
+            // "@acme/ui" -> "ui"
+            let name = name.rsplit('/').next().unwrap_or(name);
+            ScopeRule::new(&format!("/{}/", dir), name, ScopeSource::Workspace)
+        })
+        .collect()
+}
+
+/// Rules for the sections of `CODEOWNERS`.
+fn codeowners_rules(repo_root: &Path) -> Vec<ScopeRule> {
+    let candidates = [
+        "CODEOWNERS",
+        ".github/CODEOWNERS",
+        ".gitlab/CODEOWNERS",
+        "docs/CODEOWNERS",
+    ];
+    let Some(content) = candidates
+        .iter()
+        .find_map(|file| fs::read_to_string(repo_root.join(file)).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut rules = Vec::new();
+    let mut section: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // "[Section]", "^[Optional Section]" or "[Section][2] @owner"
+        if let Some(header) = line.trim_start_matches('^').strip_prefix('[') {
+            section = header
+                .split_once(']')
+                .map(|(name, _)| section_scope(name))
+                .filter(|name| !name.is_empty());
+            continue;
+        }
+        if let (Some(scope), Some(pattern)) = (&section, line.split_whitespace().next()) {
+            rules.extend(ScopeRule::new(pattern, scope, ScopeSource::Codeowners));
+        }
+    }
+    rules
+}
+
+/// "Frontend UI" -> "frontend-ui"
+fn section_scope(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Expand workspace member globs (e.g. `crates/*`) to directories.
+fn expand_members(repo_root: &Path, members: &[String]) -> Vec<String> {
+    let mut dirs = Vec::new();
+    for member in members {
+        let member = member.trim_end_matches('/');
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                let Ok(entries) = fs::read_dir(repo_root.join(parent)) else {
+                    continue;
+                };
+                let mut names: Vec<String> = entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| format!("{}/{}", parent, e.file_name().to_string_lossy()))
+                    .collect();
+                names.sort();
+                dirs.extend(names);
+            }
+            None if !member.contains('*') => dirs.push(member.to_string()),
+            // Other glob forms are not supported
+            None => {}
+        }
+    }
+    dirs
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, scope: &str, source: ScopeSource) -> ScopeRule {
+        ScopeRule::new(pattern, scope, source).unwrap()
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        let dir = rule("src/auth/", "auth", ScopeSource::Config);
+        assert!(dir.matches("src/auth/login.rs"));
+        assert!(!dir.matches("lib/src/auth/login.rs"));
+        assert!(!dir.matches("src/authz.rs"));
+
+        let ext = rule("*.md", "docs", ScopeSource::Config);
+        assert!(ext.matches("README.md"));
+        assert!(ext.matches("docs/guide/intro.md"));
+
+        let deep = rule("/packages/**/test/", "test", ScopeSource::Config);
+        assert!(deep.matches("packages/ui/test/button.ts"));
+        assert!(deep.matches("packages/test/x.ts"));
+    }
+
+    #[test]
+    fn test_resolve_priority() {
+        let resolver = ScopeResolver {
+            rules: vec![
+                rule("/crates/", "crates", ScopeSource::Config),
+                rule("/crates/core/", "core", ScopeSource::Config),
+                rule("/crates/cli/", "rco-cli", ScopeSource::Workspace),
+                rule("*.rs", "rust", ScopeSource::Codeowners),
+                rule("/docs/", "documentation", ScopeSource::Codeowners),
+                rule("/docs/api/", "api-docs", ScopeSource::Codeowners),
+            ],
+        };
+
+        assert_eq!(
+            resolver.resolve("crates/core/lib.rs").unwrap().scope,
+            "core"
+        );
+        assert_eq!(
+            resolver.resolve("crates/cli/main.rs").unwrap().scope,
+            "crates"
+        );
+        assert_eq!(resolver.resolve("docs/api/x.md").unwrap().scope, "api-docs");
+        assert!(resolver.resolve("Makefile").is_none());
+
+        let paths: Vec<String> = ["crates/core/a.rs", "crates/core/b.rs", "docs/x.md"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let candidates = resolver.candidates(&paths);
+        assert_eq!(candidates[0].scope, "core");
+        assert_eq!(candidates[0].files, 2);
+        assert_eq!(candidates[1].scope, "documentation");
+    }
+
+    #[test]
+    fn test_replace_scope() {
+        assert_eq!(
+            replace_scope("feat(ui): add button\n\nBody (details)", Some("web")),
+            "feat(web): add button\n\nBody (details)"
+        );
+        assert_eq!(replace_scope("fix(x): y", None), "fix: y");
+        assert_eq!(
+            replace_scope("fix: handle (edge) case", Some("a")),
+            "fix: handle (edge) case"
+        );
+        assert_eq!(
+            message_scope("✨ feat(auth): add login").as_deref(),
+            Some("auth")
+        );
+    }
+
+    #[test]
+    fn test_changed_paths() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n\
+                    diff --git a/old.rs b/old.rs\n--- a/old.rs\n+++ /dev/null\n";
+        assert_eq!(changed_paths(diff), vec!["src/a.rs", "old.rs"]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Persisted commit style profiles.
+//!
+//! Learned [`CommitStyleProfile`]s are cached per repository in
+//! `styles/<hash>.json` under the config directory (`$RCO_CONFIG_HOME` or
+//! `~/.config/rustycommit`), keyed by the repository root. Each cache records
+//! the last analyzed commit so later runs only fold in commits made since.
+//!
+//! A team can instead commit a shared profile to `.rco/style.json` in the
+//! repository (see `rco style export`); when present it is used as-is.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use dirs::home_dir;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::commit_style::CommitStyleProfile;
+use crate::git;
+
+/// Location of the shared profile, relative to the repository root.
+pub const SHARED_PROFILE_PATH: &str = ".rco/style.json";
+
+/// A style profile together with the point in history it was built up to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredStyleProfile {
+    /// Repository root the profile was learned from (omitted in shared files).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<String>,
+    /// Full id of the newest commit included in the profile.
+    #[serde(default)]
+    pub last_commit: Option<String>,
+    /// Unix timestamp (seconds) of the last update.
+    #[serde(default)]
+    pub updated_at: u64,
+    pub profile: CommitStyleProfile,
+}
+
+/// Where a profile was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "path", rename_all = "lowercase")]
+pub enum ProfileSource {
+    /// Committed to the repository at [`SHARED_PROFILE_PATH`].
+    Shared(PathBuf),
+    /// Learned locally and cached in the config directory.
+    Cached(PathBuf),
+}
+
+impl ProfileSource {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Shared(path) | Self::Cached(path) => path,
+        }
+    }
+}
+
+/// Path of the shared profile for a repository.
+pub fn shared_path(repo_root: &str) -> PathBuf {
+    Path::new(repo_root).join(SHARED_PROFILE_PATH)
+}
+
+/// Path of the local cache for a repository.
+pub fn cache_path(repo_root: &str) -> Result<PathBuf> {
+    let config_dir = if let Ok(config_home) = std::env::var("RCO_CONFIG_HOME") {
+        PathBuf::from(config_home)
+    } else {
+        let home = home_dir().context("Could not find home directory")?;
+        home.join(".config").join("rustycommit")
+    };
+    let digest = Sha256::digest(repo_root.as_bytes());
+    let key: String = digest
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(config_dir.join("styles").join(format!("{}.json", key)))
+}
+
+fn read_profile(path: &Path) -> Result<Option<StoredStyleProfile>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read style profile {}", path.display()))?;
+    let stored = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid style profile {}", path.display()))?;
+    Ok(Some(stored))
+}
+
+/// Write a profile as pretty-printed JSON, creating parent directories.
+pub fn write_profile(path: &Path, stored: &StoredStyleProfile) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(stored)?;
+    fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write style profile {}", path.display()))
+}
+
+/// Load the shared profile committed to the repository, if any.
+pub fn load_shared(repo_root: &str) -> Result<Option<StoredStyleProfile>> {
+    read_profile(&shared_path(repo_root))
+}
+
+/// Load the locally cached profile for the repository, if any.
+pub fn load_cached(repo_root: &str) -> Result<Option<StoredStyleProfile>> {
+    read_profile(&cache_path(repo_root)?)
+}
+
+/// Bring the cached profile up to date with `HEAD` and save it.
+///
+/// Only commits made since the last analyzed commit are read, up to `limit`.
+/// The profile is rebuilt from the newest `limit` commits when `full` is set,
+/// when there is no cache yet, or when the last analyzed commit is no longer
+/// in history (e.g. after a rebase).
+pub fn refresh(repo_root: &str, limit: usize, full: bool) -> Result<StoredStyleProfile> {
+    let head = git::get_head_commit_id()?;
+    let cached = if full { None } else { load_cached(repo_root)? };
+
+    let stored = match cached {
+        Some(stored) if stored.last_commit.as_deref() == Some(head.as_str()) => return Ok(stored),
+        Some(mut stored) => {
+            let range = stored
+                .last_commit
+                .as_ref()
+                .map(|last| format!("{}..{}", last, head));
+            match range.map(|range| git::get_commit_log(Some(&range), Some(limit))) {
+                Some(Ok(commits)) => {
+                    stored
+                        .profile
+                        .update_from_commits(&oldest_first_subjects(commits));
+                    stored
+                }
+                _ => rebuild(repo_root, limit)?,
+            }
+        }
+        None => rebuild(repo_root, limit)?,
+    };
+
+    let stored = StoredStyleProfile {
+        repo_root: Some(repo_root.to_string()),
+        last_commit: Some(head),
+        updated_at: now(),
+        ..stored
+    };
+    write_profile(&cache_path(repo_root)?, &stored)?;
+    Ok(stored)
+}
+
+/// The profile to use for the repository: the shared one when committed,
+/// otherwise the local cache refreshed up to `HEAD`.
+pub fn current(repo_root: &str, limit: usize) -> Result<(StoredStyleProfile, ProfileSource)> {
+    if let Some(shared) = load_shared(repo_root)? {
+        return Ok((shared, ProfileSource::Shared(shared_path(repo_root))));
+    }
+    let stored = refresh(repo_root, limit, false)?;
+    Ok((stored, ProfileSource::Cached(cache_path(repo_root)?)))
+}
+
+fn rebuild(repo_root: &str, limit: usize) -> Result<StoredStyleProfile> {
+    let commits = git::get_commit_log(None, Some(limit))?;
+    Ok(StoredStyleProfile {
+        repo_root: Some(repo_root.to_string()),
+        last_commit: None,
+        updated_at: 0,
+        profile: CommitStyleProfile::analyze_from_commits(&oldest_first_subjects(commits)),
+    })
+}
+
+/// Subject lines of non-merge commits, oldest first so the newest commits
+/// set the profile's prefix format.
+fn oldest_first_subjects(commits: Vec<git::CommitInfo>) -> Vec<String> {
+    commits
+        .into_iter()
+        .rev()
+        .filter(|commit| !commit.is_merge)
+        .map(|commit| commit.message.lines().next().unwrap_or("").to_string())
+        .collect()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -42,19 +42,16 @@ pub async fn check_is_latest_version() -> Result<()> {
 
     // Compare versions
     if crate_info.version != CURRENT_VERSION {
-        println!(
-            "{}",
+        let out = crate::output::context::get();
+        out.line(
             format!(
                 "📦 A new version of {} is available: {} → {}",
                 CRATE_NAME, CURRENT_VERSION, crate_info.version
             )
-            .yellow()
+            .yellow(),
         );
-        println!(
-            "{}",
-            format!("Run `cargo install {CRATE_NAME}` to update").yellow()
-        );
-        println!();
+        out.line(format!("Run `cargo install {CRATE_NAME}` to update").yellow());
+        out.line("");
     }
 
     Ok(())
@@ -60,6 +60,15 @@ fn test_auth_command_help() {
         ));
 }
 
+#[test]
+fn test_auth_login_flow_flags_conflict() {
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.args(["auth", "login", "--device", "--browser"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 #[test]
 fn test_auth_status_not_authenticated() {
     let temp_dir = tempdir().unwrap();
@@ -304,6 +313,8 @@ fn test_config_invalid_values() {
         ("RCO_EMOJI", "not_a_boolean"),
         ("RCO_TOKENS_MAX_INPUT", "not_a_number"),
         ("RCO_TOKENS_MAX_OUTPUT", "negative_number"),
+        ("RCO_BUDGET_MONTHLY_USD", "-5"),
+        ("RCO_BUDGET_ACTION", "stop"),
         ("INVALID_KEY", "any_value"),
     ];
 
@@ -548,3 +559,1331 @@ fn test_multiple_provider_configurations() {
             .stdout(predicate::str::contains(format!("RCO_MODEL: {}", model)));
     }
 }
+
+#[test]
+fn test_config_get_json_output() {
+    let temp_dir = tempdir().unwrap();
+    let home = temp_dir.path();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.env("HOME", home)
+        .args(["config", "set", "RCO_MODEL=gpt-4o-mini"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .env("HOME", home)
+        .args(["config", "get", "RCO_MODEL", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["schema_version"], 1);
+    assert_eq!(report["command"], "config get");
+    assert_eq!(report["success"], true);
+    assert_eq!(report["data"]["key"], "RCO_MODEL");
+    assert_eq!(report["data"]["value"], "gpt-4o-mini");
+
+    // Failures name the same command as successes
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .env("HOME", home)
+        .args(["config", "get", "RCO_NOT_A_KEY", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["command"], "config get");
+    assert_eq!(report["success"], false);
+}
+
+#[test]
+fn test_config_set_json_output_redacts_secrets() {
+    let temp_dir = tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .env("HOME", temp_dir.path())
+        .args(["--output-format", "json", "config", "set", "RCO_API_KEY=sk-secret"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("sk-secret"));
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["data"]["updated"][0]["key"], "RCO_API_KEY");
+}
+
+#[test]
+fn test_show_prompt_json_output() {
+    let temp_dir = tempdir().unwrap();
+    init_test_git_repo(temp_dir.path());
+
+    fs::write(temp_dir.path().join("test.txt"), "test content").unwrap();
+    StdCommand::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .args(["--show-prompt", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["command"], "commit");
+    assert_eq!(report["data"]["action"], "show_prompt");
+    assert!(report["data"]["prompt"]
+        .as_str()
+        .unwrap()
+        .contains("test.txt"));
+    assert!(report["data"]["estimated_tokens"]["diff"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn test_error_report_markdown_outside_git_repo() {
+    let temp_dir = tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .args(["--output-format", "markdown"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("# rco commit"))
+        .stdout(predicate::str::contains("- **success:** false"));
+}
+
+#[test]
+fn test_quiet_suppresses_output_but_keeps_results() {
+    let temp_dir = tempdir().unwrap();
+    let home = temp_dir.path();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.env("HOME", home)
+        .args(["-q", "config", "set", "RCO_MODEL=gpt-4o-mini"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.env("HOME", home)
+        .args(["config", "get", "RCO_MODEL", "-q"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RCO_MODEL: gpt-4o-mini"));
+}
+
+#[test]
+fn test_quiet_conflicts_with_verbose() {
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.args(["-q", "-v", "config", "status"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+fn write_usage_ledger(home: &std::path::Path, lines: &[serde_json::Value]) {
+    let dir = home.join(".config").join("rustycommit");
+    fs::create_dir_all(&dir).unwrap();
+    let body: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    fs::write(dir.join("usage.jsonl"), body.join("\n") + "\n").unwrap();
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[test]
+fn test_usage_empty_ledger() {
+    let temp_dir = tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.env("HOME", temp_dir.path())
+        .env_remove("RCO_CONFIG_HOME")
+        .arg("usage")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No usage recorded yet"));
+}
+
+#[test]
+fn test_usage_report_by_model() {
+    let temp_dir = tempdir().unwrap();
+    let now = unix_now();
+    write_usage_ledger(
+        temp_dir.path(),
+        &[
+            serde_json::json!({"timestamp": now, "provider": "openai", "model": "gpt-4o-mini",
+                "prompt_tokens": 1000, "completion_tokens": 100, "cost_usd": 0.25}),
+            serde_json::json!({"timestamp": now, "provider": "openai", "model": "gpt-4o",
+                "prompt_tokens": 2000, "completion_tokens": 200, "cost_usd": 1.0}),
+            serde_json::json!({"timestamp": now, "provider": "openai", "model": "gpt-4o-mini",
+                "prompt_tokens": 1000, "completion_tokens": 100, "cost_usd": 0.25}),
+            // Older than the --since window
+            serde_json::json!({"timestamp": 0, "provider": "openai", "model": "gpt-4o",
+                "prompt_tokens": 5000, "completion_tokens": 500, "cost_usd": 9.0}),
+        ],
+    );
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .env("HOME", temp_dir.path())
+        .env_remove("RCO_CONFIG_HOME")
+        .args([
+            "usage",
+            "--since",
+            "7d",
+            "--by",
+            "model",
+            "--output-format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["command"], "usage");
+    assert_eq!(report["data"]["by"], "model");
+    let groups = report["data"]["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["key"], "gpt-4o");
+    assert_eq!(groups[1]["key"], "gpt-4o-mini");
+    assert_eq!(groups[1]["requests"], 2);
+    assert_eq!(report["data"]["total"]["requests"], 3);
+    assert_eq!(report["data"]["total"]["prompt_tokens"], 4000);
+}
+
+#[test]
+fn test_budget_block_stops_commit() {
+    let temp_dir = tempdir().unwrap();
+    init_test_git_repo(temp_dir.path());
+    fs::write(temp_dir.path().join("test.txt"), "test content").unwrap();
+    StdCommand::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(temp_dir.path())
+        .output()
+        .unwrap();
+    write_usage_ledger(
+        temp_dir.path(),
+        &[
+            serde_json::json!({"timestamp": unix_now(), "provider": "openai",
+            "model": "gpt-4o", "prompt_tokens": 1000, "completion_tokens": 100, "cost_usd": 5.0}),
+        ],
+    );
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .env_remove("RCO_CONFIG_HOME")
+        .env("RCO_API_KEY", "dummy")
+        .env("RCO_BUDGET_MONTHLY_USD", "1")
+        .env("RCO_BUDGET_ACTION", "block")
+        .arg("--yes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Monthly budget of $1.00 reached"));
+}
+
+#[test]
+fn test_stats_report() {
+    let temp_dir = tempdir().unwrap();
+    init_test_git_repo(temp_dir.path());
+
+    let messages = [
+        "feat(auth): add login",
+        "fix(auth): handle token expiry\n\nGenerated-by: rusty-commit",
+        "Update readme",
+    ];
+    for (i, message) in messages.iter().enumerate() {
+        fs::write(temp_dir.path().join(format!("file{}.txt", i)), message).unwrap();
+        StdCommand::new("git")
+            .args(["add", "."])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        StdCommand::new("git")
+            .args(["commit", "--no-gpg-sign", "-m", message])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+    }
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .args(["stats", "HEAD~3..HEAD", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stats = &report["data"]["stats"];
+    assert_eq!(report["command"], "stats");
+    assert_eq!(stats["commits"], 3);
+    assert_eq!(stats["conventional_commits"], 2);
+    assert_eq!(stats["ai_generated_commits"], 1);
+    assert_eq!(stats["scopes"][0]["name"], "auth");
+    assert_eq!(stats["authors"][0]["email"], "test@example.com");
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(temp_dir.path())
+        .env("HOME", temp_dir.path())
+        .args(["stats", "HEAD~1..HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commits analyzed:      1"));
+}
+
+fn commit_file(dir: &std::path::Path, name: &str, message: &str) {
+    fs::write(dir.join(name), message).unwrap();
+    StdCommand::new("git")
+        .args(["add", name])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    StdCommand::new("git")
+        .args(["commit", "--no-gpg-sign", "-m", message])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+}
+
+fn style_show(dir: &std::path::Path) -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env_remove("RCO_CONFIG_HOME")
+        .args(["style", "show", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_style_profile_cached_and_shared() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    commit_file(dir, "a.txt", "feat(auth): add login");
+    commit_file(dir, "b.txt", "fix(api): handle timeout");
+
+    let report = style_show(dir);
+    assert_eq!(report["data"]["source"]["kind"], "cached");
+    assert_eq!(report["data"]["profile"]["profile"]["commits_analyzed"], 3);
+    assert!(dir
+        .join(".config")
+        .join("rustycommit")
+        .join("styles")
+        .exists());
+
+    // Only the new commit is folded in
+    commit_file(dir, "c.txt", "feat(auth): add logout");
+    let report = style_show(dir);
+    let profile = &report["data"]["profile"]["profile"];
+    assert_eq!(profile["commits_analyzed"], 4);
+    assert_eq!(profile["type_frequencies"]["feat"], 2);
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(dir)
+        .env("HOME", dir)
+        .env_remove("RCO_CONFIG_HOME")
+        .args(["style", "export"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".rco/style.json"));
+
+    let shared = fs::read_to_string(dir.join(".rco").join("style.json")).unwrap();
+    assert!(!shared.contains("repo_root"));
+    let report = style_show(dir);
+    assert_eq!(report["data"]["source"]["kind"], "shared");
+}
+
+#[test]
+fn test_show_prompt_suggests_scopes_from_layout() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+
+    fs::write(
+        dir.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("crates/engine/src")).unwrap();
+    fs::write(
+        dir.join("crates/engine/Cargo.toml"),
+        "[package]\nname = \"engine\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(dir.join("crates/engine/src/lib.rs"), "pub fn run() {}\n").unwrap();
+    fs::create_dir_all(dir.join("src/auth")).unwrap();
+    fs::write(dir.join("src/auth/login.rs"), "fn login() {}\n").unwrap();
+    StdCommand::new("git")
+        .args(["add", "crates", "src"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env("RCO_SCOPE_MAP", "src/auth=auth")
+        .args(["--show-prompt", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let prompt = report["data"]["prompt"].as_str().unwrap();
+    assert!(prompt.contains("Suggested scopes (from repository layout, best first): engine, auth"));
+}
+
+#[test]
+fn test_show_prompt_includes_few_shot_examples() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    fs::create_dir_all(dir.join("src/auth")).unwrap();
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    commit_file(dir, "src/auth/login.rs", "feat(auth): add login");
+    commit_file(dir, "docs/guide.md", "docs: add guide");
+
+    fs::write(dir.join("src/auth/login.rs"), "fn login() { todo!() }\n").unwrap();
+    StdCommand::new("git")
+        .args(["add", "src/auth/login.rs"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env("RCO_FEW_SHOT_COUNT", "1")
+        .args(["--show-prompt", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let prompt = report["data"]["prompt"].as_str().unwrap();
+    assert!(prompt.contains("EXAMPLES (past commits in this repository"));
+    assert!(prompt
+        .contains("Example 1 (1 file changed, +1 -0: src/auth/login.rs):\nfeat(auth): add login"));
+    assert!(!prompt.contains("docs: add guide"));
+}
+
+fn config_explain(dir: &std::path::Path, key: &str) -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env("RCO_SYSTEM_CONFIG", dir.join("system.toml"))
+        .env_remove("RCO_CONFIG_HOME")
+        .env_remove("RCO_MODEL")
+        .env_remove("RCO_LANGUAGE")
+        .env_remove("RCO_EMOJI")
+        .args(["config", "explain", key, "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    report["data"].clone()
+}
+
+#[test]
+fn test_config_layers_and_explain() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+
+    fs::write(dir.join("system.toml"), "language = \"es\"\nemoji = true\n").unwrap();
+    fs::create_dir_all(dir.join(".config/rustycommit")).unwrap();
+    fs::write(
+        dir.join(".config/rustycommit/config.toml"),
+        "model = \"gpt-4\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".rco.toml"),
+        "language = \"de\"\nunset = [\"emoji\"]\n\n[branch.\"release/*\"]\nmodel = \"gpt-4o\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("packages/api")).unwrap();
+    fs::write(dir.join("packages/api/.rco.json"), r#"{"language": "fr"}"#).unwrap();
+
+    // Nothing staged: the package config does not apply at the repo root
+    let language = config_explain(dir, "RCO_LANGUAGE");
+    assert_eq!(language["value"], "de");
+    assert_eq!(language["source"]["kind"], "repo");
+    assert_eq!(language["assignments"][0]["source"]["kind"], "system");
+
+    let emoji = config_explain(dir, "emoji");
+    assert_eq!(emoji["value"], false);
+    assert_eq!(emoji["source"]["kind"], "default");
+
+    let model = config_explain(dir, "RCO_MODEL");
+    assert_eq!(model["value"], "gpt-4");
+    assert_eq!(model["source"]["kind"], "global");
+
+    // Staged files select the package directory; the branch section applies
+    fs::write(dir.join("packages/api/lib.rs"), "pub fn api() {}\n").unwrap();
+    StdCommand::new("git")
+        .args(["add", "packages/api/lib.rs"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    StdCommand::new("git")
+        .args(["checkout", "-b", "release/1.0"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let language = config_explain(dir, "RCO_LANGUAGE");
+    assert_eq!(language["value"], "fr");
+    assert_eq!(language["source"]["kind"], "directory");
+
+    let model = config_explain(dir, "RCO_MODEL");
+    assert_eq!(model["value"], "gpt-4o");
+    assert_eq!(model["source"]["kind"], "branch");
+    assert_eq!(model["source"]["branch"], "release/*");
+}
+
+#[test]
+fn test_config_set_only_changes_that_key_in_global_file() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    StdCommand::new("git")
+        .args(["checkout", "-b", "release/1.0"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let global = dir.join(".config/rustycommit/config.toml");
+    fs::create_dir_all(global.parent().unwrap()).unwrap();
+    fs::write(
+        &global,
+        "schema_version = 3\ncommit_type = \"gitmoji\"\ngitpush = true\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".rco.toml"),
+        "model = \"repo-model\"\n\n[branch.\"release/*\"]\nemoji = true\n",
+    )
+    .unwrap();
+
+    rco_in(dir)
+        .env_remove("RCO_LANGUAGE")
+        .env_remove("RCO_EMOJI")
+        .args(["config", "set", "RCO_LANGUAGE=german"])
+        .assert()
+        .success();
+
+    // The repo and branch values stay out of the global file
+    let document: toml::Table = toml::from_str(&fs::read_to_string(&global).unwrap()).unwrap();
+    let expected: toml::Table = toml::from_str(
+        "schema_version = 3\ncommit_type = \"gitmoji\"\ngitpush = true\nlanguage = \"german\"\n",
+    )
+    .unwrap();
+    assert_eq!(document, expected);
+
+    rco_in(dir)
+        .args(["config", "reset", "RCO_GITPUSH"])
+        .assert()
+        .success();
+    let document: toml::Table = toml::from_str(&fs::read_to_string(&global).unwrap()).unwrap();
+    let expected: toml::Table =
+        toml::from_str("schema_version = 3\ncommit_type = \"gitmoji\"\nlanguage = \"german\"\n")
+            .unwrap();
+    assert_eq!(document, expected);
+}
+
+#[test]
+fn test_config_migrate_dry_run_and_apply() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    let repo_config = dir.join(".rco.toml");
+    let legacy = "RCO_MODEL = \"gpt-4\"\nprompt_module = \"gitmoji\"\nlangauge = \"de\"\n";
+    fs::write(&repo_config, legacy).unwrap();
+
+    let migrate = |dry_run: bool| {
+        let mut args = vec!["config", "migrate", "--output-format", "json"];
+        if dry_run {
+            args.push("--dry-run");
+        }
+        let output = Command::cargo_bin("rco")
+            .unwrap()
+            .current_dir(dir)
+            .env("HOME", dir)
+            .env("RCO_SYSTEM_CONFIG", dir.join("system.toml"))
+            .env_remove("RCO_CONFIG_HOME")
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        report["data"]["files"][0].clone()
+    };
+
+    let planned = migrate(true);
+    assert_eq!(planned["layer"], "repo");
+    assert_eq!(planned["from"], 1);
+    assert_eq!(planned["migrated"], false);
+    assert!(planned["changes"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("renamed RCO_MODEL to model")));
+    assert_eq!(planned["unknown_keys"][0]["suggestion"], "language");
+    assert_eq!(fs::read_to_string(&repo_config).unwrap(), legacy);
+
+    let applied = migrate(false);
+    assert_eq!(applied["migrated"], true);
+    assert_eq!(
+        fs::read_to_string(dir.join(".rco.toml.v1.bak")).unwrap(),
+        legacy
+    );
+    let migrated = fs::read_to_string(&repo_config).unwrap();
+    assert!(migrated.starts_with("schema_version = 3\n"));
+    assert!(migrated.contains("model = \"gpt-4\""));
+    assert!(migrated.contains("commit_type = \"gitmoji\""));
+
+    assert_eq!(migrate(false)["from"], 3);
+}
+
+#[test]
+fn test_yaml_repo_config_and_schema() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    fs::write(
+        dir.join(".rco.yml"),
+        "language: ja\nbranch:\n  \"main\":\n    emoji: true\n",
+    )
+    .unwrap();
+
+    let language = config_explain(dir, "RCO_LANGUAGE");
+    assert_eq!(language["value"], "ja");
+    assert!(language["source"]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with(".rco.yml"));
+
+    let schema_path = dir.join("rco.schema.json");
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(dir)
+        .env("HOME", dir)
+        .args(["config", "schema", "config", "-o"])
+        .arg(&schema_path)
+        .assert()
+        .success();
+    let schema: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&schema_path).unwrap()).unwrap();
+    assert_eq!(
+        schema["properties"]["model"]["description"],
+        "Model name for the provider"
+    );
+    assert!(
+        schema["properties"]["branch"]["additionalProperties"]["properties"]["emoji"].is_object()
+    );
+
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    let output = cmd
+        .current_dir(dir)
+        .env("HOME", dir)
+        .args(["config", "schema", "skill"])
+        .output()
+        .unwrap();
+    let skill: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(skill["title"], "rusty-commit skill manifest");
+}
+
+fn rco_in(dir: &std::path::Path) -> Command {
+    let mut cmd = Command::cargo_bin("rco").unwrap();
+    cmd.current_dir(dir)
+        .env("HOME", dir)
+        .env("RCO_SYSTEM_CONFIG", dir.join("system.toml"))
+        .env("RCO_DISABLE_SECURE_STORAGE", "1")
+        .env_remove("RCO_CONFIG_HOME")
+        .env_remove("RCO_API_KEY")
+        .env_remove("RCO_API_URL")
+        .env_remove("RCO_AI_PROVIDER")
+        .env_remove("RCO_MODEL");
+    cmd
+}
+
+#[test]
+fn test_config_validate_reports_invalid_values() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    fs::write(
+        dir.join(".rco.toml"),
+        "ai_provider = \"opneai\"\ndescription_max_length = 0\napi_url = \"localhost\"\n",
+    )
+    .unwrap();
+
+    let output = rco_in(dir)
+        .args(["config", "validate", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["success"], false);
+    let checks = report["data"]["checks"].as_array().unwrap();
+    let check = |name: &str| {
+        checks
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap_or_else(|| panic!("missing check {}", name))
+    };
+    assert_eq!(check("provider")["status"], "fail");
+    assert_eq!(check("provider")["hints"][0], "Did you mean 'openai'?");
+    assert_eq!(check("description_max_length")["status"], "fail");
+    assert_eq!(check("api url")["status"], "fail");
+    assert!(report["error"]["hints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|h| h == "Set it with: rco config set RCO_DESCRIPTION_MAX_LENGTH=100"));
+}
+
+#[test]
+fn test_doctor_pings_local_stand_in() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/chat/completions")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id":"chatcmpl-1","object":"chat.completion","created":0,"model":"gpt-4o-mini",
+            "choices":[{"index":0,"message":{"role":"assistant","content":"docs: rename project"},"finish_reason":"stop"}]}"#,
+        )
+        .create();
+    fs::write(
+        dir.join(".rco.toml"),
+        format!(
+            "ai_provider = \"openai\"\nmodel = \"gpt-4o-mini\"\napi_key = \"sk-test\"\napi_url = \"{}\"\n",
+            server.url()
+        ),
+    )
+    .unwrap();
+
+    let output = rco_in(dir)
+        .args(["doctor", "--ping", "--output-format", "json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    mock.assert();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let checks = report["data"]["checks"].as_array().unwrap();
+    let names: Vec<&str> = checks.iter().filter_map(|c| c["name"].as_str()).collect();
+    for name in [
+        "provider",
+        "model",
+        "api url",
+        "tokenizer",
+        "git hooks",
+        "ping",
+    ] {
+        assert!(names.contains(&name), "missing check {}", name);
+    }
+    let ping = checks.iter().find(|c| c["name"] == "ping").unwrap();
+    assert_eq!(ping["status"], "ok");
+}
+
+#[test]
+fn test_secrets_vault_keeps_api_key_encrypted() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    let key_file = dir.join("vault.key");
+    let vault = || {
+        let mut cmd = rco_in(dir);
+        cmd.env_remove("RCO_DISABLE_SECURE_STORAGE")
+            .env_remove("RCO_VAULT_PASSPHRASE")
+            .env("RCO_SECRET_BACKEND", "vault")
+            .env("RCO_VAULT_KEY_FILE", &key_file);
+        cmd
+    };
+
+    vault()
+        .args(["config", "set", "RCO_API_KEY=sk-vault-test"])
+        .assert()
+        .success();
+
+    let config_dir = dir.join(".config").join("rustycommit");
+    let sealed = fs::read_to_string(config_dir.join("secrets.vault")).unwrap();
+    assert!(!sealed.contains("sk-vault-test"));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(!config.contains("sk-vault-test"));
+
+    vault()
+        .args(["secrets", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RCO_API_KEY"));
+
+    let old_key = fs::read(&key_file).unwrap();
+    vault().args(["secrets", "rotate"]).assert().success();
+    assert_ne!(fs::read(&key_file).unwrap(), old_key);
+
+    vault()
+        .args(["secrets", "export"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("RCO_API_KEY='sk-vault-test'"));
+}
+
+#[test]
+fn test_locked_vault_refuses_to_store_api_key_in_plaintext() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+
+    // No passphrase, no key file and no terminal to prompt on
+    let output = rco_in(dir)
+        .env_remove("RCO_DISABLE_SECURE_STORAGE")
+        .env_remove("RCO_VAULT_PASSPHRASE")
+        .env_remove("RCO_VAULT_KEY_FILE")
+        .env("RCO_SECRET_BACKEND", "vault")
+        .args(["config", "set", "RCO_API_KEY=sk-locked-test"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("vault is locked"), "{stderr}");
+
+    let config_dir = dir.join(".config").join("rustycommit");
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap_or_default();
+    assert!(!config.contains("sk-locked-test"));
+    assert!(!config_dir.join("secrets.vault").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_credential_helper_serves_command_backed_account() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    let config_dir = dir.join(".config").join("rustycommit");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("accounts.toml"),
+        r#"active_account = "work"
+
+[accounts.work]
+alias = "work"
+provider = "openai"
+api_url = "https://llm.example.com/v1"
+
+[accounts.work.auth]
+type = "command"
+command = "printf 'sk-from-command\nnote: ignored'"
+"#,
+    )
+    .unwrap();
+
+    rco_in(dir)
+        .args(["credential", "get"])
+        .write_stdin("protocol=https\nhost=llm.example.com\n\n")
+        .assert()
+        .success()
+        .stdout("username=work\nprovider=openai\npassword=sk-from-command\n");
+
+    // Requests for other hosts (e.g. git itself) get nothing back
+    rco_in(dir)
+        .args(["credential", "get"])
+        .write_stdin("protocol=https\nhost=github.com\n")
+        .assert()
+        .success()
+        .stdout("");
+}
+
+fn write_accounts(dir: &std::path::Path, accounts: &str) -> std::path::PathBuf {
+    let config_dir = dir.join(".config").join("rustycommit");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("accounts.toml"), accounts).unwrap();
+    config_dir
+}
+
+#[test]
+fn test_use_account_switches_file_stored_keys() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    let config_dir = write_accounts(
+        dir,
+        r#"[accounts.work]
+alias = "work"
+provider = "openai"
+
+[accounts.work.auth]
+type = "api_key"
+key_id = "rco_work"
+
+[accounts.personal]
+alias = "personal"
+provider = "openai"
+
+[accounts.personal.auth]
+type = "api_key"
+key_id = "rco_personal"
+"#,
+    );
+
+    for (alias, key) in [("work", "sk-work"), ("personal", "sk-personal")] {
+        rco_in(dir)
+            .args(["credential", "store"])
+            .write_stdin(format!("account={alias}\npassword={key}\n"))
+            .assert()
+            .success();
+    }
+    let stored = fs::read_to_string(config_dir.join("credentials.json")).unwrap();
+    assert!(stored.contains("sk-work") && stored.contains("sk-personal"));
+    assert!(!config_dir.join("auth.json").exists());
+
+    for (alias, key) in [("personal", "sk-personal"), ("work", "sk-work")] {
+        rco_in(dir)
+            .args(["config", "use-account", alias])
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("No credentials").not());
+        rco_in(dir)
+            .args(["credential", "get"])
+            .write_stdin("\n")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(format!("password={key}\n")));
+    }
+}
+
+#[test]
+fn test_legacy_oauth_tokens_move_to_the_active_account() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    let config_dir = write_accounts(
+        dir,
+        r#"active_account = "claude"
+
+[accounts.claude]
+alias = "claude"
+provider = "anthropic"
+
+[accounts.claude.auth]
+type = "oauth"
+provider = "claude"
+account_id = "me@example.com"
+"#,
+    );
+    fs::write(
+        config_dir.join("auth.json"),
+        r#"{"access_token":"legacy-token","refresh_token":null,"expires_at":null,"token_type":"Bearer","scope":null}"#,
+    )
+    .unwrap();
+
+    rco_in(dir)
+        .args(["credential", "get"])
+        .write_stdin("account=claude\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("password=legacy-token"))
+        .stderr(predicate::str::contains("Moved existing credentials"));
+
+    let stored = fs::read_to_string(config_dir.join("credentials.json")).unwrap();
+    assert!(stored.contains("\"legacy_migrated\": true"));
+    assert!(stored.contains("legacy-token"));
+}
+
+#[test]
+fn test_legacy_api_key_moves_only_to_the_active_account() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    let config_dir = write_accounts(
+        dir,
+        r#"active_account = "work"
+
+[accounts.personal]
+alias = "personal"
+provider = "openai"
+
+[accounts.personal.auth]
+type = "api_key"
+key_id = "rco_personal"
+
+[accounts.work]
+alias = "work"
+provider = "openai"
+
+[accounts.work.auth]
+type = "api_key"
+key_id = "rco_work"
+"#,
+    );
+    fs::write(
+        config_dir.join("config.toml"),
+        "ai_provider = \"openai\"\napi_key = \"sk-legacy\"\n",
+    )
+    .unwrap();
+
+    rco_in(dir)
+        .args(["credential", "get"])
+        .write_stdin("account=work\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("password=sk-legacy"))
+        .stderr(predicate::str::contains(
+            "Moved existing credentials into account(s): work",
+        ))
+        .stderr(predicate::str::contains(
+            "Account 'personal' has no API key of its own",
+        ));
+
+    rco_in(dir)
+        .args(["credential", "get"])
+        .write_stdin("account=personal\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("password=").not());
+}
+
+#[test]
+fn test_oidc_account_logs_in_with_client_credentials() {
+    let mut issuer = mockito::Server::new();
+    let url = issuer.url();
+    issuer
+        .mock("GET", "/.well-known/openid-configuration")
+        .with_body(format!(r#"{{"issuer":"{url}","token_endpoint":"{url}/token"}}"#))
+        .create();
+    let token = issuer
+        .mock("POST", "/token")
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()),
+            mockito::Matcher::UrlEncoded("client_id".into(), "rco-ci".into()),
+            mockito::Matcher::UrlEncoded("client_secret".into(), "s3cret".into()),
+        ]))
+        .with_body(r#"{"access_token":"ci-token","token_type":"Bearer","expires_in":300}"#)
+        .expect(1)
+        .create();
+
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    write_accounts(
+        dir,
+        &format!(
+            r#"[accounts.ci]
+alias = "ci"
+provider = "openai"
+api_url = "{url}/v1"
+
+[accounts.ci.auth]
+type = "oauth"
+provider = "oidc"
+account_id = "rco_ci"
+
+[accounts.ci.auth.oidc]
+issuer = "{url}"
+client_id = "rco-ci"
+grant = "client_credentials"
+client_secret_env = "RCO_TEST_OIDC_SECRET"
+"#
+        ),
+    );
+
+    rco_in(dir)
+        .env("RCO_TEST_OIDC_SECRET", "s3cret")
+        .args(["--output-format", "json", "auth", "login", "--account", "ci"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""grant": "client_credentials""#));
+    token.assert();
+
+    rco_in(dir)
+        .args(["auth", "login", "--account", "ci"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("RCO_TEST_OIDC_SECRET is not set"));
+}
+
+#[test]
+fn test_provider_errors_exit_with_their_own_code_and_hints() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    fs::write(dir.join("test.txt"), "test content").unwrap();
+    StdCommand::new("git")
+        .args(["add", "test.txt"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let mut server = mockito::Server::new();
+    server
+        .mock("POST", "/api/generate")
+        .with_status(404)
+        .with_body(r#"{"error":"model \"llama9\" not found, try pulling it first"}"#)
+        .create();
+    server
+        .mock("POST", "/chat/completions")
+        .with_status(401)
+        .with_body(r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","code":"invalid_api_key"}}"#)
+        .create();
+
+    fs::write(
+        dir.join(".rco.toml"),
+        format!(
+            "ai_provider = \"ollama\"\nmodel = \"llama9\"\napi_url = \"{}\"\n",
+            server.url()
+        ),
+    )
+    .unwrap();
+    rco_in(dir)
+        .arg("--yes")
+        .assert()
+        .code(7)
+        .stderr(predicate::str::contains("Model not found: llama9"))
+        .stderr(predicate::str::contains("ollama pull llama9"));
+
+    fs::write(
+        dir.join(".rco.toml"),
+        format!(
+            "ai_provider = \"openai\"\nmodel = \"gpt-4o-mini\"\napi_key = \"sk-bad\"\napi_url = \"{}\"\n",
+            server.url()
+        ),
+    )
+    .unwrap();
+    let output = rco_in(dir)
+        .args(["--output-format", "json", "--yes"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let error = &report["error"];
+    assert_eq!(error["error"], "Authentication failed");
+    assert_eq!(error["provider"], "openai");
+    assert_eq!(error["exit_code"], 3);
+    assert!(error["hints"][0]
+        .as_str()
+        .unwrap()
+        .contains("platform.openai.com/api-keys"));
+}
+
+#[test]
+fn test_context_length_errors_fall_back_to_smaller_prompts() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    let content: String = (0..300)
+        .map(|i| format!("let value_{i} = compute_something({i});\n"))
+        .collect();
+    fs::write(dir.join("big.rs"), content).unwrap();
+    StdCommand::new("git")
+        .args(["add", "big.rs"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let mut server = mockito::Server::new();
+    let summary = server
+        .mock("POST", "/chat/completions")
+        .match_body(mockito::Matcher::Regex("Summary only".to_string()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id":"chatcmpl-1","object":"chat.completion","created":0,"model":"gpt-4o-mini",
+            "choices":[{"index":0,"message":{"role":"assistant","content":"feat: add big module"},"finish_reason":"stop"}]}"#,
+        )
+        .expect(1)
+        .create();
+    let too_long = server
+        .mock("POST", "/chat/completions")
+        .with_status(400)
+        .with_body(r#"{"error":{"message":"This model's maximum context length is 8192 tokens.","type":"invalid_request_error","code":"context_length_exceeded"}}"#)
+        .expect(3)
+        .create();
+    fs::write(
+        dir.join(".rco.toml"),
+        format!(
+            "ai_provider = \"openai\"\nmodel = \"gpt-4o-mini\"\napi_key = \"sk-test\"\napi_url = \"{}\"\n",
+            server.url()
+        ),
+    )
+    .unwrap();
+
+    let output = rco_in(dir)
+        .args(["--output-format", "json", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    too_long.assert();
+    summary.assert();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["data"]["final_message"], "feat: add big module");
+    assert_eq!(report["data"]["degraded"]["mode"], "file_summary");
+    assert_eq!(report["data"]["degraded"]["context_limit"], 8192);
+}
+
+#[test]
+fn test_tiny_context_window_falls_back_to_summary_at_once() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    let content: String = (0..300)
+        .map(|i| format!("let value_{i} = compute_something({i});\n"))
+        .collect();
+    fs::write(dir.join("big.rs"), content).unwrap();
+    StdCommand::new("git")
+        .args(["add", "big.rs"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    // 1024 tokens leave no room for the diff next to the prompt and the
+    // answer, so an empty diff must never be sent
+    let mut server = mockito::Server::new();
+    let summary = server
+        .mock("POST", "/chat/completions")
+        .match_body(mockito::Matcher::Regex("Summary only".to_string()))
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id":"chatcmpl-1","object":"chat.completion","created":0,"model":"gpt-4o-mini",
+            "choices":[{"index":0,"message":{"role":"assistant","content":"feat: add big module"},"finish_reason":"stop"}]}"#,
+        )
+        .expect(1)
+        .create();
+    let too_long = server
+        .mock("POST", "/chat/completions")
+        .with_status(400)
+        .with_body(r#"{"error":{"message":"This model's maximum context length is 1024 tokens.","type":"invalid_request_error","code":"context_length_exceeded"}}"#)
+        .expect(1)
+        .create();
+    fs::write(
+        dir.join(".rco.toml"),
+        format!(
+            "ai_provider = \"openai\"\nmodel = \"gpt-4o-mini\"\napi_key = \"sk-test\"\napi_url = \"{}\"\n",
+            server.url()
+        ),
+    )
+    .unwrap();
+
+    let output = rco_in(dir)
+        .args(["--output-format", "json", "--yes"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    too_long.assert();
+    summary.assert();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["data"]["degraded"]["mode"], "file_summary");
+    assert_eq!(report["data"]["degraded"]["context_limit"], 1024);
+}
+
+#[test]
+fn test_mock_provider_generates_records_and_replays() {
+    let temp_dir = tempdir().unwrap();
+    let dir = temp_dir.path();
+    init_test_git_repo(dir);
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("docs/guide.md"), "# Guide\n").unwrap();
+    StdCommand::new("git")
+        .args(["add", "docs/guide.md"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    let dry_run = |config: &str| {
+        fs::write(dir.join(".rco.toml"), config).unwrap();
+        let output = rco_in(dir)
+            .args(["--output-format", "json", "--dry-run"])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        report["data"]["messages"][0].as_str().unwrap().to_string()
+    };
+
+    // Generated from the diff: docs type, file name as subject
+    let generated = dry_run(
+        "ai_provider = \"mock\"\ncommit_type = \"conventional\"\ndescription_capitalize = false\n",
+    );
+    assert_eq!(generated, "docs: add guide.md");
+
+    // Recorded from a real provider
+    let mut server = mockito::Server::new();
+    let ollama = server
+        .mock("POST", "/api/generate")
+        .with_body(r#"{"response":"docs: write the getting started guide"}"#)
+        .expect(1)
+        .create();
+    let recorded = dry_run(&format!(
+        "ai_provider = \"ollama\"\nmodel = \"mistral\"\napi_url = \"{}\"\ntest_mock_type = \"record\"\n",
+        server.url()
+    ));
+    assert_eq!(recorded, "docs: write the getting started guide");
+    ollama.assert();
+
+    let fixtures: Vec<_> = fs::read_dir(dir.join(".rco/fixtures"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(fixtures.len(), 1);
+    let fixture: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&fixtures[0]).unwrap()).unwrap();
+    assert_eq!(fixture["provider"], "ollama");
+    assert_eq!(fixture["request"]["kind"], "commit_message");
+    assert_eq!(
+        fixture["responses"][0],
+        "docs: write the getting started guide"
+    );
+
+    // Replayed by the mock without the server
+    let replayed = dry_run("ai_provider = \"mock\"\ntest_mock_type = \"replay\"\n");
+    assert_eq!(replayed, "docs: write the getting started guide");
+    ollama.assert();
+
+    // A different diff has no recording
+    fs::write(dir.join("docs/faq.md"), "# FAQ\n").unwrap();
+    StdCommand::new("git")
+        .args(["add", "docs/faq.md"])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    let output = rco_in(dir).args(["--dry-run"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No fixture recorded"));
+}
@@ -124,7 +124,7 @@ fn test_default_config() {
     assert_eq!(config.tokens_max_input, 4096);
     assert_eq!(config.tokens_max_output, 500);
     assert_eq!(config.commit_type, "conventional");
-    assert_eq!(config.emoji, false);
+    assert!(!config.emoji);
 }
 
 #[test]
@@ -143,13 +143,42 @@ fn test_save_and_load_config() {
         // Load the config back
         let loaded_config = Config::load().unwrap();
         assert_eq!(loaded_config.api_key.as_ref(), Some(&"test_key".to_string()));
-        assert_eq!(loaded_config.emoji, true);
+        assert!(loaded_config.emoji);
         assert_eq!(loaded_config.tokens_max_output, 1000);
 
         cleanup_test_env();
     });
 }
 
+#[test]
+fn test_load_config_saved_before_newer_keys() {
+    with_test_lock(|| {
+        let temp_dir = setup_test_env("test_load_config_saved_before_newer_keys");
+
+        let mut config = Config::default();
+        config.model = "gpt-4o".to_string();
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let older: String = saved
+            .lines()
+            .filter(|line| !line.starts_with("scope_strict") && !line.starts_with("few_shot_"))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let config_dir = temp_dir
+            .path()
+            .join("config")
+            .join("test_load_config_saved_before_newer_keys");
+        fs::write(config_dir.join("config.toml"), older).unwrap();
+
+        let loaded = Config::load().unwrap();
+        assert_eq!(loaded.model, "gpt-4o");
+        assert!(!loaded.scope_strict);
+        assert_eq!(loaded.few_shot_count, 0);
+        assert_eq!(loaded.few_shot_max_tokens, 800);
+
+        cleanup_test_env();
+    });
+}
+
 #[test]
 fn test_parse_legacy_format() {
     with_test_lock(|| {
@@ -169,8 +198,8 @@ fn test_parse_legacy_format() {
         assert_eq!(config.api_key.as_deref(), Some("sk-test-key"));
         assert_eq!(config.ai_provider, "openai");
         assert_eq!(config.model, "gpt-4");
-        assert_eq!(config.emoji, true);
-        assert_eq!(config.gitpush, false);
+        assert!(config.emoji);
+        assert!(!config.gitpush);
         assert_eq!(config.language, "en");
         assert_eq!(config.tokens_max_output, 1000);
 
@@ -223,13 +252,13 @@ fn test_reset_config() {
         // Reset specific keys
         config.reset(Some(&vec!["RCO_EMOJI".to_string()])).unwrap();
         assert_eq!(config.api_key.as_deref(), Some("custom_key"));
-        assert_eq!(config.emoji, false); // Reset to default
+        assert!(!config.emoji); // Reset to default
         assert_eq!(config.tokens_max_output, 1000);
 
         // Reset all
         config.reset(None).unwrap();
         assert_eq!(config.api_key, None);
-        assert_eq!(config.emoji, false);
+        assert!(!config.emoji);
         assert_eq!(config.tokens_max_output, 500);
 
         cleanup_test_env();
@@ -234,3 +234,156 @@ fn test_get_repo_root() {
         drop(temp_dir);
     });
 }
+
+fn git_config(dir: &std::path::Path, key: &str, value: &str) {
+    Command::new("git")
+        .args(["config", key, value])
+        .current_dir(dir)
+        .output()
+        .expect("Failed to set git config");
+}
+
+#[test]
+fn test_get_signing_config() {
+    with_test_lock(|| {
+        let temp_dir = init_test_repo();
+        let original_cwd = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        let signing = git::get_signing_config().unwrap();
+        assert!(!signing.enabled);
+        assert!(!signing.should_sign(None));
+        assert!(signing.should_sign(Some(true)));
+
+        git_config(temp_dir.path(), "commit.gpgsign", "true");
+        git_config(temp_dir.path(), "gpg.format", "ssh");
+        let signing = git::get_signing_config().unwrap();
+        assert!(signing.enabled);
+        assert_eq!(signing.format, git::SigningFormat::Ssh);
+        assert!(!signing.should_sign(Some(false)));
+        // SSH signing without a key should be rejected up front
+        assert!(signing.validate().is_err());
+
+        git_config(temp_dir.path(), "user.signingkey", "/tmp/key.pub");
+        let signing = git::get_signing_config().unwrap();
+        assert_eq!(signing.key.as_deref(), Some("/tmp/key.pub"));
+        assert!(signing.validate().is_ok());
+
+        git_config(temp_dir.path(), "gpg.format", "pgp2");
+        assert!(git::get_signing_config().is_err());
+
+        std::env::set_current_dir(&original_cwd).unwrap();
+        drop(temp_dir);
+    });
+}
+
+#[test]
+fn test_commit_without_signing() {
+    with_test_lock(|| {
+        let temp_dir = init_test_repo_with_commit();
+        let original_cwd = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        // commit.gpgsign is on, but --no-sign must win
+        git_config(temp_dir.path(), "commit.gpgsign", "true");
+        fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+        git::stage_files(&["file.txt".to_string()]).unwrap();
+
+        let sha = git::commit("feat: add file", Some(false)).unwrap();
+        assert_eq!(sha.len(), 40);
+
+        let signature = git::get_commit_signature(&sha).unwrap();
+        assert_eq!(signature.status, git::SignatureStatus::Unsigned);
+        assert!(!signature.status.is_signed());
+
+        std::env::set_current_dir(&original_cwd).unwrap();
+        drop(temp_dir);
+    });
+}
+
+#[test]
+fn test_commit_without_signing_ignores_signing_config() {
+    with_test_lock(|| {
+        let temp_dir = init_test_repo_with_commit();
+        let original_cwd = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        // SSH signing without a key is only an error when signing
+        git_config(temp_dir.path(), "commit.gpgsign", "true");
+        git_config(temp_dir.path(), "gpg.format", "ssh");
+        fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+        git::stage_files(&["file.txt".to_string()]).unwrap();
+        assert!(git::commit("feat: add file", Some(false)).is_ok());
+
+        // An unsupported format is left for git to judge, not rejected up front
+        git_config(temp_dir.path(), "gpg.format", "pgp2");
+        fs::write(temp_dir.path().join("other.txt"), "content").unwrap();
+        git::stage_files(&["other.txt".to_string()]).unwrap();
+        if let Err(e) = git::commit("feat: add other file", Some(false)) {
+            assert!(!e.to_string().contains("Unsupported gpg.format"));
+        }
+
+        std::env::set_current_dir(&original_cwd).unwrap();
+        drop(temp_dir);
+    });
+}
+
+#[test]
+fn test_commit_with_ssh_signing() {
+    if which::which("ssh-keygen").is_err() {
+        return;
+    }
+
+    with_test_lock(|| {
+        let temp_dir = init_test_repo_with_commit();
+        let key_dir = tempdir().unwrap();
+        let key_path = key_dir.path().join("id_ed25519");
+        let keygen = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key_path)
+            .output()
+            .unwrap();
+        assert!(keygen.status.success());
+
+        let original_cwd = std::env::current_dir().unwrap();
+        std::env::set_current_dir(temp_dir.path()).unwrap();
+
+        let public_key = format!("{}.pub", key_path.display());
+        git_config(temp_dir.path(), "gpg.format", "ssh");
+        git_config(temp_dir.path(), "user.signingkey", &public_key);
+
+        fs::write(temp_dir.path().join("file.txt"), "content").unwrap();
+        git::stage_files(&["file.txt".to_string()]).unwrap();
+
+        let sha = git::commit("feat: add signed file", Some(true)).unwrap();
+        let signature = git::get_commit_signature(&sha).unwrap();
+        // Without gpg.ssh.allowedSignersFile git can't verify, but the
+        // commit must carry a signature
+        assert!(signature.status.is_signed());
+
+        std::env::set_current_dir(&original_cwd).unwrap();
+        drop(temp_dir);
+        drop(key_dir);
+    });
+}
+
+#[test]
+fn test_signature_status_codes() {
+    assert_eq!(
+        git::SignatureStatus::from_code("G"),
+        git::SignatureStatus::Good
+    );
+    assert_eq!(git::SignatureStatus::from_code("B"), git::SignatureStatus::Bad);
+    assert_eq!(
+        git::SignatureStatus::from_code("E"),
+        git::SignatureStatus::CannotCheck
+    );
+    assert_eq!(
+        git::SignatureStatus::from_code("N"),
+        git::SignatureStatus::Unsigned
+    );
+    assert_eq!(
+        git::SigningFormat::from_git("X509"),
+        Some(git::SigningFormat::X509)
+    );
+}
@@ -0,0 +1,454 @@
+//! Cassette tests for the provider modules.
+//!
+//! Each provider has a cassette in `tests/cassettes/<provider>.json` with the
+//! endpoint it calls and golden responses for a set of interactions. The
+//! harness serves one interaction at a time from a mockito server, points the
+//! provider at it through `api_url`, and checks what the provider makes of it:
+//!
+//! - `success`, `streaming` and `thinking` (a reasoning model's answer) give
+//!   `message`, after `strip_thinking`. `streaming` is replayed with
+//!   `RCO_STREAM` on: the request must ask for a stream (`request`, `query`),
+//!   and the answer is a server-sent event stream (JSON lines for Ollama)
+//!   sent in chunks.
+//! - `rate_limited`, `unauthorized` and `malformed` give the matching
+//!   `ProviderError`.
+//!
+//! To refresh a cassette from the real API, run `rco -vv` against it, which
+//! logs every response to `logs/debug.log` in the config directory, then:
+//!
+//! ```text
+//! RCO_CASSETTE_LOG=~/.config/rustycommit/logs/debug.log RCO_CASSETTE_PROVIDER=openai \
+//!     cargo test --test provider_cassettes_test record_cassette -- --ignored
+//! ```
+//!
+//! The last logged response of that provider (already redacted) replaces the
+//! `RCO_CASSETTE_INTERACTION` interaction (default: `success`), and its
+//! `message` is updated to what the provider makes of it.
+//!
+//! Bedrock has no `streaming` interaction, since it always asks for the whole
+//! answer at once.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use mockito::{Matcher, Server};
+use rusty_commit::config::Config;
+use rusty_commit::providers::create_provider;
+use rusty_commit::providers::error::ProviderError;
+use rusty_commit::utils::strip_thinking;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const DIFF: &str = "diff --git a/src/http.rs b/src/http.rs
+--- a/src/http.rs
++++ b/src/http.rs
+@@ -10,3 +10,9 @@ pub async fn send(request: Request) -> Result<Response> {
+-    client.execute(request).await
++    for attempt in 0..3 {
++        match client.execute(request.try_clone()?).await {
++            Err(e) if e.is_connect() && attempt < 2 => continue,
++            result => return result,
++        }
++    }
++    unreachable!()
+";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cassette {
+    provider: String,
+    model: String,
+    api_key: String,
+    /// Base URL for the provider; `{server}` is the mock server's URL.
+    api_url: String,
+    /// Path the provider is expected to post to.
+    endpoint: String,
+    /// Environment the provider reads its settings from.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    env: BTreeMap<String, String>,
+    interactions: BTreeMap<String, Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    /// Path the provider posts to, when not the cassette's `endpoint`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    endpoint: Option<String>,
+    /// Fields the request body must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<Value>,
+    /// Query parameters the request must have.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    query: BTreeMap<String, String>,
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    /// A JSON body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+    /// A body that is not JSON, or not valid JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    /// A body sent in these chunks, with chunked transfer encoding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chunks: Option<Vec<String>>,
+    /// The commit message the provider should return.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl Interaction {
+    fn body(&self) -> String {
+        match (&self.body, &self.text, &self.chunks) {
+            (Some(body), _, _) => body.to_string(),
+            (None, Some(text), _) => text.clone(),
+            (None, None, Some(chunks)) => chunks.concat(),
+            (None, None, None) => String::new(),
+        }
+    }
+}
+
+fn cassette_path(provider: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("cassettes")
+        .join(format!("{}.json", provider))
+}
+
+fn load_cassette(provider: &str) -> Cassette {
+    let path = cassette_path(provider);
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e))
+}
+
+// Replays set the cassette's environment for the whole process, so tests
+// that replay run one at a time
+static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+/// Keep the providers' stored logins, usage records and logs out of the
+/// user's config directory.
+fn isolate() {
+    static HOME: OnceLock<tempfile::TempDir> = OnceLock::new();
+    HOME.get_or_init(|| {
+        let home = tempfile::tempdir().unwrap();
+        std::env::set_var("HOME", home.path());
+        std::env::set_var("RCO_CONFIG_HOME", home.path().join("config"));
+        std::env::set_var("RCO_DISABLE_SECURE_STORAGE", "1");
+        home
+    });
+}
+
+/// Mount an interaction on the server for the cassette's endpoint.
+fn mount(server: &mut Server, cassette: &Cassette, interaction: &Interaction) -> mockito::Mock {
+    let endpoint = interaction.endpoint.as_ref().unwrap_or(&cassette.endpoint);
+    let query = match interaction.query.is_empty() {
+        true => Matcher::Any,
+        false => Matcher::AllOf(
+            interaction
+                .query
+                .iter()
+                .map(|(name, value)| Matcher::UrlEncoded(name.clone(), value.clone()))
+                .collect(),
+        ),
+    };
+    let mut mock = server
+        .mock("POST", endpoint.as_str())
+        .match_query(query)
+        .with_status(interaction.status as usize);
+    if let Some(request) = &interaction.request {
+        mock = mock.match_body(Matcher::PartialJson(request.clone()));
+    }
+    for (name, value) in &interaction.headers {
+        mock = mock.with_header(name.as_str(), value.as_str());
+    }
+    mock = match &interaction.chunks {
+        Some(chunks) => {
+            let chunks = chunks.clone();
+            mock.with_chunked_body(move |writer| {
+                for chunk in &chunks {
+                    writer.write_all(chunk.as_bytes())?;
+                    writer.flush()?;
+                }
+                Ok(())
+            })
+        }
+        None => mock.with_body(interaction.body()),
+    };
+    mock.expect(1).create()
+}
+
+fn config_for(cassette: &Cassette, server_url: &str) -> Config {
+    Config {
+        ai_provider: cassette.provider.clone(),
+        model: cassette.model.clone(),
+        api_key: Some(cassette.api_key.clone()).filter(|key| !key.is_empty()),
+        api_url: Some(cassette.api_url.replace("{server}", server_url)),
+        retry_count: 0,
+        learn_from_history: false,
+        few_shot_count: 0,
+        read_project_config: false,
+        read_agent_files: false,
+        ..Default::default()
+    }
+}
+
+/// Serve one interaction of a cassette to its provider and return what the
+/// provider made of it.
+fn replay(cassette: &Cassette, name: &str) -> anyhow::Result<String> {
+    let _guard = TEST_MUTEX
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    isolate();
+    for (key, value) in &cassette.env {
+        std::env::set_var(key, value);
+    }
+    let result = replay_interaction(cassette, name);
+    for key in cassette.env.keys() {
+        std::env::remove_var(key);
+    }
+    result
+}
+
+fn replay_interaction(cassette: &Cassette, name: &str) -> anyhow::Result<String> {
+    let interaction = cassette
+        .interactions
+        .get(name)
+        .unwrap_or_else(|| panic!("{} cassette has no '{}'", cassette.provider, name));
+
+    let mut server = Server::new();
+    let mock = mount(&mut server, cassette, interaction);
+    let config = Config {
+        stream: name == "streaming",
+        ..config_for(cassette, &server.url())
+    };
+
+    // Some providers start their own runtime while being created
+    let provider = create_provider(&config)?;
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(provider.generate_commit_message(DIFF, None, false, &config));
+    mock.assert();
+    result
+}
+
+/// Replay every interaction of a provider's cassette and check the outcome.
+fn check_cassette(provider: &str) {
+    let cassette = load_cassette(provider);
+    for name in cassette.interactions.keys() {
+        let expected = &cassette.interactions[name];
+        let result = replay(&cassette, name);
+        let context = format!("{} {}", provider, name);
+
+        match name.as_str() {
+            "success" | "streaming" | "thinking" => {
+                let message = result.unwrap_or_else(|e| panic!("{}: {:#}", context, e));
+                assert_eq!(
+                    Some(strip_thinking(&message).trim()),
+                    expected.message.as_deref(),
+                    "{}",
+                    context
+                );
+            }
+            "rate_limited" | "unauthorized" | "malformed" => {
+                let error = result.expect_err(&context);
+                let kind = ProviderError::find(&error);
+                let matched = match name.as_str() {
+                    "rate_limited" => matches!(kind, Some(ProviderError::RateLimited { .. })),
+                    "unauthorized" => matches!(kind, Some(ProviderError::Auth { .. })),
+                    _ => matches!(kind, Some(ProviderError::InvalidResponse { .. })),
+                };
+                assert!(matched, "{}: {:#} ({:?})", context, error, kind);
+            }
+            other => panic!("{}: unknown interaction '{}'", provider, other),
+        }
+    }
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_cassette() {
+    check_cassette("openai");
+}
+
+#[cfg(feature = "anthropic")]
+#[test]
+fn test_anthropic_cassette() {
+    check_cassette("anthropic");
+}
+
+#[cfg(feature = "gemini")]
+#[test]
+fn test_gemini_cassette() {
+    check_cassette("gemini");
+}
+
+#[cfg(feature = "ollama")]
+#[test]
+fn test_ollama_cassette() {
+    check_cassette("ollama");
+}
+
+#[cfg(feature = "azure")]
+#[test]
+fn test_azure_cassette() {
+    check_cassette("azure");
+}
+
+#[cfg(feature = "perplexity")]
+#[test]
+fn test_perplexity_cassette() {
+    check_cassette("perplexity");
+}
+
+#[cfg(feature = "xai")]
+#[test]
+fn test_xai_cassette() {
+    check_cassette("xai");
+}
+
+#[cfg(feature = "vertex")]
+#[test]
+fn test_vertex_cassette() {
+    check_cassette("vertex");
+}
+
+#[cfg(feature = "bedrock")]
+#[test]
+fn test_bedrock_cassette() {
+    check_cassette("bedrock");
+}
+
+#[cfg(feature = "huggingface")]
+#[test]
+fn test_huggingface_cassette() {
+    check_cassette("huggingface");
+}
+
+#[cfg(feature = "mlx")]
+#[test]
+fn test_mlx_cassette() {
+    check_cassette("mlx");
+}
+
+#[cfg(feature = "nvidia")]
+#[test]
+fn test_nvidia_cassette() {
+    check_cassette("nvidia");
+}
+
+#[cfg(feature = "flowise")]
+#[test]
+fn test_flowise_cassette() {
+    check_cassette("flowise");
+}
+
+#[test]
+fn test_cassettes_cover_every_interaction() {
+    let dir = cassette_path("x").parent().unwrap().to_path_buf();
+    let mut providers = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let provider = path.file_stem().unwrap().to_string_lossy().to_string();
+        let cassette = load_cassette(&provider);
+        assert_eq!(cassette.provider, provider);
+        let names: Vec<&str> = cassette.interactions.keys().map(String::as_str).collect();
+        let mut expected = vec![
+            "malformed",
+            "rate_limited",
+            "streaming",
+            "success",
+            "thinking",
+            "unauthorized",
+        ];
+        if provider == "bedrock" {
+            expected.retain(|name| *name != "streaming");
+        }
+        assert_eq!(names, expected, "{}", provider);
+        providers.push(provider);
+    }
+    providers.sort();
+    assert_eq!(
+        providers,
+        [
+            "anthropic",
+            "azure",
+            "bedrock",
+            "flowise",
+            "gemini",
+            "huggingface",
+            "mlx",
+            "nvidia",
+            "ollama",
+            "openai",
+            "perplexity",
+            "vertex",
+            "xai"
+        ]
+    );
+}
+
+/// Replace an interaction with the last response of a provider in a payload
+/// log written by `rco -vv`. See the module docs.
+#[test]
+#[ignore = "updates a cassette from a payload log"]
+fn record_cassette() {
+    let log = std::env::var("RCO_CASSETTE_LOG").expect("Set RCO_CASSETTE_LOG to a debug.log");
+    let provider = std::env::var("RCO_CASSETTE_PROVIDER").expect("Set RCO_CASSETTE_PROVIDER");
+    let name = std::env::var("RCO_CASSETTE_INTERACTION").unwrap_or_else(|_| "success".to_string());
+
+    let content = std::fs::read_to_string(&log).expect("Failed to read the payload log");
+    let entry: Value = content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .find(|entry| entry["direction"] == "response" && entry["provider"] == provider.as_str())
+        .unwrap_or_else(|| panic!("No {} response in {}", provider, log));
+
+    let mut cassette = load_cassette(&provider);
+    // What the request must look like stays as it was
+    let previous = cassette.interactions.get(&name);
+    let content_type = match name.as_str() {
+        "streaming" if provider == "ollama" => "application/x-ndjson",
+        "streaming" => "text/event-stream",
+        _ => "application/json",
+    };
+    let mut interaction = Interaction {
+        endpoint: previous.and_then(|i| i.endpoint.clone()),
+        request: previous.and_then(|i| i.request.clone()),
+        query: previous.map(|i| i.query.clone()).unwrap_or_default(),
+        status: entry["status"].as_u64().unwrap_or(200) as u16,
+        headers: [("content-type".to_string(), content_type.to_string())].into(),
+        body: None,
+        text: None,
+        chunks: None,
+        message: None,
+    };
+    match &entry["body"] {
+        Value::String(text) => interaction.text = Some(text.clone()),
+        body => interaction.body = Some(body.clone()),
+    }
+    if name == "streaming" {
+        let body = interaction.body();
+        let size = body.len().div_ceil(4).max(1);
+        let chunks = body.as_bytes().chunks(size);
+        interaction.chunks = Some(
+            chunks
+                .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+                .collect(),
+        );
+        interaction.body = None;
+        interaction.text = None;
+    }
+    cassette.interactions.insert(name.clone(), interaction);
+
+    // The expected message is what the provider makes of the new response
+    if matches!(name.as_str(), "success" | "streaming" | "thinking") {
+        let message = replay(&cassette, &name).expect("The recorded response does not replay");
+        let message = strip_thinking(&message).trim().to_string();
+        cassette.interactions.get_mut(&name).unwrap().message = Some(message);
+    }
+
+    let json = serde_json::to_string_pretty(&cassette).unwrap();
+    std::fs::write(cassette_path(&provider), json + "\n").unwrap();
+}
@@ -7,7 +7,20 @@
 
 use rusty_commit::config::Config;
 use rusty_commit::providers::create_provider;
-use rusty_commit::providers::prompt::build_prompt;
+use rusty_commit::providers::prompt::{build_prompt, summarize_diff};
+use std::sync::OnceLock;
+
+/// Point the usage ledger and payload log at one config directory for the
+/// whole test binary. Tests run in parallel, so a directory per test would be
+/// swapped out (and deleted) under the others.
+fn isolate() {
+    static CONFIG_HOME: OnceLock<tempfile::TempDir> = OnceLock::new();
+    CONFIG_HOME.get_or_init(|| {
+        let config_home = tempfile::tempdir().unwrap();
+        std::env::set_var("RCO_CONFIG_HOME", config_home.path());
+        config_home
+    });
+}
 
 #[test]
 fn test_build_prompt_conventional() {
@@ -67,6 +80,18 @@ fn test_build_prompt_with_language() {
     assert!(prompt.contains("Generate the commit message in es language"));
 }
 
+#[test]
+fn test_summarize_diff_keeps_files_and_line_counts() {
+    let diff = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,3 @@\n-old\n+new\n+more\n context\ndiff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n+docs\n";
+    let summary = summarize_diff(diff);
+
+    assert!(summary.contains("Summary only"));
+    assert!(summary.contains("Files Changed: 2 file(s)"));
+    assert!(summary.contains("+++ b/src/lib.rs\n  2 line(s) added, 1 removed"));
+    assert!(summary.contains("+++ b/README.md\n  1 line(s) added, 0 removed"));
+    assert!(!summary.contains("+more"));
+}
+
 #[test]
 fn test_create_provider_openai() {
     let mut config = Config::default();
@@ -139,3 +164,174 @@ fn test_create_provider_missing_api_key() {
     let provider = create_provider(&config);
     assert!(provider.is_err());
 }
+
+#[tokio::test]
+async fn test_providers_send_configured_http_headers() {
+    use rusty_commit::config::http::HttpSettings;
+
+    isolate();
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_header("x-gateway-route", "llm-prod")
+        .with_body(r#"{"response":"feat: add greeting"}"#)
+        .create_async()
+        .await;
+
+    let mut config = Config::default();
+    config.ai_provider = "ollama".to_string();
+    config.api_url = Some(server.url());
+    config.model = "mistral".to_string();
+    config.http = Some(HttpSettings {
+        headers: [("X-Gateway-Route".to_string(), "llm-prod".to_string())].into(),
+        ..Default::default()
+    });
+
+    let provider = create_provider(&config).unwrap();
+    let message = provider
+        .generate_commit_message("+hello", None, false, &config)
+        .await
+        .unwrap();
+    assert_eq!(message, "feat: add greeting");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_providers_retry_rate_limits_up_to_the_configured_count() {
+    isolate();
+
+    let mut server = mockito::Server::new_async().await;
+    let rate_limited = server
+        .mock("POST", "/v1/chat/completions")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .with_body(r#"{"error":{"message":"Rate limit reached","type":"requests"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("POST", "/v1/chat/completions")
+        .with_body(
+            r#"{"id":"1","object":"chat.completion","created":0,"model":"gpt-4o-mini","choices":[{"index":0,"message":{"role":"assistant","content":"fix: handle rate limits"},"finish_reason":"stop"}]}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let mut config = Config::default();
+    config.api_key = Some("sk-test".to_string());
+    config.api_url = Some(format!("{}/v1", server.url()));
+    config.model = "gpt-4o-mini".to_string();
+    config.retry_count = 2;
+
+    let provider = create_provider(&config).unwrap();
+    let message = provider
+        .generate_commit_message("+retry", None, false, &config)
+        .await
+        .unwrap();
+    assert_eq!(message, "fix: handle rate limits");
+    rate_limited.assert_async().await;
+    ok.assert_async().await;
+
+    // With retries off the first 429 is final
+    server.reset();
+    let rate_limited = server
+        .mock("POST", "/v1/chat/completions")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .with_body(r#"{"error":{"message":"Rate limit reached","type":"requests"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    config.retry_count = 0;
+    let provider = create_provider(&config).unwrap();
+    let err = provider
+        .generate_commit_message("+retry", None, false, &config)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("Rate limit reached"), "{err:#}");
+    rate_limited.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_variations_share_the_token_budget() {
+    isolate();
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_body(r#"{"response":"feat: add greeting"}"#)
+        .expect(3)
+        .create_async()
+        .await;
+
+    // Each request reserves about a third of the minute's 60000 tokens, so
+    // the third one waits about a second for the bucket to refill
+    let mut config = Config::default();
+    config.ai_provider = "ollama".to_string();
+    config.api_url = Some(server.url());
+    config.model = "mistral".to_string();
+    config.tokens_max_output = 20_330;
+    config.rate_limit_tpm = Some(60_000);
+    config.max_concurrency = 3;
+
+    let provider = create_provider(&config).unwrap();
+    let started = std::time::Instant::now();
+    let messages = provider
+        .generate_commit_messages("+hello", None, false, &config, 3)
+        .await
+        .unwrap();
+    let elapsed = started.elapsed();
+
+    assert_eq!(messages.len(), 3);
+    assert!(
+        elapsed >= std::time::Duration::from_millis(500),
+        "{elapsed:?}"
+    );
+    assert!(elapsed < std::time::Duration::from_secs(10), "{elapsed:?}");
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_account_usage_is_recorded_under_the_account() {
+    isolate();
+
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .with_body(r#"{"response":"feat: add greeting","prompt_eval_count":40,"eval_count":6}"#)
+        .create_async()
+        .await;
+
+    // The account serves the request while the global provider differs
+    let account: rusty_commit::config::accounts::AccountConfig =
+        serde_json::from_value(serde_json::json!({
+            "alias": "usage-account",
+            "provider": "ollama",
+            "api_url": server.url(),
+            "model": "mistral",
+            "auth": { "type": "command", "command": "echo unused" },
+            "tokens_max_input": null,
+            "tokens_max_output": null
+        }))
+        .unwrap();
+    let mut config = Config::default();
+    config.ai_provider = "anthropic".to_string();
+
+    let provider = rusty_commit::providers::create_provider_for_account(&account, &config).unwrap();
+    provider
+        .generate_commit_message("+hello", None, false, &config)
+        .await
+        .unwrap();
+    mock.assert_async().await;
+
+    let ledger = rusty_commit::usage::ledger::load_since(0).unwrap();
+    let record = ledger
+        .iter()
+        .find(|record| record.account.as_deref() == Some("usage-account"))
+        .expect("usage recorded for the account");
+    assert_eq!(record.provider, "ollama");
+    assert_eq!(record.model, "mistral");
+    assert_eq!(record.prompt_tokens, 40);
+}